@@ -43,29 +43,15 @@ class TournamentService:
         if not Path(ratings_path).exists():
             raise FileNotFoundError(f"Ratings file not found: {ratings_path}")
 
-        # Load adjustments if available
-        adjustments = None
-        if Path(self._adjustments_file).exists():
-            adjustments = tourney.read_adjustments_file(self._adjustments_file)
-
-        # Load ratings
-        self.ratings = tourney.read_ratings_file(ratings_path, adjustments)
-
-        # Load overrides
-        overrides = tourney.OverridesMap()
-        if Path(self._overrides_file).exists():
-            tourney.read_overrides_file(overrides, self._overrides_file)
-
-        # Load bracket
-        games = tourney.read_games_from_file(bracket_path, self.ratings, overrides)
-
-        # Create tournament state (expands ratings with equivalence classes)
-        self.state = tourney.TournamentState(
-            bracket=games,
-            ratings=self.ratings,
-            scoring=list(tourney.ROUND_POINTS),
-            overrides=overrides,
-            forfeit_prob=0.0,
+        adjustments_path = self._adjustments_file if Path(self._adjustments_file).exists() else None
+        overrides_path = self._overrides_file if Path(self._overrides_file).exists() else None
+
+        # Load ratings, overrides, and bracket (expands ratings with equivalence classes)
+        self.state = tourney.load_tournament(
+            bracket_path,
+            ratings_path,
+            overrides_path=overrides_path,
+            adjustments_path=adjustments_path,
         )
 
         # Use expanded ratings (includes all name variants from equivalence classes)
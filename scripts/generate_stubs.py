@@ -0,0 +1,461 @@
+"""Generate tourney_core.pyi from the Rust sources of the extension module.
+
+Reads the #[pyclass] structs, #[pymethods] blocks, and #[pyfunction]s under
+src/tourney_core/src, keeps what the #[pymodule] in lib.rs registers, and
+maps each Rust signature to Python types. Items gated on a cargo feature are
+left out, since the wheel is built with default features.
+
+    uv run python scripts/generate_stubs.py           # rewrite the stubs
+    uv run python scripts/generate_stubs.py --check   # fail if they are stale
+"""
+
+import argparse
+import re
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+CRATE_SRC = ROOT / "src" / "tourney_core" / "src"
+STUB_PATH = ROOT / "tourney_core.pyi"
+
+HEADER = '''"""Type stubs for the tourney_core extension module.
+
+Generated by scripts/generate_stubs.py from the Rust sources; do not edit.
+"""
+'''
+
+PRIMITIVES = {
+    "f32": "float",
+    "f64": "float",
+    "bool": "bool",
+    "str": "str",
+    "String": "str",
+    "char": "str",
+    "()": "None",
+    "PyObject": "Any",
+    "PyAny": "Any",
+    "PyBytes": "bytes",
+    "PyDict": "dict[str, Any]",
+    "PyList": "list[Any]",
+    "PyTuple": "tuple[Any, ...]",
+    "Round": "int",
+    "TextInput": "str | bytes",
+}
+INTEGERS = {"u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize"}
+# Wrappers whose Python type is that of their (last) type argument.
+TRANSPARENT = {"Result", "PyResult", "Py", "PyRef", "PyRefMut", "Bound", "Box", "Arc"}
+LISTS = {"Vec", "VecDeque"}
+MAPS = {"HashMap", "BTreeMap"}
+SETS = {"HashSet", "BTreeSet"}
+# Arguments pyo3 supplies rather than the caller.
+IMPLICIT_ARGS = {"self", "slf", "cls", "py"}
+
+
+@dataclass
+class Function:
+    name: str
+    params: list[tuple[str, str, str | None]]
+    returns: str
+    doc: list[str]
+    decorators: list[str] = field(default_factory=list)
+
+
+@dataclass
+class Class:
+    name: str
+    doc: list[str]
+    options: set[str]
+    properties: dict[str, tuple[str, bool, list[str]]] = field(default_factory=dict)
+    methods: list[Function] = field(default_factory=list)
+
+
+def mask_literals(text: str) -> str:
+    """`text` with comments and string/char literal contents blanked, so that
+    brace and comma matching can ignore them; offsets are unchanged."""
+    pattern = re.compile(
+        r'//[^\n]*|/\*.*?\*/|r(#*)".*?"\1|b?"(?:\\.|[^"\\])*"|\'(?:\\.|[^\\\'])\'',
+        re.S,
+    )
+
+    def blank(match: re.Match) -> str:
+        token = match.group(0)
+        if token.startswith("///"):
+            # Keep doc comments recognizable, minus anything bracket-like.
+            return re.sub(r"[{}\[\]()<>\"']", " ", token)
+        if token.startswith("//") or token.startswith("/*"):
+            return re.sub(r"[^\n]", " ", token)
+        return token[0] + re.sub(r"[^\n]", " ", token[1:-1]) + token[-1]
+
+    return pattern.sub(blank, text)
+
+
+def matching(text: str, start: int, open_: str = "{", close: str = "}") -> int:
+    """Index just past the bracket closing the one at `start`."""
+    depth = 0
+    for i in range(start, len(text)):
+        if text[i] == open_:
+            depth += 1
+        elif text[i] == close:
+            depth -= 1
+            if depth == 0:
+                return i + 1
+    raise ValueError(f"unbalanced {open_!r} at offset {start}")
+
+
+def split_top_level(text: str, sep: str = ",") -> list[str]:
+    parts, depth, current = [], 0, ""
+    for ch in text:
+        if ch in "<([{":
+            depth += 1
+        elif ch in ">)]}":
+            depth -= 1
+        if ch == sep and depth == 0:
+            parts.append(current.strip())
+            current = ""
+        else:
+            current += ch
+    if current.strip():
+        parts.append(current.strip())
+    return parts
+
+
+@dataclass
+class Source:
+    """A Rust file alongside a copy with literals and comments blanked."""
+
+    text: str
+    masked: str
+
+    def preamble(self, start: int) -> tuple[list[str], list[str]]:
+        """Doc-comment lines and attributes (each joined onto one line) of
+        the item starting at offset `start`."""
+        docs, attrs, pos = [], [], start
+        while True:
+            stripped = self.masked[:pos].rstrip()
+            line_start = self.masked.rfind("\n", 0, len(stripped)) + 1
+            line = self.text[line_start:pos].split("\n")[0].strip()
+            if stripped.endswith("]"):
+                depth, i = 0, len(stripped) - 1
+                while True:
+                    depth += {"]": 1, "[": -1}.get(self.masked[i], 0)
+                    if depth == 0:
+                        break
+                    i -= 1
+                if self.masked[i - 1] != "#":
+                    break
+                attrs.insert(0, " ".join(self.text[i - 1 : len(stripped)].split()))
+                pos = i - 1
+            elif line.startswith("///"):
+                docs.insert(0, line[4:] if line.startswith("/// ") else line[3:])
+                pos = line_start
+            else:
+                break
+        return docs, attrs
+
+
+def rust_type_to_python(rust: str, self_name: str = "Self") -> str:
+    rust = rust.strip()
+    rust = re.sub(r"^&\s*(?:'\w+\s+)?(?:mut\s+)?", "", rust)
+    rust = re.sub(r"^(?:crate|std|pyo3)(?:::\w+)*::", "", rust)
+    if rust in ("Self", self_name):
+        return self_name
+    if rust in PRIMITIVES:
+        return PRIMITIVES[rust]
+    if rust in INTEGERS:
+        return "int"
+    if rust.startswith("(") and rust.endswith(")"):
+        items = split_top_level(rust[1:-1])
+        return f"tuple[{', '.join(rust_type_to_python(item, self_name) for item in items)}]"
+    if rust.startswith("[") and rust.endswith("]"):
+        element = split_top_level(rust[1:-1], ";")[0]
+        return f"list[{rust_type_to_python(element, self_name)}]"
+    match = re.fullmatch(r"(\w+)\s*<(.*)>", rust, re.S)
+    if match:
+        outer, args = match.group(1), [a for a in split_top_level(match.group(2)) if not a.startswith("'")]
+        converted = [rust_type_to_python(arg, self_name) for arg in args]
+        if outer == "Option":
+            return f"{converted[0]} | None"
+        if outer in TRANSPARENT:
+            return converted[0]
+        if outer in LISTS:
+            return f"list[{converted[0]}]"
+        if outer in MAPS:
+            return f"dict[{converted[0]}, {converted[1]}]"
+        if outer in SETS:
+            return f"set[{converted[0]}]"
+    if re.fullmatch(r"[A-Z]\w*", rust):
+        return rust
+    print(f"warning: no Python type for Rust type {rust!r}; using Any", file=sys.stderr)
+    return "Any"
+
+
+def python_default(rust: str) -> str:
+    rust = rust.strip()
+    if rust in ("None", "true", "false"):
+        return {"None": "None", "true": "True", "false": "False"}[rust]
+    if re.fullmatch(r"-?\d[\d_]*(\.\d*)?(e-?\d+)?", rust) or re.fullmatch(r'"[^"]*"', rust):
+        return rust.replace("_", "")
+    return "..."
+
+
+def attr_value(attrs: list[str], pattern: str) -> str | None:
+    for attr in attrs:
+        match = re.search(pattern, attr)
+        if match:
+            return match.group(1)
+    return None
+
+
+def parse_function(sig: str, docs: list[str], attrs: list[str], self_name: str) -> Function:
+    """The Python view of the Rust function whose signature (up to its body)
+    is `sig`; `self_name` is the class for methods, or "Self" for functions."""
+    match = re.search(r"fn\s+(\w+)\s*(<[^(]*>)?\s*\(", sig)
+    name = match.group(1)
+    params_end = matching(sig, match.end() - 1, "(", ")")
+    rust_params = split_top_level(sig[match.end() : params_end - 1])
+    ret = re.search(r"->\s*(.*?)\s*(where\b.*)?$", sig[params_end:].strip(), re.S)
+    returns = rust_type_to_python(ret.group(1), self_name) if ret else "None"
+
+    types = {}
+    for param in rust_params:
+        if re.fullmatch(r"&?\s*(mut\s+)?self", param):
+            continue
+        pname, ptype = (s.strip() for s in param.split(":", 1))
+        if pname in IMPLICIT_ARGS or re.match(r"(&\s*)?Python\b", ptype):
+            continue
+        types[pname] = rust_type_to_python(ptype, self_name)
+
+    decorators = []
+    if any(a.startswith("#[staticmethod") for a in attrs):
+        decorators.append("@staticmethod")
+    elif any(a.startswith("#[classmethod") for a in attrs):
+        decorators.append("@classmethod")
+
+    python_name = attr_value(attrs, r'^#\[pyo3\((?:[^()]*,\s*)?name\s*=\s*"(\w+)"') or name
+    if any(a.startswith("#[new]") for a in attrs):
+        python_name, returns = "__init__", "None"
+
+    params = []
+    signature = attr_value(attrs, r"signature\s*=\s*\((.*)\)\s*\)\]$")
+    if signature is not None:
+        for entry in split_top_level(signature):
+            pname, _, default = entry.partition("=")
+            pname = pname.strip()
+            if pname == "*":
+                params.append((pname, "", None))
+            elif pname.startswith("*"):
+                params.append((pname, "Any", None))
+            else:
+                params.append((pname, types.get(pname, "Any"), python_default(default) if default else None))
+    else:
+        params = [(pname, ptype, None) for pname, ptype in types.items()]
+
+    if decorators == ["@classmethod"]:
+        params.insert(0, ("cls", "", None))
+    elif self_name != "Self" and not decorators:
+        # Including #[new], which Rust writes without a receiver.
+        params.insert(0, ("self", "", None))
+    return Function(python_name, params, returns, docs, decorators)
+
+
+def parse_fields(source: Source, start: int, end: int) -> list[tuple[str, str, list[str], list[str]]]:
+    """`(name, rust_type, docs, attrs)` for each field of the struct body
+    between offsets `start` and `end`."""
+    fields = []
+    pattern = re.compile(r"^[ \t]*((?:pub(?:\([\w:]+\))?\s+)?(\w+))\s*:\s*", re.M)
+    for match in pattern.finditer(source.masked, start, end):
+        depth, type_end = 0, match.end()
+        while type_end < end and not (depth == 0 and source.masked[type_end] == ","):
+            depth += {"<": 1, "(": 1, "[": 1, ">": -1, ")": -1, "]": -1}.get(source.masked[type_end], 0)
+            type_end += 1
+        docs, attrs = source.preamble(match.start(1))
+        fields.append((match.group(2), source.masked[match.end() : type_end].strip(), docs, attrs))
+    return fields
+
+
+def feature_gated(attrs: list[str]) -> bool:
+    """Whether the item needs an optional feature beyond `python`, which every
+    binding does."""
+    return any(
+        re.match(r"#\[cfg\((all\(|any\()?feature", attr) and attr != '#[cfg(feature = "python")]' for attr in attrs
+    )
+
+
+def parse_sources() -> tuple[dict[str, Class], dict[str, Function]]:
+    classes: dict[str, Class] = {}
+    functions: dict[str, Function] = {}
+    methods: dict[str, list[tuple[Function, list[str]]]] = {}
+    for path in sorted(CRATE_SRC.rglob("*.rs")):
+        text = path.read_text()
+        masked = mask_literals(text)
+        test_module = masked.find("#[cfg(test)]")
+        if test_module >= 0:
+            text, masked = text[:test_module], masked[:test_module]
+        source = Source(text, masked)
+
+        for match in re.finditer(r"^pub(?:\(crate\))?\s+struct\s+(\w+)[^{;(]*\{", masked, re.M):
+            docs, attrs = source.preamble(match.start())
+            pyclass = next((a for a in attrs if a.startswith("#[pyclass")), None)
+            if pyclass is None or feature_gated(attrs):
+                continue
+            cls = Class(match.group(1), docs, set(re.findall(r"\w+", pyclass[len("#[pyclass") :])))
+            body_end = matching(masked, match.end() - 1) - 1
+            for name, rust_type, field_docs, field_attrs in parse_fields(source, match.end(), body_end):
+                pyo3 = attr_value(field_attrs, r"#\[pyo3\(([^)]*)\)\]") or ""
+                if re.search(r"\bget\b", pyo3):
+                    settable = bool(re.search(r"\bset\b", pyo3))
+                    cls.properties[name] = (rust_type_to_python(rust_type, cls.name), settable, field_docs)
+            classes[cls.name] = cls
+
+        for match in re.finditer(r"^impl\s+(\w+)\s*\{", masked, re.M):
+            _, attrs = source.preamble(match.start())
+            if "#[pymethods]" not in attrs or feature_gated(attrs):
+                continue
+            name, body_end = match.group(1), matching(masked, match.end() - 1) - 1
+            for fn in re.finditer(r"^    (?:pub(?:\([\w:]+\))?\s+)?fn\s+\w+", masked[:body_end], re.M):
+                if fn.start() < match.end():
+                    continue
+                docs, fn_attrs = source.preamble(fn.start())
+                if feature_gated(fn_attrs):
+                    continue
+                sig = masked[fn.start() : masked.index("{", fn.start())]
+                methods.setdefault(name, []).append((parse_function(sig, docs, fn_attrs, name), fn_attrs))
+
+        for match in re.finditer(r"^(?:pub(?:\(crate\))?\s+)?fn\s+\w+", masked, re.M):
+            docs, attrs = source.preamble(match.start())
+            if not any(a.startswith("#[pyfunction") for a in attrs) or feature_gated(attrs):
+                continue
+            sig = masked[match.start() : masked.index("{", match.start())]
+            function = parse_function(sig, docs, attrs, "Self")
+            functions[function.name] = function
+
+    for name, class_methods in methods.items():
+        cls = classes[name]
+        for method, attrs in class_methods:
+            getter = attr_value(attrs, r"#\[getter(?:\((\w*)\))?\]")
+            setter = attr_value(attrs, r"#\[setter(?:\((\w*)\))?\]")
+            if any(a.startswith("#[getter") for a in attrs):
+                prop = getter or method.name.removeprefix("get_")
+                settable = cls.properties.get(prop, (None, False, []))[1]
+                cls.properties[prop] = (method.returns, settable, method.doc)
+            elif any(a.startswith("#[setter") for a in attrs):
+                prop = setter or method.name.removeprefix("set_")
+                python_type, _, docs = cls.properties.get(prop, (method.params[-1][1], False, method.doc))
+                cls.properties[prop] = (python_type, True, docs)
+            else:
+                cls.methods.append(method)
+    return classes, functions
+
+
+def registered(lib: str) -> tuple[list[str], list[str], list[tuple[str, str]]]:
+    """Classes, functions, and (constant, expression) pairs the module adds.
+
+    Registrations behind a feature are skipped, like feature-gated items.
+    """
+    module = lib[lib.index("#[pymodule]") :]
+    module = re.sub(r"#\[cfg\((all\(|any\()?feature[^\n]*\n[^\n]*\n", "", module)
+    classes = re.findall(r"add_class::<(\w+)>", module)
+    functions = re.findall(r"wrap_pyfunction!\((\w+)", module)
+    constants = re.findall(r'm\.add\("(\w+)",\s*(.*?)\)\?;', module)
+    return classes, functions, constants
+
+
+def constant_type(expr: str) -> str:
+    name = re.match(r"(\w+)", expr).group(1)
+    for path in CRATE_SRC.rglob("*.rs"):
+        source = path.read_text()
+        const = re.search(rf"const\s+{name}\s*:\s*([^=]+)=", source)
+        fn = re.search(rf"fn\s+{name}\s*\([^)]*\)\s*->\s*([^{{]+)\{{", source)
+        rust = const.group(1) if const else fn.group(1) if fn else None
+        if rust is not None:
+            python_type = rust_type_to_python(rust)
+            if expr.endswith(".to_vec()") and not python_type.startswith("list["):
+                python_type = f"list[{python_type}]"
+            return python_type
+    return "Any"
+
+
+def docstring(lines: list[str], indent: str) -> list[str]:
+    lines = [line.rstrip() for line in lines]
+    while lines and not lines[-1]:
+        lines.pop()
+    if not lines:
+        return []
+    text = "\n".join(lines).replace("\\", "\\\\").replace('"""', '\\"\\"\\"')
+    if len(lines) == 1:
+        return [f'{indent}"""{text}"""']
+    body = "\n".join(f"{indent}{line}" if line else "" for line in text.split("\n"))
+    return [f'{indent}"""{body.lstrip()}', f'{indent}"""']
+
+
+def render_function(function: Function, indent: str = "") -> list[str]:
+    params = []
+    for name, python_type, default in function.params:
+        param = f"{name}: {python_type}" if python_type else name
+        if default is not None:
+            param += f" = {default}"
+        params.append(param)
+    header = f"{indent}def {function.name}({', '.join(params)}) -> {function.returns}:"
+    docs = docstring(function.doc, indent + "    ")
+    lines = [f"{indent}{decorator}" for decorator in function.decorators]
+    if docs:
+        return lines + [header] + docs
+    return lines + [header + " ..."]
+
+
+def render_class(cls: Class) -> list[str]:
+    lines = [f"class {cls.name}:"]
+    lines += docstring(cls.doc, "    ")
+    for prop, (python_type, settable, docs) in cls.properties.items():
+        lines += ["    @property"]
+        lines += render_function(Function(prop, [("self", "", None)], python_type, docs), "    ")
+        if settable:
+            lines += [f"    @{prop}.setter"]
+            lines += [f"    def {prop}(self, value: {python_type}) -> None: ..."]
+    for method in cls.methods:
+        lines += render_function(method, "    ")
+    if "eq" in cls.options:
+        lines += ["    def __eq__(self, other: object) -> bool: ..."]
+    if "ord" in cls.options:
+        lines += [f"    def __{op}__(self, other: {cls.name}) -> bool: ..." for op in ("lt", "le", "gt", "ge")]
+    if "hash" in cls.options:
+        lines += ["    def __hash__(self) -> int: ..."]
+    if len(lines) == 1:
+        lines.append("    ...")
+    return lines
+
+
+def generate() -> str:
+    classes, functions = parse_sources()
+    class_names, function_names, constants = registered((CRATE_SRC / "lib.rs").read_text())
+    missing = [name for name in class_names + function_names if name not in classes and name not in functions]
+    if missing:
+        raise SystemExit(f"registered but not found in the sources: {', '.join(missing)}")
+
+    out = [HEADER, "from typing import Any", ""]
+    out += [f"{name}: {constant_type(expr)}" for name, expr in constants]
+    for name in class_names:
+        out += ["", ""] + render_class(classes[name])
+    for name in function_names:
+        out += ["", ""] + render_function(functions[name])
+    return "\n".join(out) + "\n"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n")[0])
+    parser.add_argument("--check", action="store_true", help="exit non-zero if the stubs are out of date")
+    args = parser.parse_args()
+
+    stubs = generate()
+    if args.check:
+        if not STUB_PATH.exists() or STUB_PATH.read_text() != stubs:
+            print(f"{STUB_PATH.name} is out of date; run scripts/generate_stubs.py", file=sys.stderr)
+            return 1
+        return 0
+    STUB_PATH.write_text(stubs)
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
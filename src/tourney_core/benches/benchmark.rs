@@ -1,9 +1,14 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use std::collections::HashMap;
 use tourney_core::constants::ROUND_POINTS;
+use tourney_core::entry::optimize_entry;
+use tourney_core::field::sample_field;
 use tourney_core::game_transform::game_transform_prob;
-use tourney_core::portfolio::get_all_team_deltas;
+use tourney_core::overrides::OverridesMap;
+use tourney_core::portfolio::{delta_matrix, game_delta_ref, get_all_team_deltas_ref};
+use tourney_core::standings::{score_completed_bracket, score_entries_batch};
 use tourney_core::team::Team;
+use tourney_core::testing::canonical_tournament;
 use tourney_core::tournament::TournamentState;
 use tourney_core::win_prob::calculate_win_prob;
 
@@ -30,7 +35,7 @@ fn create_64_team_tournament() -> TournamentState {
         bracket.push(game);
     }
 
-    TournamentState::new(bracket, ratings, ROUND_POINTS.to_vec(), None, 0.0)
+    TournamentState::new(bracket, ratings, ROUND_POINTS.to_vec(), None, 0.0, None)
 }
 
 fn bench_calculate_win_prob(c: &mut Criterion) {
@@ -71,12 +76,48 @@ fn bench_game_transform_prob(c: &mut Criterion) {
     });
 }
 
+fn bench_game_transform_prob_small(c: &mut Criterion) {
+    let tournament = create_64_team_tournament();
+    let slot = |teams: &[(&str, f64)]| -> HashMap<String, f64> {
+        teams.iter().map(|&(team, prob)| (team.to_string(), prob)).collect()
+    };
+    let single = slot(&[("Team0", 1.0)]);
+    let opponent = slot(&[("Team1", 1.0)]);
+    let pair = slot(&[("Team2", 0.55), ("Team3", 0.45)]);
+    let mut overrides = OverridesMap::new();
+    overrides.add_override("Team0", "Team1", 1.0).unwrap();
+
+    let mut group = c.benchmark_group("game_transform_prob");
+    group.bench_function("1x1", |b| {
+        b.iter(|| game_transform_prob(black_box(&single), black_box(&opponent), &tournament.ratings, None, 0.0))
+    });
+    group.bench_function("1x2", |b| {
+        b.iter(|| game_transform_prob(black_box(&single), black_box(&pair), &tournament.ratings, None, 0.0))
+    });
+    group.bench_function("1x1_overridden", |b| {
+        b.iter(|| {
+            game_transform_prob(black_box(&single), black_box(&opponent), &tournament.ratings, Some(&overrides), 0.0)
+        })
+    });
+    group.finish();
+}
+
 fn bench_tournament_scoring(c: &mut Criterion) {
     let tournament = create_64_team_tournament();
 
     c.bench_function("tournament_64_team_scoring", |b| {
         b.iter(|| black_box(&tournament).calculate_scores_prob())
     });
+
+    // First round already played: every round-one game is a certain override.
+    let mut played = tournament.clone();
+    for game in 0..32 {
+        let (winner, loser) = (format!("Team{}", 2 * game), format!("Team{}", 2 * game + 1));
+        played.overrides.add_override(&winner, &loser, 1.0).unwrap();
+    }
+    c.bench_function("tournament_64_team_scoring_round_one_played", |b| {
+        b.iter(|| black_box(&played).calculate_scores_prob())
+    });
 }
 
 fn bench_monte_carlo(c: &mut Criterion) {
@@ -114,19 +155,88 @@ fn bench_portfolio_deltas(c: &mut Criterion) {
         }
     }
 
-    let tournament = TournamentState::new(bracket, ratings, vec![1.0, 1.0, 2.0, 2.0], None, 0.0);
+    let tournament = TournamentState::new(bracket, ratings, vec![1.0, 1.0, 2.0, 2.0], None, 0.0, None);
 
     c.bench_function("get_all_team_deltas_16_teams", |b| {
-        b.iter(|| get_all_team_deltas(black_box(positions.clone()), black_box(&tournament), 1.0))
+        b.iter(|| get_all_team_deltas_ref(black_box(&positions), black_box(&tournament), 1.0))
+    });
+}
+
+fn bench_portfolio_deltas_64(c: &mut Criterion) {
+    let tournament = create_64_team_tournament();
+    let positions: HashMap<String, f64> = (0..64).step_by(3).map(|i| (format!("Team{}", i), i as f64 + 1.0)).collect();
+
+    let mut group = c.benchmark_group("team_deltas_64_teams");
+    group.sample_size(10);
+    group.bench_function("get_all_team_deltas", |b| {
+        b.iter(|| get_all_team_deltas_ref(black_box(&positions), black_box(&tournament), 1.0))
+    });
+    group.bench_function("delta_matrix", |b| {
+        b.iter(|| delta_matrix(black_box(&positions), black_box(&tournament), 1.0))
+    });
+    group.finish();
+}
+
+fn bench_game_delta_64(c: &mut Criterion) {
+    let tournament = create_64_team_tournament();
+    let positions: HashMap<String, f64> = (0..64).step_by(3).map(|i| (format!("Team{}", i), i as f64 + 1.0)).collect();
+
+    let mut group = c.benchmark_group("game_delta_64_teams");
+    // A first-round game, and one that can only happen in the final.
+    for (team1, team2) in [("Team0", "Team1"), ("Team0", "Team63")] {
+        group.bench_function(format!("cloned_states_{team2}"), |b| {
+            b.iter(|| {
+                let win = tournament.with_override(team1, team2, 1.0).unwrap().calculate_scores_prob();
+                let loss = tournament.with_override(team1, team2, 0.0).unwrap().calculate_scores_prob();
+                black_box((win, loss))
+            })
+        });
+        group.bench_function(format!("game_delta_{team2}"), |b| {
+            b.iter(|| game_delta_ref(black_box(&positions), black_box(&tournament), team1, team2))
+        });
+    }
+    group.finish();
+}
+
+fn bench_score_entries(c: &mut Criterion) {
+    let tournament = canonical_tournament(64);
+    let ownership: HashMap<String, Vec<f64>> =
+        tournament.get_bracket_teams().into_iter().map(|team| (team, vec![0.5; 6])).collect();
+    let entries = sample_field(&tournament, ownership, 10_000, Some(7)).unwrap();
+    // Through the Sweet 16.
+    let results = optimize_entry(&tournament, None, None).unwrap().picks[..2].to_vec();
+
+    let mut group = c.benchmark_group("score_10000_entries");
+    group.sample_size(10);
+    group.bench_function("per_entry_loop", |b| {
+        b.iter(|| {
+            entries
+                .iter()
+                .map(|entry| score_completed_bracket(entry, &tournament, Some(results.clone()), None).unwrap().earned)
+                .collect::<Vec<f64>>()
+        })
+    });
+    // The batch takes its entries by value; copies are made outside the timing.
+    group.bench_function("score_entries_batch", |b| {
+        b.iter_batched(
+            || (entries.clone(), results.clone()),
+            |(entries, results)| score_entries_batch(black_box(entries), &tournament, Some(results), None).unwrap(),
+            BatchSize::LargeInput,
+        )
     });
+    group.finish();
 }
 
 criterion_group!(
     benches,
     bench_calculate_win_prob,
     bench_game_transform_prob,
+    bench_game_transform_prob_small,
     bench_tournament_scoring,
     bench_monte_carlo,
     bench_portfolio_deltas,
+    bench_portfolio_deltas_64,
+    bench_game_delta_64,
+    bench_score_entries,
 );
 criterion_main!(benches);
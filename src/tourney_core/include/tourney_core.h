@@ -0,0 +1,33 @@
+/* C interface to tourney_core. See src/ffi.rs for details. */
+#ifndef TOURNEY_CORE_H
+#define TOURNEY_CORE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct TournamentState TournamentState;
+
+/* Create a tournament from TournamentState JSON. Returns NULL on error. */
+TournamentState *tourney_tournament_from_json(const char *json);
+
+/* Expected scores as a JSON object {"team": score}. Free with tourney_string_free. */
+char *tourney_calculate_scores(const TournamentState *state);
+
+/* Simulated scores as a JSON array of objects. A negative seed uses entropy. */
+char *tourney_run_simulations(const TournamentState *state, size_t n_simulations, int64_t seed);
+
+void tourney_tournament_free(TournamentState *state);
+void tourney_string_free(char *s);
+
+/* Message for the last failure on this thread, or NULL. */
+const char *tourney_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TOURNEY_CORE_H */
@@ -0,0 +1,61 @@
+//! Stand-ins for pyo3's `#[pyclass]`, `#[pymethods]`, and `#[pyfunction]`.
+//!
+//! tourney_core marks its Python API with pyo3 attributes in place. Built
+//! without the `python` feature, these take their place: each returns the
+//! item unchanged apart from the helper attributes pyo3 would have consumed
+//! (`#[pyo3(...)]`, `#[new]`, `#[getter]`, ...), so the same source compiles
+//! to plain Rust types and functions.
+
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+/// Attributes only pyo3's macros understand.
+const HELPERS: [&str; 8] = ["pyo3", "new", "getter", "setter", "staticmethod", "classmethod", "classattr", "args"];
+
+#[proc_macro_attribute]
+pub fn pyclass(_args: TokenStream, item: TokenStream) -> TokenStream {
+    strip_helpers(item)
+}
+
+#[proc_macro_attribute]
+pub fn pymethods(_args: TokenStream, item: TokenStream) -> TokenStream {
+    strip_helpers(item)
+}
+
+#[proc_macro_attribute]
+pub fn pyfunction(_args: TokenStream, item: TokenStream) -> TokenStream {
+    strip_helpers(item)
+}
+
+/// `tokens` without helper attributes, including those nested in groups
+/// (fields, methods, arguments).
+fn strip_helpers(tokens: TokenStream) -> TokenStream {
+    let mut out = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(ref punct) if punct.as_char() == '#' => {
+                if let Some(TokenTree::Group(attr)) = tokens.peek() {
+                    if attr.delimiter() == Delimiter::Bracket && is_helper(attr) {
+                        tokens.next();
+                        continue;
+                    }
+                }
+                out.push(token);
+            }
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(group.delimiter(), strip_helpers(group.stream()));
+                stripped.set_span(group.span());
+                out.push(TokenTree::Group(stripped));
+            }
+            token => out.push(token),
+        }
+    }
+    out.into_iter().collect()
+}
+
+fn is_helper(attr: &Group) -> bool {
+    match attr.stream().into_iter().next() {
+        Some(TokenTree::Ident(name)) => HELPERS.contains(&name.to_string().as_str()),
+        _ => false,
+    }
+}
@@ -0,0 +1,332 @@
+//! On-disk archives of raw simulation results.
+//!
+//! `run_simulations` keeps every result in memory, which caps a run at what
+//! fits in RAM. `TournamentState::run_simulations_to_file` streams results to a
+//! binary archive instead, and `SimulationArchive` memory-maps it so even very
+//! large archives can be analyzed lazily, one simulation or team at a time.
+//!
+//! File layout (all integers and floats little-endian):
+//!
+//! ```text
+//! magic       8 bytes   b"TRNYSIM1"
+//! version     u32
+//! n_teams     u32
+//! n_sims      u64
+//! data_offset u64       start of the score matrix, 8-byte aligned
+//! n_teams x { len: u32, name: len bytes of UTF-8 }
+//! padding to data_offset
+//! n_sims x n_teams f64  row-major, one row per simulation
+//! ```
+
+use memmap2::Mmap;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::error::{Result, TourneyError};
+use crate::sim::SimContext;
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::trace::trace_span;
+
+const MAGIC: &[u8; 8] = b"TRNYSIM1";
+const VERSION: u32 = 1;
+/// Bytes before the team table: magic, version, n_teams, n_sims, data_offset.
+const FIXED_HEADER_LEN: usize = 32;
+/// Simulations computed in parallel between writes.
+const WRITE_CHUNK: usize = 8192;
+
+/// Run `n_simulations` and stream every simulation's per-team scores to `path`.
+///
+/// Draws are identical to `state.run_simulations(n_simulations, seed)`; teams
+/// that score nothing in a simulation are stored as 0.
+pub fn write_simulations(state: &TournamentState, path: &Path, n_simulations: usize, seed: Option<u64>) -> Result<()> {
+    trace_span!(INFO, "write_simulations", n_simulations);
+    let teams = state.bracket_teams();
+
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(&header(teams, n_simulations as u64))?;
+
+    // Same seed sequence as `run_simulations`, drawn one chunk at a time.
+    let mut rng = seeded_rng(seed);
+    let mut remaining = n_simulations;
+    while remaining > 0 {
+        let seeds: Vec<u64> = (0..remaining.min(WRITE_CHUNK)).map(|_| rng.gen::<u64>()).collect();
+        remaining -= seeds.len();
+        let rows: Vec<Vec<f64>> = seeds
+            .par_iter()
+            .map_init(|| SimContext::new(state), |context, &sim_seed| context.simulate_dense(Some(sim_seed)).to_vec())
+            .collect();
+        for value in rows.iter().flatten() {
+            out.write_all(&value.to_le_bytes())?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn header(teams: &[String], n_sims: u64) -> Vec<u8> {
+    let table_len: usize = teams.iter().map(|t| 4 + t.len()).sum();
+    let data_offset = (FIXED_HEADER_LEN + table_len).next_multiple_of(8);
+
+    let mut bytes = Vec::with_capacity(data_offset);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(teams.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&n_sims.to_le_bytes());
+    bytes.extend_from_slice(&(data_offset as u64).to_le_bytes());
+    for team in teams {
+        bytes.extend_from_slice(&(team.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(team.as_bytes());
+    }
+    bytes.resize(data_offset, 0);
+    bytes
+}
+
+/// Read-only, memory-mapped view of a simulation archive.
+#[pyclass]
+pub struct SimulationArchive {
+    mmap: Mmap,
+    teams: Vec<String>,
+    n_simulations: usize,
+    data_offset: usize,
+}
+
+#[pymethods]
+impl SimulationArchive {
+    /// Open the archive at `path`, validating its header and length.
+    #[cfg(feature = "python")]
+    #[new]
+    pub fn py_new(path: &str) -> PyResult<Self> {
+        Ok(Self::open(path)?)
+    }
+
+    /// Team names, in column order.
+    #[getter]
+    pub fn teams(&self) -> Vec<String> {
+        self.teams.clone()
+    }
+
+    #[getter]
+    pub fn n_simulations(&self) -> usize {
+        self.n_simulations
+    }
+
+    fn __len__(&self) -> usize {
+        self.n_simulations
+    }
+
+    /// Scores from simulation `index`, in the shape `run_simulations` returns:
+    /// only teams that scored are included.
+    pub fn simulation(&self, index: usize) -> Result<HashMap<String, f64>> {
+        self.check_index(index)?;
+        Ok(self
+            .teams
+            .iter()
+            .enumerate()
+            .map(|(t, team)| (team, self.value(index, t)))
+            .filter(|&(_, score)| score != 0.0)
+            .map(|(team, score)| (team.clone(), score))
+            .collect())
+    }
+
+    /// Simulations `start..end` (clamped to the archive).
+    pub fn simulations(&self, start: usize, end: usize) -> Vec<HashMap<String, f64>> {
+        (start..end.min(self.n_simulations))
+            .map(|i| self.simulation(i).expect("index is in range"))
+            .collect()
+    }
+
+    /// Every simulation's score for `team`.
+    pub fn team_scores(&self, team: &str) -> Result<Vec<f64>> {
+        let t = self.column(team)?;
+        Ok((0..self.n_simulations).map(|i| self.value(i, t)).collect())
+    }
+
+    /// Mean score per team across the archive.
+    pub fn mean_scores(&self) -> HashMap<String, f64> {
+        let n = self.n_simulations.max(1) as f64;
+        let mut sums = vec![0.0; self.teams.len()];
+        for i in 0..self.n_simulations {
+            for (t, sum) in sums.iter_mut().enumerate() {
+                *sum += self.value(i, t);
+            }
+        }
+        self.teams.iter().cloned().zip(sums.into_iter().map(|s| s / n)).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SimulationArchive(teams={}, n_simulations={})", self.teams.len(), self.n_simulations)
+    }
+}
+
+impl SimulationArchive {
+    /// Open and validate the archive at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only; archives are written once and not
+        // modified afterwards, and every read below is bounds-checked against
+        // the validated length.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let invalid = |msg: &str| TourneyError::InvalidInput(format!("invalid simulation archive: {msg}"));
+        if mmap.len() < FIXED_HEADER_LEN || &mmap[..8] != MAGIC {
+            return Err(invalid("bad magic number"));
+        }
+        let version = read_u32(&mmap, 8);
+        if version != VERSION {
+            return Err(invalid(&format!("unsupported version {version}")));
+        }
+        let n_teams = read_u32(&mmap, 12) as usize;
+        let n_simulations = usize::try_from(read_u64(&mmap, 16)).map_err(|_| invalid("too many simulations"))?;
+        let data_offset = usize::try_from(read_u64(&mmap, 24)).map_err(|_| invalid("bad data offset"))?;
+
+        // Every team table entry takes at least its 4-byte length, so a count
+        // the table cannot hold is rejected before anything is allocated for it.
+        let table_len = data_offset.min(mmap.len()).saturating_sub(FIXED_HEADER_LEN);
+        if n_teams > table_len / 4 {
+            return Err(invalid(&format!("{n_teams} teams do not fit in a {table_len}-byte team table")));
+        }
+        let mut teams = Vec::with_capacity(n_teams);
+        let mut pos = FIXED_HEADER_LEN;
+        for _ in 0..n_teams {
+            if pos + 4 > data_offset.min(mmap.len()) {
+                return Err(invalid("truncated team table"));
+            }
+            let len = read_u32(&mmap, pos) as usize;
+            let name = mmap
+                .get(pos + 4..pos + 4 + len)
+                .filter(|_| pos + 4 + len <= data_offset)
+                .ok_or_else(|| invalid("truncated team table"))?;
+            teams.push(String::from_utf8(name.to_vec()).map_err(|_| invalid("team name is not UTF-8"))?);
+            pos += 4 + len;
+        }
+
+        let data_len = n_simulations
+            .checked_mul(n_teams)
+            .and_then(|cells| cells.checked_mul(8))
+            .and_then(|bytes| bytes.checked_add(data_offset));
+        if data_len != Some(mmap.len()) {
+            return Err(invalid(&format!(
+                "header declares {n_simulations} simulations of {n_teams} teams from byte {data_offset}, \
+                 file is {} bytes",
+                mmap.len()
+            )));
+        }
+
+        Ok(SimulationArchive {
+            mmap,
+            teams,
+            n_simulations,
+            data_offset,
+        })
+    }
+
+    /// Simulation `index`'s scores as a row, in `teams` order.
+    pub fn row(&self, index: usize) -> Result<Vec<f64>> {
+        self.check_index(index)?;
+        Ok((0..self.teams.len()).map(|t| self.value(index, t)).collect())
+    }
+
+    fn value(&self, sim: usize, team: usize) -> f64 {
+        let at = self.data_offset + 8 * (sim * self.teams.len() + team);
+        f64::from_le_bytes(self.mmap[at..at + 8].try_into().expect("slice is 8 bytes"))
+    }
+
+    fn column(&self, team: &str) -> Result<usize> {
+        self.teams
+            .iter()
+            .position(|t| t == team)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("team not in archive: {team}")))
+    }
+
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index < self.n_simulations {
+            Ok(())
+        } else {
+            Err(TourneyError::InvalidInput(format!(
+                "simulation {index} out of range for archive of {}",
+                self.n_simulations
+            )))
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().expect("slice is 4 bytes"))
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().expect("slice is 8 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("tourney_archive_{}_{name}.bin", std::process::id()))
+    }
+
+    #[test]
+    fn test_archive_round_trips_run_simulations() {
+        let state = canonical_tournament(16);
+        let path = temp_path("round_trip");
+        write_simulations(&state, &path, 300, Some(11)).unwrap();
+
+        let archive = SimulationArchive::open(&path).unwrap();
+        assert_eq!(archive.n_simulations(), 300);
+        assert_eq!(archive.teams(), state.get_bracket_teams());
+        let expected = state.run_simulations(300, Some(11));
+        assert_eq!(archive.simulations(0, 1000), expected);
+        assert_eq!(archive.team_scores("Team 01").unwrap().len(), 300);
+        assert!(archive.simulation(300).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_archives_rejected() {
+        let state = canonical_tournament(4);
+        let path = temp_path("corrupt");
+        write_simulations(&state, &path, 10, Some(1)).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        std::fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+        assert!(SimulationArchive::open(&path).is_err());
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        std::fs::write(&path, bad_magic).unwrap();
+        assert!(SimulationArchive::open(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_header_rejected() {
+        let state = canonical_tournament(4);
+        let path = temp_path("oversized");
+        write_simulations(&state, &path, 10, Some(1)).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let open_with = |offset: usize, field: &[u8]| {
+            let mut patched = bytes.clone();
+            patched[offset..offset + field.len()].copy_from_slice(field);
+            std::fs::write(&path, patched).unwrap();
+            SimulationArchive::open(&path).err().unwrap().to_string()
+        };
+
+        // A team count no team table could hold fails without allocating for it.
+        let err = open_with(12, &u32::MAX.to_le_bytes());
+        assert!(err.contains("do not fit"), "{err}");
+        // A simulation count whose byte size overflows is reported, not computed.
+        let err = open_with(16, &u64::MAX.to_le_bytes());
+        assert!(err.contains(&format!("{} simulations", u64::MAX)), "{err}");
+
+        std::fs::write(&path, &bytes[..FIXED_HEADER_LEN + 2]).unwrap();
+        assert!(SimulationArchive::open(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
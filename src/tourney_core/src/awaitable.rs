@@ -0,0 +1,34 @@
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::PyCFunction;
+use std::sync::Mutex;
+
+/// Run `work` on the event loop's default executor and return an awaitable.
+///
+/// The executor thread releases the GIL while `work` runs (which is free to use
+/// rayon internally), so the caller's event loop is never blocked. `finish` runs
+/// with the GIL held to convert the result, and may update Python-side state.
+pub(crate) fn spawn_awaitable<T, W, F>(py: Python<'_>, work: W, finish: F) -> PyResult<PyObject>
+where
+    T: Send + 'static,
+    W: FnOnce() -> T + Send + 'static,
+    F: FnOnce(Python<'_>, T) -> PyResult<PyObject> + Send + 'static,
+{
+    let event_loop = py.import_bound("asyncio")?.call_method0("get_running_loop")?;
+
+    // Python may call the job at most once, but PyCFunction closures must be `Fn`.
+    let job = Mutex::new(Some((work, finish)));
+    let call = PyCFunction::new_closure_bound(py, None, None, move |args, _kwargs| {
+        let py = args.py();
+        let (work, finish) = job
+            .lock()
+            .ok()
+            .and_then(|mut slot| slot.take())
+            .ok_or_else(|| PyRuntimeError::new_err("background job already ran"))?;
+        let value = py.allow_threads(work);
+        finish(py, value)
+    })?;
+
+    let future = event_loop.call_method1("run_in_executor", (py.None(), call))?;
+    Ok(future.unbind())
+}
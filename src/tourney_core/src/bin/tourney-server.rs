@@ -0,0 +1,17 @@
+//! Standalone HTTP scoring service.
+//!
+//! Usage: `tourney-server [ADDR]` (default `127.0.0.1:8080`).
+
+use std::net::SocketAddr;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let addr: SocketAddr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8080".to_string())
+        .parse()
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+
+    eprintln!("tourney-server listening on {addr}");
+    tourney_core::server::serve(addr).await
+}
@@ -0,0 +1,182 @@
+//! Portfolios spanning several tournaments.
+//!
+//! A `PortfolioBook` holds one `PortfolioState` per event (men's bracket,
+//! women's bracket, NIT, ...) with a per-event scale applied to its points, so
+//! value, risk, and deltas can be computed for the whole book at once. Events
+//! are independent, so combined simulations pair up independent draws.
+
+// pyo3 0.22's generated wrappers re-convert a `PyErr` returned as `PyResult`, which
+// trips this lint outside any item an attribute could reach; the module is the tightest scope.
+#![cfg_attr(feature = "python", allow(clippy::useless_conversion))]
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyKeyError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::portfolio::{get_all_team_deltas_ref, get_portfolio_value_ref, PortfolioState, RiskSummary};
+use crate::tournament::seeded_rng;
+
+/// Collection of per-tournament portfolios valued together.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct PortfolioBook {
+    /// Event names, in insertion order.
+    #[pyo3(get)]
+    pub names: Vec<String>,
+
+    pub portfolios: Vec<PortfolioState>,
+
+    /// Multiplier applied to each event's points.
+    pub scales: Vec<f64>,
+}
+
+#[pymethods]
+impl PortfolioBook {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) the portfolio for event `name`.
+    #[pyo3(signature = (name, portfolio, scale = 1.0))]
+    pub fn add(&mut self, name: &str, portfolio: PortfolioState, scale: f64) {
+        match self.index(name) {
+            Some(i) => {
+                self.portfolios[i] = portfolio;
+                self.scales[i] = scale;
+            }
+            None => {
+                self.names.push(name.to_string());
+                self.portfolios.push(portfolio);
+                self.scales.push(scale);
+            }
+        }
+    }
+
+    /// Portfolio for event `name`.
+    #[cfg(feature = "python")]
+    pub fn get(&self, name: &str) -> PyResult<PortfolioState> {
+        self.portfolio(name).cloned().ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
+
+    /// Scaled expected value of each event's portfolio.
+    pub fn value_by_tournament(&self) -> HashMap<String, f64> {
+        self.names
+            .par_iter()
+            .zip(&self.portfolios)
+            .zip(&self.scales)
+            .map(|((name, portfolio), &scale)| (name.clone(), scale * portfolio.get_value()))
+            .collect()
+    }
+
+    /// Combined scaled expected value of the book.
+    pub fn get_value(&self) -> f64 {
+        self.value_by_tournament().values().sum()
+    }
+
+    /// Risk of the combined book from `n_simulations` joint simulations.
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn risk(&self, n_simulations: usize, seed: Option<u64>) -> RiskSummary {
+        RiskSummary::from_values(&self.simulated_values(n_simulations, seed))
+    }
+
+    /// Scaled portfolio deltas for every team, keyed by event then team.
+    #[pyo3(signature = (point_delta = 1.0))]
+    pub fn compute_deltas(&self, point_delta: f64) -> HashMap<String, HashMap<String, f64>> {
+        self.names
+            .iter()
+            .zip(&self.portfolios)
+            .zip(&self.scales)
+            .map(|((name, portfolio), &scale)| {
+                let (team_deltas, _) =
+                    get_all_team_deltas_ref(portfolio.positions(), portfolio.tournament(), point_delta);
+                let scaled = team_deltas.into_iter().map(|(team, d)| (team, d * scale)).collect();
+                (name.clone(), scaled)
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.names.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PortfolioBook({} tournaments)", self.names.len())
+    }
+}
+
+impl PortfolioBook {
+    /// Portfolio for event `name`, if there is one.
+    pub fn portfolio(&self, name: &str) -> Option<&PortfolioState> {
+        self.index(name).map(|i| &self.portfolios[i])
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Combined book value in each of `n_simulations` joint simulations.
+    ///
+    /// Each event gets its own seed stream derived from `seed`.
+    pub fn simulated_values(&self, n_simulations: usize, seed: Option<u64>) -> Vec<f64> {
+        let mut rng = seeded_rng(seed);
+        let event_seeds: Vec<u64> = self.portfolios.iter().map(|_| rng.gen()).collect();
+
+        let mut totals = vec![0.0; n_simulations];
+        for ((portfolio, &scale), &event_seed) in self.portfolios.iter().zip(&self.scales).zip(&event_seeds) {
+            let sims = portfolio.tournament().run_simulations(n_simulations, Some(event_seed));
+            for (total, sim) in totals.iter_mut().zip(&sims) {
+                *total += scale * get_portfolio_value_ref(portfolio.positions(), sim);
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::Team;
+    use crate::tournament::TournamentState;
+
+    fn make_portfolio(prefix: &str, shares: f64) -> PortfolioState {
+        let names: Vec<String> = (0..4).map(|i| format!("{prefix}{i}")).collect();
+        let ratings = names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.clone(), Team::new(n.clone(), 0.02 * i as f64, 0.0, 68.0, false)))
+            .collect();
+        let bracket = names.iter().map(|n| [(n.clone(), 1.0)].into_iter().collect()).collect();
+        let tournament = TournamentState::new(bracket, ratings, vec![1.0, 1.0], None, 0.0, None);
+        let positions = [(names[3].clone(), shares)].into_iter().collect();
+        PortfolioState::new(tournament, positions, 1.0)
+    }
+
+    #[test]
+    fn test_combined_value_applies_scales() {
+        let mut book = PortfolioBook::new();
+        let mens = make_portfolio("M", 10.0);
+        let womens = make_portfolio("W", 4.0);
+        let (mens_value, womens_value) = (mens.get_value(), womens.get_value());
+        book.add("mens", mens, 1.0);
+        book.add("womens", womens, 0.5);
+
+        assert!((book.get_value() - (mens_value + 0.5 * womens_value)).abs() < 1e-9);
+        assert_eq!(book.compute_deltas(1.0)["womens"].len(), 4);
+    }
+
+    #[test]
+    fn test_risk_mean_matches_value() {
+        let mut book = PortfolioBook::new();
+        book.add("mens", make_portfolio("M", 10.0), 1.0);
+        book.add("nit", make_portfolio("N", 10.0), 2.0);
+
+        let risk = book.risk(5000, Some(5));
+        assert!((risk.mean - book.get_value()).abs() < 1.0, "{} vs {}", risk.mean, book.get_value());
+        assert!(risk.stddev > 0.0);
+    }
+}
@@ -0,0 +1,612 @@
+//! Pre-Selection Sunday bracket projection.
+//!
+//! Before the field is announced, a team's value depends on whether it gets
+//! in, what seed it draws, and who lands in its region. `project_field` samples
+//! plausible brackets from the ratings: each sample perturbs every team's net
+//! rating with committee noise, selects the top of the resulting S-curve, and
+//! snakes it into regions. Expected scores are averaged over the samples, so
+//! positions can be priced before the bracket exists.
+//!
+//! Given conference tournaments, each sample also draws every conference's
+//! champion, whose automatic bid is guaranteed; the rest of the field is
+//! at-large teams from the top of the perceived ratings. `auto_bid_impact`
+//! measures how each conference tournament outcome moves the bubble.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use statrs::distribution::Normal;
+use std::collections::HashMap;
+
+use crate::constants::AVG_SCORING;
+use crate::error::{Result, TourneyError};
+use crate::seeding::standard_seed_order;
+use crate::team::Team;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// How the selection committee turns ratings into a bracket.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SeedingModel {
+    /// Teams selected (a power of two, divisible into `n_regions`).
+    #[pyo3(get, set)]
+    pub field_size: usize,
+
+    #[pyo3(get, set)]
+    pub n_regions: usize,
+
+    /// Standard deviation of the committee's view of each team's net rating
+    /// (points per 100 possessions) around the model's.
+    #[pyo3(get, set)]
+    pub noise: f64,
+}
+
+#[pymethods]
+impl SeedingModel {
+    #[new]
+    #[pyo3(signature = (field_size = 64, n_regions = 4, noise = 2.0))]
+    pub fn new(field_size: usize, n_regions: usize, noise: f64) -> Self {
+        SeedingModel {
+            field_size,
+            n_regions,
+            noise,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SeedingModel(field_size={}, n_regions={}, noise={})",
+            self.field_size, self.n_regions, self.noise
+        )
+    }
+}
+
+impl Default for SeedingModel {
+    fn default() -> Self {
+        SeedingModel::new(64, 4, 2.0)
+    }
+}
+
+impl SeedingModel {
+    fn validate(&self, n_teams: usize) -> Result<()> {
+        let region_size = self.field_size / self.n_regions.max(1);
+        if self.n_regions == 0
+            || !self.field_size.is_power_of_two()
+            || region_size * self.n_regions != self.field_size
+            || !region_size.is_power_of_two()
+        {
+            return Err(TourneyError::InvalidInput(format!(
+                "field of {} cannot be split into {} power-of-2 regions",
+                self.field_size, self.n_regions
+            )));
+        }
+        if n_teams < self.field_size {
+            return Err(TourneyError::InvalidInput(format!(
+                "{n_teams} rated teams cannot fill a field of {}",
+                self.field_size
+            )));
+        }
+        if self.noise.is_nan() || self.noise < 0.0 {
+            return Err(TourneyError::InvalidInput(format!("noise must be non-negative, got {}", self.noise)));
+        }
+        Ok(())
+    }
+
+    /// Team indices into `teams` from best to worst perceived rating.
+    fn perceived_order<R: Rng>(&self, teams: &[&Team], rng: &mut R) -> Vec<usize> {
+        let noise = Normal::new(0.0, self.noise).ok().filter(|_| self.noise > 0.0);
+        let mut perceived: Vec<(f64, usize)> = teams
+            .iter()
+            .enumerate()
+            .map(|(i, team)| {
+                let draw = noise.map_or(0.0, |n| rng.sample(n));
+                (team.net_rating(AVG_SCORING) + draw, i)
+            })
+            .collect();
+        perceived.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| teams[a.1].name.cmp(&teams[b.1].name)));
+        perceived.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// The selected field in S-curve order: every team in `champions`, plus
+    /// at-large teams from the top of `order` for the remaining spots.
+    fn select(&self, order: &[usize], champions: &[usize]) -> Vec<usize> {
+        let mut at_large = self.field_size - champions.len();
+        order
+            .iter()
+            .copied()
+            .filter(|team| {
+                if champions.contains(team) {
+                    return true;
+                }
+                let selected = at_large > 0;
+                at_large = at_large.saturating_sub(1);
+                selected
+            })
+            .collect()
+    }
+
+    /// Bracket slots for a field in S-curve order.
+    fn place(&self, s_curve: &[String]) -> Vec<String> {
+        let region_size = self.field_size / self.n_regions;
+        let slot_of_line: Vec<usize> = {
+            let order = standard_seed_order(region_size);
+            let mut inverse = vec![0; region_size];
+            for (slot, &line) in order.iter().enumerate() {
+                inverse[line] = slot;
+            }
+            inverse
+        };
+        let mut slots = vec![String::new(); self.field_size];
+        for (rank, team) in s_curve.iter().enumerate() {
+            let (line, pos) = (rank / self.n_regions, rank % self.n_regions);
+            // Serpentine: odd seed lines fill regions in reverse.
+            let region = if line % 2 == 0 { pos } else { self.n_regions - 1 - pos };
+            slots[region * region_size + slot_of_line[line]] = team.clone();
+        }
+        slots
+    }
+
+    /// Sample one bracket: the selected field in S-curve order and the slots.
+    fn sample<R: Rng>(&self, teams: &[&Team], auto_bids: Option<&AutoBids>, rng: &mut R) -> (Vec<String>, Vec<String>) {
+        let order = self.perceived_order(teams, rng);
+        let champions = auto_bids.map(|bids| bids.draw(rng)).unwrap_or_default();
+        let s_curve: Vec<String> =
+            self.select(&order, &champions).into_iter().map(|i| teams[i].name.clone()).collect();
+        let slots = self.place(&s_curve);
+        (s_curve, slots)
+    }
+}
+
+/// Rated teams in the fixed order noise draws are assigned in.
+fn sorted_teams(ratings: &HashMap<String, Team>) -> Vec<&Team> {
+    let mut teams: Vec<&Team> = ratings.values().collect();
+    teams.sort_by(|a, b| a.name.cmp(&b.name));
+    teams
+}
+
+/// Each conference's champion distribution, by index into the rated teams.
+struct AutoBids {
+    conferences: Vec<(String, Vec<(usize, f64)>)>,
+}
+
+impl AutoBids {
+    fn new(
+        conference_tournaments: &HashMap<String, TournamentState>,
+        teams: &[&Team],
+        model: &SeedingModel,
+    ) -> Result<Self> {
+        if conference_tournaments.len() > model.field_size {
+            return Err(TourneyError::InvalidInput(format!(
+                "{} automatic bids do not fit in a field of {}",
+                conference_tournaments.len(),
+                model.field_size
+            )));
+        }
+        let index: HashMap<&str, usize> = teams.iter().enumerate().map(|(i, t)| (t.name.as_str(), i)).collect();
+        let mut conference_of: HashMap<usize, &str> = HashMap::new();
+        let mut conferences: Vec<(String, Vec<(usize, f64)>)> = Vec::new();
+        for (conference, state) in conference_tournaments {
+            let mut champions = Vec::new();
+            for (team, prob) in champion_probs(state) {
+                let &i = index.get(team.as_str()).ok_or_else(|| {
+                    TourneyError::InvalidInput(format!("{conference}: {team} is not in the ratings"))
+                })?;
+                if let Some(other) = conference_of.insert(i, conference) {
+                    return Err(TourneyError::InvalidInput(format!("{team} plays in both {other} and {conference}")));
+                }
+                champions.push((i, prob));
+            }
+            conferences.push((conference.clone(), champions));
+        }
+        conferences.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(AutoBids { conferences })
+    }
+
+    /// One champion per conference, in conference order.
+    fn draw<R: Rng>(&self, rng: &mut R) -> Vec<usize> {
+        self.conferences
+            .iter()
+            .map(|(_, champions)| {
+                let mut u = rng.gen::<f64>();
+                for &(team, prob) in champions {
+                    u -= prob;
+                    if u < 0.0 {
+                        return team;
+                    }
+                }
+                champions.last().expect("every conference has a champion").0
+            })
+            .collect()
+    }
+}
+
+/// Probability of each team winning `state`'s tournament, by name.
+fn champion_probs(state: &TournamentState) -> Vec<(String, f64)> {
+    let mut probs: Vec<(String, f64)> = if state.bracket().len() == 1 {
+        state.bracket()[0].iter().map(|(team, &prob)| (team.clone(), prob)).collect()
+    } else {
+        let mut unit = state.clone();
+        unit.scoring = vec![1.0; state.bracket().len().ilog2() as usize + usize::from(state.play_in_round)];
+        unit.team_multipliers.clear();
+        let by_round = unit.calculate_scores_by_round();
+        by_round.into_iter().filter_map(|(team, rounds)| Some((team, *rounds.last()?))).collect()
+    };
+    probs.retain(|&(_, prob)| prob > 0.0);
+    probs.sort_by(|a, b| a.0.cmp(&b.0));
+    probs
+}
+
+/// Pre-bracket projections averaged over sampled brackets.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct BracketologyResult {
+    /// Expected tournament points, counting 0 when not selected.
+    #[pyo3(get)]
+    pub expected_scores: HashMap<String, f64>,
+
+    /// Probability of making the field.
+    #[pyo3(get)]
+    pub selection_prob: HashMap<String, f64>,
+
+    /// Probability of each seed line (index 0 = 1 seed), for selected teams.
+    #[pyo3(get)]
+    pub seed_probs: HashMap<String, Vec<f64>>,
+
+    #[pyo3(get)]
+    pub n_brackets: usize,
+}
+
+#[pymethods]
+impl BracketologyResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "BracketologyResult(teams={}, n_brackets={})",
+            self.expected_scores.len(),
+            self.n_brackets
+        )
+    }
+}
+
+/// One bracket drawn from `model`, as bracket slots.
+#[pyfunction]
+#[pyo3(signature = (ratings, model = None, seed = None))]
+pub fn sample_bracket(
+    ratings: HashMap<String, Team>,
+    model: Option<SeedingModel>,
+    seed: Option<u64>,
+) -> Result<Vec<HashMap<String, f64>>> {
+    let model = model.unwrap_or_default();
+    model.validate(ratings.len())?;
+    let (_, slots) = model.sample(&sorted_teams(&ratings), None, &mut seeded_rng(seed));
+    Ok(slots.into_iter().map(|team| [(team, 1.0)].into_iter().collect()).collect())
+}
+
+/// Expected scores, selection odds, and seed distributions from `n_brackets`
+/// brackets sampled with `model`, scored with `scoring`.
+///
+/// `conference_tournaments` (conference name to its tournament) adds
+/// automatic bids for each sampled conference champion.
+#[pyfunction]
+#[pyo3(signature = (ratings, scoring, model = None, n_brackets = 1000, seed = None, conference_tournaments = None))]
+pub fn project_field(
+    ratings: HashMap<String, Team>,
+    scoring: Vec<f64>,
+    model: Option<SeedingModel>,
+    n_brackets: usize,
+    seed: Option<u64>,
+    conference_tournaments: Option<HashMap<String, TournamentState>>,
+) -> Result<BracketologyResult> {
+    let model = model.unwrap_or_default();
+    model.validate(ratings.len())?;
+    let lines = model.field_size / model.n_regions;
+    let teams = sorted_teams(&ratings);
+    let auto_bids = conference_tournaments
+        .map(|tournaments| AutoBids::new(&tournaments, &teams, &model))
+        .transpose()?;
+
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_brackets).map(|_| rng.gen()).collect();
+    let samples: Vec<(Vec<String>, HashMap<String, f64>)> = seeds
+        .par_iter()
+        .map(|&bracket_seed| {
+            let (s_curve, slots) = model.sample(&teams, auto_bids.as_ref(), &mut seeded_rng(Some(bracket_seed)));
+            let bracket = slots.into_iter().map(|team| [(team, 1.0)].into_iter().collect()).collect();
+            let state = TournamentState::new(bracket, ratings.clone(), scoring.clone(), None, 0.0, None);
+            (s_curve, state.calculate_scores_prob())
+        })
+        .collect();
+
+    let mut expected_scores: HashMap<String, f64> = ratings.keys().map(|t| (t.clone(), 0.0)).collect();
+    let mut selection_prob = expected_scores.clone();
+    let mut seed_probs: HashMap<String, Vec<f64>> = HashMap::new();
+    for (s_curve, scores) in samples {
+        for (rank, team) in s_curve.iter().enumerate() {
+            *selection_prob.get_mut(team).expect("selected teams are rated") += 1.0;
+            seed_probs.entry(team.clone()).or_insert_with(|| vec![0.0; lines])[rank / model.n_regions] += 1.0;
+        }
+        for (team, score) in scores {
+            *expected_scores.get_mut(&team).expect("bracket teams are rated") += score;
+        }
+    }
+    // Totals are divided once at the end so that certain events come out as exactly 1.
+    let n = n_brackets.max(1) as f64;
+    expected_scores.values_mut().chain(selection_prob.values_mut()).for_each(|v| *v /= n);
+    seed_probs.values_mut().flatten().for_each(|p| *p /= n);
+
+    Ok(BracketologyResult {
+        expected_scores,
+        selection_prob,
+        seed_probs,
+        n_brackets,
+    })
+}
+
+/// How conference tournament outcomes change the selected field.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct AutoBidImpact {
+    /// Probability of winning the conference tournament and its automatic bid.
+    #[pyo3(get)]
+    pub auto_bid_prob: HashMap<String, f64>,
+
+    /// Probability of making the field by either route.
+    #[pyo3(get)]
+    pub selection_prob: HashMap<String, f64>,
+
+    /// Probability of making the field without winning the conference.
+    #[pyo3(get)]
+    pub at_large_prob: HashMap<String, f64>,
+
+    /// Conference -> possible champion -> each bubble team's probability of
+    /// making the field given that champion. Bubble teams are those with a
+    /// `selection_prob` strictly between 0 and 1.
+    #[pyo3(get)]
+    pub selection_given_champion: HashMap<String, HashMap<String, HashMap<String, f64>>>,
+
+    /// Conference -> possible champion -> probability that its automatic bid
+    /// goes to a team the committee would not have taken at-large, costing
+    /// the last at-large team its place ("bid stealing").
+    #[pyo3(get)]
+    pub bid_steal_prob: HashMap<String, HashMap<String, f64>>,
+
+    #[pyo3(get)]
+    pub n_brackets: usize,
+}
+
+#[pymethods]
+impl AutoBidImpact {
+    fn __repr__(&self) -> String {
+        format!(
+            "AutoBidImpact(conferences={}, n_brackets={})",
+            self.bid_steal_prob.len(),
+            self.n_brackets
+        )
+    }
+}
+
+/// Counts accumulated over sampled fields, indexed like the rated teams; the
+/// conditional counts are indexed by candidate champion in conference order.
+#[derive(Clone)]
+struct FieldTally {
+    selected: Vec<u32>,
+    at_large: Vec<u32>,
+    given_champion: Vec<Vec<u32>>,
+    steals: Vec<u32>,
+}
+
+impl FieldTally {
+    fn new(n_teams: usize, n_candidates: usize) -> Self {
+        FieldTally {
+            selected: vec![0; n_teams],
+            at_large: vec![0; n_teams],
+            given_champion: vec![vec![0; n_teams]; n_candidates],
+            steals: vec![0; n_candidates],
+        }
+    }
+
+    fn merge(mut self, other: FieldTally) -> Self {
+        let add = |a: &mut Vec<u32>, b: Vec<u32>| a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+        add(&mut self.selected, other.selected);
+        add(&mut self.at_large, other.at_large);
+        add(&mut self.steals, other.steals);
+        for (a, b) in self.given_champion.iter_mut().zip(other.given_champion) {
+            add(a, b);
+        }
+        self
+    }
+}
+
+/// Selection odds from `n_brackets` fields sampled with `model` and the
+/// conference champions drawn from `conference_tournaments`, and how each
+/// possible champion changes them.
+///
+/// Conditional odds hold each sample's committee view and other champions
+/// fixed while swapping in each candidate champion, so even unlikely
+/// champions' effects are measured on every sample.
+#[pyfunction]
+#[pyo3(signature = (ratings, conference_tournaments, model = None, n_brackets = 1000, seed = None))]
+pub fn auto_bid_impact(
+    ratings: HashMap<String, Team>,
+    conference_tournaments: HashMap<String, TournamentState>,
+    model: Option<SeedingModel>,
+    n_brackets: usize,
+    seed: Option<u64>,
+) -> Result<AutoBidImpact> {
+    let model = model.unwrap_or_default();
+    model.validate(ratings.len())?;
+    let teams = sorted_teams(&ratings);
+    let auto_bids = AutoBids::new(&conference_tournaments, &teams, &model)?;
+    let n_candidates: usize = auto_bids.conferences.iter().map(|(_, champions)| champions.len()).sum();
+    let n_at_large = model.field_size - auto_bids.conferences.len();
+
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_brackets).map(|_| rng.gen()).collect();
+    let tally = seeds
+        .par_iter()
+        .fold(
+            || FieldTally::new(teams.len(), n_candidates),
+            |mut tally, &bracket_seed| {
+                let mut rng = seeded_rng(Some(bracket_seed));
+                let order = model.perceived_order(&teams, &mut rng);
+                let mut champions = auto_bids.draw(&mut rng);
+                for team in model.select(&order, &champions) {
+                    tally.selected[team] += 1;
+                    tally.at_large[team] += u32::from(!champions.contains(&team));
+                }
+
+                let mut candidate = 0;
+                for (k, (_, candidates)) in auto_bids.conferences.iter().enumerate() {
+                    // The first `n_at_large + 1` teams not champion elsewhere:
+                    // the at-large field plus the first team out.
+                    let actual = champions[k];
+                    champions[k] = usize::MAX;
+                    let line: Vec<usize> = order
+                        .iter()
+                        .copied()
+                        .filter(|team| !champions.contains(team))
+                        .take(n_at_large + 1)
+                        .collect();
+                    for &(champion, _) in candidates {
+                        let steal = !line[..n_at_large].contains(&champion);
+                        tally.steals[candidate] += u32::from(steal);
+                        let at_large = if steal { &line[..n_at_large] } else { &line[..] };
+                        let given = &mut tally.given_champion[candidate];
+                        for &team in champions.iter().filter(|&&team| team != usize::MAX).chain(at_large) {
+                            given[team] += 1;
+                        }
+                        if steal {
+                            given[champion] += 1;
+                        }
+                        candidate += 1;
+                    }
+                    champions[k] = actual;
+                }
+                tally
+            },
+        )
+        .reduce(|| FieldTally::new(teams.len(), n_candidates), FieldTally::merge);
+
+    let n = n_brackets.max(1) as f64;
+    let by_team = |counts: &[u32]| -> HashMap<String, f64> {
+        teams.iter().zip(counts).map(|(team, &count)| (team.name.clone(), f64::from(count) / n)).collect()
+    };
+    let selection_prob = by_team(&tally.selected);
+    let bubble: Vec<usize> =
+        (0..teams.len()).filter(|&i| tally.selected[i] > 0 && (tally.selected[i] as usize) < n_brackets).collect();
+
+    let mut auto_bid_prob: HashMap<String, f64> = teams.iter().map(|team| (team.name.clone(), 0.0)).collect();
+    let mut selection_given_champion = HashMap::new();
+    let mut bid_steal_prob = HashMap::new();
+    let mut candidate = 0;
+    for (conference, candidates) in &auto_bids.conferences {
+        let (mut given, mut steals) = (HashMap::new(), HashMap::new());
+        for &(champion, prob) in candidates {
+            let name = &teams[champion].name;
+            auto_bid_prob.insert(name.clone(), prob);
+            let counts = &tally.given_champion[candidate];
+            let odds = bubble.iter().map(|&i| (teams[i].name.clone(), f64::from(counts[i]) / n)).collect();
+            given.insert(name.clone(), odds);
+            steals.insert(name.clone(), f64::from(tally.steals[candidate]) / n);
+            candidate += 1;
+        }
+        selection_given_champion.insert(conference.clone(), given);
+        bid_steal_prob.insert(conference.clone(), steals);
+    }
+
+    Ok(AutoBidImpact {
+        auto_bid_prob,
+        selection_prob,
+        at_large_prob: by_team(&tally.at_large),
+        selection_given_champion,
+        bid_steal_prob,
+        n_brackets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariants::available_points;
+    use crate::testing::{canonical_ratings, canonical_tournament};
+
+    #[test]
+    fn test_noiseless_bracket_is_s_curve() {
+        let model = SeedingModel::new(16, 4, 0.0);
+        let bracket = sample_bracket(canonical_ratings(20), Some(model.clone()), Some(1)).unwrap();
+        // 1 seeds open each region.
+        let opener = |slot: usize| bracket[slot].keys().next().unwrap().clone();
+        assert_eq!(opener(0), "Team 01");
+        assert_eq!(opener(4), "Team 02");
+        assert_eq!(opener(12), "Team 04");
+        // Serpentine: the best 2 seed (rank 5) joins the worst 1 seed's region.
+        assert!((12..16).any(|slot| opener(slot) == "Team 05"));
+
+        let scoring = vec![1.0, 1.0, 2.0, 2.0];
+        let result = project_field(canonical_ratings(20), scoring, Some(model), 20, Some(2), None).unwrap();
+        assert_eq!(result.selection_prob["Team 16"], 1.0);
+        assert_eq!(result.selection_prob["Team 17"], 0.0);
+        assert_eq!(result.seed_probs["Team 01"][0], 1.0);
+        let total: f64 = result.expected_scores.values().sum();
+        let mut reference = canonical_tournament(16);
+        reference.scoring = vec![1.0, 1.0, 2.0, 2.0];
+        assert!((total - available_points(&reference)).abs() < 1e-9);
+    }
+
+    fn conference(ratings: &HashMap<String, Team>, teams: &[&str]) -> TournamentState {
+        let bracket = teams.iter().map(|team| [(team.to_string(), 1.0)].into_iter().collect()).collect();
+        TournamentState::new(bracket, ratings.clone(), vec![1.0; 4], None, 0.0, None)
+    }
+
+    #[test]
+    fn test_auto_bid_steals_bubble_spot() {
+        let ratings = canonical_ratings(20);
+        let tournaments: HashMap<String, TournamentState> =
+            [("Mid".to_string(), conference(&ratings, &["Team 12", "Team 20"]))].into_iter().collect();
+        let model = SeedingModel::new(16, 4, 0.0);
+        let impact = auto_bid_impact(ratings.clone(), tournaments.clone(), Some(model.clone()), 400, Some(5)).unwrap();
+
+        let upset = impact.auto_bid_prob["Team 20"];
+        assert!(upset > 0.0 && upset < 0.5);
+        assert_eq!(impact.bid_steal_prob["Mid"]["Team 20"], 1.0);
+        assert_eq!(impact.bid_steal_prob["Mid"]["Team 12"], 0.0);
+        // Without noise the only bubble teams are the thief and its victim.
+        let given = &impact.selection_given_champion["Mid"];
+        assert_eq!(given["Team 20"].len(), 2);
+        assert_eq!(given["Team 20"]["Team 16"], 0.0);
+        assert_eq!(given["Team 12"]["Team 16"], 1.0);
+        assert!((impact.selection_prob["Team 16"] + impact.selection_prob["Team 20"] - 1.0).abs() < 1e-12);
+        assert_eq!(impact.at_large_prob["Team 12"], impact.selection_prob["Team 20"]);
+
+        let projected = project_field(ratings.clone(), vec![1.0; 4], Some(model), 400, Some(5), Some(tournaments));
+        assert_eq!(projected.unwrap().selection_prob["Team 20"], impact.selection_prob["Team 20"]);
+    }
+
+    #[test]
+    fn test_auto_bid_validation() {
+        let ratings = canonical_ratings(20);
+        let unrated = conference(&canonical_ratings(24), &["Team 01", "Team 24"]);
+        let model = SeedingModel::new(16, 4, 0.0);
+        let tournaments = [("A".to_string(), unrated)].into_iter().collect();
+        assert!(auto_bid_impact(ratings.clone(), tournaments, Some(model.clone()), 10, None).is_err());
+
+        let tournaments = [
+            ("A".to_string(), conference(&ratings, &["Team 01", "Team 02"])),
+            ("B".to_string(), conference(&ratings, &["Team 02", "Team 03"])),
+        ];
+        let tournaments = tournaments.into_iter().collect();
+        assert!(auto_bid_impact(ratings, tournaments, Some(model), 10, None).is_err());
+    }
+
+    #[test]
+    fn test_noise_spreads_selection() {
+        let model = SeedingModel::new(16, 4, 5.0);
+        let result = project_field(canonical_ratings(24), vec![1.0; 4], Some(model), 400, Some(3), None).unwrap();
+        let bubble = result.selection_prob["Team 16"];
+        assert!(bubble > 0.0 && bubble < 1.0);
+        assert!(result.selection_prob["Team 01"] > result.selection_prob["Team 20"]);
+        let too_big = SeedingModel::new(16, 4, 1.0);
+        assert!(project_field(canonical_ratings(8), vec![1.0; 4], Some(too_big), 1, None, None).is_err());
+        assert!(sample_bracket(canonical_ratings(16), Some(SeedingModel::new(16, 3, 1.0)), None).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Step-by-step construction of a `TournamentState`.
+//!
+//! `TournamentState::new` takes its inputs positionally, and `scoring` and
+//! `overrides` are easy to swap. `TournamentStateBuilder` names each input and
+//! checks the result at `build`, the way `TournamentState::from_files` checks
+//! a state read from disk.
+
+use std::collections::HashMap;
+
+use crate::constants::ROUND_POINTS;
+use crate::error::{Result, TourneyError};
+use crate::overrides::OverridesMap;
+use crate::team::Team;
+use crate::tournament::{check_ratings, TournamentState};
+use crate::win_prob::ModelParams;
+
+/// Builder for `TournamentState`; see `TournamentState::builder`.
+///
+/// `bracket` and `ratings` are required. `scoring` defaults to
+/// `ROUND_POINTS`, `overrides` to none, `forfeit_prob` to 0, and `model` to
+/// `ModelParams::default()`.
+#[derive(Clone, Debug, Default)]
+pub struct TournamentStateBuilder {
+    bracket: Option<Vec<HashMap<String, f64>>>,
+    ratings: Option<HashMap<String, Team>>,
+    scoring: Option<Vec<f64>>,
+    overrides: Option<OverridesMap>,
+    forfeit_prob: f64,
+    model: Option<ModelParams>,
+}
+
+impl TournamentStateBuilder {
+    pub fn new() -> Self {
+        TournamentStateBuilder::default()
+    }
+
+    /// Bracket slots, each a map of team to probability of holding the slot.
+    pub fn bracket(mut self, bracket: Vec<HashMap<String, f64>>) -> Self {
+        self.bracket = Some(bracket);
+        self
+    }
+
+    /// Ratings by team name.
+    pub fn ratings(mut self, ratings: HashMap<String, Team>) -> Self {
+        self.ratings = Some(ratings);
+        self
+    }
+
+    /// Points per round won, starting with the first round.
+    pub fn scoring(mut self, scoring: Vec<f64>) -> Self {
+        self.scoring = Some(scoring);
+        self
+    }
+
+    pub fn overrides(mut self, overrides: OverridesMap) -> Self {
+        self.overrides = Some(overrides);
+        self
+    }
+
+    /// Probability that a team forfeits any given game.
+    pub fn forfeit_prob(mut self, forfeit_prob: f64) -> Self {
+        self.forfeit_prob = forfeit_prob;
+        self
+    }
+
+    /// Win probability model parameters.
+    pub fn model(mut self, model: ModelParams) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// The state, once every bracket team has a finite rating, `scoring`
+    /// covers every round with finite points, and the state passes
+    /// `TournamentState::validate` (which checks `forfeit_prob` and `model`
+    /// before `verify_invariants`).
+    pub fn build(self) -> Result<TournamentState> {
+        let missing = |step: &str| TourneyError::InvalidInput(format!("TournamentState needs {step}"));
+        let bracket = self.bracket.ok_or_else(|| missing("a bracket"))?;
+        let ratings = self.ratings.ok_or_else(|| missing("ratings"))?;
+        if bracket.is_empty() {
+            return Err(TourneyError::InvalidInput("bracket has no slots".to_string()));
+        }
+        check_ratings(&bracket, &ratings)?;
+
+        let scoring = self.scoring.unwrap_or_else(|| ROUND_POINTS.to_vec());
+        let n_rounds = bracket.len().ilog2() as usize;
+        if scoring.len() < n_rounds {
+            return Err(TourneyError::InvalidInput(format!(
+                "scoring has {} rounds but the bracket has {n_rounds}",
+                scoring.len()
+            )));
+        }
+        if let Some(points) = scoring.iter().find(|points| !points.is_finite()) {
+            return Err(TourneyError::InvalidInput(format!("round points must be finite, got {points}")));
+        }
+
+        let mut state = TournamentState::new(bracket, ratings, scoring, self.overrides, self.forfeit_prob, None);
+        if let Some(model) = self.model {
+            state.model_params = model;
+        }
+        state.validate()?;
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_build_matches_positional_constructor() {
+        let reference = canonical_tournament(8);
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("Team 01", "Team 08", 0.8).unwrap();
+        let mut model = ModelParams::default();
+        model.set_confidence(0.9).unwrap();
+
+        let state = TournamentState::builder()
+            .bracket(reference.bracket().to_vec())
+            .ratings(reference.ratings.clone())
+            .overrides(overrides.clone())
+            .forfeit_prob(0.01)
+            .model(model.clone())
+            .build()
+            .unwrap();
+        let mut expected = TournamentState::new(
+            reference.bracket().to_vec(),
+            reference.ratings.clone(),
+            ROUND_POINTS.to_vec(),
+            Some(overrides),
+            0.01,
+            None,
+        );
+        expected.model_params = model;
+        assert_eq!(state.calculate_scores_prob(), expected.calculate_scores_prob());
+    }
+
+    #[test]
+    fn test_build_validates() {
+        let reference = canonical_tournament(8);
+        let builder = TournamentState::builder()
+            .bracket(reference.bracket().to_vec())
+            .ratings(reference.ratings.clone());
+        assert!(builder.clone().build().is_ok());
+
+        assert!(TournamentState::builder().ratings(reference.ratings.clone()).build().is_err());
+        assert!(builder.clone().scoring(vec![1.0, 2.0]).build().is_err());
+        assert!(builder.clone().scoring(vec![1.0, f64::NAN, 2.0]).build().is_err());
+        assert!(builder.clone().forfeit_prob(1.5).build().is_err());
+
+        let mut ratings = reference.ratings.clone();
+        ratings.remove("Team 03");
+        let err = builder.clone().ratings(ratings).build().err().unwrap();
+        assert!(err.to_string().contains("Team 03"), "{err}");
+        let mut ratings = reference.ratings.clone();
+        ratings.get_mut("Team 05").unwrap().offense = f64::INFINITY;
+        let err = builder.clone().ratings(ratings).build().err().unwrap();
+        assert!(err.to_string().contains("Team 05"), "{err}");
+
+        let mut bracket = reference.bracket().to_vec();
+        bracket.truncate(6);
+        assert!(builder.clone().bracket(bracket).build().is_err());
+    }
+
+    #[test]
+    fn test_build_rejects_bad_model() {
+        let reference = canonical_tournament(8);
+        let builder = TournamentState::builder()
+            .bracket(reference.bracket().to_vec())
+            .ratings(reference.ratings.clone());
+
+        let mut model = ModelParams::default();
+        model.scoring_stddev = f64::NAN;
+        let err = builder.clone().model(model).build().err().unwrap();
+        assert!(err.to_string().contains("scoring_stddev"), "{err}");
+
+        let mut model = ModelParams::default();
+        model.avg_tempo = 0.0;
+        assert!(builder.clone().model(model).build().is_err());
+
+        let model: ModelParams =
+            serde_json::from_str(r#"{"avg_scoring": 105.0, "avg_tempo": 67.7, "scoring_stddev": 11.0, "confidence": -1.0}"#)
+                .unwrap();
+        assert!(builder.model(model).build().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Fitting the model's `confidence` to observed results.
+//!
+//! `ModelParams::confidence` scales the log-odds of every model probability.
+//! Given predictions made at confidence 1 and what actually happened, the
+//! maximum likelihood confidence is a one-parameter logistic regression of the
+//! outcomes on the predicted log-odds (no intercept), which Newton's method
+//! solves in a few steps: a fit below 1 means the model was overconfident,
+//! above 1 underconfident.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::error::{Result, TourneyError};
+use crate::win_prob::apply_confidence;
+
+/// Largest confidence a fit returns. When the model called every result
+/// right, the likelihood keeps rising with confidence; the fit returns this
+/// instead.
+pub const MAX_CONFIDENCE: f64 = 100.0;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+/// Maximum likelihood confidence for predictions `probs` (each the
+/// probability that an event happens, at confidence 1) and `outcomes`
+/// (whether it did). Certain predictions carry no information about the
+/// confidence and are skipped.
+#[pyfunction]
+pub fn fit_confidence(probs: Vec<f64>, outcomes: Vec<bool>) -> Result<f64> {
+    if probs.len() != outcomes.len() {
+        return Err(TourneyError::InvalidInput(format!(
+            "got {} probabilities but {} outcomes",
+            probs.len(),
+            outcomes.len()
+        )));
+    }
+    if let Some(p) = probs.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+        return Err(TourneyError::InvalidInput(format!("probabilities must be in [0, 1], got {p}")));
+    }
+    let games: Vec<(f64, f64)> = probs
+        .iter()
+        .zip(&outcomes)
+        .filter(|(p, _)| **p > 0.0 && **p < 1.0)
+        .map(|(p, &happened)| ((p / (1.0 - p)).ln(), f64::from(u8::from(happened))))
+        .filter(|(logit, _)| *logit != 0.0)
+        .collect();
+    if games.is_empty() {
+        return Err(TourneyError::InvalidInput(
+            "need at least one prediction other than 0, 0.5, or 1 to fit confidence".to_string(),
+        ));
+    }
+
+    if games.iter().all(|&(logit, outcome)| (logit > 0.0) == (outcome == 1.0)) {
+        return Ok(MAX_CONFIDENCE);
+    }
+
+    let mut confidence: f64 = 1.0;
+    for _ in 0..MAX_ITERATIONS {
+        let (mut gradient, mut curvature) = (0.0, 0.0);
+        for &(logit, outcome) in &games {
+            let p = 1.0 / (1.0 + (-confidence * logit).exp());
+            gradient += logit * (outcome - p);
+            curvature += logit * logit * p * (1.0 - p);
+        }
+        let next = (confidence + gradient / curvature.max(f64::MIN_POSITIVE)).clamp(0.0, MAX_CONFIDENCE);
+        let step = (next - confidence).abs();
+        confidence = next;
+        if step < TOLERANCE {
+            break;
+        }
+    }
+    Ok(confidence)
+}
+
+/// Mean log loss of `probs` against `outcomes` after scaling by `confidence`.
+pub fn log_loss(probs: &[f64], outcomes: &[bool], confidence: f64) -> f64 {
+    let total: f64 = probs
+        .iter()
+        .zip(outcomes)
+        .map(|(&p, &happened)| {
+            let p = apply_confidence(p, confidence);
+            -(if happened { p } else { 1.0 - p }).max(f64::MIN_POSITIVE).ln()
+        })
+        .sum();
+    total / probs.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test_recovers_known_confidence() {
+        // Outcomes drawn from probabilities shrunk to confidence 0.6.
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let probs: Vec<f64> = (0..20_000).map(|_| rng.gen_range(0.02..0.98)).collect();
+        let outcomes: Vec<bool> = probs.iter().map(|&p| rng.gen::<f64>() < apply_confidence(p, 0.6)).collect();
+        let fit = fit_confidence(probs.clone(), outcomes.clone()).unwrap();
+        assert!((fit - 0.6).abs() < 0.05, "fit {fit}");
+        assert!(log_loss(&probs, &outcomes, fit) <= log_loss(&probs, &outcomes, 1.0));
+
+        // Every favorite won: as confident as the fit allows.
+        assert_eq!(fit_confidence(vec![0.7, 0.2], vec![true, false]).unwrap(), MAX_CONFIDENCE);
+        assert!(fit_confidence(vec![0.7], vec![true, false]).is_err());
+        assert!(fit_confidence(vec![1.0, 0.5], vec![true, false]).is_err());
+    }
+
+    #[test]
+    fn test_state_fit_ignores_current_confidence() {
+        let mut state = canonical_tournament(16);
+        let teams = state.get_bracket_teams();
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let mut games = Vec::new();
+        for _ in 0..2000 {
+            let (i, j) = (rng.gen_range(0..16), rng.gen_range(0..16));
+            if i == j {
+                continue;
+            }
+            // Results no better than coin flips.
+            let (winner, loser) = if rng.gen::<bool>() { (i, j) } else { (j, i) };
+            games.push((teams[winner].clone(), teams[loser].clone()));
+        }
+        let fit = state.fit_confidence(games.clone()).unwrap();
+        assert!(fit < 0.2, "fit {fit}");
+        state.set_confidence(0.5).unwrap();
+        assert_eq!(state.fit_confidence(games).unwrap(), fit);
+        assert!(state.fit_confidence(vec![("Team 01".to_string(), "Nobody".to_string())]).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! Paired simulation of two tournament scenarios.
+//!
+//! Simulating two states independently and differencing the means buries
+//! small effects (a single override, a rating tweak) under sampling noise from
+//! both runs. `compare_states_sim` uses common random numbers instead: each
+//! game in each simulation draws from its own RNG stream, keyed by the
+//! simulation and the game's position in the bracket, so both states see the
+//! same draws game for game and only outcomes the change actually flips differ.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::game_transform::{game_transform_sim_with, resolve_game_to_winner};
+use crate::reduce::DETERMINISTIC_CHUNK;
+use crate::tournament::{reseed_games, seeded_rng, TournamentState};
+use crate::trace::trace_span;
+
+/// One team's paired score difference between two scenarios.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct PairedDifference {
+    #[pyo3(get)]
+    pub team: String,
+
+    /// Mean simulated score in the first state.
+    #[pyo3(get)]
+    pub mean_a: f64,
+
+    /// Mean simulated score in the second state.
+    #[pyo3(get)]
+    pub mean_b: f64,
+
+    /// Mean of the per-simulation differences, `mean_a - mean_b`.
+    #[pyo3(get)]
+    pub diff: f64,
+
+    /// Standard error of `diff`.
+    #[pyo3(get)]
+    pub std_error: f64,
+}
+
+#[pymethods]
+impl PairedDifference {
+    /// `diff` in units of its standard error (0 when the difference is exact).
+    #[getter]
+    pub fn z_score(&self) -> f64 {
+        if self.std_error > 0.0 {
+            self.diff / self.std_error
+        } else {
+            0.0
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PairedDifference({}, diff={:+.4} ± {:.4}, a={:.4}, b={:.4})",
+            self.team, self.diff, self.std_error, self.mean_a, self.mean_b
+        )
+    }
+}
+
+/// Simulate `state_a` and `state_b` with common random numbers and return
+/// each team's paired score difference, largest absolute difference first.
+///
+/// Both states must have brackets of the same size; teams missing from one
+/// state's bracket score 0 there.
+#[pyfunction]
+#[pyo3(signature = (state_a, state_b, n_simulations = 10000, seed = None))]
+pub fn compare_states_sim(
+    state_a: &TournamentState,
+    state_b: &TournamentState,
+    n_simulations: usize,
+    seed: Option<u64>,
+) -> Result<Vec<PairedDifference>> {
+    if state_a.bracket().len() != state_b.bracket().len() {
+        return Err(TourneyError::InvalidInput(format!(
+            "brackets differ in size ({} vs {} slots)",
+            state_a.bracket().len(),
+            state_b.bracket().len()
+        )));
+    }
+
+    if [state_a, state_b].iter().any(|state| state.persistent_forfeits && state.forfeit_prob > 0.0) {
+        // Forfeits are drawn per game node, with no per-team stream to fix them on.
+        return Err(TourneyError::InvalidInput("paired simulation does not support persistent forfeits".to_string()));
+    }
+
+    trace_span!(INFO, "compare_states_sim", n_simulations);
+    let mut teams = state_a.get_bracket_teams();
+    for team in state_b.get_bracket_teams() {
+        if !teams.contains(&team) {
+            teams.push(team);
+        }
+    }
+    let index: HashMap<&str, usize> = teams.iter().enumerate().map(|(i, t)| (t.as_str(), i)).collect();
+
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+
+    // Per team: sums of a, b, (a - b), and (a - b)^2. Fixed-size chunks
+    // combined in order keep the result independent of thread count.
+    let zero = || vec![[0.0f64; 4]; teams.len()];
+    let partials: Vec<Vec<[f64; 4]>> = seeds
+        .par_chunks(DETERMINISTIC_CHUNK)
+        .map(|chunk| {
+            let mut acc = zero();
+            for &sim_seed in chunk {
+                let mut a = vec![0.0; teams.len()];
+                let mut b = vec![0.0; teams.len()];
+                for (team, score) in simulate_paired(state_a, sim_seed) {
+                    a[index[team.as_str()]] += score;
+                }
+                for (team, score) in simulate_paired(state_b, sim_seed) {
+                    b[index[team.as_str()]] += score;
+                }
+                for (t, sums) in acc.iter_mut().enumerate() {
+                    let d = a[t] - b[t];
+                    sums[0] += a[t];
+                    sums[1] += b[t];
+                    sums[2] += d;
+                    sums[3] += d * d;
+                }
+            }
+            acc
+        })
+        .collect();
+    let sums = partials.into_iter().fold(zero(), |mut total, partial| {
+        for (l, r) in total.iter_mut().zip(partial) {
+            for k in 0..4 {
+                l[k] += r[k];
+            }
+        }
+        total
+    });
+
+    let n = n_simulations.max(1) as f64;
+    let mut diffs: Vec<PairedDifference> = teams
+        .into_iter()
+        .zip(sums)
+        .map(|(team, [sum_a, sum_b, sum_d, sum_d2])| {
+            let diff = sum_d / n;
+            let variance = if n > 1.0 { ((sum_d2 - n * diff * diff) / (n - 1.0)).max(0.0) } else { 0.0 };
+            PairedDifference {
+                team,
+                mean_a: sum_a / n,
+                mean_b: sum_b / n,
+                diff,
+                std_error: (variance / n).sqrt(),
+            }
+        })
+        .collect();
+    diffs.sort_by(|a, b| b.diff.abs().total_cmp(&a.diff.abs()).then_with(|| a.team.cmp(&b.team)));
+    Ok(diffs)
+}
+
+/// One simulation of `state` in which every bracket node draws from its own
+/// stream of `sim_seed`'s RNG, so the draws do not depend on earlier outcomes.
+fn simulate_paired(state: &TournamentState, sim_seed: u64) -> HashMap<String, f64> {
+    let node_rng = |level: usize, index: usize| -> ChaCha8Rng {
+        let mut rng = seeded_rng(Some(sim_seed));
+        rng.set_stream(((level as u64) << 32) | index as u64);
+        rng
+    };
+    let offset = usize::from(state.play_in_round);
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    let mut award = |team: &str, scoring_round: usize| {
+        let points = state.scoring.get(scoring_round).copied().unwrap_or(1.0);
+        *scores.entry(team.to_string()).or_insert(0.0) += points * state.team_multiplier(team);
+    };
+
+    let reseed_keys = if state.reseed { state.reseed_keys() } else { HashMap::new() };
+    let mut games = state.bracket().to_vec();
+    if state.play_in_round {
+        for (i, game) in games.iter_mut().enumerate().filter(|(_, game)| game.len() > 1) {
+            let winner = resolve_game_to_winner(game, &mut node_rng(0, i));
+            award(&winner, 0);
+            *game = [(winner, 1.0)].into_iter().collect();
+        }
+    }
+
+    let mut level = 1;
+    while games.len() > 1 {
+        if state.reseed && level > 1 {
+            games = reseed_games(games, &reseed_keys);
+        }
+        games = games
+            .chunks(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let parent = game_transform_sim_with(
+                    &pair[0],
+                    &pair[1],
+                    &state.ratings,
+                    Some(&state.overrides),
+                    state.forfeit_prob,
+                    &state.model_params,
+                    &mut node_rng(level, i),
+                );
+                for team in parent.keys() {
+                    award(team, level - 1 + offset);
+                }
+                parent
+            })
+            .collect();
+        level += 1;
+    }
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_identical_states_have_zero_difference() {
+        let state = canonical_tournament(16);
+        let diffs = compare_states_sim(&state, &state, 500, Some(3)).unwrap();
+        assert_eq!(diffs.len(), 16);
+        assert!(diffs.iter().all(|d| d.diff == 0.0 && d.std_error == 0.0));
+        let expected = state.calculate_scores_prob();
+        let top = diffs.iter().find(|d| d.team == "Team 01").unwrap();
+        assert!((top.mean_a - expected["Team 01"]).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_override_effect_is_paired() {
+        let state_a = canonical_tournament(16);
+        let mut state_b = state_a.clone();
+        // Team 01 opens against Team 13 in region 1.
+        state_b.overrides.add_override("Team 01", "Team 13", 0.5).unwrap();
+
+        let diffs = compare_states_sim(&state_a, &state_b, 2000, Some(9)).unwrap();
+        let top = diffs.iter().find(|d| d.team == "Team 01").unwrap();
+        assert!(top.diff > 0.0 && top.z_score() > 3.0);
+        // Teams in other regions are only affected once they could meet Team 01.
+        let far = diffs.iter().find(|d| d.team == "Team 02").unwrap();
+        assert!(far.std_error < top.std_error);
+
+        let small = canonical_tournament(4);
+        assert!(compare_states_sim(&state_a, &small, 10, None).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Simulations conditioned on bracket outcomes.
+//!
+//! A condition is a set of `(team, round)` requirements, each met when the
+//! team wins its game in that main-bracket round (so round 3 of a 64-team
+//! bracket is "reaches the Final Four"). Simulations are rejection-sampled:
+//! unconditional simulations are drawn in seed order and those meeting every
+//! requirement are kept, which gives the exact conditional distribution at
+//! the cost of discarding the rest. The acceptance rate doubles as an
+//! estimate of the condition's probability.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::game_id::Round;
+use crate::sim::SimContext;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// Fewest simulations drawn per batch.
+const MIN_BATCH: usize = 256;
+
+/// Default cap on simulations drawn, per simulation requested.
+const DEFAULT_ATTEMPTS_PER_SIMULATION: usize = 1000;
+
+/// Simulations that met a condition.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ConditionalSimulations {
+    /// Per-simulation scores in the shape `run_simulations` returns.
+    #[pyo3(get)]
+    pub simulations: Vec<HashMap<String, f64>>,
+
+    /// Simulations drawn to find them.
+    #[pyo3(get)]
+    pub attempts: usize,
+}
+
+#[pymethods]
+impl ConditionalSimulations {
+    /// Fraction of drawn simulations that met the condition: an estimate of
+    /// its probability.
+    #[getter]
+    pub fn acceptance_rate(&self) -> f64 {
+        self.simulations.len() as f64 / self.attempts.max(1) as f64
+    }
+
+    /// Mean score per team over the accepted simulations.
+    pub fn expected_scores(&self) -> HashMap<String, f64> {
+        let n = self.simulations.len().max(1) as f64;
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for sim in &self.simulations {
+            for (team, score) in sim {
+                *totals.entry(team.clone()).or_insert(0.0) += score;
+            }
+        }
+        totals.values_mut().for_each(|total| *total /= n);
+        totals
+    }
+
+    fn __len__(&self) -> usize {
+        self.simulations.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConditionalSimulations({} accepted of {} attempts)",
+            self.simulations.len(),
+            self.attempts
+        )
+    }
+}
+
+/// Up to `n_simulations` simulations of `state` meeting every requirement in
+/// `condition`, drawing at most `max_attempts` (default 1000 per simulation
+/// requested). Fewer are returned if the cap is reached first.
+pub fn simulate_conditional(
+    state: &TournamentState,
+    n_simulations: usize,
+    condition: &[(String, Round)],
+    seed: Option<u64>,
+    max_attempts: Option<usize>,
+) -> Result<ConditionalSimulations> {
+    let teams = state.bracket_teams();
+    let n_slots = state.bracket().len();
+    let offset = usize::from(state.play_in_round);
+    let requirements = condition
+        .iter()
+        .map(|(team, round)| {
+            let index = teams
+                .iter()
+                .position(|t| t == team)
+                .ok_or_else(|| TourneyError::InvalidInput(format!("{team} is not in the bracket")))?;
+            if round.n_games(n_slots) == 0 {
+                return Err(TourneyError::InvalidInput(format!("{round} is out of range")));
+            }
+            Ok((index, round.scoring_index(state.play_in_round)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let n_rounds = n_slots.max(1).ilog2() as usize + offset;
+    let max_attempts = max_attempts.unwrap_or(n_simulations.saturating_mul(DEFAULT_ATTEMPTS_PER_SIMULATION));
+
+    let mut rng = seeded_rng(seed);
+    let mut simulations = Vec::with_capacity(n_simulations);
+    let mut attempts = 0;
+    while simulations.len() < n_simulations && attempts < max_attempts {
+        // Size each batch to finish at the acceptance rate seen so far.
+        let rate = if attempts == 0 { 1.0 } else { (simulations.len() as f64 / attempts as f64).max(1e-3) };
+        let wanted = ((n_simulations - simulations.len()) as f64 / rate).ceil() as usize;
+        let batch = wanted.max(MIN_BATCH).min(max_attempts - attempts);
+        let seeds: Vec<u64> = (0..batch).map(|_| rng.gen()).collect();
+        let results: Vec<Option<HashMap<String, f64>>> = seeds
+            .par_iter()
+            .map_init(
+                || (SimContext::new(state), vec![false; teams.len() * n_rounds]),
+                |(context, won), &sim_seed| {
+                    won.fill(false);
+                    let scores = context.simulate_dense_observed(Some(sim_seed), |team, round| {
+                        if let Some(flag) = won.get_mut(team * n_rounds + round) {
+                            *flag = true;
+                        }
+                    });
+                    if !requirements.iter().all(|&(team, round)| won[team * n_rounds + round]) {
+                        return None;
+                    }
+                    // Like `simulate_scores`, keep only teams that won a game.
+                    let scored = (0..teams.len()).filter(|&i| won[i * n_rounds..][..n_rounds].contains(&true));
+                    Some(scored.map(|i| (teams[i].clone(), scores[i])).collect())
+                },
+            )
+            .collect();
+        // Taken in seed order, so the result does not depend on thread count.
+        for result in results {
+            attempts += 1;
+            simulations.extend(result);
+            if simulations.len() == n_simulations {
+                break;
+            }
+        }
+    }
+    Ok(ConditionalSimulations { simulations, attempts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_condition_holds_in_every_simulation() {
+        let state = canonical_tournament(16);
+        let condition = vec![("Team 16".to_string(), Round(1))];
+        let result = simulate_conditional(&state, 100, &condition, Some(7), None).unwrap();
+        assert_eq!(result.simulations.len(), 100);
+        assert!(result.acceptance_rate() < 0.5);
+        // Two wins earn the underdog at least the first two rounds' points.
+        let min_points = state.scoring[0] + state.scoring[1];
+        assert!(result.simulations.iter().all(|sim| sim["Team 16"] >= min_points));
+        let unconditional = state.calculate_scores_prob();
+        assert!(result.expected_scores()["Team 16"] > unconditional["Team 16"]);
+
+        let again = simulate_conditional(&state, 100, &condition, Some(7), None).unwrap();
+        assert_eq!(again.attempts, result.attempts);
+    }
+
+    #[test]
+    fn test_impossible_and_invalid_conditions() {
+        let state = canonical_tournament(4);
+        // Teams 01 and 04 meet in round 0, so both cannot win it.
+        let condition = vec![("Team 01".to_string(), Round(0)), ("Team 04".to_string(), Round(0))];
+        let result = simulate_conditional(&state, 10, &condition, Some(1), Some(500)).unwrap();
+        assert!(result.simulations.is_empty());
+        assert_eq!(result.attempts, 500);
+
+        assert!(simulate_conditional(&state, 10, &[("Nobody".to_string(), Round(0))], None, None).is_err());
+        assert!(simulate_conditional(&state, 10, &[("Team 01".to_string(), Round(2))], None, None).is_err());
+    }
+}
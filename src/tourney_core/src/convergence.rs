@@ -0,0 +1,274 @@
+//! Monte Carlo error for simulated outputs.
+//!
+//! Simulated expected scores and championship probabilities are sample means,
+//! so each carries a standard error that shrinks like `1/sqrt(n)`. A
+//! `ConvergenceReport` gives those errors alongside the estimates, and with a
+//! target, `simulate_with_diagnostics` keeps adding batches of simulations
+//! until the largest error for the chosen metric falls below it.
+//!
+//! Simulations use the seeds `run_simulations` would, so a fixed count with
+//! the same seed reproduces its results.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use serde::Serialize;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::sim::SimContext;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// Metrics a stopping rule can target.
+const METRICS: [&str; 2] = ["points", "champion"];
+
+/// Estimates and their Monte Carlo standard errors.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct ConvergenceReport {
+    #[pyo3(get)]
+    pub n_simulations: usize,
+
+    /// Whether the target standard error was reached (always true without one).
+    #[pyo3(get)]
+    pub converged: bool,
+
+    #[pyo3(get)]
+    pub expected_scores: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub score_std_errors: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub champion_probs: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub champion_std_errors: HashMap<String, f64>,
+
+    /// `(simulations so far, largest standard error of the target metric)`
+    /// after each batch.
+    #[pyo3(get)]
+    pub history: Vec<(usize, f64)>,
+}
+
+#[pymethods]
+impl ConvergenceReport {
+    /// Normal-approximation confidence interval `(low, high)` on `team`'s
+    /// `metric` (`"points"` or `"champion"`).
+    #[pyo3(signature = (team, metric = "points", level = 0.95))]
+    pub fn confidence_interval(&self, team: &str, metric: &str, level: f64) -> Result<(f64, f64)> {
+        if !(0.0..1.0).contains(&level) {
+            return Err(TourneyError::InvalidInput(format!("level must be in [0, 1), got {level}")));
+        }
+        let (means, errors) = match metric {
+            "points" => (&self.expected_scores, &self.score_std_errors),
+            "champion" => (&self.champion_probs, &self.champion_std_errors),
+            other => return Err(unknown_metric(other)),
+        };
+        let mean = means.get(team).copied().unwrap_or(0.0);
+        let half_width = z_score(level) * errors.get(team).copied().unwrap_or(0.0);
+        Ok((mean - half_width, mean + half_width))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConvergenceReport(n_simulations={}, converged={})",
+            self.n_simulations, self.converged
+        )
+    }
+}
+
+/// Per-team running sums over a set of simulations.
+#[derive(Clone)]
+struct Moments {
+    n: usize,
+    sum: Vec<f64>,
+    sum_sq: Vec<f64>,
+    titles: Vec<u64>,
+}
+
+impl Moments {
+    fn new(n_teams: usize) -> Self {
+        Moments {
+            n: 0,
+            sum: vec![0.0; n_teams],
+            sum_sq: vec![0.0; n_teams],
+            titles: vec![0; n_teams],
+        }
+    }
+
+    fn merge(mut self, other: Moments) -> Moments {
+        self.n += other.n;
+        self.sum.iter_mut().zip(other.sum).for_each(|(a, b)| *a += b);
+        self.sum_sq.iter_mut().zip(other.sum_sq).for_each(|(a, b)| *a += b);
+        self.titles.iter_mut().zip(other.titles).for_each(|(a, b)| *a += b);
+        self
+    }
+
+    fn mean(&self, team: usize) -> f64 {
+        self.sum[team] / self.n.max(1) as f64
+    }
+
+    fn score_std_error(&self, team: usize) -> f64 {
+        if self.n < 2 {
+            return f64::INFINITY;
+        }
+        let n = self.n as f64;
+        let mean = self.mean(team);
+        let variance = ((self.sum_sq[team] - n * mean * mean) / (n - 1.0)).max(0.0);
+        (variance / n).sqrt()
+    }
+
+    fn champion_prob(&self, team: usize) -> f64 {
+        self.titles[team] as f64 / self.n.max(1) as f64
+    }
+
+    fn champion_std_error(&self, team: usize) -> f64 {
+        if self.n == 0 {
+            return f64::INFINITY;
+        }
+        let p = self.champion_prob(team);
+        (p * (1.0 - p) / self.n as f64).sqrt()
+    }
+
+    fn max_std_error(&self, metric: &str) -> f64 {
+        let error = |team| match metric {
+            "points" => self.score_std_error(team),
+            _ => self.champion_std_error(team),
+        };
+        (0..self.sum.len()).map(error).fold(0.0, f64::max)
+    }
+}
+
+/// Simulate `state` `n_simulations` times in batches of `batch_size`,
+/// stopping early once the largest standard error of `metric` across teams
+/// is below `target_std_error`.
+pub fn simulate_with_diagnostics(
+    state: &TournamentState,
+    n_simulations: usize,
+    target_std_error: Option<f64>,
+    metric: &str,
+    batch_size: usize,
+    seed: Option<u64>,
+) -> Result<ConvergenceReport> {
+    if !METRICS.contains(&metric) {
+        return Err(unknown_metric(metric));
+    }
+    if batch_size == 0 {
+        return Err(TourneyError::InvalidInput("batch_size must be positive".to_string()));
+    }
+    if let Some(target) = target_std_error.filter(|t| t.is_nan() || *t <= 0.0) {
+        return Err(TourneyError::InvalidInput(format!("target_std_error must be positive, got {target}")));
+    }
+    let teams = state.bracket_teams();
+    let final_round = state.bracket().len().max(1).ilog2() as usize + usize::from(state.play_in_round);
+    let final_round = final_round.saturating_sub(1);
+
+    let mut rng = seeded_rng(seed);
+    let mut moments = Moments::new(teams.len());
+    let mut history = Vec::new();
+    let mut converged = target_std_error.is_none();
+    while moments.n < n_simulations {
+        let seeds: Vec<u64> = (0..batch_size.min(n_simulations - moments.n)).map(|_| rng.gen()).collect();
+        let batch = seeds
+            .par_iter()
+            .fold(
+                || (SimContext::new(state), Moments::new(teams.len())),
+                |(mut context, mut acc), &sim_seed| {
+                    let mut champion = None;
+                    let scores = context.simulate_dense_observed(Some(sim_seed), |team, round| {
+                        if round == final_round {
+                            champion = Some(team);
+                        }
+                    });
+                    for (team, &score) in scores.iter().enumerate() {
+                        acc.sum[team] += score;
+                        acc.sum_sq[team] += score * score;
+                    }
+                    if let Some(team) = champion {
+                        acc.titles[team] += 1;
+                    }
+                    acc.n += 1;
+                    (context, acc)
+                },
+            )
+            .map(|(_, acc)| acc)
+            .reduce(|| Moments::new(teams.len()), Moments::merge);
+        moments = moments.merge(batch);
+        let max_error = moments.max_std_error(metric);
+        history.push((moments.n, max_error));
+        if target_std_error.is_some_and(|target| max_error < target) {
+            converged = true;
+            break;
+        }
+    }
+
+    let by_team = |value: &dyn Fn(usize) -> f64| -> HashMap<String, f64> {
+        teams.iter().enumerate().map(|(i, team)| (team.clone(), value(i))).collect()
+    };
+    Ok(ConvergenceReport {
+        n_simulations: moments.n,
+        converged,
+        expected_scores: by_team(&|i| moments.mean(i)),
+        score_std_errors: by_team(&|i| moments.score_std_error(i)),
+        champion_probs: by_team(&|i| moments.champion_prob(i)),
+        champion_std_errors: by_team(&|i| moments.champion_std_error(i)),
+        history,
+    })
+}
+
+fn unknown_metric(metric: &str) -> TourneyError {
+    TourneyError::InvalidInput(format!("unknown metric {metric:?}; expected \"points\" or \"champion\""))
+}
+
+/// Two-sided standard normal quantile for confidence `level`.
+fn z_score(level: f64) -> f64 {
+    Normal::new(0.0, 1.0).unwrap().inverse_cdf(0.5 + level / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_fixed_count_matches_run_simulations() {
+        let state = canonical_tournament(16);
+        let report = simulate_with_diagnostics(&state, 1000, None, "points", 300, Some(5)).unwrap();
+        assert_eq!(report.n_simulations, 1000);
+        assert!(report.converged);
+        assert_eq!(report.history.iter().map(|h| h.0).collect::<Vec<_>>(), vec![300, 600, 900, 1000]);
+
+        let sims = state.run_simulations(1000, Some(5));
+        for team in state.bracket_teams() {
+            let mean = sims.iter().map(|s| s.get(team).copied().unwrap_or(0.0)).sum::<f64>() / 1000.0;
+            assert!((report.expected_scores[team] - mean).abs() < 1e-9);
+        }
+        assert!((report.champion_probs.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let (low, high) = report.confidence_interval("Team 01", "champion", 0.95).unwrap();
+        let p = report.champion_probs["Team 01"];
+        assert!(low < p && p < high);
+        assert!(((high - low) / 2.0 - 1.96 * report.champion_std_errors["Team 01"]).abs() < 1e-3);
+        assert!(report.confidence_interval("Team 01", "wins", 0.95).is_err());
+    }
+
+    #[test]
+    fn test_stops_at_target() {
+        let state = canonical_tournament(8);
+        let report = simulate_with_diagnostics(&state, 100_000, Some(0.01), "champion", 500, Some(2)).unwrap();
+        assert!(report.converged);
+        assert!(report.n_simulations < 100_000);
+        assert!(report.champion_std_errors.values().all(|&se| se < 0.01));
+        // Stopped at the first batch under the target.
+        let (_, before) = report.history[report.history.len() - 2];
+        assert!(before >= 0.01);
+
+        let capped = simulate_with_diagnostics(&state, 1000, Some(1e-6), "champion", 500, Some(2)).unwrap();
+        assert!(!capped.converged);
+        assert_eq!(capped.n_simulations, 1000);
+        assert!(simulate_with_diagnostics(&state, 1000, None, "upsets", 500, None).is_err());
+    }
+}
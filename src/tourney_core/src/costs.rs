@@ -0,0 +1,98 @@
+//! Transaction costs for suggested trades.
+//!
+//! Trading a team costs more than `shares * price`: a flat fee per trade, a
+//! commission proportional to notional, and half the bid-ask spread on every
+//! share when crossing to the other side. `TransactionCosts` prices all three
+//! so the rebalancer and the reference strategies can weigh a trade's benefit
+//! against what it really costs.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TourneyError};
+
+/// Fixed, proportional, and spread costs of a trade. The default is free.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionCosts {
+    /// Flat fee per trade, regardless of size.
+    #[pyo3(get)]
+    pub fixed: f64,
+
+    /// Fee as a fraction of notional (`|shares| * price`).
+    #[pyo3(get)]
+    pub proportional: f64,
+
+    /// Full bid-ask spread in price units; each share pays half of it.
+    #[pyo3(get)]
+    pub spread: f64,
+}
+
+#[pymethods]
+impl TransactionCosts {
+    #[cfg(feature = "python")]
+    #[new]
+    #[pyo3(signature = (fixed = 0.0, proportional = 0.0, spread = 0.0))]
+    pub fn py_new(fixed: f64, proportional: f64, spread: f64) -> PyResult<Self> {
+        Ok(Self::new(fixed, proportional, spread)?)
+    }
+
+    /// Cost of trading `shares` (either sign) at `price`, on top of
+    /// `shares * price`. Zero-share trades are free.
+    pub fn cost(&self, shares: f64, price: f64) -> f64 {
+        if shares == 0.0 {
+            return 0.0;
+        }
+        self.fixed + shares.abs() * (self.proportional * price.abs() + self.spread / 2.0)
+    }
+
+    /// All-in price per share for trading `shares` at `price`: higher than
+    /// `price` when buying, lower when selling.
+    pub fn effective_price(&self, shares: f64, price: f64) -> f64 {
+        if shares == 0.0 {
+            return price;
+        }
+        price + self.cost(shares, price) / shares
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TransactionCosts(fixed={}, proportional={}, spread={})",
+            self.fixed, self.proportional, self.spread
+        )
+    }
+}
+
+impl TransactionCosts {
+    pub fn new(fixed: f64, proportional: f64, spread: f64) -> Result<Self> {
+        if [fixed, proportional, spread].iter().any(|&c| c.is_nan() || c < 0.0) {
+            return Err(TourneyError::InvalidInput(
+                "transaction costs must be non-negative".to_string(),
+            ));
+        }
+        Ok(TransactionCosts {
+            fixed,
+            proportional,
+            spread,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_costs_charge_both_directions() {
+        let costs = TransactionCosts::new(1.0, 0.01, 0.2).unwrap();
+        // 1 fixed + 10 * (0.01 * 5 + 0.1)
+        assert!((costs.cost(10.0, 5.0) - 2.5).abs() < 1e-12);
+        assert!((costs.cost(-10.0, 5.0) - 2.5).abs() < 1e-12);
+        assert_eq!(costs.cost(0.0, 5.0), 0.0);
+        assert!((costs.effective_price(10.0, 5.0) - 5.25).abs() < 1e-12);
+        assert!((costs.effective_price(-10.0, 5.0) - 4.75).abs() < 1e-12);
+        assert_eq!(TransactionCosts::default().effective_price(3.0, 2.0), 2.0);
+        assert!(TransactionCosts::new(-1.0, 0.0, 0.0).is_err());
+    }
+}
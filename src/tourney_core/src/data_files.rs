@@ -0,0 +1,356 @@
+//! Readers for the plain-text data files.
+//!
+//! * Ratings (`ratings.txt`): `name|offense|defense|tempo`, with raw
+//!   efficiencies, optionally followed by `|conference|seed|wins-losses|short_name`
+//!   (empty fields are skipped).
+//! * Adjustments (`adjustments.txt`): `name|±points`, added to offense and
+//!   subtracted from defense before the ratings are made relative.
+//! * Bracket (`bracket.txt`), in one of two versions. Either way a play-in
+//!   pair fills its slot with each team's chance of winning the play-in game.
+//!   - Legacy: one slot per line in bracket order, either a team or a
+//!     `team1,team2` play-in pair.
+//!   - Version 2: a `# bracket v2` line, then CSV with a header naming a
+//!     `team` column and optionally `seed`, `region`, and `play_in` columns,
+//!     one team per row in bracket order. Consecutive rows with the same
+//!     non-empty `play_in` label share a slot; regions, if given, must be
+//!     contiguous, equal, power-of-2 blocks of slots.
+//! * Overrides (`overrides.txt`): `team1,team2,probability`; see
+//!   `OverridesMap::from_csv`.
+//!
+//! Blank lines are ignored everywhere; any other malformed line is an error
+//! naming its line number. The `*_str` readers take file contents as `str` or
+//! UTF-8 `bytes`, for callers (web uploads, tests) that have no file on disk.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::overrides::OverridesMap;
+use crate::team::Team;
+use crate::win_prob::calculate_win_prob;
+
+/// Contents of the file at `path`, with the path in any error.
+pub(crate) fn read_file(path: &str) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|err| std::io::Error::new(err.kind(), format!("{path}: {err}")).into())
+}
+
+/// File contents from Python: `str`, or `bytes` holding UTF-8 text.
+#[cfg_attr(feature = "python", derive(FromPyObject))]
+pub enum TextInput {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl TextInput {
+    pub fn into_string(self) -> Result<String> {
+        match self {
+            TextInput::Text(text) => Ok(text),
+            TextInput::Bytes(bytes) => String::from_utf8(bytes)
+                .map_err(|err| TourneyError::InvalidInput(format!("input is not valid UTF-8: {err}"))),
+        }
+    }
+}
+
+/// Parse ratings file contents into teams by name, applying `adjustments`
+/// (points per team) if given.
+#[pyfunction]
+#[pyo3(signature = (text, adjustments = None))]
+pub fn read_ratings_str(text: TextInput, adjustments: Option<HashMap<String, f64>>) -> Result<HashMap<String, Team>> {
+    parse_ratings(&text.into_string()?, adjustments.as_ref())
+}
+
+/// Prefix of the first line of a versioned bracket file, before the version.
+const BRACKET_VERSION_MARKER: &str = "# bracket v";
+
+/// The structure a bracket file describes, before play-in pairs are weighted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BracketFile {
+    /// Teams in each slot, in bracket order (two for a play-in pair).
+    pub slots: Vec<Vec<String>>,
+    /// Seeds given by the file.
+    pub seeds: HashMap<String, u32>,
+    /// Region names in bracket order, each covering an equal block of slots
+    /// (empty if the file names none).
+    pub regions: Vec<String>,
+}
+
+impl BracketFile {
+    /// Slot probabilities, weighting play-in pairs by their win probability
+    /// under `ratings` and `overrides`.
+    pub fn slot_probabilities(
+        &self,
+        ratings: &HashMap<String, Team>,
+        overrides: &OverridesMap,
+    ) -> Result<Vec<HashMap<String, f64>>> {
+        let rating = |slot: usize, name: &str| {
+            ratings
+                .get(name)
+                .ok_or_else(|| TourneyError::InvalidInput(format!("slot {slot}: no rating for play-in team {name}")))
+        };
+        self.slots
+            .iter()
+            .enumerate()
+            .map(|(slot, teams)| match &teams[..] {
+                [name1, name2] => {
+                    let prob = calculate_win_prob(rating(slot, name1)?, rating(slot, name2)?, Some(overrides), 0.0);
+                    Ok([(name1.clone(), prob), (name2.clone(), 1.0 - prob)].into_iter().collect())
+                }
+                _ => Ok(teams.iter().map(|name| (name.clone(), 1.0)).collect()),
+            })
+            .collect()
+    }
+}
+
+/// Parse bracket file contents into slots. Play-in teams must be rated in
+/// `ratings` under the names the bracket uses.
+#[pyfunction]
+#[pyo3(signature = (text, ratings, overrides = None))]
+pub fn read_games_str(
+    text: TextInput,
+    ratings: HashMap<String, Team>,
+    overrides: Option<OverridesMap>,
+) -> Result<Vec<HashMap<String, f64>>> {
+    parse_bracket(&text.into_string()?, &ratings, &overrides.unwrap_or_default())
+}
+
+fn numbered_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+fn parse_number<T: std::str::FromStr>(line_no: usize, what: &str, value: &str) -> Result<T> {
+    value
+        .trim()
+        .trim_start_matches('+')
+        .parse()
+        .map_err(|_| TourneyError::InvalidInput(format!("line {line_no}: invalid {what} {value:?}")))
+}
+
+/// Parse an adjustments file into points per team.
+pub fn parse_adjustments(text: &str) -> Result<HashMap<String, f64>> {
+    numbered_lines(text)
+        .map(|(line_no, line)| match line.split('|').collect::<Vec<_>>()[..] {
+            [name, points] => Ok((name.to_string(), parse_number(line_no, "adjustment", points)?)),
+            _ => Err(TourneyError::InvalidInput(format!("line {line_no}: expected name|adjustment"))),
+        })
+        .collect()
+}
+
+/// Parse a ratings file, applying `adjustments` (points per team) if given.
+pub fn parse_ratings(text: &str, adjustments: Option<&HashMap<String, f64>>) -> Result<HashMap<String, Team>> {
+    let mut ratings = HashMap::new();
+    for (line_no, line) in numbered_lines(text) {
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        if fields.len() < 4 {
+            return Err(TourneyError::InvalidInput(format!(
+                "line {line_no}: expected name|offense|defense|tempo"
+            )));
+        }
+        let name = fields[0].to_string();
+        let adjustment = adjustments.and_then(|a| a.get(&name)).copied().unwrap_or(0.0);
+        let offense = parse_number::<f64>(line_no, "offense", fields[1])? + adjustment;
+        let defense = parse_number::<f64>(line_no, "defense", fields[2])? - adjustment;
+        let tempo = parse_number(line_no, "tempo", fields[3])?;
+        let mut team = Team::new(name.clone(), offense, defense, tempo, true);
+
+        let extra = |i: usize| fields.get(i).copied().filter(|field| !field.is_empty());
+        team.conference = extra(4).map(str::to_string);
+        team.seed = extra(5).map(|seed| parse_number(line_no, "seed", seed)).transpose()?;
+        if let Some(record) = extra(6) {
+            let (wins, losses) = record
+                .split_once('-')
+                .ok_or_else(|| TourneyError::InvalidInput(format!("line {line_no}: invalid record {record:?}")))?;
+            team.wins = Some(parse_number(line_no, "wins", wins)?);
+            team.losses = Some(parse_number(line_no, "losses", losses)?);
+        }
+        team.short_name = extra(7).map(str::to_string);
+        ratings.insert(name, team);
+    }
+    Ok(ratings)
+}
+
+/// Parse a bracket file of either version into its structure.
+pub fn parse_bracket_file(text: &str) -> Result<BracketFile> {
+    let first = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default();
+    let file = match first.strip_prefix(BRACKET_VERSION_MARKER) {
+        Some("2") => parse_bracket_v2(text)?,
+        Some(version) => {
+            return Err(TourneyError::InvalidInput(format!("unsupported bracket format version {version:?}")));
+        }
+        None => parse_bracket_legacy(text)?,
+    };
+    if !file.slots.len().is_power_of_two() {
+        return Err(TourneyError::InvalidInput(format!(
+            "bracket has {} slots; it must have a power-of-2 number of teams",
+            file.slots.len()
+        )));
+    }
+    Ok(file)
+}
+
+/// Parse a bracket file into slots, weighting play-in pairs by their win
+/// probability under `ratings` and `overrides`.
+pub fn parse_bracket(
+    text: &str,
+    ratings: &HashMap<String, Team>,
+    overrides: &OverridesMap,
+) -> Result<Vec<HashMap<String, f64>>> {
+    parse_bracket_file(text)?.slot_probabilities(ratings, overrides)
+}
+
+fn parse_bracket_legacy(text: &str) -> Result<BracketFile> {
+    let mut file = BracketFile::default();
+    for (line_no, line) in numbered_lines(text) {
+        let names: Vec<String> = line.split(',').map(|name| name.trim().to_string()).collect();
+        if names.len() > 2 {
+            return Err(TourneyError::InvalidInput(format!(
+                "line {line_no}: expected a team or a team1,team2 play-in pair"
+            )));
+        }
+        file.slots.push(names);
+    }
+    Ok(file)
+}
+
+fn parse_bracket_v2(text: &str) -> Result<BracketFile> {
+    // Drop the version line; the CSV header follows it.
+    let body = text.trim_start().split_once('\n').map_or("", |(_, rest)| rest);
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(body.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+    let team_col = column("team").ok_or_else(|| TourneyError::InvalidInput("bracket has no team column".to_string()))?;
+    let (seed_col, region_col, play_in_col) = (column("seed"), column("region"), column("play_in"));
+
+    let mut file = BracketFile::default();
+    let mut slot_regions: Vec<Option<String>> = Vec::new();
+    let mut open_play_in: Option<String> = None;
+    for record in reader.records() {
+        let record = record?;
+        let row = file.slots.iter().map(Vec::len).sum::<usize>() + 1;
+        let field = |col: Option<usize>| col.and_then(|c| record.get(c)).filter(|value| !value.is_empty());
+        let team = field(Some(team_col))
+            .ok_or_else(|| TourneyError::InvalidInput(format!("row {row}: missing team")))?
+            .to_string();
+        if let Some(seed) = field(seed_col) {
+            let seed = seed
+                .parse()
+                .map_err(|_| TourneyError::InvalidInput(format!("row {row}: invalid seed {seed:?}")))?;
+            file.seeds.insert(team.clone(), seed);
+        }
+        let region = field(region_col).map(str::to_string);
+        let play_in = field(play_in_col).map(str::to_string);
+
+        if play_in.is_some() && play_in == open_play_in {
+            if slot_regions.last() != Some(&region) {
+                return Err(TourneyError::InvalidInput(format!("row {row}: play-in pair spans regions")));
+            }
+            file.slots.last_mut().expect("open play-in has a slot").push(team);
+            open_play_in = None;
+            continue;
+        }
+        if let Some(label) = &open_play_in {
+            return Err(TourneyError::InvalidInput(format!("row {row}: play-in {label:?} has only one team")));
+        }
+        file.slots.push(vec![team]);
+        slot_regions.push(region);
+        open_play_in = play_in;
+    }
+    if let Some(label) = open_play_in {
+        return Err(TourneyError::InvalidInput(format!("play-in {label:?} has only one team")));
+    }
+    file.regions = region_blocks(&slot_regions)?;
+    Ok(file)
+}
+
+/// Region names in bracket order, checking that the regions are contiguous,
+/// equal, power-of-2 blocks of slots (or named nowhere).
+fn region_blocks(slot_regions: &[Option<String>]) -> Result<Vec<String>> {
+    if slot_regions.iter().all(Option::is_none) {
+        return Ok(Vec::new());
+    }
+    let mut regions: Vec<(String, usize)> = Vec::new();
+    for (slot, region) in slot_regions.iter().enumerate() {
+        let region = region
+            .as_ref()
+            .ok_or_else(|| TourneyError::InvalidInput(format!("slot {slot} has no region")))?;
+        if let Some((_, size)) = regions.last_mut().filter(|(name, _)| name == region) {
+            *size += 1;
+        } else if regions.iter().any(|(name, _)| name == region) {
+            return Err(TourneyError::InvalidInput(format!("region {region} is not contiguous")));
+        } else {
+            regions.push((region.clone(), 1));
+        }
+    }
+    let size = regions[0].1;
+    if !size.is_power_of_two() || regions.iter().any(|&(_, n)| n != size) {
+        return Err(TourneyError::InvalidInput(
+            "regions must each cover the same power-of-2 number of slots".to_string(),
+        ));
+    }
+    Ok(regions.into_iter().map(|(name, _)| name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ratings_with_metadata_and_adjustments() {
+        let text = "Duke|120.0|90.0|70.0|ACC|1|30-3|DUKE\n\nTexas|115.0|95.0|68.0\n";
+        let adjustments = parse_adjustments("Duke|-1.5\nTexas|+2\n").unwrap();
+        let ratings = parse_ratings(text, Some(&adjustments)).unwrap();
+        let duke = &ratings["Duke"];
+        assert_eq!(duke.label(), "(1) DUKE 30-3");
+        assert_eq!(duke.conference.as_deref(), Some("ACC"));
+        let unadjusted = parse_ratings(text, None).unwrap();
+        assert!((duke.net_rating(104.6) - unadjusted["Duke"].net_rating(104.6) + 3.0).abs() < 1e-9);
+
+        assert!(parse_ratings("Duke|120.0|90.0\n", None).is_err());
+        assert!(parse_ratings("Duke|120.0|90.0|fast\n", None).is_err());
+        assert!(parse_adjustments("Duke\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_bracket_play_ins() {
+        let ratings = parse_ratings("A|120|90|70\nB|110|100|70\nC|100|100|70\nD|100|110|70\n", None).unwrap();
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("C", "D", 0.25).unwrap();
+        let bracket = parse_bracket("A\nB\nC, D\nA\n", &ratings, &overrides).unwrap();
+        assert_eq!(bracket[2]["C"], 0.25);
+        assert_eq!(bracket[2]["D"], 0.75);
+
+        assert!(parse_bracket("A\nB\nC\n", &ratings, &overrides).is_err());
+        assert!(parse_bracket("A\nB\nC,E\nD\n", &ratings, &overrides).is_err());
+        assert!(parse_bracket("A\nB\nC,D,A\nD\n", &ratings, &overrides).is_err());
+    }
+
+    #[test]
+    fn test_bracket_v2() {
+        let text = "# bracket v2\nteam,seed,region,play_in\nA,1,East,\nB,4,East,\nC,2,West,\nD,3,West,x\nE,3,West,x\n";
+        let file = parse_bracket_file(text).unwrap();
+        assert_eq!(file.slots, [vec!["A"], vec!["B"], vec!["C"], vec!["D", "E"]]);
+        assert_eq!(file.regions, ["East", "West"]);
+        assert_eq!(file.seeds["E"], 3);
+        // The legacy format reads the same slots, without metadata.
+        assert_eq!(parse_bracket_file("A\nB\nC\nD,E\n").unwrap().slots, file.slots);
+
+        let broken = |from: &str, to: &str| parse_bracket_file(&text.replace(from, to)).is_err();
+        assert!(broken("v2", "v3"));
+        assert!(broken("E,3,West,x", "E,3,West,y"));
+        assert!(broken("E,3,West,x", "E,3,East,x"));
+        assert!(broken("B,4,East", "B,4,West"));
+        assert!(broken("team,", "name,"));
+    }
+
+    #[test]
+    fn test_text_input_from_bytes() {
+        let ratings = read_ratings_str(TextInput::Bytes(b"A|120|90|70\n".to_vec()), None).unwrap();
+        assert_eq!(ratings["A"].tempo, 70.0);
+        assert!(TextInput::Bytes(vec![0xff, 0xfe]).into_string().is_err());
+        let bracket = read_games_str(TextInput::Text("A\nA\n".to_string()), ratings, None).unwrap();
+        assert_eq!(bracket.len(), 2);
+    }
+}
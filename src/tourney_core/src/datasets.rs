@@ -0,0 +1,163 @@
+//! Historical seasons bundled with the library (the `data` feature).
+//!
+//! Past seasons' files are compiled in so backtests, calibration checks,
+//! examples, and tests have ground truth without external files. Each season
+//! carries whichever of its bracket, ratings, and game results are bundled, in
+//! the plain-text formats `data_files` reads (results in the overrides format,
+//! with probability 1 or 0 for the first team):
+//!
+//! * 2022: ratings for every Division I team and first-round results.
+//! * 2025: the bracket, with seeds.
+//!
+//! To add a season, put its files under `data/<season>/` and list them in
+//! `SEASONS`.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::data_files::{parse_bracket_file, parse_ratings};
+use crate::error::{Result, TourneyError};
+use crate::overrides::OverridesMap;
+use crate::team::Team;
+
+/// One season's bundled files.
+struct SeasonFiles {
+    season: u16,
+    bracket: Option<&'static str>,
+    ratings: Option<&'static str>,
+    results: Option<&'static str>,
+}
+
+const SEASONS: &[SeasonFiles] = &[
+    SeasonFiles {
+        season: 2022,
+        bracket: None,
+        ratings: Some(include_str!("../data/2022/ratings.txt")),
+        results: Some(include_str!("../data/2022/results.txt")),
+    },
+    SeasonFiles {
+        season: 2025,
+        bracket: Some(include_str!("../data/2025/bracket.txt")),
+        ratings: None,
+        results: None,
+    },
+];
+
+/// A bundled season's data. Parts that are not bundled are empty.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct HistoricalSeason {
+    #[pyo3(get)]
+    pub season: u16,
+
+    /// Teams in each bracket slot, in bracket order (two for a play-in pair).
+    #[pyo3(get)]
+    pub bracket: Vec<Vec<String>>,
+
+    #[pyo3(get)]
+    pub seeds: HashMap<String, u32>,
+
+    #[pyo3(get)]
+    pub ratings: HashMap<String, Team>,
+
+    /// Completed games as `(winner, loser)`, in file order.
+    #[pyo3(get)]
+    pub results: Vec<(String, String)>,
+}
+
+#[pymethods]
+impl HistoricalSeason {
+    /// The results as certain overrides, for replaying them on a
+    /// `TournamentState`.
+    pub fn results_overrides(&self) -> Result<OverridesMap> {
+        let mut overrides = OverridesMap::new();
+        for (winner, loser) in &self.results {
+            overrides.add_override(winner, loser, 1.0)?;
+        }
+        Ok(overrides)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "HistoricalSeason({}, {} slots, {} rated teams, {} results)",
+            self.season,
+            self.bracket.len(),
+            self.ratings.len(),
+            self.results.len()
+        )
+    }
+}
+
+/// Seasons with bundled data, oldest first.
+#[pyfunction]
+pub fn historical_seasons() -> Vec<u16> {
+    SEASONS.iter().map(|files| files.season).collect()
+}
+
+/// Load a bundled season.
+#[pyfunction]
+pub fn load_season(season: u16) -> Result<HistoricalSeason> {
+    let files = SEASONS.iter().find(|files| files.season == season).ok_or_else(|| {
+        TourneyError::InvalidInput(format!("no bundled data for {season}; available: {:?}", historical_seasons()))
+    })?;
+    let bracket = files.bracket.map(parse_bracket_file).transpose()?.unwrap_or_default();
+    Ok(HistoricalSeason {
+        season,
+        bracket: bracket.slots,
+        seeds: bracket.seeds,
+        ratings: files.ratings.map(|text| parse_ratings(text, None)).transpose()?.unwrap_or_default(),
+        results: files.results.map(parse_results).transpose()?.unwrap_or_default(),
+    })
+}
+
+/// `(winner, loser)` pairs from overrides-format lines with probability 1 or 0.
+fn parse_results(text: &str) -> Result<Vec<(String, String)>> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            match fields[..] {
+                [team1, team2, prob] if prob.parse::<f64>() == Ok(1.0) => Ok((team1.to_string(), team2.to_string())),
+                [team1, team2, prob] if prob.parse::<f64>() == Ok(0.0) => Ok((team2.to_string(), team1.to_string())),
+                _ => Err(TourneyError::InvalidInput(format!("result {line:?} is not a decided game"))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_season_loads_consistently() {
+        for season in historical_seasons() {
+            let data = load_season(season).unwrap();
+            assert!(data.bracket.is_empty() || data.bracket.len() == 64);
+            for team in data.bracket.iter().flatten() {
+                assert!(data.seeds.contains_key(team), "{season}: {team} has no seed");
+            }
+            if !data.ratings.is_empty() {
+                for (winner, loser) in &data.results {
+                    assert!(data.ratings.contains_key(winner) && data.ratings.contains_key(loser));
+                }
+            }
+        }
+        assert!(load_season(1900).is_err());
+    }
+
+    #[test]
+    fn test_results() {
+        let data = load_season(2022).unwrap();
+        assert!(data.results.contains(&("Saint Peter's".to_string(), "Kentucky".to_string())));
+        let overrides = data.results_overrides().unwrap();
+        assert_eq!(overrides.get_override("Kentucky", "Saint Peter's"), Some(0.0));
+
+        assert_eq!(
+            parse_results("A,B,1\nC,D,0.000\n").unwrap(),
+            vec![("A".to_string(), "B".to_string()), ("D".to_string(), "C".to_string())]
+        );
+        assert!(parse_results("A,B,0.6\n").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Weighted blends of win probability models.
+//!
+//! An `EnsembleModel` holds several `ModelParams` with weights. Set as the
+//! `ensemble` of a `TournamentState`'s `model_params`, it replaces the single
+//! model in every win probability the library computes (bracket scoring,
+//! simulations, group and league play, matrices): each is the weighted mean
+//! of the members' probabilities. How far the members disagree about a
+//! matchup (their weighted standard deviation) is a signal of how much the
+//! blended probability depends on modeling choices.
+//!
+//! Only win probabilities are blended. Everything else the model constants
+//! feed (net ratings, forfeit resolution, confidence, probability bounds) uses
+//! the carrying `ModelParams`.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TourneyError};
+use crate::overrides::OverridesMap;
+use crate::team::Team;
+use crate::win_prob::{calculate_win_prob_with, ModelParams};
+
+/// Win probability models blended by weight.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleModel {
+    #[pyo3(get)]
+    pub members: Vec<ModelParams>,
+
+    /// Member weights, normalized to sum to 1.
+    #[pyo3(get)]
+    pub weights: Vec<f64>,
+}
+
+#[pymethods]
+impl EnsembleModel {
+    /// Blend `members`, equally unless `weights` (any non-negative scale) are
+    /// given.
+    #[new]
+    #[pyo3(signature = (members, weights = None))]
+    pub fn new(members: Vec<ModelParams>, weights: Option<Vec<f64>>) -> Result<Self> {
+        let weights = weights.unwrap_or_else(|| vec![1.0; members.len()]);
+        if members.is_empty() || weights.len() != members.len() {
+            return Err(TourneyError::InvalidInput(format!(
+                "an ensemble needs at least one member and one weight per member, got {} and {}",
+                members.len(),
+                weights.len()
+            )));
+        }
+        if members.iter().any(|member| member.ensemble.is_some()) {
+            return Err(TourneyError::InvalidInput("ensemble members cannot be ensembles".to_string()));
+        }
+        let total: f64 = weights.iter().sum();
+        if weights.iter().any(|w| !(*w >= 0.0 && w.is_finite())) || total <= 0.0 {
+            return Err(TourneyError::InvalidInput(format!(
+                "weights must be non-negative with a positive sum, got {weights:?}"
+            )));
+        }
+        Ok(EnsembleModel {
+            weights: weights.iter().map(|w| w / total).collect(),
+            members,
+        })
+    }
+
+    /// Blended probability that `team1` beats `team2`.
+    #[cfg(feature = "python")]
+    #[pyo3(name = "win_prob", signature = (team1, team2, overrides = None, forfeit_prob = 0.0))]
+    fn py_win_prob(&self, team1: &Team, team2: &Team, overrides: Option<OverridesMap>, forfeit_prob: f64) -> f64 {
+        self.win_prob(team1, team2, overrides.as_ref(), forfeit_prob)
+    }
+
+    /// Each member's probability that `team1` beats `team2`.
+    #[pyo3(signature = (team1, team2, overrides = None, forfeit_prob = 0.0))]
+    pub fn member_probs(
+        &self,
+        team1: &Team,
+        team2: &Team,
+        overrides: Option<OverridesMap>,
+        forfeit_prob: f64,
+    ) -> Vec<f64> {
+        self.probs(team1, team2, overrides.as_ref(), forfeit_prob).collect()
+    }
+
+    /// Weighted standard deviation of the members' probabilities that
+    /// `team1` beats `team2`.
+    #[cfg(feature = "python")]
+    #[pyo3(name = "disagreement", signature = (team1, team2, overrides = None, forfeit_prob = 0.0))]
+    fn py_disagreement(&self, team1: &Team, team2: &Team, overrides: Option<OverridesMap>, forfeit_prob: f64) -> f64 {
+        self.disagreement(team1, team2, overrides.as_ref(), forfeit_prob)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EnsembleModel({} members, weights={:?})", self.members.len(), self.weights)
+    }
+}
+
+impl EnsembleModel {
+    fn probs<'a>(
+        &'a self,
+        team1: &'a Team,
+        team2: &'a Team,
+        overrides: Option<&'a OverridesMap>,
+        forfeit_prob: f64,
+    ) -> impl Iterator<Item = f64> + 'a {
+        self.members
+            .iter()
+            .map(move |member| calculate_win_prob_with(team1, team2, overrides, forfeit_prob, member))
+    }
+
+    /// Blended probability that `team1` beats `team2`, summed in member order.
+    pub fn win_prob(&self, team1: &Team, team2: &Team, overrides: Option<&OverridesMap>, forfeit_prob: f64) -> f64 {
+        self.probs(team1, team2, overrides, forfeit_prob)
+            .zip(&self.weights)
+            .map(|(p, w)| p * w)
+            .sum()
+    }
+
+    pub fn disagreement(&self, team1: &Team, team2: &Team, overrides: Option<&OverridesMap>, forfeit_prob: f64) -> f64 {
+        let mean = self.win_prob(team1, team2, overrides, forfeit_prob);
+        let variance: f64 = self
+            .probs(team1, team2, overrides, forfeit_prob)
+            .zip(&self.weights)
+            .map(|(p, w)| w * (p - mean).powi(2))
+            .sum();
+        variance.sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    fn teams() -> (Team, Team) {
+        (
+            Team::new("A".to_string(), 0.08, -0.04, 68.0, false),
+            Team::new("B".to_string(), 0.01, 0.02, 66.0, false),
+        )
+    }
+
+    #[test]
+    fn test_blend_and_disagreement() {
+        let (a, b) = teams();
+        let sharp = ModelParams::new(104.6, 67.7, 9.0);
+        let flat = ModelParams::new(104.6, 67.7, 14.0);
+        let ensemble = EnsembleModel::new(vec![sharp.clone(), flat.clone()], Some(vec![3.0, 1.0])).unwrap();
+        assert_eq!(ensemble.weights, vec![0.75, 0.25]);
+
+        let (p_sharp, p_flat) =
+            (calculate_win_prob_with(&a, &b, None, 0.0, &sharp), calculate_win_prob_with(&a, &b, None, 0.0, &flat));
+        let blended = ensemble.win_prob(&a, &b, None, 0.0);
+        assert!((blended - (0.75 * p_sharp + 0.25 * p_flat)).abs() < 1e-12);
+        assert!(ensemble.disagreement(&a, &b, None, 0.0) > 0.0);
+
+        let same = EnsembleModel::new(vec![sharp.clone(), sharp.clone()], None).unwrap();
+        assert!(same.disagreement(&a, &b, None, 0.0) < 1e-12);
+
+        assert!(EnsembleModel::new(Vec::new(), None).is_err());
+        assert!(EnsembleModel::new(vec![sharp.clone()], Some(vec![-1.0])).is_err());
+        let mut nested = sharp;
+        nested.ensemble = Some(same);
+        assert!(EnsembleModel::new(vec![nested], None).is_err());
+    }
+
+    #[test]
+    fn test_tournament_uses_ensemble() {
+        let mut state = canonical_tournament(8);
+        let baseline = state.calculate_scores_prob();
+        let single = EnsembleModel::new(vec![state.model_params.clone()], None).unwrap();
+        state.model_params.ensemble = Some(single);
+        let scores = state.calculate_scores_prob();
+        for (team, score) in &baseline {
+            assert!((scores[team] - score).abs() < 1e-9);
+        }
+
+        let flat = EnsembleModel::new(vec![ModelParams::new(104.6, 67.7, 1e6)], None).unwrap();
+        state.model_params.ensemble = Some(flat);
+        let teams = state.get_bracket_teams();
+        let (a, b) = (&state.ratings[&teams[0]], &state.ratings[&teams[1]]);
+        assert!((calculate_win_prob_with(a, b, None, 0.0, &state.model_params) - 0.5).abs() < 1e-3);
+        assert!(state.model_disagreement(&teams[0], &teams[1]).unwrap() < 1e-12);
+        assert!(state.model_disagreement(&teams[0], "Nobody").is_err());
+    }
+}
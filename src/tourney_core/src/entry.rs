@@ -0,0 +1,589 @@
+//! Bracket pool entries and an expected-points entry optimizer.
+//!
+//! A `BracketEntry` picks a winner for every main-bracket game. Because an
+//! entry's expected score is a sum over games of (points for the round) x
+//! (probability the picked team wins that game), the best entry can be found
+//! exactly with one pass up the bracket tree: each game keeps, per possible
+//! winner, the best score its subtree can earn with that winner.
+//!
+//! `PickConstraints` restrict the search: a lock forces a team to be picked
+//! through a round (and so every earlier round), and a forbid rules out
+//! picking a team to win a round (and so every later round).
+//!
+//! Pools pay the best entry, not the most points on average, so
+//! `optimize_entries` builds several entries jointly: it scores a pool of
+//! candidate entries against an opponent field over simulations and greedily
+//! adds whichever candidate wins the most simulations none of the chosen
+//! entries already win. Entries that win together add little, so the set
+//! diversifies on its own.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Result, TourneyError};
+use crate::export::bracket_levels;
+use crate::game_id::{GameId, Round};
+use crate::scoring::ScoringRule;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// A complete set of main-bracket picks.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BracketEntry {
+    /// Picked winners by round: `picks[r][g]` wins game `g` of round `r`, with
+    /// games in bracket order.
+    #[pyo3(get)]
+    pub picks: Vec<Vec<String>>,
+}
+
+#[pymethods]
+impl BracketEntry {
+    #[cfg(feature = "python")]
+    #[new]
+    pub fn py_new(picks: Vec<Vec<String>>) -> PyResult<Self> {
+        Ok(Self::new(picks)?)
+    }
+
+    /// The picked champion.
+    pub fn champion(&self) -> Option<String> {
+        self.picks.last().and_then(|round| round.first()).cloned()
+    }
+
+    /// Last round (0-based) `team` is picked to win, if any.
+    pub fn furthest_round(&self, team: &str) -> Option<Round> {
+        let round = self.picks.iter().rposition(|round| round.iter().any(|t| t == team))?;
+        Round::try_from(round).ok()
+    }
+
+    /// Expected points of this entry in `tournament`, with `scoring[r]` points
+    /// per correct round-`r` pick (default: the tournament's round points).
+    #[pyo3(signature = (tournament, scoring = None))]
+    pub fn expected_score(&self, tournament: &TournamentState, scoring: Option<Vec<f64>>) -> Result<f64> {
+        let levels = bracket_levels(tournament)?;
+        let points = round_points(tournament, scoring.as_deref());
+        Ok(self
+            .picks
+            .iter()
+            .enumerate()
+            .map(|(round, picks)| {
+                let games = &levels[round + 1];
+                let hit: f64 = picks.iter().zip(games).map(|(team, game)| game.get(team).copied().unwrap_or(0.0)).sum();
+                points[round] * hit
+            })
+            .sum())
+    }
+
+    /// Points earned so far given `results[r]`, the teams that have won
+    /// main-bracket round `r`, under `rule` (default: the tournament's round
+    /// points). Rule rounds are main-bracket rounds.
+    #[pyo3(signature = (tournament, results, rule = None))]
+    pub fn score_results(
+        &self,
+        tournament: &TournamentState,
+        results: Vec<Vec<String>>,
+        rule: Option<ScoringRule>,
+    ) -> f64 {
+        let winners: Vec<HashSet<String>> = results.into_iter().map(|round| round.into_iter().collect()).collect();
+        match rule {
+            Some(rule) => self.score_with_rule(tournament, &winners, &rule),
+            None => self.score(&winners, &round_points(tournament, None)),
+        }
+    }
+
+    /// Whether every lock and forbid in `constraints` holds for this entry.
+    pub fn satisfies(&self, constraints: &PickConstraints) -> bool {
+        let reached = |team: &str| self.furthest_round(team);
+        constraints.locks.iter().all(|(team, round)| reached(team).is_some_and(|r| r >= *round))
+            && constraints.forbids.iter().all(|(team, round)| reached(team).is_none_or(|r| r < *round))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BracketEntry(rounds={}, champion={:?})", self.picks.len(), self.champion())
+    }
+}
+
+impl BracketEntry {
+    /// Points scored under `rule` given the teams that won each round.
+    ///
+    /// A correct pick's opponent is the team that won the other feeder game
+    /// (for first-round games, the other slot's team; play-in partners share a
+    /// seed). Unseeded teams count as seed 0.
+    pub fn score_with_rule(
+        &self,
+        tournament: &TournamentState,
+        winners: &[HashSet<String>],
+        rule: &ScoringRule,
+    ) -> f64 {
+        let seed = |team: &str| tournament.ratings.get(team).and_then(|t| t.seed).unwrap_or(0);
+        let slot = |team: &str| tournament.bracket().iter().position(|game| game.contains_key(team));
+        let mut total = 0.0;
+        for (round, (picks, won)) in self.picks.iter().zip(winners).enumerate() {
+            for team in picks.iter().filter(|team| won.contains(*team)) {
+                let Some(team_slot) = slot(team) else { continue };
+                let opponent_seed = if round == 0 {
+                    tournament.bracket()[team_slot ^ 1].keys().min().map_or(0, |opponent| seed(opponent))
+                } else {
+                    let side = (team_slot >> round) ^ 1;
+                    winners[round - 1]
+                        .iter()
+                        .find(|other| slot(other).is_some_and(|s| s >> round == side))
+                        .map_or(0, |opponent| seed(opponent))
+                };
+                total += rule.pick_points(round, seed(team), opponent_seed);
+            }
+        }
+        total
+    }
+
+    /// Points scored given the teams that won each round.
+    pub fn score(&self, winners: &[HashSet<String>], points: &[f64]) -> f64 {
+        self.picks
+            .iter()
+            .zip(winners)
+            .zip(points)
+            .map(|((picks, won), &round_points)| {
+                round_points * picks.iter().filter(|team| won.contains(*team)).count() as f64
+            })
+            .sum()
+    }
+}
+
+impl BracketEntry {
+    /// Validate that each round halves the previous one and every pick won
+    /// one of its two feeder games.
+    pub fn new(picks: Vec<Vec<String>>) -> Result<Self> {
+        for (round, games) in picks.iter().enumerate().skip(1) {
+            let feeders = &picks[round - 1];
+            if feeders.len() != 2 * games.len() {
+                return Err(TourneyError::InvalidInput(format!(
+                    "round {round} has {} picks but round {} has {}",
+                    games.len(),
+                    round - 1,
+                    feeders.len()
+                )));
+            }
+            if let Some((game, team)) =
+                games.iter().enumerate().find(|(g, team)| !feeders[2 * g..2 * g + 2].contains(team))
+            {
+                return Err(TourneyError::InvalidInput(format!(
+                    "{team} is picked to win round {round} game {game} without winning a feeder game"
+                )));
+            }
+        }
+        Ok(BracketEntry { picks })
+    }
+}
+
+/// Picks an optimized entry must (locks) or must not (forbids) contain, as
+/// `(team, round)` pairs with 0-based main-bracket rounds.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PickConstraints {
+    /// `team` must be picked to win every round through `round`.
+    #[pyo3(get)]
+    pub locks: Vec<(String, Round)>,
+
+    /// `team` must not be picked to win `round` or any later round.
+    #[pyo3(get)]
+    pub forbids: Vec<(String, Round)>,
+}
+
+#[pymethods]
+impl PickConstraints {
+    #[new]
+    #[pyo3(signature = (locks = None, forbids = None))]
+    pub fn new(locks: Option<Vec<(String, Round)>>, forbids: Option<Vec<(String, Round)>>) -> Self {
+        PickConstraints {
+            locks: locks.unwrap_or_default(),
+            forbids: forbids.unwrap_or_default(),
+        }
+    }
+
+    /// Require `team` to be picked through `round`.
+    pub fn lock(&mut self, team: String, round: Round) {
+        self.locks.push((team, round));
+    }
+
+    /// Rule out picking `team` to win `round` (or later).
+    pub fn forbid(&mut self, team: String, round: Round) {
+        self.forbids.push((team, round));
+    }
+
+    fn __repr__(&self) -> String {
+        let pairs = |picks: &[(String, Round)]| -> Vec<(String, u8)> {
+            picks.iter().map(|(team, round)| (team.clone(), round.0)).collect()
+        };
+        format!("PickConstraints(locks={:?}, forbids={:?})", pairs(&self.locks), pairs(&self.forbids))
+    }
+}
+
+impl PickConstraints {
+    /// The team each game must be won by.
+    fn required_winners(&self, slots: &HashMap<&str, usize>, n_rounds: usize) -> Result<HashMap<GameId, String>> {
+        let mut required: HashMap<GameId, String> = HashMap::new();
+        for (team, last) in &self.locks {
+            let slot = *slots
+                .get(team.as_str())
+                .ok_or_else(|| TourneyError::InvalidInput(format!("locked team not in bracket: {team}")))?;
+            if last.index() >= n_rounds {
+                return Err(TourneyError::InvalidInput(format!(
+                    "cannot lock {team} through round {last}; the bracket has {n_rounds} rounds"
+                )));
+            }
+            for round in 0..=last.0 {
+                let game = GameId::for_slot(slot, Round(round));
+                match required.get(&game) {
+                    Some(other) if other != team => {
+                        return Err(TourneyError::InvalidInput(format!(
+                            "locks on {team} and {other} conflict in round {round}"
+                        )))
+                    }
+                    _ => {
+                        required.insert(game, team.clone());
+                    }
+                }
+            }
+        }
+        Ok(required)
+    }
+
+    /// Whether `team` may be picked to win `round`.
+    fn allows(&self, team: &str, round: Round) -> bool {
+        !self.forbids.iter().any(|(t, r)| t == team && *r <= round)
+    }
+}
+
+/// Per-round points for entries: `scoring` if given, else the tournament's.
+pub(crate) fn round_points(tournament: &TournamentState, scoring: Option<&[f64]>) -> Vec<f64> {
+    let n_rounds = tournament.bracket().len().max(1).ilog2() as usize;
+    (0..n_rounds as u8)
+        .map(Round)
+        .map(|round| match scoring {
+            Some(points) => points.get(round.index()).copied().unwrap_or(1.0),
+            None => tournament.round_points(round),
+        })
+        .collect()
+}
+
+/// The entry with the highest expected score in `tournament` that respects
+/// `constraints`, with `scoring[r]` points per correct round-`r` pick (default:
+/// the tournament's round points). Ties go to the alphabetically first team.
+#[pyfunction]
+#[pyo3(signature = (tournament, scoring = None, constraints = None))]
+pub fn optimize_entry(
+    tournament: &TournamentState,
+    scoring: Option<Vec<f64>>,
+    constraints: Option<PickConstraints>,
+) -> Result<BracketEntry> {
+    if tournament.reseed {
+        return Err(TourneyError::InvalidInput(
+            "bracket entries follow the fixed tree and are undefined when reseeding".to_string(),
+        ));
+    }
+    let constraints = constraints.unwrap_or_default();
+    let levels = bracket_levels(tournament)?;
+    let points = round_points(tournament, scoring.as_deref());
+    let slots: HashMap<&str, usize> = tournament
+        .bracket()
+        .iter()
+        .enumerate()
+        .flat_map(|(slot, game)| game.keys().map(move |team| (team.as_str(), slot)))
+        .collect();
+    let required = constraints.required_winners(&slots, points.len())?;
+
+    // best[l][node][team]: best subtree score given `team` wins the node.
+    let mut best: Vec<Vec<HashMap<String, f64>>> =
+        vec![levels[0].iter().map(|slot| slot.keys().map(|team| (team.clone(), 0.0)).collect()).collect()];
+    for (round, &round_points) in points.iter().enumerate() {
+        let round_id = Round::try_from(round)?;
+        let below = &best[round];
+        let level = below
+            .chunks(2)
+            .enumerate()
+            .map(|(game, pair)| {
+                let mut scores = HashMap::new();
+                for (side, other) in [(&pair[0], &pair[1]), (&pair[1], &pair[0])] {
+                    let Some((_, other_best)) = best_of(other) else { continue };
+                    for (team, &subtree) in side {
+                        let required_ok = required.get(&GameId::new(round_id, game)).is_none_or(|t| t == team);
+                        if required_ok && constraints.allows(team, round_id) {
+                            let win_prob = levels[round + 1][game].get(team).copied().unwrap_or(0.0);
+                            scores.insert(team.clone(), subtree + other_best + round_points * win_prob);
+                        }
+                    }
+                }
+                scores
+            })
+            .collect();
+        best.push(level);
+    }
+
+    // Walk back down, giving each node's winner to the child it came from.
+    let n_rounds = points.len();
+    let mut picks: Vec<Vec<String>> = vec![Vec::new(); n_rounds];
+    let (champion, _) = best[n_rounds]
+        .first()
+        .and_then(best_of)
+        .ok_or_else(|| TourneyError::InvalidInput("constraints leave no valid champion".to_string()))?;
+    picks[n_rounds - 1] = vec![champion.clone()];
+    for round in (0..n_rounds - 1).rev() {
+        picks[round] = picks[round + 1]
+            .iter()
+            .enumerate()
+            .flat_map(|(game, winner)| {
+                [2 * game, 2 * game + 1].map(|child| {
+                    let node = &best[round + 1][child];
+                    if node.contains_key(winner) {
+                        winner.clone()
+                    } else {
+                        best_of(node).expect("feasible parent has feasible children").0.clone()
+                    }
+                })
+            })
+            .collect();
+    }
+    BracketEntry::new(picks)
+}
+
+/// Entries chosen jointly for a pool, in the order they were added.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct MultiEntryResult {
+    #[pyo3(get)]
+    pub entries: Vec<BracketEntry>,
+
+    /// Probability that at least one entry wins the pool.
+    #[pyo3(get)]
+    pub win_prob: f64,
+
+    /// Win probability each entry adds over the entries before it.
+    #[pyo3(get)]
+    pub marginal_win_prob: Vec<f64>,
+
+    /// Each entry's own probability of winning the pool.
+    #[pyo3(get)]
+    pub entry_win_prob: Vec<f64>,
+}
+
+#[pymethods]
+impl MultiEntryResult {
+    fn __repr__(&self) -> String {
+        format!("MultiEntryResult(entries={}, win_prob={:.4})", self.entries.len(), self.win_prob)
+    }
+}
+
+/// Candidate entries for `optimize_entries`: the unconstrained optimum, then
+/// optimal entries locked to a (champion, runner-up) pair, most likely pairs
+/// first. Pairs that conflict with `constraints` are skipped.
+fn candidate_entries(
+    tournament: &TournamentState,
+    scoring: Option<&[f64]>,
+    constraints: &PickConstraints,
+    n_candidates: usize,
+) -> Result<Vec<BracketEntry>> {
+    let levels = bracket_levels(tournament)?;
+    let n_rounds = levels.len() - 1;
+    let mut pairs: Vec<(f64, String, Option<String>)> = Vec::new();
+    if n_rounds >= 2 {
+        let halves = &levels[n_rounds - 1];
+        for (half, other) in [(0, 1), (1, 0)] {
+            for champion in halves[half].keys() {
+                let title_prob = levels[n_rounds][0].get(champion).copied().unwrap_or(0.0);
+                for (runner_up, &final_prob) in &halves[other] {
+                    pairs.push((title_prob * final_prob, champion.clone(), Some(runner_up.clone())));
+                }
+            }
+        }
+    } else if n_rounds == 1 {
+        pairs.extend(levels[1][0].iter().map(|(team, &p)| (p, team.clone(), None)));
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| (&a.1, &a.2).cmp(&(&b.1, &b.2))));
+
+    let mut candidates = vec![optimize_entry(tournament, scoring.map(<[f64]>::to_vec), Some(constraints.clone()))?];
+    for (_, champion, runner_up) in pairs {
+        if candidates.len() >= n_candidates {
+            break;
+        }
+        let mut locked = constraints.clone();
+        locked.lock(champion, Round::try_from(n_rounds - 1)?);
+        if let Some(runner_up) = runner_up {
+            locked.lock(runner_up, Round::try_from(n_rounds - 2)?);
+        }
+        if let Ok(entry) = optimize_entry(tournament, scoring.map(<[f64]>::to_vec), Some(locked)) {
+            if !candidates.contains(&entry) {
+                candidates.push(entry);
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// Choose `n_entries` entries jointly to maximize the probability that at
+/// least one of them beats every entry in `opponents`.
+///
+/// Up to `n_candidates` candidate entries (each respecting `constraints`) are
+/// scored against the field in `n_simulations` simulations; a candidate wins a
+/// simulation when it scores at least as much as the best opponent. Entries
+/// are then added greedily by the simulations they newly win, which accounts
+/// for the overlap between one's own entries.
+#[pyfunction]
+#[pyo3(signature = (
+    tournament, n_entries, opponents, scoring = None, constraints = None, n_candidates = 32,
+    n_simulations = 2000, seed = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn optimize_entries(
+    tournament: &TournamentState,
+    n_entries: usize,
+    opponents: Vec<BracketEntry>,
+    scoring: Option<Vec<f64>>,
+    constraints: Option<PickConstraints>,
+    n_candidates: usize,
+    n_simulations: usize,
+    seed: Option<u64>,
+) -> Result<MultiEntryResult> {
+    if opponents.is_empty() {
+        return Err(TourneyError::InvalidInput("need at least one opponent entry".to_string()));
+    }
+    let constraints = constraints.unwrap_or_default();
+    let candidates = candidate_entries(tournament, scoring.as_deref(), &constraints, n_candidates.max(1))?;
+    let points = round_points(tournament, scoring.as_deref());
+
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+    // wins[s][c]: candidate c wins simulation s.
+    let wins: Vec<Vec<bool>> = seeds
+        .par_iter()
+        .map(|&sim_seed| {
+            let winners = tournament.simulate_round_winners(Some(sim_seed));
+            let best_opponent = opponents.iter().map(|e| e.score(&winners, &points)).fold(f64::NEG_INFINITY, f64::max);
+            candidates.iter().map(|c| c.score(&winners, &points) >= best_opponent).collect()
+        })
+        .collect();
+
+    let n = n_simulations.max(1) as f64;
+    let mut covered = vec![false; wins.len()];
+    let mut chosen: Vec<usize> = Vec::new();
+    let mut marginal_win_prob = Vec::new();
+    while chosen.len() < n_entries.min(candidates.len()) {
+        let newly_won = |c: usize| wins.iter().zip(&covered).filter(|(w, &done)| w[c] && !done).count();
+        let next = (0..candidates.len())
+            .filter(|c| !chosen.contains(c))
+            .max_by(|&a, &b| newly_won(a).cmp(&newly_won(b)).then_with(|| b.cmp(&a)))
+            .expect("fewer entries chosen than candidates");
+        marginal_win_prob.push(newly_won(next) as f64 / n);
+        for (done, w) in covered.iter_mut().zip(&wins) {
+            *done |= w[next];
+        }
+        chosen.push(next);
+    }
+
+    Ok(MultiEntryResult {
+        win_prob: covered.iter().filter(|&&done| done).count() as f64 / n,
+        entry_win_prob: chosen.iter().map(|&c| wins.iter().filter(|w| w[c]).count() as f64 / n).collect(),
+        entries: chosen.into_iter().map(|c| candidates[c].clone()).collect(),
+        marginal_win_prob,
+    })
+}
+
+/// Highest-scoring team at a node (ties to the alphabetically first).
+fn best_of(node: &HashMap<String, f64>) -> Option<(&String, f64)> {
+    node.iter()
+        .map(|(team, &score)| (team, score))
+        .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_unconstrained_entry_beats_alternatives() {
+        let state = canonical_tournament(16);
+        let entry = optimize_entry(&state, None, None).unwrap();
+        assert_eq!(entry.picks.iter().map(Vec::len).collect::<Vec<_>>(), [8, 4, 2, 1]);
+        assert_eq!(entry.champion().as_deref(), Some("Team 01"));
+
+        // Any other entry, e.g. one fading the favorite, scores less.
+        let faded = PickConstraints::new(None, Some(vec![("Team 01".into(), Round(0))]));
+        let worse = optimize_entry(&state, None, Some(faded)).unwrap();
+        assert!(worse.expected_score(&state, None).unwrap() < entry.expected_score(&state, None).unwrap());
+    }
+
+    #[test]
+    fn test_locks_and_forbids_respected() {
+        let state = canonical_tournament(16);
+        let free = optimize_entry(&state, None, None).unwrap();
+
+        let mut constraints = PickConstraints::default();
+        constraints.lock("Team 13".to_string(), Round(1));
+        constraints.forbid("Team 02".to_string(), Round(3));
+        let entry = optimize_entry(&state, None, Some(constraints.clone())).unwrap();
+        assert!(entry.satisfies(&constraints));
+        assert!(!free.satisfies(&constraints));
+        assert!(entry.furthest_round("Team 13").unwrap() >= Round(1));
+        assert!(entry.expected_score(&state, None).unwrap() < free.expected_score(&state, None).unwrap());
+        // Team 13 opens against Team 01, so the lock knocks Team 01 out early.
+        assert_eq!(entry.furthest_round("Team 01"), None);
+    }
+
+    #[test]
+    fn test_joint_entries_diversify() {
+        let state = canonical_tournament(16);
+        let opponents: Vec<BracketEntry> = ["Team 01", "Team 02", "Team 03"]
+            .iter()
+            .map(|champion| {
+                let locks = PickConstraints::new(Some(vec![(champion.to_string(), Round(3))]), None);
+                optimize_entry(&state, None, Some(locks)).unwrap()
+            })
+            .collect();
+        let single = optimize_entries(&state, 1, opponents.clone(), None, None, 16, 500, Some(4)).unwrap();
+        let triple = optimize_entries(&state, 3, opponents, None, None, 16, 500, Some(4)).unwrap();
+
+        assert_eq!(single.entries[0], triple.entries[0]);
+        assert!(triple.win_prob >= single.win_prob);
+        assert!((triple.marginal_win_prob.iter().sum::<f64>() - triple.win_prob).abs() < 1e-12);
+        // Entries sharing a champion win together, so the set spreads its champions.
+        let champions: HashSet<Option<String>> = triple.entries.iter().map(BracketEntry::champion).collect();
+        assert_eq!(champions.len(), 3);
+        assert!(optimize_entries(&state, 2, Vec::new(), None, None, 8, 10, None).is_err());
+    }
+
+    #[test]
+    fn test_score_results_with_bonus_rule() {
+        let mut state = canonical_tournament(4);
+        for (rank, team) in ["Team 01", "Team 02", "Team 03", "Team 04"].iter().enumerate() {
+            state.ratings.get_mut(*team).unwrap().seed = Some(rank as u32 + 1);
+        }
+        let opener = |slot: usize| state.bracket()[slot].keys().next().unwrap().clone();
+        let entry = BracketEntry::new(vec![vec![opener(1), opener(2)], vec![opener(1)]]).unwrap();
+        let results = vec![vec![opener(1), opener(2)], vec![opener(1)]];
+        let seeds = |slot: usize| state.ratings[&opener(slot)].seed.unwrap();
+
+        let plain = 2.0 * state.round_points(Round(0)) + state.round_points(Round(1));
+        assert_eq!(entry.score_results(&state, results.clone(), None), plain);
+        let rule = ScoringRule::combine(vec![
+            ScoringRule::new(vec![1.0, 2.0], 1.0).unwrap(),
+            ScoringRule::upset_bonus_rule(vec![1.0, 1.0]),
+        ]);
+        let upset = |winner: usize, loser: usize| seeds(winner).saturating_sub(seeds(loser)) as f64;
+        let expected = 1.0 + upset(1, 0) + 1.0 + upset(2, 3) + 2.0 + upset(1, 2);
+        assert_eq!(entry.score_results(&state, results, Some(rule)), expected);
+    }
+
+    #[test]
+    fn test_infeasible_constraints_rejected() {
+        let state = canonical_tournament(16);
+        let finalists = vec![("Team 01".into(), Round(3)), ("Team 02".into(), Round(3))];
+        let conflicting = PickConstraints::new(Some(finalists), None);
+        assert!(optimize_entry(&state, None, Some(conflicting)).is_err());
+        let unknown = PickConstraints::new(Some(vec![("Nobody".into(), Round(0))]), None);
+        assert!(optimize_entry(&state, None, Some(unknown)).is_err());
+        assert!(BracketEntry::new(vec![vec!["A".into(), "B".into()], vec!["C".into()]]).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Bracket entry import and export.
+//!
+//! Bracket challenge sites export a pool's entries in two common shapes, both
+//! supported here so a real office pool can be scored without hand-converting
+//! every entry:
+//!
+//! * Wide CSV (ESPN group export, Yahoo "download picks"): one row per entry,
+//!   the entry name followed by every pick in game order, round by round and
+//!   top to bottom within a round. An optional header row starting with
+//!   `name` is skipped.
+//! * JSON (ESPN bracket dump): a list of `{"name": ..., "picks": [{"round":
+//!   r, "game": g, "team": ...}]}` objects with 1-based rounds and games.
+//!
+//! Site team names rarely match the ratings' names, so importers take an
+//! optional alias map applied to every pick.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::entry::BracketEntry;
+use crate::error::{Result, TourneyError};
+
+#[derive(Serialize, Deserialize)]
+struct EntryRecord {
+    name: String,
+    picks: Vec<PickRecord>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PickRecord {
+    round: usize,
+    game: usize,
+    team: String,
+}
+
+/// Split game-ordered picks into rounds (the first half is round 0, and so on).
+fn picks_by_round(name: &str, flat: Vec<String>) -> Result<BracketEntry> {
+    let n_teams = flat.len() + 1;
+    if flat.is_empty() || !n_teams.is_power_of_two() {
+        return Err(TourneyError::InvalidInput(format!(
+            "entry {name:?} has {} picks; a full bracket has one fewer than a power of two",
+            flat.len()
+        )));
+    }
+    let mut rounds = Vec::new();
+    let mut rest = flat.into_iter();
+    let mut games = n_teams / 2;
+    while games >= 1 {
+        rounds.push(rest.by_ref().take(games).collect());
+        games /= 2;
+    }
+    BracketEntry::new(rounds)
+}
+
+fn resolve(team: &str, aliases: Option<&HashMap<String, String>>) -> String {
+    aliases.and_then(|a| a.get(team)).cloned().unwrap_or_else(|| team.to_string())
+}
+
+/// Parse a wide CSV export into `(name, entry)` pairs.
+#[pyfunction]
+#[pyo3(signature = (text, aliases = None))]
+pub fn entries_from_csv(text: &str, aliases: Option<HashMap<String, String>>) -> Result<Vec<(String, BracketEntry)>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let mut entries = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let mut fields = record.iter();
+        let Some(name) = fields.next() else { continue };
+        if row == 0 && name.eq_ignore_ascii_case("name") {
+            continue;
+        }
+        let picks = fields.filter(|f| !f.is_empty()).map(|team| resolve(team, aliases.as_ref())).collect();
+        entries.push((name.to_string(), picks_by_round(name, picks)?));
+    }
+    Ok(entries)
+}
+
+/// Write `(name, entry)` pairs as a wide CSV export, with a header row.
+#[pyfunction]
+pub fn entries_to_csv(entries: Vec<(String, BracketEntry)>) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+    let n_picks = entries.first().map_or(0, |(_, entry)| entry.picks.iter().map(Vec::len).sum());
+    let header = std::iter::once("name".to_string()).chain((1..=n_picks).map(|i| format!("pick_{i}")));
+    writer.write_record(header)?;
+    for (name, entry) in &entries {
+        writer.write_record(std::iter::once(name).chain(entry.picks.iter().flatten()))?;
+    }
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV output is UTF-8"))
+}
+
+/// Parse a JSON bracket dump into `(name, entry)` pairs.
+#[pyfunction]
+#[pyo3(signature = (text, aliases = None))]
+pub fn entries_from_json(text: &str, aliases: Option<HashMap<String, String>>) -> Result<Vec<(String, BracketEntry)>> {
+    let records: Vec<EntryRecord> = serde_json::from_str(text)?;
+    records
+        .into_iter()
+        .map(|record| {
+            let mut picks = record.picks;
+            if let Some(pick) = picks.iter().find(|p| p.round == 0 || p.game == 0) {
+                return Err(TourneyError::InvalidInput(format!(
+                    "entry {:?}: rounds and games are 1-based, got round {} game {}",
+                    record.name, pick.round, pick.game
+                )));
+            }
+            picks.sort_by_key(|p| (p.round, p.game));
+            let flat = picks.into_iter().map(|p| resolve(&p.team, aliases.as_ref())).collect();
+            Ok((record.name.clone(), picks_by_round(&record.name, flat)?))
+        })
+        .collect()
+}
+
+/// Write `(name, entry)` pairs as a JSON bracket dump.
+#[pyfunction]
+pub fn entries_to_json(entries: Vec<(String, BracketEntry)>) -> Result<String> {
+    let records: Vec<EntryRecord> = entries
+        .into_iter()
+        .map(|(name, entry)| EntryRecord {
+            name,
+            picks: entry
+                .picks
+                .into_iter()
+                .enumerate()
+                .flat_map(|(round, teams)| {
+                    teams.into_iter().enumerate().map(move |(game, team)| PickRecord {
+                        round: round + 1,
+                        game: game + 1,
+                        team,
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::optimize_entry;
+    use crate::testing::canonical_tournament;
+
+    fn pool() -> Vec<(String, BracketEntry)> {
+        let state = canonical_tournament(8);
+        vec![("Chalk".to_string(), optimize_entry(&state, None, None).unwrap())]
+    }
+
+    #[test]
+    fn test_csv_and_json_round_trip() {
+        let entries = pool();
+        let csv = entries_to_csv(entries.clone()).unwrap();
+        assert!(csv.starts_with("name,pick_1,"));
+        assert_eq!(entries_from_csv(&csv, None).unwrap(), entries);
+        let json = entries_to_json(entries.clone()).unwrap();
+        assert_eq!(entries_from_json(&json, None).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_aliases_and_malformed_entries() {
+        assert!(entries_from_csv("Office,UNC,Duke,UNC\nShort,UNC,Duke\n", None).is_err());
+
+        let aliases: HashMap<String, String> = [("UNC".to_string(), "North Carolina".to_string())].into();
+        let parsed = entries_from_csv("Office,UNC,Duke,UNC\n", Some(aliases)).unwrap();
+        assert_eq!(parsed[0].1.champion().as_deref(), Some("North Carolina"));
+        // A pick that did not win a feeder game is rejected.
+        assert!(entries_from_csv("Bad,UNC,Duke,Kansas\n", None).is_err());
+        let zero_based = r#"[{"name": "x", "picks": [{"round": 0, "game": 1, "team": "A"}]}]"#;
+        assert!(entries_from_json(zero_based, None).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(feature = "python")]
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+#[cfg(feature = "python")]
+use pyo3::PyErr;
+use std::fmt;
+
+/// Errors raised by fallible tourney_core operations.
+#[derive(Debug)]
+pub enum TourneyError {
+    /// Malformed JSON input or a value that could not be serialized.
+    Json(serde_json::Error),
+    /// Malformed CSV input.
+    Csv(csv::Error),
+    /// Failure reading or writing a file.
+    Io(std::io::Error),
+    /// Failure in a persistence backend.
+    Storage(String),
+    /// An argument or input value outside its valid domain.
+    InvalidInput(String),
+    /// No usable GPU, or a failure running work on it.
+    Gpu(String),
+}
+
+impl fmt::Display for TourneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TourneyError::Json(err) => write!(f, "invalid JSON: {err}"),
+            TourneyError::Csv(err) => write!(f, "invalid CSV: {err}"),
+            TourneyError::Io(err) => write!(f, "I/O error: {err}"),
+            TourneyError::Storage(msg) => write!(f, "storage error: {msg}"),
+            TourneyError::InvalidInput(msg) => f.write_str(msg),
+            TourneyError::Gpu(msg) => write!(f, "GPU error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for TourneyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TourneyError::Json(err) => Some(err),
+            TourneyError::Csv(err) => Some(err),
+            TourneyError::Io(err) => Some(err),
+            TourneyError::Storage(_) | TourneyError::InvalidInput(_) | TourneyError::Gpu(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for TourneyError {
+    fn from(err: serde_json::Error) -> Self {
+        TourneyError::Json(err)
+    }
+}
+
+impl From<csv::Error> for TourneyError {
+    fn from(err: csv::Error) -> Self {
+        TourneyError::Csv(err)
+    }
+}
+
+impl From<std::io::Error> for TourneyError {
+    fn from(err: std::io::Error) -> Self {
+        TourneyError::Io(err)
+    }
+}
+
+#[cfg(feature = "storage")]
+impl From<rusqlite::Error> for TourneyError {
+    fn from(err: rusqlite::Error) -> Self {
+        TourneyError::Storage(err.to_string())
+    }
+}
+
+#[cfg(feature = "xlsx")]
+impl From<calamine::XlsxError> for TourneyError {
+    fn from(err: calamine::XlsxError) -> Self {
+        match err {
+            calamine::XlsxError::Io(err) => TourneyError::Io(err),
+            err => TourneyError::InvalidInput(format!("invalid xlsx workbook: {err}")),
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+impl From<TourneyError> for PyErr {
+    fn from(err: TourneyError) -> Self {
+        match err {
+            TourneyError::Io(_) => PyIOError::new_err(err.to_string()),
+            TourneyError::Gpu(_) => PyRuntimeError::new_err(err.to_string()),
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// Result alias for fallible tourney_core operations.
+pub type Result<T> = std::result::Result<T, TourneyError>;
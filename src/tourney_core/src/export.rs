@@ -0,0 +1,222 @@
+//! Bracket tree exports for rendering.
+//!
+//! Both formats walk the bracket as a tree: leaves are the bracket slots and
+//! each internal node is a game, annotated with every team's probability of
+//! winning it (i.e. advancing past that round).
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::error::{Result, TourneyError};
+use crate::game_transform::game_transform_prob_with;
+use crate::propagate;
+use crate::tournament::TournamentState;
+
+/// Teams shown per node in DOT labels before the rest are elided.
+const DOT_TEAMS_PER_NODE: usize = 4;
+
+/// Outcome distribution of every node, level by level: level 0 is the bracket
+/// slots, level `r + 1` the games of round `r`.
+///
+/// Games are propagated one at a time up the tree, except with persistent
+/// forfeits: a team that forfeits stays out, so a game's outcome depends on
+/// how its teams got there and the levels come from exact advancement, as in
+/// scoring. That needs every team in a single slot.
+///
+/// Reseeded tournaments re-pair the survivors after every round, so later
+/// games are not nodes of the bracket tree and there are no levels to report.
+pub fn bracket_levels(state: &TournamentState) -> Result<Vec<Vec<HashMap<String, f64>>>> {
+    if state.reseed {
+        return Err(TourneyError::InvalidInput(
+            "reseeded tournaments have no fixed bracket tree to report games on".to_string(),
+        ));
+    }
+    if state.persistent_forfeits && state.forfeit_prob > 0.0 {
+        let rounds = propagate::advancement(state).ok_or_else(|| {
+            TourneyError::InvalidInput(
+                "bracket levels with persistent forfeits need a power-of-2 bracket with each team in one slot"
+                    .to_string(),
+            )
+        })?;
+        return Ok(levels_from_advancement(state, &rounds));
+    }
+
+    let mut levels = vec![state.bracket().to_vec()];
+    while levels.last().is_some_and(|level| level.len() > 1) {
+        let games = levels.last().unwrap();
+        let next = games
+            .chunks(2)
+            .map(|pair| {
+                game_transform_prob_with(
+                    &pair[0],
+                    &pair[1],
+                    &state.ratings,
+                    Some(&state.overrides),
+                    state.forfeit_prob,
+                    &state.model_params,
+                )
+            })
+            .collect();
+        levels.push(next);
+    }
+    Ok(levels)
+}
+
+/// Levels from `propagate::advancement` output: each game holds the teams of
+/// the slots below it, with their chances of winning that round.
+fn levels_from_advancement(state: &TournamentState, rounds: &[Vec<f64>]) -> Vec<Vec<HashMap<String, f64>>> {
+    let teams = state.bracket_teams();
+    // `bracket_teams` lists each slot's teams together, in slot order.
+    let mut starts = vec![0];
+    for slot in state.bracket() {
+        starts.push(starts.last().unwrap() + slot.len());
+    }
+    let mut levels = vec![state.bracket().to_vec()];
+    for (round, probs) in rounds.iter().enumerate() {
+        let width = 2 << round;
+        let games = (0..state.bracket().len() / width)
+            .map(|game| {
+                let range = starts[game * width]..starts[(game + 1) * width];
+                teams[range.clone()].iter().cloned().zip(probs[range].iter().copied()).collect()
+            })
+            .collect();
+        levels.push(games);
+    }
+    levels
+}
+
+/// Teams in a node, most likely first (ties by name).
+fn ranked(node: &HashMap<String, f64>) -> Vec<(&String, f64)> {
+    let mut teams: Vec<(&String, f64)> = node.iter().map(|(team, &p)| (team, p)).collect();
+    teams.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    teams
+}
+
+/// Graphviz DOT graph of the bracket, with edges from each node to the game
+/// its winner plays next.
+pub fn to_dot(state: &TournamentState) -> Result<String> {
+    let levels = bracket_levels(state)?;
+    let mut dot = String::from("digraph bracket {\n  rankdir=LR;\n  node [shape=box, fontname=\"Helvetica\"];\n");
+    for (level, nodes) in levels.iter().enumerate() {
+        for (i, node) in nodes.iter().enumerate() {
+            let teams = ranked(node);
+            let mut lines: Vec<String> = teams
+                .iter()
+                .take(DOT_TEAMS_PER_NODE)
+                .map(|(team, p)| format!("{} {:.1}%", escape(&label(state, team)), 100.0 * p))
+                .collect();
+            if teams.len() > DOT_TEAMS_PER_NODE {
+                lines.push(format!("+{} more", teams.len() - DOT_TEAMS_PER_NODE));
+            }
+            let _ = writeln!(dot, "  n{level}_{i} [label=\"{}\"];", lines.join("\\n"));
+            if level > 0 {
+                for child in [2 * i, 2 * i + 1] {
+                    let _ = writeln!(dot, "  n{}_{child} -> n{level}_{i};", level - 1);
+                }
+            }
+        }
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// Nested JSON tree rooted at the championship game.
+///
+/// Each node has `round` (-1 for bracket slots, otherwise the 0-based round),
+/// `teams` as `{team, label, prob}` entries sorted by probability, and, for games,
+/// the two `children` feeding it.
+pub fn to_bracket_json(state: &TournamentState) -> Result<Value> {
+    let levels = bracket_levels(state)?;
+    Ok(node_json(state, &levels, levels.len() - 1, 0))
+}
+
+fn node_json(state: &TournamentState, levels: &[Vec<HashMap<String, f64>>], level: usize, index: usize) -> Value {
+    let teams: Vec<Value> = ranked(&levels[level][index])
+        .into_iter()
+        .map(|(team, prob)| json!({ "team": team, "label": label(state, team), "prob": prob }))
+        .collect();
+    if level == 0 {
+        json!({ "round": -1, "teams": teams })
+    } else {
+        let children = vec![
+            node_json(state, levels, level - 1, 2 * index),
+            node_json(state, levels, level - 1, 2 * index + 1),
+        ];
+        json!({ "round": level - 1, "teams": teams, "children": children })
+    }
+}
+
+/// Display label from team metadata (seed, short name, record) when available.
+fn label(state: &TournamentState, team: &str) -> String {
+    state.ratings.get(team).map_or_else(|| team.to_string(), |t| t.label())
+}
+
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_dot_has_nodes_and_edges() {
+        let dot = to_dot(&canonical_tournament(4)).unwrap();
+        assert!(dot.starts_with("digraph bracket {"));
+        // 4 slots + 2 semifinals + 1 final, and two edges into each game.
+        assert_eq!(dot.matches("[label=").count(), 7);
+        assert_eq!(dot.matches(" -> ").count(), 6);
+        assert!(dot.contains("(1) Team 01 100.0%"));
+        assert_eq!(escape("D\"Q"), "D\\\"Q");
+    }
+
+    #[test]
+    fn test_json_tree_probabilities() {
+        let state = canonical_tournament(4);
+        let tree = to_bracket_json(&state).unwrap();
+        assert_eq!(tree["round"], 1);
+        let total: f64 = tree["teams"].as_array().unwrap().iter().map(|t| t["prob"].as_f64().unwrap()).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(tree["children"][0]["children"][1]["teams"][0]["team"], "Team 04");
+        assert_eq!(tree["children"][0]["children"][0]["teams"][0]["label"], "(1) Team 01");
+
+        // The strongest team is listed first in the final.
+        assert_eq!(tree["teams"][0]["team"], "Team 01");
+    }
+
+    #[test]
+    fn test_levels_follow_persistent_forfeits() {
+        let mut state = canonical_tournament(16);
+        state.forfeit_prob = 0.2;
+        let per_game = bracket_levels(&state).unwrap();
+        state.persistent_forfeits = true;
+        let levels = bracket_levels(&state).unwrap();
+
+        // The final matches scoring, which tracks who has forfeited.
+        let final_points = state.scoring.last().unwrap();
+        for (team, rounds) in state.scores_by_round_internal(false, None) {
+            assert!((levels[4][0][&team] - rounds[3] / final_points).abs() < 1e-9, "{team}");
+        }
+        assert!(levels[4][0]["Team 01"] - per_game[4][0]["Team 01"] > 0.01);
+        for (level, games) in levels.iter().enumerate() {
+            assert_eq!(games.len(), 16 >> level);
+            for game in games {
+                assert!((game.values().sum::<f64>() - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reseed_has_no_levels() {
+        let mut state = canonical_tournament(8);
+        state.reseed = true;
+        assert!(bracket_levels(&state).is_err());
+        assert!(to_dot(&state).is_err());
+        assert!(crate::rooting::upcoming_games(&state).is_err());
+        assert!(crate::report::text_report(&state, None).is_err());
+        // The per-pairing checks still apply.
+        assert!(crate::invariants::verify_invariants(&state, 1e-9).is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+//! Concentration of portfolio value and risk across parts of the bracket.
+//!
+//! Regions are consecutive, equally sized blocks of bracket slots. A team's
+//! seed is its rating's `seed` when set, and is otherwise inferred from its
+//! slot's position within the region (see `seed_for_slot`). Shares are computed from gross (absolute) amounts so that long
+//! and short positions in the same bucket do not hide each other.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::seeding::{seed_band, seed_for_slot};
+use crate::tournament::TournamentState;
+
+/// Number of regions assumed when no region names are given.
+const DEFAULT_REGIONS: usize = 4;
+
+/// Value and delta held in one slice of the bracket.
+#[pyclass]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExposureBucket {
+    /// Net expected value of positions in this bucket.
+    #[pyo3(get)]
+    pub value: f64,
+
+    /// Fraction of the portfolio's gross expected value in this bucket.
+    #[pyo3(get)]
+    pub value_share: f64,
+
+    /// Net portfolio delta of teams in this bucket.
+    #[pyo3(get)]
+    pub delta: f64,
+
+    /// Fraction of the portfolio's gross delta in this bucket.
+    #[pyo3(get)]
+    pub delta_share: f64,
+}
+
+#[pymethods]
+impl ExposureBucket {
+    fn __repr__(&self) -> String {
+        format!(
+            "ExposureBucket(value={:.4} ({:.1}%), delta={:.4} ({:.1}%))",
+            self.value,
+            100.0 * self.value_share,
+            self.delta,
+            100.0 * self.delta_share
+        )
+    }
+}
+
+/// Portfolio exposure broken down by region, seed band, and round.
+#[pyclass]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExposureReport {
+    #[pyo3(get)]
+    pub by_region: HashMap<String, ExposureBucket>,
+
+    /// Keyed by bands of four seeds ("1-4", "5-8", ...).
+    #[pyo3(get)]
+    pub by_seed_band: HashMap<String, ExposureBucket>,
+
+    /// Expected portfolio value earned in each scoring round.
+    #[pyo3(get)]
+    pub by_round: Vec<f64>,
+}
+
+#[pymethods]
+impl ExposureReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "ExposureReport({} regions, {} seed bands, {} rounds)",
+            self.by_region.len(),
+            self.by_seed_band.len(),
+            self.by_round.len()
+        )
+    }
+}
+
+/// Running gross totals used to turn bucket amounts into shares.
+#[derive(Default)]
+struct Gross {
+    value: HashMap<String, f64>,
+    delta: HashMap<String, f64>,
+}
+
+impl ExposureReport {
+    /// Build a report for `positions` in `tournament`.
+    ///
+    /// `team_deltas` are per-team portfolio deltas as produced by
+    /// `get_all_team_deltas`. `region_names` defaults to the tournament's
+    /// `regions`, or if it has none, four regions named "Region 1" to "Region 4".
+    pub fn build(
+        positions: &HashMap<String, f64>,
+        tournament: &TournamentState,
+        team_deltas: &HashMap<String, f64>,
+        region_names: Option<Vec<String>>,
+    ) -> Result<Self> {
+        let region_names = region_names.unwrap_or_else(|| match &tournament.regions {
+            regions if regions.is_empty() => (1..=DEFAULT_REGIONS).map(|i| format!("Region {i}")).collect(),
+            regions => regions.clone(),
+        });
+        let n_slots = tournament.bracket().len();
+        if region_names.is_empty() || !n_slots.is_multiple_of(region_names.len()) {
+            return Err(TourneyError::InvalidInput(format!(
+                "bracket of {n_slots} slots cannot be split into {} regions",
+                region_names.len()
+            )));
+        }
+        let region_size = n_slots / region_names.len();
+        if !region_size.is_power_of_two() {
+            return Err(TourneyError::InvalidInput(format!(
+                "regions must have a power-of-2 number of slots, got {region_size}"
+            )));
+        }
+
+        let by_round_scores = tournament.scores_by_round_internal(false, None);
+        let mut report = ExposureReport::default();
+        let mut region_gross = Gross::default();
+        let mut band_gross = Gross::default();
+
+        for (slot, game) in tournament.bracket().iter().enumerate() {
+            let region = &region_names[slot / region_size];
+            let slot_seed = seed_for_slot(slot, region_size);
+
+            for team in game.keys() {
+                let seed = tournament
+                    .ratings
+                    .get(team)
+                    .and_then(|rating| rating.seed)
+                    .filter(|&seed| seed > 0)
+                    .map_or(slot_seed, |seed| seed as usize);
+                let band = seed_band(seed);
+                let position = positions.get(team).copied().unwrap_or(0.0);
+                let rounds = by_round_scores.get(team);
+                let value = position * rounds.map_or(0.0, |r| r.iter().sum());
+                let delta = team_deltas.get(team).copied().unwrap_or(0.0);
+
+                if let Some(rounds) = rounds {
+                    if report.by_round.len() < rounds.len() {
+                        report.by_round.resize(rounds.len(), 0.0);
+                    }
+                    for (total, points) in report.by_round.iter_mut().zip(rounds) {
+                        *total += position * points;
+                    }
+                }
+
+                add(&mut report.by_region, &mut region_gross, region, value, delta);
+                add(&mut report.by_seed_band, &mut band_gross, &band, value, delta);
+            }
+        }
+
+        finish_shares(&mut report.by_region, &region_gross);
+        finish_shares(&mut report.by_seed_band, &band_gross);
+        Ok(report)
+    }
+}
+
+fn add(buckets: &mut HashMap<String, ExposureBucket>, gross: &mut Gross, key: &str, value: f64, delta: f64) {
+    let bucket = buckets.entry(key.to_string()).or_default();
+    bucket.value += value;
+    bucket.delta += delta;
+    *gross.value.entry(key.to_string()).or_insert(0.0) += value.abs();
+    *gross.delta.entry(key.to_string()).or_insert(0.0) += delta.abs();
+}
+
+fn finish_shares(buckets: &mut HashMap<String, ExposureBucket>, gross: &Gross) {
+    let total_value: f64 = gross.value.values().sum();
+    let total_delta: f64 = gross.delta.values().sum();
+    for (key, bucket) in buckets.iter_mut() {
+        if total_value > 0.0 {
+            bucket.value_share = gross.value[key] / total_value;
+        }
+        if total_delta > 0.0 {
+            bucket.delta_share = gross.delta[key] / total_delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_report_concentrated_in_one_region() {
+        // Slots 0 and 1 hold the 1 and 8 seeds.
+        let tournament = canonical_tournament(8);
+        let positions: HashMap<String, f64> =
+            [("Team 01".to_string(), 3.0), ("Team 08".to_string(), 1.0)].into_iter().collect();
+        let scores = tournament.calculate_scores_prob();
+        let report = ExposureReport::build(&positions, &tournament, &HashMap::new(), None).unwrap();
+
+        // Both slots fall in the first of four two-slot regions.
+        assert_eq!(report.by_region.len(), 4);
+        assert!((report.by_region["Region 1"].value_share - 1.0).abs() < 1e-9);
+        let expected = 3.0 * scores["Team 01"] + scores["Team 08"];
+        assert!((report.by_region["Region 1"].value - expected).abs() < 1e-9);
+        assert!((report.by_round.iter().sum::<f64>() - expected).abs() < 1e-9);
+        assert_eq!(report.by_round.len(), 3);
+
+        assert!((report.by_seed_band["1-4"].value - 3.0 * scores["Team 01"]).abs() < 1e-9);
+        assert!((report.by_seed_band["5-8"].value - scores["Team 08"]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delta_shares_and_invalid_regions() {
+        // Slot 0 holds Team 01 and slot 7 holds Team 06.
+        let tournament = canonical_tournament(8);
+        let positions = HashMap::new();
+        let deltas: HashMap<String, f64> = [("Team 01".to_string(), 1.0), ("Team 06".to_string(), -3.0)].into_iter().collect();
+        let names = vec!["East".to_string(), "West".to_string()];
+        let report = ExposureReport::build(&positions, &tournament, &deltas, Some(names)).unwrap();
+
+        assert!((report.by_region["East"].delta_share - 0.25).abs() < 1e-9);
+        assert!((report.by_region["West"].delta - -3.0).abs() < 1e-9);
+
+        // Without explicit names, the tournament's own regions are used.
+        let mut named = canonical_tournament(8);
+        named.regions = vec!["North".to_string(), "South".to_string()];
+        let report = ExposureReport::build(&positions, &named, &deltas, None).unwrap();
+        assert!((report.by_region["South"].delta - -3.0).abs() < 1e-9);
+
+        let three = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        assert!(ExposureReport::build(&positions, &tournament, &deltas, Some(three)).is_err());
+    }
+
+    #[test]
+    fn test_seed_bands_in_ncaa_regions() {
+        let mut tournament = canonical_tournament(64);
+        let team_in = |tournament: &TournamentState, slot: usize| tournament.bracket()[slot].keys().next().cloned();
+        let (fourth, fifteenth) = (team_in(&tournament, 4).unwrap(), team_in(&tournament, 14).unwrap());
+        let deltas: HashMap<String, f64> = [(fourth.clone(), 1.0), (fifteenth.clone(), 2.0)].into_iter().collect();
+
+        // Ratings carry seeds: the canonical layout puts the 4 and 6 seeds here.
+        let report = ExposureReport::build(&HashMap::new(), &tournament, &deltas, None).unwrap();
+        assert_eq!(tournament.ratings[&fourth].seed, Some(4));
+        assert_eq!(tournament.ratings[&fifteenth].seed, Some(6));
+        assert!((report.by_seed_band["1-4"].delta - 1.0).abs() < 1e-9);
+        assert!((report.by_seed_band["5-8"].delta - 2.0).abs() < 1e-9);
+
+        // Without seeds, slots 4 and 14 of a 16-slot region are the 5 and 2 seeds.
+        for team in tournament.ratings.values_mut() {
+            team.seed = None;
+        }
+        let report = ExposureReport::build(&HashMap::new(), &tournament, &deltas, None).unwrap();
+        assert!((report.by_seed_band["5-8"].delta - 1.0).abs() < 1e-9);
+        assert!((report.by_seed_band["1-4"].delta - 2.0).abs() < 1e-9);
+    }
+}
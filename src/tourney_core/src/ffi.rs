@@ -0,0 +1,239 @@
+//! C ABI for non-Python consumers (R, Julia, Excel add-ins).
+//!
+//! States are created from the JSON produced by `TournamentState::to_json` and
+//! handed out as opaque pointers. Results are returned as JSON strings owned by
+//! the library; release them with `tourney_string_free`. On failure a function
+//! returns null and `tourney_last_error` describes what went wrong. Panics are
+//! caught at the boundary and reported the same way, since unwinding out of
+//! an `extern "C"` function aborts the host process.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::tournament::TournamentState;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Run an FFI function body, returning `on_panic` with the panic message as
+/// the last error if it panics.
+fn guard<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        set_last_error(format!("internal error: {}", panic_message(payload.as_ref())));
+        on_panic
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message,
+        (_, Some(message)) => message,
+        _ => "panic",
+    }
+}
+
+/// Convert an owned JSON string into a C string handed to the caller.
+fn into_c_string(json: String) -> *mut c_char {
+    match CString::new(json) {
+        Ok(s) => s.into_raw(),
+        Err(err) => {
+            set_last_error(err.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Create a tournament from a JSON document.
+///
+/// Returns null on malformed input; see `tourney_last_error`.
+///
+/// # Safety
+/// `json` must be null or a valid NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn tourney_tournament_from_json(json: *const c_char) -> *mut TournamentState {
+    guard(ptr::null_mut(), || {
+        clear_last_error();
+        if json.is_null() {
+            set_last_error("json must not be null");
+            return ptr::null_mut();
+        }
+        let json = match CStr::from_ptr(json).to_str() {
+            Ok(s) => s,
+            Err(err) => {
+                set_last_error(err.to_string());
+                return ptr::null_mut();
+            }
+        };
+        match TournamentState::from_json(json) {
+            Ok(state) => Box::into_raw(Box::new(state)),
+            Err(err) => {
+                set_last_error(err.to_string());
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Compute expected scores, returned as a JSON object of team name to score.
+///
+/// # Safety
+/// `state` must be null or a pointer returned by `tourney_tournament_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn tourney_calculate_scores(state: *const TournamentState) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        clear_last_error();
+        let Some(state) = state.as_ref() else {
+            set_last_error("state must not be null");
+            return ptr::null_mut();
+        };
+        match serde_json::to_string(&state.calculate_scores_prob()) {
+            Ok(json) => into_c_string(json),
+            Err(err) => {
+                set_last_error(err.to_string());
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Run Monte Carlo simulations, returned as a JSON array of score objects.
+///
+/// A negative `seed` draws a seed from system entropy.
+///
+/// # Safety
+/// `state` must be null or a pointer returned by `tourney_tournament_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn tourney_run_simulations(
+    state: *const TournamentState,
+    n_simulations: usize,
+    seed: i64,
+) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        clear_last_error();
+        let Some(state) = state.as_ref() else {
+            set_last_error("state must not be null");
+            return ptr::null_mut();
+        };
+        let seed = u64::try_from(seed).ok();
+        match serde_json::to_string(&state.run_simulations(n_simulations, seed)) {
+            Ok(json) => into_c_string(json),
+            Err(err) => {
+                set_last_error(err.to_string());
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Release a tournament created by `tourney_tournament_from_json`.
+///
+/// # Safety
+/// `state` must be null or a pointer returned by `tourney_tournament_from_json`
+/// that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn tourney_tournament_free(state: *mut TournamentState) {
+    guard((), || {
+        if !state.is_null() {
+            drop(Box::from_raw(state));
+        }
+    })
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+/// `s` must be null or a string returned by this library that has not already
+/// been freed.
+#[no_mangle]
+pub unsafe extern "C" fn tourney_string_free(s: *mut c_char) {
+    guard((), || {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    })
+}
+
+/// Message describing the last failure on this thread, or null if the last
+/// call succeeded. The pointer is valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn tourney_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "bracket": [{"A": 1.0}, {"B": 1.0}],
+        "ratings": {
+            "A": {"name": "A", "offense": 0.05, "defense": -0.02, "tempo": 68.0},
+            "B": {"name": "B", "offense": 0.0, "defense": 0.0, "tempo": 67.7}
+        },
+        "scoring": [1.0]
+    }"#;
+
+    #[test]
+    fn test_scores_round_trip() {
+        let json = CString::new(JSON).unwrap();
+        unsafe {
+            let state = tourney_tournament_from_json(json.as_ptr());
+            assert!(!state.is_null());
+
+            let scores = tourney_calculate_scores(state);
+            assert!(!scores.is_null());
+            let parsed: std::collections::HashMap<String, f64> =
+                serde_json::from_str(CStr::from_ptr(scores).to_str().unwrap()).unwrap();
+            assert!((parsed["A"] + parsed["B"] - 1.0).abs() < 1e-10);
+
+            tourney_string_free(scores);
+            tourney_tournament_free(state);
+        }
+    }
+
+    #[test]
+    fn test_invalid_json_sets_error() {
+        let json = CString::new("{not json").unwrap();
+        unsafe {
+            let state = tourney_tournament_from_json(json.as_ptr());
+            assert!(state.is_null());
+            assert!(!tourney_last_error().is_null());
+        }
+    }
+
+    #[test]
+    fn test_unrated_team_is_rejected() {
+        let json = CString::new(JSON.replace(r#"{"B": 1.0}"#, r#"{"C": 1.0}"#)).unwrap();
+        unsafe {
+            let state = tourney_tournament_from_json(json.as_ptr());
+            assert!(state.is_null());
+            let error = CStr::from_ptr(tourney_last_error()).to_str().unwrap();
+            assert!(error.contains('C'), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_panic_sets_error() {
+        let mut state = crate::testing::canonical_tournament(4);
+        state.bracket_mut().truncate(3);
+        unsafe {
+            let scores = tourney_calculate_scores(&state);
+            assert!(scores.is_null());
+            let error = CStr::from_ptr(tourney_last_error()).to_str().unwrap();
+            assert!(error.starts_with("internal error"), "{error}");
+        }
+    }
+}
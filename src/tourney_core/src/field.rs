@@ -0,0 +1,133 @@
+//! Synthetic opponent fields from public pick percentages.
+//!
+//! National bracket challenges publish, per team, the share of entries picking
+//! it to win each round. `sample_field` turns those ownership tables into
+//! complete entries: each game is filled, round by round, with one of the two
+//! teams the entry advanced into it, chosen in proportion to their ownership
+//! for that round. The result plugs into `optimize_entries` and
+//! `bracket_pool_equity` as the opponents a real pool would contain.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::entry::BracketEntry;
+use crate::error::{Result, TourneyError};
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// Sample `n_entries` opponent entries from `ownership`, where
+/// `ownership[team][r]` is the share of the public picking `team` to win
+/// main-bracket round `r` (0-based).
+///
+/// Teams or rounds missing from `ownership` count as unowned; a game between
+/// two unowned teams is a coin flip.
+#[pyfunction]
+#[pyo3(signature = (tournament, ownership, n_entries, seed = None))]
+pub fn sample_field(
+    tournament: &TournamentState,
+    ownership: HashMap<String, Vec<f64>>,
+    n_entries: usize,
+    seed: Option<u64>,
+) -> Result<Vec<BracketEntry>> {
+    if let Some((team, share)) = ownership
+        .iter()
+        .flat_map(|(team, shares)| shares.iter().map(move |&share| (team, share)))
+        .find(|&(_, share)| !(0.0..=1.0).contains(&share))
+    {
+        return Err(TourneyError::InvalidInput(format!(
+            "ownership for {team} must be between 0 and 1, got {share}"
+        )));
+    }
+    let n_rounds = tournament.bracket().len().max(1).ilog2() as usize;
+    // Slot teams sorted so draws do not depend on hash-map order.
+    let slots: Vec<Vec<String>> = tournament
+        .bracket()
+        .iter()
+        .map(|slot| {
+            let mut teams: Vec<String> = slot.keys().cloned().collect();
+            teams.sort();
+            teams
+        })
+        .collect();
+    let owned = |team: &str, round: usize| ownership.get(team).and_then(|s| s.get(round)).copied().unwrap_or(0.0);
+
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_entries).map(|_| rng.gen()).collect();
+    seeds
+        .par_iter()
+        .map(|&entry_seed| {
+            let mut rng = seeded_rng(Some(entry_seed));
+            let mut picks: Vec<Vec<String>> = Vec::with_capacity(n_rounds);
+            for round in 0..n_rounds {
+                let feeders: Vec<Vec<&String>> = match picks.last() {
+                    None => slots.iter().map(|teams| teams.iter().collect()).collect(),
+                    Some(previous) => previous.iter().map(|team| vec![team]).collect(),
+                };
+                let winners = feeders
+                    .chunks(2)
+                    .map(|pair| {
+                        let teams: Vec<&String> = pair.iter().flatten().copied().collect();
+                        let weights: Vec<f64> = teams.iter().map(|team| owned(team, round)).collect();
+                        let total: f64 = weights.iter().sum();
+                        let index = if total > 0.0 {
+                            let mut draw = rng.gen::<f64>() * total;
+                            weights.iter().position(|&w| {
+                                draw -= w;
+                                draw < 0.0
+                            })
+                            .unwrap_or(teams.len() - 1)
+                        } else {
+                            rng.gen_range(0..teams.len())
+                        };
+                        teams[index].clone()
+                    })
+                    .collect();
+                picks.push(winners);
+            }
+            BracketEntry::new(picks)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    /// Ownership that favors better-ranked teams more in every round.
+    fn ownership(state: &TournamentState) -> HashMap<String, Vec<f64>> {
+        state
+            .get_bracket_teams()
+            .into_iter()
+            .map(|team| {
+                let rank: f64 = team[5..].parse().unwrap();
+                let shares = (0..4).map(|round| (1.0 - rank / 17.0).powi(round + 1)).collect();
+                (team, shares)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_field_follows_ownership() {
+        let state = canonical_tournament(16);
+        let field = sample_field(&state, ownership(&state), 2000, Some(8)).unwrap();
+        assert_eq!(field.len(), 2000);
+        let champions = |team: &str| field.iter().filter(|e| e.champion().as_deref() == Some(team)).count();
+        assert!(champions("Team 01") > champions("Team 04"));
+        assert!(champions("Team 04") > champions("Team 16"));
+        assert_eq!(field, sample_field(&state, ownership(&state), 2000, Some(8)).unwrap());
+    }
+
+    #[test]
+    fn test_unowned_games_are_coin_flips_and_bad_shares_rejected() {
+        let state = canonical_tournament(4);
+        let field = sample_field(&state, HashMap::new(), 400, Some(2)).unwrap();
+        let first = field.iter().filter(|e| e.picks[0][0] == "Team 01").count();
+        assert!((150..250).contains(&first));
+
+        let bad: HashMap<String, Vec<f64>> = [("Team 01".to_string(), vec![1.5])].into();
+        assert!(sample_field(&state, bad, 10, None).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! Typed rounds and games.
+//!
+//! Bracket code has long passed rounds and game positions around as bare
+//! `usize`s, which leaves callers to know that round 0 is the first main-bracket
+//! round, that game `g` of round `r` is fed by slots `g << (r + 1)` onwards,
+//! and that `scoring` is shifted by one when the play-in round is scored.
+//! `Round` and `GameId` carry that knowledge instead, so a specific game (say,
+//! the second game of round two, in the East region) has a name that can be
+//! stored, compared, and looked up.
+//!
+//! `Round` is a plain int on the Python side; `GameId` is a hashable class.
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+
+use crate::error::{Result, TourneyError};
+
+/// A main-bracket round, 0-based (round 0 is the first round after any
+/// play-in games).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Round(pub u8);
+
+impl Round {
+    pub const FIRST: Round = Round(0);
+
+    pub fn index(self) -> usize {
+        usize::from(self.0)
+    }
+
+    /// The round after this one.
+    pub fn next(self) -> Round {
+        Round(self.0 + 1)
+    }
+
+    /// Index of this round's points in `TournamentState::scoring`, which
+    /// starts with the play-in round when that is scored.
+    pub fn scoring_index(self, play_in_round: bool) -> usize {
+        self.index() + usize::from(play_in_round)
+    }
+
+    /// Number of games in this round of a bracket with `n_slots` slots.
+    pub fn n_games(self, n_slots: usize) -> usize {
+        n_slots.checked_shr(u32::from(self.0) + 1).unwrap_or(0)
+    }
+}
+
+impl From<u8> for Round {
+    fn from(round: u8) -> Self {
+        Round(round)
+    }
+}
+
+impl From<Round> for usize {
+    fn from(round: Round) -> Self {
+        round.index()
+    }
+}
+
+impl TryFrom<usize> for Round {
+    type Error = TourneyError;
+
+    fn try_from(round: usize) -> Result<Self> {
+        u8::try_from(round)
+            .map(Round)
+            .map_err(|_| TourneyError::InvalidInput(format!("round {round} is out of range")))
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round {}", self.0)
+    }
+}
+
+#[cfg(feature = "python")]
+impl<'py> FromPyObject<'py> for Round {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let round: usize = ob.extract()?;
+        Round::try_from(round).map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+}
+
+#[cfg(feature = "python")]
+impl IntoPy<PyObject> for Round {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.0.into_py(py)
+    }
+}
+
+#[cfg(feature = "python")]
+impl ToPyObject for Round {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.0.to_object(py)
+    }
+}
+
+/// One game of the main bracket: game `index` (0-based, in bracket order) of
+/// `round`.
+#[pyclass(frozen, eq, hash, ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GameId {
+    #[pyo3(get)]
+    pub round: Round,
+
+    #[pyo3(get)]
+    pub index: usize,
+}
+
+impl GameId {
+    /// Bracket slots whose teams can reach this game.
+    pub fn slot_range(&self) -> Range<usize> {
+        let width = 2usize.saturating_pow(u32::from(self.round.0) + 1);
+        self.index.saturating_mul(width)..(self.index + 1).saturating_mul(width)
+    }
+
+    /// The game's two feeding games (`None` in round 0, which is fed
+    /// directly by slots).
+    pub fn feeders(&self) -> Option<(GameId, GameId)> {
+        let round = Round(self.round.0.checked_sub(1)?);
+        Some((GameId::new(round, 2 * self.index), GameId::new(round, 2 * self.index + 1)))
+    }
+}
+
+impl fmt::Display for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "game {} of {}", self.index, self.round)
+    }
+}
+
+#[pymethods]
+impl GameId {
+    #[new]
+    pub fn new(round: Round, index: usize) -> Self {
+        GameId { round, index }
+    }
+
+    /// The game in `round` that the team in bracket slot `slot` plays, if it
+    /// gets there.
+    #[staticmethod]
+    pub fn for_slot(slot: usize, round: Round) -> Self {
+        GameId::new(round, slot.checked_shr(u32::from(round.0) + 1).unwrap_or(0))
+    }
+
+    /// The game in `round` within region `region` (0-based) of a bracket of
+    /// `n_slots` slots split into `n_regions` equal regions.
+    #[staticmethod]
+    pub fn in_region(round: Round, region: usize, game: usize, n_slots: usize, n_regions: usize) -> Result<Self> {
+        let per_region = round.n_games(n_slots) / n_regions.max(1);
+        if n_regions == 0 || per_region == 0 || region >= n_regions || game >= per_region {
+            return Err(TourneyError::InvalidInput(format!(
+                "no game {game} in region {region} of {round} for {n_regions} regions of a {n_slots}-slot bracket"
+            )));
+        }
+        Ok(GameId::new(round, region * per_region + game))
+    }
+
+    /// The game this game's winner plays next.
+    pub fn next(&self) -> GameId {
+        GameId::new(self.round.next(), self.index / 2)
+    }
+
+    /// Region (0-based) of a bracket of `n_slots` slots split into
+    /// `n_regions` regions, or `None` once the game draws from several.
+    pub fn region(&self, n_slots: usize, n_regions: usize) -> Option<usize> {
+        let region_size = n_slots / n_regions.max(1);
+        let slots = self.slot_range();
+        (region_size > 0 && slots.len() <= region_size).then(|| slots.start / region_size)
+    }
+
+    /// First and one-past-last bracket slot feeding this game.
+    #[cfg(feature = "python")]
+    fn slots(&self) -> (usize, usize) {
+        let slots = self.slot_range();
+        (slots.start, slots.end)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GameId(round={}, index={})", self.round.0, self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_geometry() {
+        let game = GameId::new(Round(1), 3);
+        assert_eq!(game.slot_range(), 12..16);
+        assert_eq!(game.feeders(), Some((GameId::new(Round(0), 6), GameId::new(Round(0), 7))));
+        assert_eq!(game.next(), GameId::new(Round(2), 1));
+        assert_eq!(GameId::for_slot(13, Round(1)), game);
+        assert_eq!(GameId::new(Round(0), 0).feeders(), None);
+        assert_eq!(Round(2).scoring_index(true), 3);
+        assert_eq!(Round(2).n_games(64), 8);
+    }
+
+    #[test]
+    fn test_regions() {
+        // 64 slots, four regions of 16: round 1 has four games per region.
+        let east = GameId::in_region(Round(1), 2, 1, 64, 4).unwrap();
+        assert_eq!(east, GameId::new(Round(1), 9));
+        assert_eq!(east.region(64, 4), Some(2));
+        // The national semifinals draw from two regions each.
+        assert_eq!(GameId::new(Round(4), 0).region(64, 4), None);
+        assert!(GameId::in_region(Round(4), 0, 0, 64, 4).is_err());
+        assert!(Round::try_from(300usize).is_err());
+    }
+}
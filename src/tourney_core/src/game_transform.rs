@@ -2,8 +2,9 @@ use rand::Rng;
 use std::collections::HashMap;
 
 use crate::overrides::OverridesMap;
+use crate::reduce::pairwise_sum;
 use crate::team::Team;
-use crate::win_prob::calculate_win_prob;
+use crate::win_prob::{calculate_win_prob_with, draw_forfeits, ModelParams};
 
 /// Probabilistic game transformation.
 ///
@@ -26,21 +27,104 @@ pub fn game_transform_prob(
     overrides: Option<&OverridesMap>,
     forfeit_prob: f64,
 ) -> HashMap<String, f64> {
-    let mut parent: HashMap<String, f64> = HashMap::new();
+    game_transform_prob_with(child1, child2, teams, overrides, forfeit_prob, &ModelParams::default())
+}
 
+/// `game_transform_prob` under the given model constants.
+pub fn game_transform_prob_with(
+    child1: &HashMap<String, f64>,
+    child2: &HashMap<String, f64>,
+    teams: &HashMap<String, Team>,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: &ModelParams,
+) -> HashMap<String, f64> {
+    // Most early-round slots hold a single team.
+    if let (Some((name1, &win1)), Some((name2, &win2)), 1, 1) =
+        (child1.iter().next(), child2.iter().next(), child1.len(), child2.len())
+    {
+        let game_prob = win1 * win2;
+        let p1 = matchup_prob(name1, name2, teams, overrides, forfeit_prob, params);
+        let mut parent = HashMap::with_capacity(2);
+        parent.insert(name1.clone(), game_prob * p1);
+        *parent.entry(name2.clone()).or_insert(0.0) += game_prob * (1.0 - p1);
+        return parent;
+    }
+
+    // Accumulate by position so names are cloned and hashed once per team
+    // rather than once per pairing. Each team's terms are added in the same
+    // order as a nested loop over the maps would, so results are unchanged.
+    let entries2: Vec<(&String, f64)> = child2.iter().map(|(name, &p)| (name, p)).collect();
+    let mut totals1 = Vec::with_capacity(child1.len());
+    let mut totals2 = vec![0.0; entries2.len()];
     for (name1, &win1) in child1.iter() {
+        let mut total1 = 0.0;
+        for (&(name2, win2), total2) in entries2.iter().zip(totals2.iter_mut()) {
+            let game_prob = win1 * win2;
+            let p1 = matchup_prob(name1, name2, teams, overrides, forfeit_prob, params);
+            total1 += game_prob * p1;
+            *total2 += game_prob * (1.0 - p1);
+        }
+        totals1.push((name1, total1));
+    }
+
+    let mut parent = HashMap::with_capacity(child1.len() + child2.len());
+    let totals = totals1.into_iter().chain(entries2.into_iter().map(|(name, _)| name).zip(totals2));
+    for (name, total) in totals {
+        *parent.entry(name.clone()).or_insert(0.0) += total;
+    }
+    parent
+}
+
+/// Probability `name1` beats `name2`. An override settles the matchup
+/// without looking up ratings or evaluating the model.
+fn matchup_prob(
+    name1: &str,
+    name2: &str,
+    teams: &HashMap<String, Team>,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: &ModelParams,
+) -> f64 {
+    if let Some(prob) = overrides.and_then(|ovr| ovr.get(name1, name2)) {
+        return prob;
+    }
+    let team1 = teams.get(name1).unwrap_or_else(|| panic!("team not found in ratings: {name1}"));
+    let team2 = teams.get(name2).unwrap_or_else(|| panic!("team not found in ratings: {name2}"));
+    calculate_win_prob_with(team1, team2, overrides, forfeit_prob, params)
+}
+
+/// `game_transform_prob_with`, accumulating in team-name order with pairwise
+/// sums so the result does not depend on hash-map iteration order.
+pub fn game_transform_prob_deterministic(
+    child1: &HashMap<String, f64>,
+    child2: &HashMap<String, f64>,
+    teams: &HashMap<String, Team>,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: &ModelParams,
+) -> HashMap<String, f64> {
+    let (entries1, entries2) = (sorted_entries(child1), sorted_entries(child2));
+
+    let mut terms: HashMap<&String, Vec<f64>> = HashMap::new();
+    for &(name1, win1) in &entries1 {
         let team1 = teams.get(name1).unwrap_or_else(|| panic!("team not found in ratings: {name1}"));
-        for (name2, &win2) in child2.iter() {
+        for &(name2, win2) in &entries2 {
             let team2 = teams.get(name2).unwrap_or_else(|| panic!("team not found in ratings: {name2}"));
             let game_prob = win1 * win2;
-            let p1 = calculate_win_prob(team1, team2, overrides, forfeit_prob);
-
-            *parent.entry(name1.clone()).or_insert(0.0) += game_prob * p1;
-            *parent.entry(name2.clone()).or_insert(0.0) += game_prob * (1.0 - p1);
+            let p1 = calculate_win_prob_with(team1, team2, overrides, forfeit_prob, params);
+            terms.entry(name1).or_default().push(game_prob * p1);
+            terms.entry(name2).or_default().push(game_prob * (1.0 - p1));
         }
     }
 
-    parent
+    terms.into_iter().map(|(name, t)| (name.clone(), pairwise_sum(&t))).collect()
+}
+
+fn sorted_entries(game: &HashMap<String, f64>) -> Vec<(&String, f64)> {
+    let mut entries: Vec<(&String, f64)> = game.iter().map(|(name, &p)| (name, p)).collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
 }
 
 /// Resolve a multi-team game to a single winner via simulation.
@@ -48,7 +132,7 @@ pub fn game_transform_prob(
 /// If the game has multiple teams (play-in), picks a winner weighted by probability.
 /// If the game has one team, returns that team's name.
 /// Keys are sorted to ensure deterministic results for a given RNG seed.
-fn resolve_game_to_winner<R: Rng>(game: &HashMap<String, f64>, rng: &mut R) -> String {
+pub fn resolve_game_to_winner<R: Rng>(game: &HashMap<String, f64>, rng: &mut R) -> String {
     if game.len() == 1 {
         return game.keys().next().unwrap().clone();
     }
@@ -92,6 +176,19 @@ pub fn game_transform_sim<R: Rng>(
     overrides: Option<&OverridesMap>,
     forfeit_prob: f64,
     rng: &mut R,
+) -> HashMap<String, f64> {
+    game_transform_sim_with(child1, child2, teams, overrides, forfeit_prob, &ModelParams::default(), rng)
+}
+
+/// `game_transform_sim` under the given model constants.
+pub fn game_transform_sim_with<R: Rng>(
+    child1: &HashMap<String, f64>,
+    child2: &HashMap<String, f64>,
+    teams: &HashMap<String, Team>,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: &ModelParams,
+    rng: &mut R,
 ) -> HashMap<String, f64> {
     // Resolve any play-in games first
     let name1 = resolve_game_to_winner(child1, rng);
@@ -100,26 +197,19 @@ pub fn game_transform_sim<R: Rng>(
     let team1 = teams.get(&name1).unwrap_or_else(|| panic!("team not found in ratings: {name1}"));
     let team2 = teams.get(&name2).unwrap_or_else(|| panic!("team not found in ratings: {name2}"));
 
-    // Simulate forfeits
-    let team1_forfeit = rng.gen::<f64>() < forfeit_prob;
-    let team2_forfeit = rng.gen::<f64>() < forfeit_prob;
-
-    if team1_forfeit && team2_forfeit {
-        // Both forfeit - this is an edge case, return empty
-        // In practice this is extremely rare
-        return HashMap::new();
-    } else if team1_forfeit {
-        let mut result = HashMap::new();
-        result.insert(name2, 1.0);
-        return result;
-    } else if team2_forfeit {
-        let mut result = HashMap::new();
-        result.insert(name1, 1.0);
-        return result;
+    // Simulate forfeits; a double forfeit still advances someone. Overrides
+    // are final, as in `calculate_win_prob_with`, so they skip this.
+    let overridden = overrides.is_some_and(|ovr| ovr.get(&name1, &name2).is_some());
+    if !overridden {
+        match draw_forfeits(rng, forfeit_prob, params.double_forfeit, team1.seed, team2.seed) {
+            Some(true) => return [(name1, 1.0)].into(),
+            Some(false) => return [(name2, 1.0)].into(),
+            None => {}
+        }
     }
 
     // Normal game simulation
-    let prob = calculate_win_prob(team1, team2, overrides, 0.0); // Don't double-apply forfeit
+    let prob = calculate_win_prob_with(team1, team2, overrides, 0.0, params); // Don't double-apply forfeit
     let winner = if rng.gen::<f64>() < prob { name1 } else { name2 };
 
     let mut result = HashMap::new();
@@ -130,6 +220,7 @@ pub fn game_transform_sim<R: Rng>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::win_prob::DoubleForfeit;
 
     fn make_teams() -> HashMap<String, Team> {
         let mut teams = HashMap::new();
@@ -186,6 +277,35 @@ mod tests {
         assert!((sum - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_game_transform_prob_matches_pairwise_sum() {
+        let teams = make_teams();
+        let slot = |entries: &[(&str, f64)]| -> HashMap<String, f64> {
+            entries.iter().map(|&(name, p)| (name.to_string(), p)).collect()
+        };
+        let win = |a: &str, b: &str| calculate_win_prob_with(&teams[a], &teams[b], None, 0.0, &ModelParams::default());
+
+        let single = game_transform_prob(&slot(&[("A", 1.0)]), &slot(&[("B", 1.0)]), &teams, None, 0.0);
+        assert_eq!(single, slot(&[("A", win("A", "B")), ("B", 1.0 - win("A", "B"))]));
+
+        let parent = game_transform_prob(&slot(&[("B", 0.7), ("C", 0.3)]), &slot(&[("A", 1.0)]), &teams, None, 0.0);
+        let a = 0.7 * (1.0 - win("B", "A")) + 0.3 * (1.0 - win("C", "A"));
+        assert!((parent["A"] - a).abs() < 1e-15);
+        assert!((parent["B"] - 0.7 * win("B", "A")).abs() < 1e-15);
+        assert!((parent["C"] - 0.3 * win("C", "A")).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_overridden_game_needs_no_ratings() {
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("X", "Y", 1.0).unwrap();
+        let child1: HashMap<String, f64> = [("X".to_string(), 1.0)].into();
+        let child2: HashMap<String, f64> = [("Y".to_string(), 1.0)].into();
+
+        let parent = game_transform_prob(&child1, &child2, &HashMap::new(), Some(&overrides), 0.0);
+        assert_eq!(parent, [("X".to_string(), 1.0), ("Y".to_string(), 0.0)].into());
+    }
+
     #[test]
     fn test_game_transform_sim() {
         let teams = make_teams();
@@ -235,4 +355,26 @@ mod tests {
         let a_ratio = a_wins as f64 / n as f64;
         assert!((a_ratio - 0.6).abs() < 0.05, "A should win ~60% of the time, got {}", a_ratio);
     }
+
+    #[test]
+    fn test_double_forfeit_advances_someone() {
+        let mut teams = make_teams();
+        teams.get_mut("A").unwrap().seed = Some(2);
+        teams.get_mut("B").unwrap().seed = Some(1);
+        let child1: HashMap<String, f64> = [("A".to_string(), 1.0)].into();
+        let child2: HashMap<String, f64> = [("B".to_string(), 1.0)].into();
+
+        let mut rng = crate::tournament::seeded_rng(Some(3));
+        for policy in [DoubleForfeit::CoinFlip, DoubleForfeit::HigherSeed, DoubleForfeit::Redraw] {
+            let mut params = ModelParams::default();
+            params.double_forfeit = policy;
+            for _ in 0..20 {
+                let parent = game_transform_sim_with(&child1, &child2, &teams, None, 1.0, &params, &mut rng);
+                assert_eq!(parent.len(), 1);
+                if policy == DoubleForfeit::HigherSeed {
+                    assert!(parent.contains_key("B"));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,458 @@
+//! GPU Monte Carlo backend (the `gpu` feature).
+//!
+//! Large risk runs (10M+ simulations) spend nearly all their time resolving
+//! brackets, which is embarrassingly parallel. This backend packs the bracket
+//! into flat buffers and runs one simulation per shader invocation via wgpu
+//! (Vulkan, Metal, or DX12), with the shader in `gpu_sim.wgsl`.
+//!
+//! Win probabilities for every pair of bracket teams are computed once on the
+//! CPU with overrides and forfeits applied, so results follow the same model as
+//! `run_simulations`. The GPU draws from its own counter-based RNG and works in
+//! `f32`, so results agree statistically, not bit for bit.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::error::{Result, TourneyError};
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::win_prob::calculate_win_prob_with;
+
+/// Slots one shader invocation can hold; must match `MAX_SLOTS` in the shader.
+pub const MAX_SLOTS: usize = 128;
+/// Teams one shader invocation can score; must match `MAX_TEAMS` in the shader.
+pub const MAX_TEAMS: usize = 256;
+
+const WORKGROUP_SIZE: u32 = 64;
+/// Invocations used when reducing to per-team statistics.
+const STATS_THREADS: u32 = 65_536;
+
+const SHADER: &str = include_str!("gpu_sim.wgsl");
+
+/// Uniform block shared with the shader; field order matches `Params` there.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Pod, Zeroable)]
+struct Params {
+    n_teams: u32,
+    n_slots: u32,
+    score_cols: u32,
+    play_in: u32,
+    n_sims: u32,
+    sim_offset: u32,
+    n_threads: u32,
+    mode: u32,
+    seed_lo: u32,
+    seed_hi: u32,
+    _pad: [u32; 2],
+}
+
+/// A tournament flattened into the buffers the shader reads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpuInputs {
+    /// Bracket teams; indices into this list identify teams on the GPU.
+    pub teams: Vec<String>,
+    /// Row-major `teams x teams` matrix of P(row team beats column team).
+    pub win_prob: Vec<f32>,
+    /// `slot_teams[slot_offsets[s]..slot_offsets[s + 1]]` are slot `s`'s teams.
+    pub slot_offsets: Vec<u32>,
+    pub slot_teams: Vec<u32>,
+    pub slot_probs: Vec<f32>,
+    /// Row-major `teams x score_cols` points per scoring round, multipliers applied.
+    pub points: Vec<f32>,
+    pub score_cols: usize,
+    pub play_in: bool,
+}
+
+impl GpuInputs {
+    /// Flatten `state`, rejecting brackets the shader cannot hold.
+    pub fn from_state(state: &TournamentState) -> Result<Self> {
+        if state.reseed {
+            return Err(TourneyError::InvalidInput("GPU simulation does not support reseeding".to_string()));
+        }
+        if state.persistent_forfeits && state.forfeit_prob > 0.0 {
+            return Err(TourneyError::InvalidInput("GPU simulation does not support persistent forfeits".to_string()));
+        }
+        let n_slots = state.bracket().len();
+        if !n_slots.is_power_of_two() || n_slots > MAX_SLOTS {
+            return Err(TourneyError::InvalidInput(format!(
+                "GPU simulation needs a power-of-2 bracket of at most {MAX_SLOTS} slots, got {n_slots}"
+            )));
+        }
+        let teams = state.bracket_teams();
+        if teams.len() > MAX_TEAMS {
+            return Err(TourneyError::InvalidInput(format!(
+                "GPU simulation supports at most {MAX_TEAMS} teams, got {}",
+                teams.len()
+            )));
+        }
+        let index: HashMap<&str, u32> = teams.iter().enumerate().map(|(i, t)| (t.as_str(), i as u32)).collect();
+        let rating = |team: &String| {
+            state.ratings.get(team).unwrap_or_else(|| panic!("team not found in ratings: {team}"))
+        };
+
+        let mut win_prob = vec![0.0f32; teams.len() * teams.len()];
+        for (i, a) in teams.iter().enumerate() {
+            for (j, b) in teams.iter().enumerate().filter(|&(j, _)| j != i) {
+                let p = calculate_win_prob_with(
+                    rating(a),
+                    rating(b),
+                    Some(&state.overrides),
+                    state.forfeit_prob,
+                    &state.model_params,
+                );
+                win_prob[i * teams.len() + j] = p as f32;
+            }
+        }
+
+        let mut slot_offsets = vec![0u32];
+        let (mut slot_teams, mut slot_probs) = (Vec::new(), Vec::new());
+        for slot in state.bracket() {
+            // Sorted so the packed layout does not depend on hash order.
+            let mut entries: Vec<(&String, &f64)> = slot.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (team, &p) in entries {
+                slot_teams.push(index[team.as_str()]);
+                slot_probs.push(p as f32);
+            }
+            slot_offsets.push(slot_teams.len() as u32);
+        }
+
+        let play_in = state.play_in_round;
+        let score_cols = n_slots.ilog2() as usize + usize::from(play_in);
+        let points = teams
+            .iter()
+            .flat_map(|team| {
+                let multiplier = state.team_multiplier(team);
+                (0..score_cols).map(move |col| (state.scoring.get(col).copied().unwrap_or(1.0) * multiplier) as f32)
+            })
+            .collect();
+
+        Ok(GpuInputs {
+            teams: teams.to_vec(),
+            win_prob,
+            slot_offsets,
+            slot_teams,
+            slot_probs,
+            points,
+            score_cols,
+            play_in,
+        })
+    }
+}
+
+/// A wgpu device with the simulation pipeline compiled.
+pub struct GpuSimulator {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    max_binding_size: u64,
+}
+
+impl GpuSimulator {
+    /// Open the default high-performance adapter and compile the shader.
+    pub fn new() -> Result<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or_else(|| TourneyError::Gpu("no GPU adapter available".to_string()))?;
+
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("tourney_core"),
+                required_features: wgpu::Features::empty(),
+                required_limits: limits.clone(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|err| TourneyError::Gpu(err.to_string()))?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("gpu_sim"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("gpu_sim"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Ok(GpuSimulator {
+            device,
+            queue,
+            pipeline,
+            max_binding_size: u64::from(limits.max_storage_buffer_binding_size),
+        })
+    }
+
+    /// Per-simulation score maps, as `TournamentState::run_simulations`.
+    pub fn run_simulations(
+        &self,
+        state: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<HashMap<String, f64>>> {
+        let inputs = GpuInputs::from_state(state)?;
+        let n_teams = inputs.teams.len();
+        let n_sims = sim_count(n_simulations)?;
+        let seed = derive_seed(seed);
+
+        // Chunk so each dispatch's output fits one storage binding.
+        let per_sim_bytes = (n_teams.max(1) * 4) as u64;
+        let max_threads = u64::from(u16::MAX) * u64::from(WORKGROUP_SIZE);
+        let chunk = (self.max_binding_size / per_sim_bytes).min(max_threads).max(1) as u32;
+
+        let mut results = Vec::with_capacity(n_simulations);
+        let mut offset = 0u32;
+        while offset < n_sims {
+            let n_threads = chunk.min(n_sims - offset);
+            let params = params(&inputs, n_sims, offset, n_threads, 0, seed);
+            let out = self.dispatch(&inputs, params, n_threads as usize * n_teams)?;
+            results.extend(out.chunks(n_teams.max(1)).map(|scores| {
+                inputs
+                    .teams
+                    .iter()
+                    .zip(scores)
+                    .filter(|(_, &score)| score != 0.0)
+                    .map(|(team, &score)| (team.clone(), f64::from(score)))
+                    .collect::<HashMap<String, f64>>()
+            }));
+            offset += n_threads;
+        }
+        results.truncate(n_simulations);
+        Ok(results)
+    }
+
+    /// Per-team (mean, standard deviation) of simulated scores, reduced on the
+    /// GPU without materializing individual simulations.
+    pub fn simulation_stats(
+        &self,
+        state: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<HashMap<String, (f64, f64)>> {
+        let inputs = GpuInputs::from_state(state)?;
+        let n_teams = inputs.teams.len();
+        let n_sims = sim_count(n_simulations)?;
+        if n_sims == 0 {
+            return Ok(HashMap::new());
+        }
+        let n_threads = n_sims.min(STATS_THREADS);
+        let params = params(&inputs, n_sims, 0, n_threads, 1, derive_seed(seed));
+        let out = self.dispatch(&inputs, params, n_threads as usize * 2 * n_teams)?;
+
+        let mut sum = vec![0.0f64; n_teams];
+        let mut sum_sq = vec![0.0f64; n_teams];
+        for partial in out.chunks(2 * n_teams) {
+            for t in 0..n_teams {
+                sum[t] += f64::from(partial[t]);
+                sum_sq[t] += f64::from(partial[n_teams + t]);
+            }
+        }
+        let n = f64::from(n_sims);
+        Ok(inputs
+            .teams
+            .into_iter()
+            .enumerate()
+            .map(|(t, team)| {
+                let mean = sum[t] / n;
+                let variance = (sum_sq[t] / n - mean * mean).max(0.0);
+                (team, (mean, variance.sqrt()))
+            })
+            .collect())
+    }
+
+    /// Run one dispatch and read back `out_len` floats.
+    fn dispatch(&self, inputs: &GpuInputs, params: Params, out_len: usize) -> Result<Vec<f32>> {
+        let out_bytes = (out_len.max(1) * std::mem::size_of::<f32>()) as u64;
+        if out_bytes > self.max_binding_size {
+            return Err(TourneyError::Gpu(format!(
+                "output of {out_bytes} bytes exceeds the adapter's {} byte storage binding limit",
+                self.max_binding_size
+            )));
+        }
+
+        let storage = |label: &str, contents: &[u8]| {
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents,
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+        };
+        let uniform = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        // Zero-length bindings are invalid, so pad empty arrays to one element.
+        let win_prob = storage("win_prob", bytemuck::cast_slice(&padded(&inputs.win_prob)));
+        let slot_offsets = storage("slot_offsets", bytemuck::cast_slice(&inputs.slot_offsets));
+        let slot_teams = storage("slot_teams", bytemuck::cast_slice(&padded(&inputs.slot_teams)));
+        let slot_probs = storage("slot_probs", bytemuck::cast_slice(&padded(&inputs.slot_probs)));
+        let points = storage("points", bytemuck::cast_slice(&padded(&inputs.points)));
+        let out = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("out"),
+            size: out_bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: out_bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bindings = [&uniform, &win_prob, &slot_offsets, &slot_teams, &slot_probs, &points, &out];
+        let entries: Vec<wgpu::BindGroupEntry> = bindings
+            .iter()
+            .enumerate()
+            .map(|(i, buffer)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("gpu_sim"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("gpu_sim"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(params.n_threads.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&out, 0, &readback, 0, out_bytes);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|err| TourneyError::Gpu(err.to_string()))?
+            .map_err(|err| TourneyError::Gpu(err.to_string()))?;
+
+        let data = slice.get_mapped_range();
+        let mut values: Vec<f32> = bytemuck::cast_slice(&data).to_vec();
+        values.truncate(out_len);
+        Ok(values)
+    }
+}
+
+/// Process-wide simulator, created on first use.
+pub fn shared_simulator() -> Result<&'static GpuSimulator> {
+    static SIMULATOR: OnceLock<std::result::Result<GpuSimulator, String>> = OnceLock::new();
+    SIMULATOR
+        .get_or_init(|| GpuSimulator::new().map_err(|err| err.to_string()))
+        .as_ref()
+        .map_err(|msg| TourneyError::Gpu(msg.clone()))
+}
+
+fn params(inputs: &GpuInputs, n_sims: u32, sim_offset: u32, n_threads: u32, mode: u32, seed: u64) -> Params {
+    Params {
+        n_teams: inputs.teams.len() as u32,
+        n_slots: (inputs.slot_offsets.len() - 1) as u32,
+        score_cols: inputs.score_cols as u32,
+        play_in: u32::from(inputs.play_in),
+        n_sims,
+        sim_offset,
+        n_threads,
+        mode,
+        seed_lo: seed as u32,
+        seed_hi: (seed >> 32) as u32,
+        _pad: [0; 2],
+    }
+}
+
+fn sim_count(n_simulations: usize) -> Result<u32> {
+    u32::try_from(n_simulations)
+        .map_err(|_| TourneyError::InvalidInput(format!("at most {} GPU simulations per run", u32::MAX)))
+}
+
+/// Shader seed drawn from the crate's seeded RNG, so `None` is still random.
+fn derive_seed(seed: Option<u64>) -> u64 {
+    use rand::Rng;
+    seeded_rng(seed).gen()
+}
+
+fn padded<T: Pod + Zeroable>(values: &[T]) -> Vec<T> {
+    if values.is_empty() {
+        vec![T::zeroed()]
+    } else {
+        values.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_shader_validates() {
+        let module = wgpu::naga::front::wgsl::parse_str(SHADER).expect("shader parses");
+        wgpu::naga::valid::Validator::new(
+            wgpu::naga::valid::ValidationFlags::all(),
+            wgpu::naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .expect("shader validates");
+        assert!(SHADER.contains(&format!("MAX_SLOTS: u32 = {MAX_SLOTS}u")));
+        assert!(SHADER.contains(&format!("MAX_TEAMS: u32 = {MAX_TEAMS}u")));
+    }
+
+    #[test]
+    fn test_inputs_pack_bracket() {
+        let mut state = canonical_tournament(4);
+        state.team_multipliers.insert("Team 02".to_string(), 2.0);
+        let inputs = GpuInputs::from_state(&state).unwrap();
+        let n = inputs.teams.len();
+        assert_eq!(inputs.slot_offsets, vec![0, 1, 2, 3, 4]);
+        assert_eq!(inputs.score_cols, 2);
+        for i in 0..n {
+            for j in (0..n).filter(|&j| j != i) {
+                let total = inputs.win_prob[i * n + j] + inputs.win_prob[j * n + i];
+                assert!((total - 1.0).abs() < 1e-6);
+            }
+        }
+        let team02 = inputs.teams.iter().position(|t| t == "Team 02").unwrap();
+        assert_eq!(inputs.points[team02 * 2 + 1], 2.0 * state.scoring[1] as f32);
+        assert!(GpuInputs::from_state(&canonical_tournament(256)).is_err());
+    }
+
+    #[test]
+    fn test_gpu_matches_cpu_expected_scores() {
+        // Sandboxes and CI runners often have no adapter; nothing to check then.
+        let Ok(gpu) = shared_simulator() else { return };
+        let state = canonical_tournament(16);
+        let expected = state.calculate_scores_prob();
+        let stats = gpu.simulation_stats(&state, 200_000, Some(7)).unwrap();
+        for (team, (mean, _)) in &stats {
+            let expected = expected.get(team).copied().unwrap_or(0.0);
+            assert!((mean - expected).abs() < 0.05 + 0.02 * expected, "{team}: {mean} vs {expected}");
+        }
+        let sims = gpu.run_simulations(&state, 1000, Some(7)).unwrap();
+        assert_eq!(sims.len(), 1000);
+        let total: f64 = sims[0].values().sum();
+        assert!((total - crate::invariants::available_points(&state)).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,142 @@
+// Monte Carlo bracket resolution, one simulation per invocation step.
+//
+// Inputs are packed on the host (see gpu.rs): a dense team-vs-team win
+// probability matrix with overrides and forfeits already applied, bracket
+// slots as CSR-style (offset, team, probability) arrays, and a per-team table
+// of points per scoring round with multipliers applied.
+//
+// mode 0 writes every simulation's per-team scores; mode 1 has each
+// invocation run a strided subset of simulations and write per-team sums and
+// sums of squares for the host to reduce.
+
+struct Params {
+    n_teams: u32,
+    n_slots: u32,
+    score_cols: u32,
+    play_in: u32,
+    n_sims: u32,
+    sim_offset: u32,
+    n_threads: u32,
+    mode: u32,
+    seed_lo: u32,
+    seed_hi: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+const MAX_SLOTS: u32 = 128u;
+const MAX_TEAMS: u32 = 256u;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> win_prob: array<f32>;
+@group(0) @binding(2) var<storage, read> slot_offsets: array<u32>;
+@group(0) @binding(3) var<storage, read> slot_teams: array<u32>;
+@group(0) @binding(4) var<storage, read> slot_probs: array<f32>;
+@group(0) @binding(5) var<storage, read> points: array<f32>;
+@group(0) @binding(6) var<storage, read_write> out: array<f32>;
+
+var<private> rng_state: u32;
+var<private> alive: array<u32, MAX_SLOTS>;
+var<private> score: array<f32, MAX_TEAMS>;
+var<private> sum: array<f32, MAX_TEAMS>;
+var<private> sum_sq: array<f32, MAX_TEAMS>;
+
+fn pcg(v: u32) -> u32 {
+    let state = v * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn next_uniform() -> f32 {
+    rng_state = pcg(rng_state);
+    return f32(rng_state >> 8u) / 16777216.0;
+}
+
+fn simulate(sim: u32) {
+    rng_state = pcg(sim ^ pcg(params.seed_lo ^ pcg(params.seed_hi)));
+    for (var t = 0u; t < params.n_teams; t++) {
+        score[t] = 0.0;
+    }
+
+    // Resolve each slot to a single team, scoring play-in wins if enabled.
+    for (var s = 0u; s < params.n_slots; s++) {
+        let start = slot_offsets[s];
+        let end = slot_offsets[s + 1u];
+        var chosen = slot_teams[end - 1u];
+        if (end - start > 1u) {
+            let u = next_uniform();
+            var cumulative = 0.0;
+            for (var k = start; k < end; k++) {
+                cumulative += slot_probs[k];
+                if (u < cumulative) {
+                    chosen = slot_teams[k];
+                    break;
+                }
+            }
+            if (params.play_in != 0u) {
+                score[chosen] += points[chosen * params.score_cols];
+            }
+        }
+        alive[s] = chosen;
+    }
+
+    // Play the main rounds, compacting winners to the front of `alive`.
+    var width = params.n_slots;
+    var col = params.play_in;
+    loop {
+        if (width <= 1u) {
+            break;
+        }
+        let half = width / 2u;
+        for (var i = 0u; i < half; i++) {
+            let a = alive[2u * i];
+            let b = alive[2u * i + 1u];
+            var winner = b;
+            if (next_uniform() < win_prob[a * params.n_teams + b]) {
+                winner = a;
+            }
+            score[winner] += points[winner * params.score_cols + col];
+            alive[i] = winner;
+        }
+        width = half;
+        col += 1u;
+    }
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let thread = gid.x;
+    if (thread >= params.n_threads) {
+        return;
+    }
+
+    if (params.mode == 0u) {
+        let sim = params.sim_offset + thread;
+        if (sim >= params.n_sims) {
+            return;
+        }
+        simulate(sim);
+        let base = thread * params.n_teams;
+        for (var t = 0u; t < params.n_teams; t++) {
+            out[base + t] = score[t];
+        }
+        return;
+    }
+
+    for (var t = 0u; t < params.n_teams; t++) {
+        sum[t] = 0.0;
+        sum_sq[t] = 0.0;
+    }
+    for (var sim = thread; sim < params.n_sims; sim += params.n_threads) {
+        simulate(sim);
+        for (var t = 0u; t < params.n_teams; t++) {
+            sum[t] += score[t];
+            sum_sq[t] += score[t] * score[t];
+        }
+    }
+    let base = thread * 2u * params.n_teams;
+    for (var t = 0u; t < params.n_teams; t++) {
+        out[base + t] = sum[t];
+        out[base + params.n_teams + t] = sum_sq[t];
+    }
+}
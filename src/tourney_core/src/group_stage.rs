@@ -0,0 +1,314 @@
+//! Round-robin group stage feeding a knockout bracket.
+//!
+//! Every team plays every other team in its group once. Teams are ranked by
+//! wins, then head-to-head wins among the tied teams, then a random draw, and
+//! the top `advance` of each group fill the knockout bracket's slots.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::team::Team;
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::win_prob::calculate_win_prob_with;
+
+/// Final order of one simulated group, with each team's win total.
+#[derive(Clone, Debug)]
+pub struct GroupStanding {
+    pub teams: Vec<String>,
+    pub wins: Vec<u32>,
+}
+
+/// Group stage configuration.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct GroupStage {
+    #[pyo3(get)]
+    pub groups: Vec<Vec<String>>,
+
+    /// Number of teams advancing from each group.
+    #[pyo3(get)]
+    pub advance: usize,
+
+    /// Knockout slot order as (group index, finishing place) pairs.
+    #[pyo3(get)]
+    pub seeding: Vec<(usize, usize)>,
+
+    /// Points awarded per group-stage win.
+    #[pyo3(get, set)]
+    pub group_game_points: f64,
+}
+
+#[pymethods]
+impl GroupStage {
+    /// Create a group stage.
+    ///
+    /// Without an explicit `seeding`, two advancing teams per group are crossed
+    /// over between neighbouring groups (A1 v B2, B1 v A2); otherwise slots are
+    /// filled group by group.
+    #[cfg(feature = "python")]
+    #[new]
+    #[pyo3(signature = (groups, advance, seeding = None, group_game_points = 0.0))]
+    pub fn py_new(
+        groups: Vec<Vec<String>>,
+        advance: usize,
+        seeding: Option<Vec<(usize, usize)>>,
+        group_game_points: f64,
+    ) -> PyResult<Self> {
+        Ok(GroupStage::new(groups, advance, seeding, group_game_points)?)
+    }
+
+    /// Probability of each team finishing in each group place.
+    ///
+    /// Uses `tournament` for ratings, overrides, and forfeit probability.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn finish_probs(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<HashMap<String, Vec<f64>>> {
+        let mut rng = seeded_rng(seed);
+        let mut probs: HashMap<String, Vec<f64>> = HashMap::new();
+        for _ in 0..n_simulations {
+            for standing in self.simulate_groups(tournament, &mut rng)? {
+                for (place, team) in standing.teams.iter().enumerate() {
+                    let places = probs.entry(team.clone()).or_insert_with(|| vec![0.0; standing.teams.len()]);
+                    places[place] += 1.0 / n_simulations as f64;
+                }
+            }
+        }
+        Ok(probs)
+    }
+
+    /// Simulate the group stage followed by `tournament`'s knockout scoring.
+    ///
+    /// The knockout bracket is built from each simulated group result; the
+    /// bracket stored on `tournament` is ignored. Returns per-simulation scores.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn run_simulations(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<HashMap<String, f64>>> {
+        let mut rng = seeded_rng(seed);
+        let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+        seeds
+            .par_iter()
+            .map(|&sim_seed| self.simulate_scores(tournament, sim_seed))
+            .collect()
+    }
+
+    /// Expected scores across group and knockout stages, estimated by simulation.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn expected_scores(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<HashMap<String, f64>> {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for sim in self.run_simulations(tournament, n_simulations, seed)? {
+            for (team, score) in sim {
+                *totals.entry(team).or_insert(0.0) += score / n_simulations as f64;
+            }
+        }
+        Ok(totals)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GroupStage({} groups, top {} advance)", self.groups.len(), self.advance)
+    }
+}
+
+impl GroupStage {
+    pub fn new(
+        groups: Vec<Vec<String>>,
+        advance: usize,
+        seeding: Option<Vec<(usize, usize)>>,
+        group_game_points: f64,
+    ) -> Result<Self> {
+        if let Some(group) = groups.iter().find(|g| g.len() < advance) {
+            return Err(TourneyError::InvalidInput(format!(
+                "group {group:?} has fewer than {advance} teams"
+            )));
+        }
+        let seeding = seeding.unwrap_or_else(|| default_seeding(groups.len(), advance));
+        if !seeding.len().is_power_of_two() {
+            return Err(TourneyError::InvalidInput(
+                "knockout bracket must have a power-of-2 number of slots".to_string(),
+            ));
+        }
+        if let Some(&(g, p)) = seeding.iter().find(|&&(g, p)| g >= groups.len() || p >= advance) {
+            return Err(TourneyError::InvalidInput(format!(
+                "seeding slot (group {g}, place {p}) does not refer to an advancing team"
+            )));
+        }
+        Ok(GroupStage {
+            groups,
+            advance,
+            seeding,
+            group_game_points,
+        })
+    }
+
+    /// Simulate every group once. Fails if a group team has no rating.
+    pub fn simulate_groups<R: Rng>(&self, tournament: &TournamentState, rng: &mut R) -> Result<Vec<GroupStanding>> {
+        self.groups
+            .iter()
+            .map(|group| Ok(simulate_group(group, &tournament.ratings_of(group)?, tournament, rng)))
+            .collect()
+    }
+
+    /// Knockout bracket (one team per slot) for a set of group results.
+    pub fn knockout_bracket(&self, standings: &[GroupStanding]) -> Vec<HashMap<String, f64>> {
+        self.seeding
+            .iter()
+            .map(|&(group, place)| [(standings[group].teams[place].clone(), 1.0)].into_iter().collect())
+            .collect()
+    }
+
+    /// One full simulation: group stage points plus knockout scores.
+    pub fn simulate_scores(&self, tournament: &TournamentState, seed: u64) -> Result<HashMap<String, f64>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let standings = self.simulate_groups(tournament, &mut rng)?;
+
+        let mut knockout = tournament.clone();
+        knockout.set_bracket(self.knockout_bracket(&standings));
+        let mut scores = knockout.calculate_scores_sim(Some(rng.gen()));
+
+        for standing in &standings {
+            for (team, &wins) in standing.teams.iter().zip(&standing.wins) {
+                let points = f64::from(wins) * self.group_game_points;
+                *scores.entry(team.clone()).or_insert(0.0) += points;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+fn default_seeding(n_groups: usize, advance: usize) -> Vec<(usize, usize)> {
+    if advance == 2 && n_groups.is_multiple_of(2) {
+        (0..n_groups)
+            .step_by(2)
+            .flat_map(|g| [(g, 0), (g + 1, 1), (g + 1, 0), (g, 1)])
+            .collect()
+    } else {
+        (0..n_groups)
+            .flat_map(|g| (0..advance).map(move |p| (g, p)))
+            .collect()
+    }
+}
+
+/// Play every pairing in `group` once and rank the teams. `ratings` holds
+/// each group team's rating, in group order.
+fn simulate_group<R: Rng>(
+    group: &[String],
+    ratings: &[&Team],
+    tournament: &TournamentState,
+    rng: &mut R,
+) -> GroupStanding {
+    let n = group.len();
+    let mut beat = vec![vec![false; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let p = calculate_win_prob_with(
+                ratings[i],
+                ratings[j],
+                Some(&tournament.overrides),
+                tournament.forfeit_prob,
+                &tournament.model_params,
+            );
+            if rng.gen::<f64>() < p {
+                beat[i][j] = true;
+            } else {
+                beat[j][i] = true;
+            }
+        }
+    }
+
+    let wins: Vec<u32> = beat.iter().map(|row| row.iter().filter(|&&b| b).count() as u32).collect();
+    let draw: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        let head_to_head = |x: usize| {
+            (0..n)
+                .filter(|&o| wins[o] == wins[x] && beat[x][o])
+                .count()
+        };
+        wins[b]
+            .cmp(&wins[a])
+            .then_with(|| head_to_head(b).cmp(&head_to_head(a)))
+            .then_with(|| draw[b].total_cmp(&draw[a]))
+    });
+
+    GroupStanding {
+        teams: order.iter().map(|&i| group[i].clone()).collect(),
+        wins: order.iter().map(|&i| wins[i]).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{canonical_ratings, canonical_team_name};
+
+    fn make_tournament(ratings: HashMap<String, Team>) -> TournamentState {
+        TournamentState::new(Vec::new(), ratings, vec![1.0, 2.0], None, 0.0, None)
+    }
+
+    /// Two groups of four from an 8-team canonical field, snaked by rank.
+    fn groups() -> Vec<Vec<String>> {
+        (0..2).map(|g| (0..4).map(|i| canonical_team_name(2 * i + g)).collect()).collect()
+    }
+
+    #[test]
+    fn test_default_crossover_seeding() {
+        let stage = GroupStage::new(groups(), 2, None, 0.0).unwrap();
+        assert_eq!(stage.seeding, vec![(0, 0), (1, 1), (1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn test_invalid_bracket_size_rejected() {
+        assert!(GroupStage::new(groups(), 3, None, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_finish_probs_sum_to_one() {
+        let tournament = make_tournament(canonical_ratings(8));
+        let stage = GroupStage::new(groups(), 2, None, 0.0).unwrap();
+        let probs = stage.finish_probs(&tournament, 2000, Some(1)).unwrap();
+
+        for places in probs.values() {
+            assert!((places.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+        // The strongest team should usually win its group.
+        assert!(probs["Team 01"][0] > probs["Team 07"][0]);
+    }
+
+    #[test]
+    fn test_scores_include_group_and_knockout_points() {
+        let tournament = make_tournament(canonical_ratings(8));
+        let stage = GroupStage::new(groups(), 2, None, 0.5).unwrap();
+
+        for sim in stage.run_simulations(&tournament, 50, Some(9)).unwrap() {
+            // 12 group games at 0.5 plus knockout 2 * 1.0 + 2.0
+            assert!((sim.values().sum::<f64>() - 10.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_unrated_team_rejected() {
+        let tournament = make_tournament(canonical_ratings(7));
+        let stage = GroupStage::new(groups(), 2, None, 0.0).unwrap();
+        let err = stage.expected_scores(&tournament, 10, Some(1)).err().unwrap();
+        assert!(err.to_string().contains("Team 08"), "{err}");
+        assert!(stage.finish_probs(&tournament, 10, Some(1)).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Dated rating observations for scoring past dates and recency weighting.
+//!
+//! Observations are keyed by Unix timestamp (seconds). Between two
+//! observations a team's efficiencies and tempo are linearly interpolated;
+//! after the last one the latest rating holds. Alternatively ratings can be
+//! blended with exponential time decay so recent form counts for more.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::team::Team;
+
+/// Rating observations over time for a set of teams.
+#[pyclass]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RatedTeamSeries {
+    /// Per-team observations, sorted by timestamp.
+    observations: HashMap<String, Vec<(i64, Team)>>,
+}
+
+#[pymethods]
+impl RatedTeamSeries {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `team`'s rating as observed at `timestamp`, replacing any
+    /// observation of the same team at the same time.
+    pub fn add(&mut self, timestamp: i64, team: Team) {
+        let series = self.observations.entry(team.name.clone()).or_default();
+        match series.binary_search_by_key(&timestamp, |(t, _)| *t) {
+            Ok(i) => series[i].1 = team,
+            Err(i) => series.insert(i, (timestamp, team)),
+        }
+    }
+
+    /// Record a full ratings snapshot observed at `timestamp`.
+    pub fn add_ratings(&mut self, timestamp: i64, ratings: HashMap<String, Team>) {
+        for team in ratings.into_values() {
+            self.add(timestamp, team);
+        }
+    }
+
+    /// Names of all teams with at least one observation, sorted.
+    pub fn teams(&self) -> Vec<String> {
+        let mut teams: Vec<String> = self.observations.keys().cloned().collect();
+        teams.sort();
+        teams
+    }
+
+    /// Observation timestamps for `team`, oldest first.
+    pub fn timestamps(&self, team: &str) -> Vec<i64> {
+        self.observations
+            .get(team)
+            .map(|series| series.iter().map(|(t, _)| *t).collect())
+            .unwrap_or_default()
+    }
+
+    /// `team`'s rating at `timestamp`, or None before its first observation.
+    ///
+    /// With `interpolate`, ratings between observations are linearly
+    /// interpolated; otherwise the latest observation at or before
+    /// `timestamp` is used.
+    #[pyo3(signature = (team, timestamp, interpolate = true))]
+    pub fn rating_at(&self, team: &str, timestamp: i64, interpolate: bool) -> Option<Team> {
+        let series = self.observations.get(team)?;
+        let after = series.partition_point(|(t, _)| *t <= timestamp);
+        let (t0, before) = series.get(after.checked_sub(1)?)?;
+        match series.get(after) {
+            Some((t1, next)) if interpolate => {
+                let w = (timestamp - t0) as f64 / (t1 - t0) as f64;
+                Some(Team {
+                    offense: lerp(before.offense, next.offense, w),
+                    defense: lerp(before.defense, next.defense, w),
+                    tempo: lerp(before.tempo, next.tempo, w),
+                    ..before.clone()
+                })
+            }
+            _ => Some(before.clone()),
+        }
+    }
+
+    /// Ratings of every team observed at or before `timestamp`.
+    #[pyo3(signature = (timestamp, interpolate = true))]
+    pub fn ratings_at(&self, timestamp: i64, interpolate: bool) -> HashMap<String, Team> {
+        self.observations
+            .keys()
+            .filter_map(|name| Some((name.clone(), self.rating_at(name, timestamp, interpolate)?)))
+            .collect()
+    }
+
+    /// `team`'s rating at `timestamp` as an exponentially time-decayed average
+    /// of observations at or before `timestamp`, with weights halving every
+    /// `half_life` seconds, which must be positive. Metadata comes from the
+    /// latest observation.
+    pub fn decayed_rating(&self, team: &str, timestamp: i64, half_life: f64) -> Result<Option<Team>> {
+        check_half_life(half_life)?;
+        let Some(series) = self.observations.get(team) else {
+            return Ok(None);
+        };
+        let past = &series[..series.partition_point(|(t, _)| *t <= timestamp)];
+        let Some((_, latest)) = past.last() else {
+            return Ok(None);
+        };
+
+        let (mut offense, mut defense, mut tempo, mut total) = (0.0, 0.0, 0.0, 0.0);
+        for (t, obs) in past {
+            let weight = 0.5f64.powf((timestamp - t) as f64 / half_life);
+            offense += weight * obs.offense;
+            defense += weight * obs.defense;
+            tempo += weight * obs.tempo;
+            total += weight;
+        }
+        Ok(Some(Team {
+            offense: offense / total,
+            defense: defense / total,
+            tempo: tempo / total,
+            ..latest.clone()
+        }))
+    }
+
+    /// Time-decayed ratings of every team observed at or before `timestamp`.
+    pub fn decayed_ratings(&self, timestamp: i64, half_life: f64) -> Result<HashMap<String, Team>> {
+        let mut ratings = HashMap::new();
+        for name in self.observations.keys() {
+            if let Some(team) = self.decayed_rating(name, timestamp, half_life)? {
+                ratings.insert(name.clone(), team);
+            }
+        }
+        Ok(ratings)
+    }
+
+    fn __len__(&self) -> usize {
+        self.observations.len()
+    }
+
+    fn __repr__(&self) -> String {
+        let n_obs: usize = self.observations.values().map(Vec::len).sum();
+        format!("RatedTeamSeries({} teams, {} observations)", self.observations.len(), n_obs)
+    }
+}
+
+/// Decay needs a positive half-life: zero or NaN makes the weights NaN, and a
+/// negative one weights old observations above recent ones.
+fn check_half_life(half_life: f64) -> Result<()> {
+    if half_life > 0.0 {
+        Ok(())
+    } else {
+        Err(TourneyError::InvalidInput(format!("half_life must be positive, got {half_life}")))
+    }
+}
+
+fn lerp(a: f64, b: f64, w: f64) -> f64 {
+    a + (b - a) * w
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series() -> RatedTeamSeries {
+        let mut series = RatedTeamSeries::new();
+        series.add(100, Team::new("A".to_string(), 0.0, 0.0, 66.0, false));
+        series.add(200, Team::new("A".to_string(), 0.1, -0.1, 70.0, false));
+        series.add(150, Team::new("B".to_string(), 0.05, 0.0, 68.0, false));
+        series
+    }
+
+    #[test]
+    fn test_rating_at_interpolates_between_observations() {
+        let series = series();
+        assert!(series.rating_at("A", 99, true).is_none());
+
+        let mid = series.rating_at("A", 150, true).unwrap();
+        assert!((mid.offense - 0.05).abs() < 1e-12);
+        assert!((mid.tempo - 68.0).abs() < 1e-12);
+        assert_eq!(series.rating_at("A", 150, false).unwrap().offense, 0.0);
+        assert_eq!(series.rating_at("A", 500, true).unwrap().offense, 0.1);
+
+        let early = series.ratings_at(120, true);
+        assert_eq!(early.len(), 1);
+        assert!(early.contains_key("A"));
+    }
+
+    #[test]
+    fn test_decay_weights_recent_observations() {
+        let series = series();
+        // At t=200 the t=100 observation is one half-life old: weights 1 and 0.5.
+        let decayed = series.decayed_rating("A", 200, 100.0).unwrap().unwrap();
+        assert!((decayed.offense - 0.1 / 1.5).abs() < 1e-12);
+        assert!((series.decayed_rating("A", 100, 100.0).unwrap().unwrap().offense).abs() < 1e-12);
+        assert!(series.decayed_rating("C", 200, 100.0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decay_rejects_bad_half_life() {
+        let series = series();
+        for half_life in [0.0, -100.0, f64::NAN] {
+            assert!(series.decayed_rating("A", 200, half_life).is_err(), "{half_life}");
+            assert!(series.decayed_ratings(200, half_life).is_err(), "{half_life}");
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Bootstrapped confidence intervals on expected scores and portfolio value.
+//!
+//! Simulated expectations carry two kinds of error: simulation noise, from
+//! running finitely many tournaments, and rating uncertainty, from the
+//! ratings themselves being estimates. Both are captured by resampling.
+//! Without rating uncertainty, the simulations are resampled with replacement
+//! and each resample's means give one draw of the estimate. With it, the
+//! simulations are split across `n_rating_draws` perturbed copies of the
+//! ratings (each team's offense and defense shifted by independent normal
+//! noise), and a two-stage bootstrap resamples rating draws and then
+//! simulations within each, so the intervals widen with the spread across
+//! rating draws.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use serde::Serialize;
+use statrs::distribution::Normal;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::portfolio::percentile;
+use crate::rng::{RngStream, RngStreams};
+use crate::sim::SimContext;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// A point estimate and the confidence interval around it.
+#[pyclass]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Interval {
+    #[pyo3(get)]
+    pub low: f64,
+
+    /// Estimate from all simulations.
+    #[pyo3(get)]
+    pub mid: f64,
+
+    #[pyo3(get)]
+    pub high: f64,
+}
+
+#[pymethods]
+impl Interval {
+    #[getter]
+    pub fn width(&self) -> f64 {
+        self.high - self.low
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.low <= value && value <= self.high
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Interval({:.4} [{:.4}, {:.4}])", self.mid, self.low, self.high)
+    }
+}
+
+/// Confidence intervals from `score_intervals`.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct ScoreIntervals {
+    /// Per bracket team.
+    #[pyo3(get)]
+    pub expected_scores: HashMap<String, Interval>,
+
+    /// Value of the given positions, when positions were given.
+    #[pyo3(get)]
+    pub portfolio_value: Option<Interval>,
+
+    #[pyo3(get)]
+    pub level: f64,
+
+    #[pyo3(get)]
+    pub n_simulations: usize,
+}
+
+#[pymethods]
+impl ScoreIntervals {
+    fn __repr__(&self) -> String {
+        format!(
+            "ScoreIntervals({} teams, level={}, portfolio_value={:?})",
+            self.expected_scores.len(),
+            self.level,
+            self.portfolio_value
+        )
+    }
+}
+
+/// Options for `score_intervals`.
+#[derive(Clone, Copy, Debug)]
+pub struct IntervalOptions {
+    /// Standard deviation of the noise added to each team's offense and
+    /// defense (in the same relative-efficiency units); 0 for simulation
+    /// noise only.
+    pub rating_sd: f64,
+    pub n_rating_draws: usize,
+    pub level: f64,
+    pub n_resamples: usize,
+}
+
+impl IntervalOptions {
+    fn validate(&self, n_simulations: usize) -> Result<()> {
+        if !(self.rating_sd >= 0.0 && self.rating_sd.is_finite()) {
+            return Err(TourneyError::InvalidInput(format!(
+                "rating_sd must be non-negative, got {}",
+                self.rating_sd
+            )));
+        }
+        if !(self.level > 0.0 && self.level < 1.0) {
+            return Err(TourneyError::InvalidInput(format!("level must be in (0, 1), got {}", self.level)));
+        }
+        if n_simulations == 0 || self.n_resamples == 0 {
+            return Err(TourneyError::InvalidInput(
+                "n_simulations and n_resamples must be positive".to_string(),
+            ));
+        }
+        if self.rating_sd > 0.0 && !(1..=n_simulations).contains(&self.n_rating_draws) {
+            return Err(TourneyError::InvalidInput(format!(
+                "n_rating_draws must be between 1 and n_simulations ({n_simulations}), got {}",
+                self.n_rating_draws
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Confidence intervals on each bracket team's expected score in `state`
+/// and, with `positions`, on the portfolio's expected value, from
+/// `n_simulations` simulations resampled `options.n_resamples` times.
+pub fn score_intervals(
+    state: &TournamentState,
+    n_simulations: usize,
+    positions: Option<&HashMap<String, f64>>,
+    options: IntervalOptions,
+    seed: Option<u64>,
+) -> Result<ScoreIntervals> {
+    options.validate(n_simulations)?;
+    let teams = state.bracket_teams();
+    let streams = RngStreams::new(seed);
+    let groups = if options.rating_sd > 0.0 {
+        let mut rng = streams.stream(RngStream::ParameterUncertainty);
+        (0..options.n_rating_draws)
+            .map(|_| perturbed(state, options.rating_sd, &mut rng))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        vec![state.clone()]
+    };
+
+    // Each simulation's row: team scores, then portfolio value.
+    let weights: Option<Vec<f64>> =
+        positions.map(|p| teams.iter().map(|team| p.get(team).copied().unwrap_or(0.0)).collect());
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+    let (base, extra) = (n_simulations / groups.len(), n_simulations % groups.len());
+    let mut start = 0;
+    let samples: Vec<Vec<Vec<f64>>> = groups
+        .iter()
+        .enumerate()
+        .map(|(g, group_state)| {
+            let group_seeds = &seeds[start..start + base + usize::from(g < extra)];
+            start += group_seeds.len();
+            group_seeds
+                .par_iter()
+                .map_init(
+                    || SimContext::new(group_state),
+                    |context, &sim_seed| {
+                        let mut row = context.simulate_dense(Some(sim_seed)).to_vec();
+                        if let Some(weights) = &weights {
+                            row.push(weights.iter().zip(&row).map(|(w, score)| w * score).sum());
+                        }
+                        row
+                    },
+                )
+                .collect()
+        })
+        .collect();
+
+    let n_columns = teams.len() + usize::from(weights.is_some());
+    let mids = mean_rows(samples.iter().flatten(), n_columns);
+    let resample_seeds: Vec<u64> = {
+        let mut rng = streams.stream(RngStream::Bootstrap);
+        (0..options.n_resamples).map(|_| rng.gen()).collect()
+    };
+    let resamples: Vec<Vec<f64>> = resample_seeds
+        .par_iter()
+        .map(|&resample_seed| {
+            let mut rng: ChaCha8Rng = rand::SeedableRng::seed_from_u64(resample_seed);
+            let mut rows = Vec::with_capacity(n_simulations);
+            for _ in 0..samples.len() {
+                let group = &samples[rng.gen_range(0..samples.len())];
+                rows.extend((0..group.len()).map(|_| &group[rng.gen_range(0..group.len())]));
+            }
+            mean_rows(rows.into_iter(), n_columns)
+        })
+        .collect();
+
+    let tail = (1.0 - options.level) / 2.0;
+    let intervals: Vec<Interval> = (0..n_columns)
+        .map(|column| {
+            let mut values: Vec<f64> = resamples.iter().map(|means| means[column]).collect();
+            values.sort_by(f64::total_cmp);
+            Interval {
+                low: percentile(&values, tail),
+                mid: mids[column],
+                high: percentile(&values, 1.0 - tail),
+            }
+        })
+        .collect();
+    Ok(ScoreIntervals {
+        expected_scores: teams.iter().cloned().zip(intervals.iter().copied()).collect(),
+        portfolio_value: weights.map(|_| intervals[teams.len()]),
+        level: options.level,
+        n_simulations,
+    })
+}
+
+/// `state` with every bracket team's offense and defense shifted by
+/// independent normal noise of standard deviation `sd`.
+fn perturbed<R: Rng>(state: &TournamentState, sd: f64, rng: &mut R) -> Result<TournamentState> {
+    let noise = Normal::new(0.0, sd).map_err(|e| TourneyError::InvalidInput(e.to_string()))?;
+    let mut state = state.clone();
+    for team in state.bracket_teams().to_vec() {
+        let rating = state
+            .ratings
+            .get_mut(&team)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("no rating for {team}")))?;
+        rating.offense += rng.sample(noise);
+        rating.defense += rng.sample(noise);
+    }
+    Ok(state)
+}
+
+/// Column means of `rows`.
+fn mean_rows<'a>(rows: impl Iterator<Item = &'a Vec<f64>>, n_columns: usize) -> Vec<f64> {
+    let mut sums = vec![0.0; n_columns];
+    let mut n = 0usize;
+    for row in rows {
+        sums.iter_mut().zip(row).for_each(|(sum, value)| *sum += value);
+        n += 1;
+    }
+    sums.iter().map(|sum| sum / n.max(1) as f64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    fn options(rating_sd: f64) -> IntervalOptions {
+        IntervalOptions {
+            rating_sd,
+            n_rating_draws: 20,
+            level: 0.9,
+            n_resamples: 200,
+        }
+    }
+
+    #[test]
+    fn test_intervals_bracket_simulated_means() {
+        let state = canonical_tournament(8);
+        let positions: HashMap<String, f64> = [("Team 01".to_string(), 2.0), ("Team 08".to_string(), -1.0)].into();
+        let result = score_intervals(&state, 2000, Some(&positions), options(0.0), Some(3)).unwrap();
+
+        let sims = state.run_simulations(2000, Some(3));
+        let exact = state.calculate_scores_prob();
+        for team in state.bracket_teams() {
+            let interval = result.expected_scores[team];
+            let mean = sims.iter().map(|s| s.get(team).copied().unwrap_or(0.0)).sum::<f64>() / 2000.0;
+            assert!((interval.mid - mean).abs() < 1e-9);
+            assert!(interval.low <= interval.mid && interval.mid <= interval.high);
+            // Loose: a 90% interval can miss, but not by several widths.
+            assert!((exact[team] - interval.mid).abs() < 2.0 * interval.width() + 1e-9);
+        }
+        let value = result.portfolio_value.unwrap();
+        let expected = 2.0 * result.expected_scores["Team 01"].mid - result.expected_scores["Team 08"].mid;
+        assert!((value.mid - expected).abs() < 1e-9);
+        assert!(value.width() > 0.0);
+    }
+
+    #[test]
+    fn test_rating_uncertainty_widens_intervals() {
+        let state = canonical_tournament(8);
+        let noise_only = score_intervals(&state, 2000, None, options(0.0), Some(9)).unwrap();
+        let uncertain = score_intervals(&state, 2000, None, options(0.05), Some(9)).unwrap();
+        assert!(uncertain.portfolio_value.is_none());
+        let width = |r: &ScoreIntervals| r.expected_scores["Team 01"].width();
+        assert!(width(&uncertain) > width(&noise_only));
+
+        assert!(score_intervals(&state, 2000, None, options(-0.1), None).is_err());
+        let mut bad_level = options(0.0);
+        bad_level.level = 1.0;
+        assert!(score_intervals(&state, 2000, None, bad_level, None).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Consistency checks for a tournament under its active model.
+//!
+//! Custom ratings, overrides, or hand-edited brackets can quietly break the
+//! assumptions the scoring code relies on. `verify_invariants` checks them and
+//! reports every violation instead of panicking on the first one.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use crate::export::bracket_levels;
+use crate::game_id::Round;
+use crate::tournament::TournamentState;
+use crate::win_prob::calculate_win_prob_with;
+
+/// A failed invariant check.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InvariantViolation {
+    /// Short name of the check, e.g. "game_probabilities".
+    #[pyo3(get)]
+    pub check: String,
+
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl InvariantViolation {
+    fn __repr__(&self) -> String {
+        format!("InvariantViolation({}: {})", self.check, self.detail)
+    }
+}
+
+impl InvariantViolation {
+    fn new(check: &str, detail: String) -> Self {
+        InvariantViolation {
+            check: check.to_string(),
+            detail,
+        }
+    }
+}
+
+/// Check `tournament` for internal consistency, returning all violations.
+///
+/// Checks that the bracket has a power-of-2 number of slots whose teams all
+/// have ratings; that every slot and every game's outcome probabilities lie in
+/// [0, 1] and sum to 1; that P(A beats B) + P(B beats A) = 1 for every pair of
+/// bracket teams; and, when no team multipliers are set, that total expected
+/// points equal the points available.
+#[pyfunction]
+#[pyo3(signature = (tournament, tolerance = 1e-9))]
+pub fn verify_invariants(tournament: &TournamentState, tolerance: f64) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+    let teams = tournament.bracket_teams();
+
+    if !tournament.bracket().len().is_power_of_two() {
+        violations.push(InvariantViolation::new(
+            "bracket_size",
+            format!("bracket has {} slots, not a power of 2", tournament.bracket().len()),
+        ));
+    }
+    let missing: Vec<&String> = teams.iter().filter(|t| !tournament.ratings.contains_key(*t)).collect();
+    if !missing.is_empty() {
+        violations.push(InvariantViolation::new("ratings", format!("teams without ratings: {missing:?}")));
+    }
+    // The remaining checks evaluate the model and need a well-formed bracket.
+    if !violations.is_empty() {
+        return violations;
+    }
+
+    for (i, slot) in tournament.bracket().iter().enumerate() {
+        check_distribution(&mut violations, "slot_probabilities", &format!("slot {i}"), slot.values(), tolerance);
+    }
+    // Modes the levels cannot represent (see `bracket_levels`) are left to
+    // the pairwise checks below.
+    for (level, games) in bracket_levels(tournament).unwrap_or_default().iter().enumerate().skip(1) {
+        for (i, game) in games.iter().enumerate() {
+            let name = format!("round {} game {i}", level - 1);
+            check_distribution(&mut violations, "game_probabilities", &name, game.values(), tolerance);
+        }
+    }
+
+    for (i, name1) in teams.iter().enumerate() {
+        for name2 in &teams[i + 1..] {
+            let (team1, team2) = (&tournament.ratings[name1], &tournament.ratings[name2]);
+            let p12 = calculate_win_prob_with(
+                team1,
+                team2,
+                Some(&tournament.overrides),
+                tournament.forfeit_prob,
+                &tournament.model_params,
+            );
+            let p21 = calculate_win_prob_with(
+                team2,
+                team1,
+                Some(&tournament.overrides),
+                tournament.forfeit_prob,
+                &tournament.model_params,
+            );
+            if !close(p12 + p21, 1.0, tolerance) {
+                violations.push(InvariantViolation::new(
+                    "pairwise_complement",
+                    format!("P({name1} beats {name2}) + P({name2} beats {name1}) = {}", p12 + p21),
+                ));
+            }
+        }
+    }
+
+    if tournament.team_multipliers.values().all(|&m| m == 1.0) {
+        let expected: f64 = tournament.calculate_scores_prob().values().sum();
+        let available = available_points(tournament);
+        if !close(expected, available, tolerance) {
+            violations.push(InvariantViolation::new(
+                "total_points",
+                format!("total expected points {expected} != points available {available}"),
+            ));
+        }
+    }
+
+    violations
+}
+
+/// Points awarded across the whole tournament when every game is played.
+pub fn available_points(tournament: &TournamentState) -> f64 {
+    let n_rounds = tournament.bracket().len().max(1).ilog2() as usize;
+    let main: f64 = (0..n_rounds as u8)
+        .map(Round)
+        .map(|round| round.n_games(tournament.bracket().len()) as f64 * tournament.round_points(round))
+        .sum();
+    main + tournament.play_in_games().len() as f64 * tournament.play_in_points()
+}
+
+/// Within tolerance; NaN never is.
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+fn check_distribution<'a>(
+    violations: &mut Vec<InvariantViolation>,
+    check: &str,
+    name: &str,
+    probs: impl Iterator<Item = &'a f64>,
+    tolerance: f64,
+) {
+    let probs: Vec<f64> = probs.copied().collect();
+    if let Some(p) = probs.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+        violations.push(InvariantViolation::new(check, format!("{name} has probability {p} outside [0, 1]")));
+    }
+    let total: f64 = probs.iter().sum();
+    if !close(total, 1.0, tolerance) {
+        violations.push(InvariantViolation::new(check, format!("{name} probabilities sum to {total}")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_canonical_tournaments_pass() {
+        for n in [4, 16, 64] {
+            assert_eq!(verify_invariants(&canonical_tournament(n), 1e-9), Vec::new());
+        }
+        let mut state = canonical_tournament(16);
+        state.forfeit_prob = 0.05;
+        state.overrides.add_override("Team 01", "Team 16", 0.2).unwrap();
+        assert_eq!(verify_invariants(&state, 1e-9), Vec::new());
+    }
+
+    #[test]
+    fn test_corrupt_bracket_reported() {
+        let mut state = canonical_tournament(4);
+        state.bracket_mut()[0].insert("Team 02".to_string(), 0.7);
+        state.bracket_mut()[0].insert("Team 01".to_string(), 0.7);
+        let violations = verify_invariants(&state, 1e-9);
+        assert!(violations.iter().any(|v| v.check == "slot_probabilities"));
+        assert!(violations.iter().any(|v| v.check == "game_probabilities"));
+
+        state.bracket_mut()[1].insert("Nobody".to_string(), 1.0);
+        assert_eq!(verify_invariants(&state, 1e-9)[0].check, "ratings");
+    }
+}
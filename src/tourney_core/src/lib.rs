@@ -1,32 +1,153 @@
 //! Tourney Core - High-performance NCAA tournament scoring library.
 //!
 //! This library provides Rust implementations of tournament scoring algorithms
-//! with Python bindings via PyO3.
+//! with Python bindings via PyO3. The bindings are the default `python`
+//! feature; built without it (`default-features = false`) the same types and
+//! functions are plain Rust, with no Python toolchain or interpreter needed.
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+// Without pyo3, its attributes are no-ops that strip their helper attributes.
+#[cfg(not(feature = "python"))]
+#[macro_use]
+extern crate tourney_core_macros;
+#[cfg(feature = "python")]
 use std::collections::HashMap;
 
+pub mod archive;
+#[cfg(feature = "python")]
+mod awaitable;
+pub mod book;
+pub mod bracketology;
+pub mod builder;
+pub mod calibration;
+pub mod compare;
+pub mod conditional;
 pub mod constants;
+pub mod convergence;
+pub mod costs;
+pub mod data_files;
+#[cfg(feature = "data")]
+pub mod datasets;
+pub mod ensemble;
+pub mod entry;
+pub mod entry_formats;
+pub mod error;
+pub mod export;
+pub mod field;
+pub mod exposure;
+pub mod ffi;
+pub mod game_id;
 pub mod game_transform;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod group_stage;
+pub mod history;
+pub mod intervals;
+pub mod invariants;
+pub mod market;
+pub mod matchup;
+pub mod odds;
 pub mod overrides;
+pub mod pool;
 pub mod portfolio;
+mod propagate;
+pub mod ratings;
+pub mod reduce;
+pub mod report;
+pub mod rng;
+pub mod rooting;
+pub mod round_robin;
+pub mod scenarios;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod schedule;
+pub mod sim;
+pub mod sim_log;
+pub mod scoring;
+pub mod seeding;
+pub mod sensitivity;
+pub mod standings;
+pub mod storage;
+pub mod strategy;
+pub mod swiss;
 pub mod team;
+pub mod testing;
+mod trace;
 pub mod tournament;
+#[cfg(feature = "python")]
+pub mod views;
+pub mod win_matrix;
 pub mod win_prob;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
 
+pub use archive::SimulationArchive;
+pub use book::PortfolioBook;
+pub use bracketology::{auto_bid_impact, project_field, sample_bracket, AutoBidImpact, BracketologyResult, SeedingModel};
+pub use builder::TournamentStateBuilder;
+pub use calibration::fit_confidence;
+pub use compare::{compare_states_sim, PairedDifference};
+pub use conditional::ConditionalSimulations;
 pub use constants::{calcutta_points, AVG_SCORING, AVG_TEMPO, ROUND_POINTS, SCORING_STDDEV};
-pub use overrides::OverridesMap;
+pub use convergence::ConvergenceReport;
+pub use costs::TransactionCosts;
+pub use data_files::{read_games_str, read_ratings_str, TextInput};
+#[cfg(feature = "data")]
+pub use datasets::{historical_seasons, load_season, HistoricalSeason};
+pub use ensemble::EnsembleModel;
+pub use entry::{optimize_entries, optimize_entry, BracketEntry, MultiEntryResult, PickConstraints};
+pub use entry_formats::{entries_from_csv, entries_from_json, entries_to_csv, entries_to_json};
+pub use error::{Result, TourneyError};
+pub use exposure::{ExposureBucket, ExposureReport};
+pub use group_stage::GroupStage;
+pub use history::RatedTeamSeries;
+pub use intervals::{Interval, ScoreIntervals};
+pub use invariants::{verify_invariants, InvariantViolation};
+pub use matchup::MatchupAdjuster;
+pub use odds::{american_to_decimal, decimal_to_probability, implied_probabilities, remove_overround};
+pub use overrides::{OverrideAudit, OverrideMeta, OverridesDiff, OverridesMap};
+pub use field::sample_field;
+pub use game_id::{GameId, Round};
+pub use pool::{bracket_pool_equity, pool_equity, Payouts};
 pub use portfolio::{
-    game_delta, get_all_team_deltas, get_portfolio_value, get_team_delta,
-    get_team_pairwise_deltas, get_team_portfolio_delta, PortfolioState, TeamDelta,
+    delta_matrix, find_edges, game_delta, game_delta_curve, game_delta_ref, get_all_team_deltas,
+    get_all_team_deltas_ref, get_delta_matrix, get_group_delta, get_group_portfolio_delta, get_portfolio_value,
+    get_team_delta, get_team_pairwise_deltas, get_team_portfolio_delta, rebalance_to_target, DeltaMatrix, Edge,
+    PortfolioState, PositionAttribution, RebalancePlan, RiskSummary, TeamDelta, TradePreview,
 };
+pub use ratings::{adjusted_ratings, elo_from_games, EloRatings, GameResult};
+pub use report::{bracket_report, html_report, text_report};
+pub use rng::{RngStream, RngStreams};
+pub use rooting::{entry_rooting_interests, rooting_interests, upcoming_games, RootingInterest};
+pub use round_robin::{RoundRobin, RoundRobinStandings};
+pub use scenarios::{scenario_tree, ScenarioNode};
+pub use schedule::ScheduleStrength;
+pub use scoring::ScoringRule;
+pub use sensitivity::{sensitivity_report, SensitivityPoint, SensitivityReport};
+pub use sim::SimContext;
+pub use sim_log::SimulationLog;
+pub use standings::{
+    entry_status, outcome_matrix, project_standings, score_completed_bracket, score_entries_batch, BracketScore,
+    EntryScoreTable, EntryStanding, EntryStatus, OutcomeMatrix,
+};
+pub use swiss::SwissStage;
 pub use team::Team;
 pub use tournament::TournamentState;
-pub use win_prob::{calculate_expected_scores, calculate_win_prob};
+#[cfg(feature = "python")]
+pub use views::{BracketView, RatingsView};
+pub use win_matrix::WinProbMatrix;
+pub use win_prob::{
+    calculate_expected_scores, calculate_win_prob, matchup_expected_scores, matchup_percentiles, matchup_tempo,
+    project_matchup, DoubleForfeit, MatchupPercentiles, MatchupProjection, ModelParams,
+};
+#[cfg(feature = "xlsx")]
+pub use xlsx::{read_positions_xlsx, read_ratings_xlsx};
 
 /// Calculate win probability for a matchup.
 ///
 /// Python-friendly wrapper around the core win probability function.
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (team1, team2, overrides = None, forfeit_prob = 0.0))]
 fn py_calculate_win_prob(
@@ -38,7 +159,67 @@ fn py_calculate_win_prob(
     calculate_win_prob(team1, team2, overrides, forfeit_prob)
 }
 
+/// Expected (team1, team2) scores for a matchup, at the pairing's tempo
+/// override if `overrides` has one, under `params` (default: `ModelParams()`).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (team1, team2, overrides = None, params = None))]
+fn py_calculate_expected_scores(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    params: Option<ModelParams>,
+) -> (f64, f64) {
+    matchup_expected_scores(team1, team2, overrides, &params.unwrap_or_default())
+}
+
+/// Expected possessions for a matchup: the pairing's tempo override if
+/// `overrides` has one, otherwise the product of the teams' tempos over the
+/// national average (`params.avg_tempo`, default: `ModelParams()`).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (team1, team2, overrides = None, params = None))]
+fn py_expected_tempo(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    params: Option<ModelParams>,
+) -> f64 {
+    matchup_tempo(team1, team2, overrides, &params.unwrap_or_default())
+}
+
+/// Expected scores, margin, total, and win probability for a matchup, under
+/// `params` (default: `ModelParams()`).
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (team1, team2, overrides = None, forfeit_prob = 0.0, params = None))]
+fn py_project_matchup(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: Option<ModelParams>,
+) -> MatchupProjection {
+    project_matchup(team1, team2, overrides, forfeit_prob, params.as_ref())
+}
+
+/// Quantiles of the margin and total for a matchup, from the same overrides
+/// and `params` as `project_matchup`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (team1, team2, quantiles = vec![0.1, 0.5, 0.9], overrides = None, params = None))]
+fn py_matchup_percentiles(
+    team1: &Team,
+    team2: &Team,
+    quantiles: Vec<f64>,
+    overrides: Option<&OverridesMap>,
+    params: Option<ModelParams>,
+) -> Result<MatchupPercentiles> {
+    matchup_percentiles(team1, team2, quantiles, overrides, params.as_ref())
+}
+
 /// Probabilistic game transformation.
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (child1, child2, teams, overrides = None, forfeit_prob = 0.0))]
 fn py_game_transform_prob(
@@ -52,26 +233,150 @@ fn py_game_transform_prob(
 }
 
 /// Python module definition
+#[cfg(feature = "python")]
 #[pymodule]
 fn tourney_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Classes
     m.add_class::<Team>()?;
+    m.add_class::<MatchupProjection>()?;
+    m.add_class::<MatchupPercentiles>()?;
+    m.add_class::<ModelParams>()?;
+    m.add_class::<RatedTeamSeries>()?;
     m.add_class::<OverridesMap>()?;
+    m.add_class::<OverrideMeta>()?;
+    m.add_class::<OverridesDiff>()?;
+    m.add_class::<OverrideAudit>()?;
     m.add_class::<TournamentState>()?;
+    m.add_class::<BracketView>()?;
+    m.add_class::<RatingsView>()?;
+    m.add_class::<GameId>()?;
     m.add_class::<PortfolioState>()?;
     m.add_class::<TeamDelta>()?;
+    m.add_class::<DeltaMatrix>()?;
+    m.add_class::<RiskSummary>()?;
+    m.add_class::<PositionAttribution>()?;
+    m.add_class::<RebalancePlan>()?;
+    m.add_class::<TransactionCosts>()?;
+    m.add_class::<TradePreview>()?;
+    m.add_class::<Edge>()?;
+    m.add_class::<ExposureBucket>()?;
+    m.add_class::<ExposureReport>()?;
+    m.add_class::<PortfolioBook>()?;
+    m.add_class::<Payouts>()?;
+    m.add_class::<ScoringRule>()?;
+    m.add_class::<BracketEntry>()?;
+    m.add_class::<PickConstraints>()?;
+    m.add_class::<MultiEntryResult>()?;
+    m.add_class::<EntryStanding>()?;
+    m.add_class::<EntryStatus>()?;
+    m.add_class::<OutcomeMatrix>()?;
+    m.add_class::<BracketScore>()?;
+    m.add_class::<EntryScoreTable>()?;
+    m.add_class::<RootingInterest>()?;
+    m.add_class::<ScenarioNode>()?;
+    m.add_class::<GroupStage>()?;
+    m.add_class::<SwissStage>()?;
+    m.add_class::<RoundRobin>()?;
+    m.add_class::<RoundRobinStandings>()?;
+    m.add_class::<InvariantViolation>()?;
+    m.add_class::<SimulationArchive>()?;
+    m.add_class::<SimulationLog>()?;
+    m.add_class::<ConditionalSimulations>()?;
+    m.add_class::<ConvergenceReport>()?;
+    m.add_class::<EnsembleModel>()?;
+    m.add_class::<MatchupAdjuster>()?;
+    m.add_class::<Interval>()?;
+    m.add_class::<ScoreIntervals>()?;
+    m.add_class::<PairedDifference>()?;
+    m.add_class::<ScheduleStrength>()?;
+    m.add_class::<WinProbMatrix>()?;
+    m.add_class::<SeedingModel>()?;
+    m.add_class::<BracketologyResult>()?;
+    m.add_class::<AutoBidImpact>()?;
+    m.add_class::<SensitivityPoint>()?;
+    m.add_class::<SensitivityReport>()?;
 
     // Core functions
     m.add_function(wrap_pyfunction!(py_calculate_win_prob, m)?)?;
     m.add_function(wrap_pyfunction!(py_game_transform_prob, m)?)?;
+    m.add_function(wrap_pyfunction!(py_calculate_expected_scores, m)?)?;
+    m.add_function(wrap_pyfunction!(py_expected_tempo, m)?)?;
+    m.add_function(wrap_pyfunction!(py_project_matchup, m)?)?;
+    m.add_function(wrap_pyfunction!(py_matchup_percentiles, m)?)?;
+    m.add_function(wrap_pyfunction!(fit_confidence, m)?)?;
+
+    // Data file readers
+    m.add_function(wrap_pyfunction!(read_ratings_str, m)?)?;
+    m.add_function(wrap_pyfunction!(read_games_str, m)?)?;
+    m.add_function(wrap_pyfunction!(elo_from_games, m)?)?;
+    m.add_function(wrap_pyfunction!(adjusted_ratings, m)?)?;
+    m.add_class::<GameResult>()?;
+    m.add_class::<EloRatings>()?;
+    #[cfg(feature = "xlsx")]
+    m.add_function(wrap_pyfunction!(read_ratings_xlsx, m)?)?;
+    #[cfg(feature = "xlsx")]
+    m.add_function(wrap_pyfunction!(read_positions_xlsx, m)?)?;
+
+    // Bundled historical seasons
+    #[cfg(feature = "data")]
+    m.add_function(wrap_pyfunction!(historical_seasons, m)?)?;
+    #[cfg(feature = "data")]
+    m.add_function(wrap_pyfunction!(load_season, m)?)?;
+    #[cfg(feature = "data")]
+    m.add_class::<HistoricalSeason>()?;
 
     // Portfolio functions
     m.add_function(wrap_pyfunction!(get_portfolio_value, m)?)?;
     m.add_function(wrap_pyfunction!(game_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(game_delta_curve, m)?)?;
     m.add_function(wrap_pyfunction!(get_team_delta, m)?)?;
     m.add_function(wrap_pyfunction!(get_team_portfolio_delta, m)?)?;
     m.add_function(wrap_pyfunction!(get_team_pairwise_deltas, m)?)?;
+    m.add_function(wrap_pyfunction!(get_group_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(get_group_portfolio_delta, m)?)?;
     m.add_function(wrap_pyfunction!(get_all_team_deltas, m)?)?;
+    m.add_function(wrap_pyfunction!(get_delta_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(find_edges, m)?)?;
+    m.add_function(wrap_pyfunction!(rebalance_to_target, m)?)?;
+    m.add_function(wrap_pyfunction!(upcoming_games, m)?)?;
+    m.add_function(wrap_pyfunction!(rooting_interests, m)?)?;
+    m.add_function(wrap_pyfunction!(entry_rooting_interests, m)?)?;
+
+    // Pool functions
+    m.add_function(wrap_pyfunction!(pool_equity, m)?)?;
+    m.add_function(wrap_pyfunction!(bracket_pool_equity, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_field, m)?)?;
+    m.add_function(wrap_pyfunction!(entries_from_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(entries_to_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(entries_from_json, m)?)?;
+    m.add_function(wrap_pyfunction!(entries_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(project_standings, m)?)?;
+    m.add_function(wrap_pyfunction!(outcome_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(score_completed_bracket, m)?)?;
+    m.add_function(wrap_pyfunction!(score_entries_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(entry_status, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize_entries, m)?)?;
+
+    // Scenario comparison
+    m.add_function(wrap_pyfunction!(compare_states_sim, m)?)?;
+    m.add_function(wrap_pyfunction!(scenario_tree, m)?)?;
+
+    // Diagnostics
+    m.add_function(wrap_pyfunction!(verify_invariants, m)?)?;
+    m.add_function(wrap_pyfunction!(sensitivity_report, m)?)?;
+    m.add_function(wrap_pyfunction!(project_field, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_bracket, m)?)?;
+    m.add_function(wrap_pyfunction!(auto_bid_impact, m)?)?;
+
+    // Report functions
+    m.add_function(wrap_pyfunction!(bracket_report, m)?)?;
+
+    // Odds functions
+    m.add_function(wrap_pyfunction!(american_to_decimal, m)?)?;
+    m.add_function(wrap_pyfunction!(decimal_to_probability, m)?)?;
+    m.add_function(wrap_pyfunction!(remove_overround, m)?)?;
+    m.add_function(wrap_pyfunction!(implied_probabilities, m)?)?;
 
     // Constants
     m.add("AVG_SCORING", AVG_SCORING)?;
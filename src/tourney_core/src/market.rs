@@ -0,0 +1,252 @@
+//! A lightweight team-share market for backtesting against fill dynamics.
+//!
+//! `backtest` fills every trade at its stated price, which flatters strategies
+//! that trade size or chase stale quotes. Here each team has a limit order
+//! book stocked by quoting agents: every agent perceives the model value with
+//! its own noise and quotes a bid and an ask around that perception. A
+//! strategy sees book mids as prices, and each `Action::Trade` is a limit
+//! order that walks the book up to its price, so large orders fill partially
+//! or at worse prices and consumed liquidity stays gone until the next step.
+
+use rand::Rng;
+use statrs::distribution::Normal;
+use std::collections::HashMap;
+
+use crate::portfolio::get_portfolio_value_ref;
+use crate::strategy::{Action, BacktestReport, BacktestStep, Observation, Strategy};
+use crate::tournament::seeded_rng;
+
+/// One resting order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub price: f64,
+    pub shares: f64,
+}
+
+/// Shares filled by an order and the cash they cost (negative for sales).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fill {
+    pub shares: f64,
+    pub cost: f64,
+}
+
+impl Fill {
+    /// Average price per share, if anything filled.
+    pub fn average_price(&self) -> Option<f64> {
+        (self.shares != 0.0).then(|| self.cost / self.shares)
+    }
+}
+
+/// Resting bids and asks for one team.
+#[derive(Clone, Debug, Default)]
+pub struct OrderBook {
+    /// Best (highest) first.
+    bids: Vec<Quote>,
+    /// Best (lowest) first.
+    asks: Vec<Quote>,
+}
+
+impl OrderBook {
+    /// Rest a bid to buy `shares` at `price`.
+    pub fn bid(&mut self, price: f64, shares: f64) {
+        let at = self.bids.partition_point(|q| q.price >= price);
+        self.bids.insert(at, Quote { price, shares });
+    }
+
+    /// Rest an ask to sell `shares` at `price`.
+    pub fn ask(&mut self, price: f64, shares: f64) {
+        let at = self.asks.partition_point(|q| q.price <= price);
+        self.asks.insert(at, Quote { price, shares });
+    }
+
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|q| q.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|q| q.price)
+    }
+
+    /// Midpoint of the best bid and ask, or whichever side exists.
+    pub fn mid(&self) -> Option<f64> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    /// Buy (positive `shares`) or sell (negative) up to `shares`, taking
+    /// resting orders no worse than `limit`, best price first.
+    pub fn execute(&mut self, shares: f64, limit: f64) -> Fill {
+        let (side, buying) = if shares > 0.0 { (&mut self.asks, true) } else { (&mut self.bids, false) };
+        let mut remaining = shares.abs();
+        let mut fill = Fill::default();
+        while remaining > 0.0 {
+            let Some(best) = side.first_mut() else { break };
+            if (buying && best.price > limit) || (!buying && best.price < limit) {
+                break;
+            }
+            let taken = remaining.min(best.shares);
+            let signed = if buying { taken } else { -taken };
+            fill.shares += signed;
+            fill.cost += signed * best.price;
+            remaining -= taken;
+            best.shares -= taken;
+            if best.shares <= 0.0 {
+                side.remove(0);
+            }
+        }
+        fill
+    }
+}
+
+/// How the quoting agents behave.
+#[derive(Clone, Debug)]
+pub struct MarketConfig {
+    /// Quoting agents per team.
+    pub n_agents: usize,
+    /// Standard deviation of an agent's perceived value, as a fraction of the
+    /// model value.
+    pub noise: f64,
+    /// Distance from an agent's perceived value to its bid and ask, as a
+    /// fraction of that value.
+    pub half_spread: f64,
+    /// Shares each agent quotes on each side.
+    pub depth: f64,
+}
+
+impl Default for MarketConfig {
+    fn default() -> Self {
+        MarketConfig {
+            n_agents: 5,
+            noise: 0.05,
+            half_spread: 0.02,
+            depth: 10.0,
+        }
+    }
+}
+
+/// Order books for every team.
+#[derive(Clone, Debug, Default)]
+pub struct Market {
+    pub books: HashMap<String, OrderBook>,
+}
+
+impl Market {
+    /// Fresh books with every agent quoting around `values` (model values).
+    /// Teams worth nothing get no quotes.
+    pub fn quote<R: Rng>(values: &HashMap<String, f64>, config: &MarketConfig, rng: &mut R) -> Self {
+        let noise = Normal::new(0.0, config.noise).ok().filter(|_| config.noise > 0.0);
+        // Sorted so the draws are assigned to teams in a fixed order.
+        let mut teams: Vec<(&String, f64)> = values.iter().map(|(team, &value)| (team, value)).collect();
+        teams.sort_by(|a, b| a.0.cmp(b.0));
+        let mut books = HashMap::new();
+        for (team, value) in teams.into_iter().filter(|&(_, value)| value > 0.0) {
+            let mut book = OrderBook::default();
+            for _ in 0..config.n_agents {
+                let perceived = value * (1.0 + noise.map_or(0.0, |n| rng.sample(n))).max(0.0);
+                book.bid(perceived * (1.0 - config.half_spread), config.depth);
+                book.ask(perceived * (1.0 + config.half_spread), config.depth);
+            }
+            books.insert(team.clone(), book);
+        }
+        Market { books }
+    }
+
+    /// Book mids, the prices strategies see.
+    pub fn prices(&self) -> HashMap<String, f64> {
+        self.books.iter().filter_map(|(team, book)| Some((team.clone(), book.mid()?))).collect()
+    }
+}
+
+/// Run `strategy` over `steps` against a simulated market.
+///
+/// Each step's books are re-quoted around that step's model values (the
+/// step's own `prices` are ignored). Trades are limit orders at their stated
+/// price; `n_trades` counts orders that filled at all.
+pub fn backtest_market(
+    strategy: &mut dyn Strategy,
+    steps: &[BacktestStep],
+    initial_positions: HashMap<String, f64>,
+    config: &MarketConfig,
+    seed: Option<u64>,
+) -> BacktestReport {
+    let mut rng = seeded_rng(seed);
+    let mut positions = initial_positions;
+    let mut cash = 0.0;
+    let mut n_trades = 0;
+    let mut final_value = 0.0;
+
+    for step in steps {
+        let scores = step.tournament.calculate_scores_prob();
+        let mut market = Market::quote(&scores, config, &mut rng);
+        let prices = market.prices();
+        let actions = strategy.decide(&Observation::new(&step.tournament, &positions, &prices, &scores));
+        for action in actions {
+            if let Action::Trade { team, shares, price } = action {
+                let Some(book) = market.books.get_mut(&team) else { continue };
+                let fill = book.execute(shares, price);
+                if fill.shares != 0.0 {
+                    cash -= fill.cost;
+                    *positions.entry(team).or_insert(0.0) += fill.shares;
+                    n_trades += 1;
+                }
+            }
+        }
+        final_value = get_portfolio_value_ref(&positions, &scores);
+    }
+
+    BacktestReport {
+        strategy: strategy.name().to_string(),
+        positions,
+        cash,
+        final_value,
+        n_trades,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{backtest, BuyUndervalued};
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_orders_walk_the_book_up_to_their_limit() {
+        let mut book = OrderBook::default();
+        book.ask(1.2, 5.0);
+        book.ask(1.0, 5.0);
+        book.bid(0.9, 4.0);
+        assert_eq!(book.mid(), Some(0.95));
+
+        let fill = book.execute(8.0, 1.5);
+        assert_eq!(fill, Fill { shares: 8.0, cost: 5.0 * 1.0 + 3.0 * 1.2 });
+        assert_eq!(book.best_ask(), Some(1.2));
+        // The limit stops the remaining buy; sales only hit bids at or above it.
+        assert_eq!(book.execute(5.0, 1.1).shares, 0.0);
+        assert_eq!(book.execute(-10.0, 0.5), Fill { shares: -4.0, cost: -3.6 });
+        assert_eq!(book.mid(), Some(1.2));
+    }
+
+    #[test]
+    fn test_market_fills_cost_more_than_assumed_mids() {
+        let tournament = canonical_tournament(8);
+        let config = MarketConfig::default();
+        let mut rng = seeded_rng(Some(1));
+        let prices = Market::quote(&tournament.calculate_scores_prob(), &config, &mut rng).prices();
+        let steps = vec![BacktestStep { tournament, prices }];
+        let strategy = || BuyUndervalued {
+            min_edge: 0.0,
+            max_position: 1000.0,
+            lot_size: 100.0,
+            costs: Default::default(),
+        };
+
+        let frictionless = backtest(&mut strategy(), &steps, HashMap::new());
+        let simulated = backtest_market(&mut strategy(), &steps, HashMap::new(), &config, Some(1));
+        // Books are only `n_agents * depth` deep, so large orders fill partially.
+        let held = |report: &BacktestReport| report.positions.values().map(|s| s.abs()).sum::<f64>();
+        assert!(held(&simulated) < held(&frictionless));
+        assert!(simulated.positions.values().all(|s| s.abs() <= config.n_agents as f64 * config.depth));
+    }
+}
@@ -0,0 +1,245 @@
+//! Style-based adjustments to the margin model.
+//!
+//! The efficiency model sees each team only through its offense, defense,
+//! and tempo. A `MatchupAdjuster`, set as `ModelParams::matchup_adjuster`,
+//! lets a user encode how particular styles interact without changing the
+//! model: after the expected margin and its standard deviation are computed,
+//! each term shifts one or the other using features of the two teams. A
+//! feature is `"offense"`, `"defense"`, `"tempo"`, or any key of
+//! `Team::style`; teams without a style key count as 0.
+//!
+//! Margin terms are differences between the teams, so swapping them flips
+//! the shift and the two teams' probabilities still sum to 1. Standard
+//! deviation terms are symmetric and scale the standard deviation, e.g. a
+//! tempo clash (`abs_difference` of `"tempo"`) or two high-variance
+//! three-point shooting teams (`sum` of a three-point rate).
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::error::{Result, TourneyError};
+use crate::team::Team;
+
+/// How a standard deviation term combines the two teams' feature values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Combine {
+    Sum,
+    AbsDifference,
+    Product,
+}
+
+impl Combine {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Combine::Sum => "sum",
+            Combine::AbsDifference => "abs_difference",
+            Combine::Product => "product",
+        }
+    }
+
+    fn apply(&self, value1: f64, value2: f64) -> f64 {
+        match self {
+            Combine::Sum => value1 + value2,
+            Combine::AbsDifference => (value1 - value2).abs(),
+            Combine::Product => value1 * value2,
+        }
+    }
+}
+
+impl FromStr for Combine {
+    type Err = TourneyError;
+
+    fn from_str(combine: &str) -> Result<Self> {
+        match combine {
+            "sum" => Ok(Combine::Sum),
+            "abs_difference" => Ok(Combine::AbsDifference),
+            "product" => Ok(Combine::Product),
+            _ => Err(TourneyError::InvalidInput(format!(
+                "unknown combine {combine:?}; expected \"sum\", \"abs_difference\" or \"product\""
+            ))),
+        }
+    }
+}
+
+/// Shift of the expected margin, in points.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct MarginTerm {
+    feature: String,
+    against: Option<String>,
+    coefficient: f64,
+}
+
+/// Relative change in the margin's standard deviation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct StddevTerm {
+    feature: String,
+    combine: Combine,
+    coefficient: f64,
+}
+
+/// Margin and standard deviation adjustments from the teams' styles.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchupAdjuster {
+    margin_terms: Vec<MarginTerm>,
+    stddev_terms: Vec<StddevTerm>,
+}
+
+#[pymethods]
+impl MatchupAdjuster {
+    #[new]
+    pub fn new() -> Self {
+        MatchupAdjuster::default()
+    }
+
+    /// Shift team1's expected margin by `coefficient` points per unit of
+    /// `feature1 - feature2`, or with `against`, per unit of
+    /// `feature1 * against2 - feature2 * against1` (one team's strength
+    /// meeting the other's weakness).
+    #[pyo3(signature = (feature, coefficient, against = None))]
+    pub fn add_margin_term(&mut self, feature: String, coefficient: f64, against: Option<String>) -> Result<()> {
+        self.margin_terms.push(MarginTerm {
+            feature,
+            against,
+            coefficient: finite(coefficient)?,
+        });
+        Ok(())
+    }
+
+    /// Scale the margin's standard deviation by `1 + coefficient * x`, where
+    /// `x` combines the teams' `feature` values by `combine` (`"sum"`,
+    /// `"abs_difference"`, or `"product"`). Scales from all terms multiply
+    /// and are floored at 0.
+    #[pyo3(signature = (feature, coefficient, combine = "sum"))]
+    pub fn add_stddev_term(&mut self, feature: String, coefficient: f64, combine: &str) -> Result<()> {
+        self.stddev_terms.push(StddevTerm {
+            feature,
+            combine: combine.parse()?,
+            coefficient: finite(coefficient)?,
+        });
+        Ok(())
+    }
+
+    /// `(margin, stddev)` after this adjuster's terms, for team1's expected
+    /// `margin` over team2 with standard deviation `stddev`.
+    pub fn adjust(&self, team1: &Team, team2: &Team, margin: f64, stddev: f64) -> (f64, f64) {
+        let shift: f64 = self
+            .margin_terms
+            .iter()
+            .map(|term| {
+                let (value1, value2) = (feature(team1, &term.feature), feature(team2, &term.feature));
+                let difference = match &term.against {
+                    Some(against) => value1 * feature(team2, against) - value2 * feature(team1, against),
+                    None => value1 - value2,
+                };
+                term.coefficient * difference
+            })
+            .sum();
+        let scale: f64 = self
+            .stddev_terms
+            .iter()
+            .map(|term| {
+                let combined = term.combine.apply(feature(team1, &term.feature), feature(team2, &term.feature));
+                (1.0 + term.coefficient * combined).max(0.0)
+            })
+            .product();
+        // A zero spread would leave an even game undefined.
+        (margin + shift, (stddev * scale).max(f64::MIN_POSITIVE))
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.margin_terms.len() + self.stddev_terms.len()
+    }
+
+    fn __repr__(&self) -> String {
+        let terms: Vec<String> = self
+            .margin_terms
+            .iter()
+            .map(|term| match &term.against {
+                Some(against) => format!("margin {:+} * {} vs {}", term.coefficient, term.feature, against),
+                None => format!("margin {:+} * {}", term.coefficient, term.feature),
+            })
+            .chain(
+                self.stddev_terms
+                    .iter()
+                    .map(|term| format!("stddev {:+} * {}({})", term.coefficient, term.combine.name(), term.feature)),
+            )
+            .collect();
+        format!("MatchupAdjuster([{}])", terms.join(", "))
+    }
+}
+
+/// `team`'s value of `name`: a rating or a style feature (0 if missing).
+fn feature(team: &Team, name: &str) -> f64 {
+    match name {
+        "offense" => team.offense,
+        "defense" => team.defense,
+        "tempo" => team.tempo,
+        _ => team.style.get(name).copied().unwrap_or(0.0),
+    }
+}
+
+fn finite(coefficient: f64) -> Result<f64> {
+    if !coefficient.is_finite() {
+        return Err(TourneyError::InvalidInput(format!("coefficient must be finite, got {coefficient}")));
+    }
+    Ok(coefficient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::win_prob::{calculate_win_prob_with, ModelParams};
+
+    fn teams() -> (Team, Team) {
+        let mut shooters = Team::new("A".to_string(), 0.03, 0.0, 72.0, false);
+        shooters.style.insert("three_rate".to_string(), 0.45);
+        let mut bigs = Team::new("B".to_string(), 0.0, -0.01, 64.0, false);
+        bigs.style.insert("three_defense".to_string(), 0.2);
+        (shooters, bigs)
+    }
+
+    #[test]
+    fn test_adjust_terms() {
+        let (a, b) = teams();
+        let mut adjuster = MatchupAdjuster::new();
+        assert_eq!(adjuster.adjust(&a, &b, 2.0, 10.0), (2.0, 10.0));
+
+        adjuster.add_margin_term("three_rate".to_string(), 10.0, Some("three_defense".to_string())).unwrap();
+        adjuster.add_stddev_term("tempo".to_string(), 0.01, "abs_difference").unwrap();
+        adjuster.add_stddev_term("three_rate".to_string(), 0.5, "sum").unwrap();
+        let (margin, stddev) = adjuster.adjust(&a, &b, 2.0, 10.0);
+        assert!((margin - (2.0 + 10.0 * 0.45 * 0.2)).abs() < 1e-12);
+        assert!((stddev - 10.0 * 1.08 * 1.225).abs() < 1e-12);
+        let (swapped_margin, swapped_stddev) = adjuster.adjust(&b, &a, -2.0, 10.0);
+        assert!((swapped_margin + margin).abs() < 1e-12);
+        assert_eq!(swapped_stddev, stddev);
+
+        assert!(adjuster.add_stddev_term("tempo".to_string(), 0.1, "ratio").is_err());
+        assert!(adjuster.add_margin_term("tempo".to_string(), f64::NAN, None).is_err());
+        assert_eq!(adjuster.__len__(), 3);
+    }
+
+    #[test]
+    fn test_model_params_apply_adjuster() {
+        let (a, b) = teams();
+        let mut params = ModelParams::default();
+        let base = calculate_win_prob_with(&a, &b, None, 0.0, &params);
+
+        let mut adjuster = MatchupAdjuster::new();
+        adjuster.add_margin_term("three_rate".to_string(), 5.0, None).unwrap();
+        params.matchup_adjuster = Some(adjuster);
+        let adjusted = calculate_win_prob_with(&a, &b, None, 0.0, &params);
+        assert!(adjusted > base);
+        assert!((adjusted + calculate_win_prob_with(&b, &a, None, 0.0, &params) - 1.0).abs() < 1e-12);
+
+        // A spread collapsed to nothing makes the favorite certain.
+        let mut flat = MatchupAdjuster::new();
+        flat.add_stddev_term("tempo".to_string(), -1.0, "product").unwrap();
+        params.matchup_adjuster = Some(flat);
+        assert_eq!(calculate_win_prob_with(&a, &b, None, 0.0, &params), 1.0);
+    }
+}
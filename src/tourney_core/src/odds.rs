@@ -0,0 +1,147 @@
+//! Conversion of bookmaker futures odds into championship probabilities.
+//!
+//! Raw implied probabilities from a book sum to more than 1 (the overround, or
+//! vig). `remove_overround` rescales them into a proper distribution, either
+//! proportionally or with the power method, which trims longshots harder to
+//! account for favourite-longshot bias.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+
+/// Convert American odds (+150, -200) to decimal odds (2.5, 1.5).
+#[pyfunction]
+pub fn american_to_decimal(odds: f64) -> Result<f64> {
+    if odds >= 100.0 {
+        Ok(1.0 + odds / 100.0)
+    } else if odds <= -100.0 {
+        Ok(1.0 + 100.0 / -odds)
+    } else {
+        Err(TourneyError::InvalidInput(format!(
+            "American odds must be at least +100 or at most -100, got {odds}"
+        )))
+    }
+}
+
+/// Implied probability of decimal odds, including the book's margin.
+#[pyfunction]
+pub fn decimal_to_probability(odds: f64) -> Result<f64> {
+    if odds <= 1.0 {
+        return Err(TourneyError::InvalidInput(format!(
+            "decimal odds must be greater than 1, got {odds}"
+        )));
+    }
+    Ok(1.0 / odds)
+}
+
+/// Rescale raw implied probabilities so they sum to 1.
+///
+/// `method` is `"proportional"` (divide by the total) or `"power"` (raise each
+/// probability to the exponent k that makes them sum to 1).
+#[pyfunction]
+#[pyo3(signature = (probs, method = "proportional"))]
+pub fn remove_overround(probs: HashMap<String, f64>, method: &str) -> Result<HashMap<String, f64>> {
+    if let Some((team, p)) = probs.iter().find(|(_, &p)| !(p > 0.0 && p < 1.0)) {
+        return Err(TourneyError::InvalidInput(format!(
+            "implied probability for {team} must be between 0 and 1, got {p}"
+        )));
+    }
+    match method {
+        "proportional" => {
+            let total: f64 = probs.values().sum();
+            Ok(probs.into_iter().map(|(team, p)| (team, p / total)).collect())
+        }
+        "power" => {
+            let k = power_exponent(&probs);
+            Ok(probs.into_iter().map(|(team, p)| (team, p.powf(k))).collect())
+        }
+        other => Err(TourneyError::InvalidInput(format!(
+            "unknown overround method {other:?}; expected \"proportional\" or \"power\""
+        ))),
+    }
+}
+
+/// Championship probabilities from a book of futures odds.
+///
+/// `format` is `"american"` or `"decimal"`; see `remove_overround` for `method`.
+#[pyfunction]
+#[pyo3(signature = (odds, format = "american", method = "proportional"))]
+pub fn implied_probabilities(
+    odds: HashMap<String, f64>,
+    format: &str,
+    method: &str,
+) -> Result<HashMap<String, f64>> {
+    let raw = odds
+        .into_iter()
+        .map(|(team, o)| {
+            let decimal = match format {
+                "american" => american_to_decimal(o)?,
+                "decimal" => o,
+                other => {
+                    return Err(TourneyError::InvalidInput(format!(
+                        "unknown odds format {other:?}; expected \"american\" or \"decimal\""
+                    )))
+                }
+            };
+            Ok((team, decimal_to_probability(decimal)?))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+    remove_overround(raw, method)
+}
+
+/// Exponent k with sum(p^k) = 1, found by bisection.
+///
+/// The sum is decreasing in k, so k > 1 when the book is overround.
+fn power_exponent(probs: &HashMap<String, f64>) -> f64 {
+    let total = |k: f64| probs.values().map(|p| p.powf(k)).sum::<f64>();
+    let (mut lo, mut hi) = (1e-6, 1.0);
+    while total(hi) > 1.0 {
+        lo = hi;
+        hi *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if total(mid) > 1.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> HashMap<String, f64> {
+        [("A".to_string(), 150.0), ("B".to_string(), -120.0), ("C".to_string(), 400.0)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_odds_conversion() {
+        assert!((american_to_decimal(150.0).unwrap() - 2.5).abs() < 1e-12);
+        assert!((american_to_decimal(-200.0).unwrap() - 1.5).abs() < 1e-12);
+        assert!((decimal_to_probability(4.0).unwrap() - 0.25).abs() < 1e-12);
+        assert!(american_to_decimal(50.0).is_err());
+        assert!(decimal_to_probability(1.0).is_err());
+    }
+
+    #[test]
+    fn test_overround_removal_methods() {
+        let proportional = implied_probabilities(book(), "american", "proportional").unwrap();
+        let power = implied_probabilities(book(), "american", "power").unwrap();
+
+        for probs in [&proportional, &power] {
+            assert!((probs.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+        // The power method shades the longshot down relative to proportional.
+        assert!(power["C"] < proportional["C"]);
+        assert!(power["B"] > proportional["B"]);
+        assert!(implied_probabilities(book(), "fractional", "power").is_err());
+    }
+}
@@ -1,80 +1,1024 @@
+// pyo3 0.22's generated wrappers re-convert a `PyErr` returned as `PyResult`, which
+// trips this lint outside any item an attribute could reach; the module is the tightest scope.
+#![cfg_attr(feature = "python", allow(clippy::useless_conversion))]
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyKeyError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::data_files::TextInput;
+use crate::error::{Result, TourneyError};
+use crate::game_id::Round;
+use crate::team::Team;
+use crate::win_prob::{calculate_win_prob, margin_win_prob, ModelParams};
+
+/// Provenance and expiry of a single override.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OverrideMeta {
+    /// Where the override came from (e.g. "injury feed", "manual").
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Unix timestamp (seconds) when the override was set.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+
+    /// Last round (0-based) in which the override applies, judged by the
+    /// map's clock (`OverridesMap.set_clock`), not by the round of the game
+    /// being scored.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_round: Option<Round>,
+
+    /// Unix timestamp after which the override no longer applies.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+#[pymethods]
+impl OverrideMeta {
+    #[new]
+    #[pyo3(signature = (source = None, timestamp = None, note = None, expires_round = None, expires_at = None))]
+    pub fn new(
+        source: Option<String>,
+        timestamp: Option<i64>,
+        note: Option<String>,
+        expires_round: Option<Round>,
+        expires_at: Option<i64>,
+    ) -> Self {
+        OverrideMeta {
+            source,
+            timestamp,
+            note,
+            expires_round,
+            expires_at,
+        }
+    }
+
+    /// Whether the override has expired as of `round` and/or `timestamp`.
+    #[pyo3(signature = (round = None, timestamp = None))]
+    pub fn is_expired(&self, round: Option<Round>, timestamp: Option<i64>) -> bool {
+        let round_expired = matches!((self.expires_round, round), (Some(last), Some(r)) if r > last);
+        let time_expired = matches!((self.expires_at, timestamp), (Some(at), Some(t)) if t > at);
+        round_expired || time_expired
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OverrideMeta(source={:?}, note={:?}, expires_round={:?}, expires_at={:?})",
+            self.source,
+            self.note,
+            self.expires_round.map(|round| round.0),
+            self.expires_at
+        )
+    }
+}
 
 /// Manual probability overrides for specific matchups.
 ///
 /// Overrides are stored with team names in lexicographic order.
 /// When retrieving an override, the probability is automatically
 /// flipped if the teams are provided in reverse order.
+///
+/// Overrides may carry an `OverrideMeta`. Expired overrides stay in the map but
+/// are ignored by lookups once the clock (see `set_clock`) passes their expiry.
+/// The clock only moves when it is set: it is the round the tournament has
+/// reached and the time, not the round of the game being scored, so an
+/// override expiring after round 1 still applies to a projected round-2 game
+/// until `current_round` reaches 2. The clock is not serialized.
+///
+/// A map may sit on top of a `base` layer (e.g. a feed of injury overrides
+/// under manual session tweaks): lookups fall through to the base for any
+/// matchup this layer has no active override for.
 #[pyclass]
 #[derive(Clone, Debug, Default)]
 pub struct OverridesMap {
-    overrides: HashMap<(String, String), f64>,
+    // Tables are shared between clones and copied on first write, so handing
+    // a state's overrides to Python does not copy every entry.
+    overrides: Arc<HashMap<(String, String), f64>>,
+    metadata: Arc<HashMap<(String, String), OverrideMeta>>,
+
+    /// Expected possessions for specific pairings (e.g. slow-down game plans).
+    tempos: Arc<HashMap<(String, String), f64>>,
+
+    /// Current tournament round (0-based) used for round expiries.
+    #[pyo3(get)]
+    current_round: Option<Round>,
+
+    /// Current Unix timestamp used for date expiries.
+    #[pyo3(get)]
+    as_of: Option<i64>,
+
+    base: Option<Arc<OverridesMap>>,
+
+    /// Clamp out-of-range probabilities into [0, 1] instead of rejecting them.
+    #[pyo3(get, set)]
+    pub clamp: bool,
+}
+
+/// An override that disagrees sharply with the rating-based probability.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct OverrideAudit {
+    #[pyo3(get)]
+    pub team1: String,
+
+    #[pyo3(get)]
+    pub team2: String,
+
+    /// Overridden probability of team1 beating team2.
+    #[pyo3(get)]
+    pub override_prob: f64,
+
+    /// Probability of team1 beating team2 from ratings alone.
+    #[pyo3(get)]
+    pub model_prob: f64,
+}
+
+#[pymethods]
+impl OverrideAudit {
+    /// `override_prob - model_prob`.
+    #[getter]
+    pub fn difference(&self) -> f64 {
+        self.override_prob - self.model_prob
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OverrideAudit({} vs {}: override={:.3}, model={:.3})",
+            self.team1, self.team2, self.override_prob, self.model_prob
+        )
+    }
+}
+
+/// Differences between two override maps.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OverridesDiff {
+    /// Matchups only in the other map, as (team1, team2, prob).
+    #[pyo3(get)]
+    pub added: Vec<(String, String, f64)>,
+
+    /// Matchups only in this map, as (team1, team2, prob).
+    #[pyo3(get)]
+    pub removed: Vec<(String, String, f64)>,
+
+    /// Matchups in both with different probabilities, as (team1, team2, old, new).
+    #[pyo3(get)]
+    pub changed: Vec<(String, String, f64, f64)>,
+}
+
+#[pymethods]
+impl OverridesDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OverridesDiff({} added, {} removed, {} changed)",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
 }
 
 #[pymethods]
 impl OverridesMap {
     #[new]
     pub fn new() -> Self {
-        OverridesMap {
-            overrides: HashMap::new(),
-        }
+        OverridesMap::default()
     }
 
     /// Add or update an override for a matchup.
-    pub fn add_override(&mut self, name1: &str, name2: &str, prob: f64) {
+    ///
+    /// Probabilities outside [0, 1] are rejected unless `clamp` is set. Any
+    /// metadata previously attached to the matchup is dropped.
+    pub fn add_override(&mut self, name1: &str, name2: &str, prob: f64) -> Result<()> {
+        let prob = self.validate(name1, name2, prob)?;
         let (key, value) = if name1 < name2 {
             ((name1.to_string(), name2.to_string()), prob)
         } else {
             ((name2.to_string(), name1.to_string()), 1.0 - prob)
         };
-        self.overrides.insert(key, value);
+        Arc::make_mut(&mut self.metadata).remove(&key);
+        Arc::make_mut(&mut self.overrides).insert(key, value);
+        Ok(())
+    }
+
+    /// Set the expected tempo (possessions) for a matchup, replacing the
+    /// ratings-based pace in win probabilities and expected scores for that
+    /// pairing only.
+    pub fn set_tempo_override(&mut self, name1: &str, name2: &str, tempo: f64) -> Result<()> {
+        if !(tempo.is_finite() && tempo > 0.0) {
+            return Err(TourneyError::InvalidInput(format!(
+                "tempo override for {name1} vs {name2} must be positive, got {tempo}"
+            )));
+        }
+        Arc::make_mut(&mut self.tempos).insert(key(name1, name2), tempo);
+        Ok(())
+    }
+
+    pub fn remove_tempo_override(&mut self, name1: &str, name2: &str) {
+        Arc::make_mut(&mut self.tempos).remove(&key(name1, name2));
+    }
+
+    /// Tempo override for a matchup, falling through to the base layer.
+    pub fn get_tempo_override(&self, name1: &str, name2: &str) -> Option<f64> {
+        self.tempos
+            .get(&key(name1, name2))
+            .copied()
+            .or_else(|| self.base.as_ref().and_then(|base| base.get_tempo_override(name1, name2)))
+    }
+
+    /// All tempo overrides across layers as sorted (team1, team2, tempo)
+    /// tuples, with team1 < team2.
+    pub fn tempo_overrides(&self) -> Vec<(String, String, f64)> {
+        let mut items: Vec<_> = self
+            .flatten()
+            .tempos
+            .iter()
+            .map(|((team1, team2), &tempo)| (team1.clone(), team2.clone(), tempo))
+            .collect();
+        items.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        items
+    }
+
+    /// Add or update an override with provenance and expiry metadata.
+    pub fn add_override_with_meta(&mut self, name1: &str, name2: &str, prob: f64, meta: OverrideMeta) -> Result<()> {
+        self.add_override(name1, name2, prob)?;
+        Arc::make_mut(&mut self.metadata).insert(key(name1, name2), meta);
+        Ok(())
     }
 
     /// Remove an override for a matchup.
     pub fn remove_override(&mut self, name1: &str, name2: &str) {
-        let key = if name1 < name2 {
-            (name1.to_string(), name2.to_string())
-        } else {
-            (name2.to_string(), name1.to_string())
-        };
-        self.overrides.remove(&key);
+        let key = key(name1, name2);
+        Arc::make_mut(&mut self.overrides).remove(&key);
+        Arc::make_mut(&mut self.metadata).remove(&key);
     }
 
-    /// Get the override probability for a matchup, if one exists.
+    /// Get the override probability for a matchup, if one is in effect.
     /// Returns the probability of name1 beating name2.
     pub fn get_override(&self, name1: &str, name2: &str) -> Option<f64> {
-        let (key, flip) = if name1 < name2 {
-            ((name1.to_string(), name2.to_string()), false)
-        } else {
-            ((name2.to_string(), name1.to_string()), true)
-        };
-        self.overrides.get(&key).map(|&p| if flip { 1.0 - p } else { p })
+        let flip = name1 > name2;
+        self.lookup(&key(name1, name2), self.current_round, self.as_of)
+            .map(|p| if flip { 1.0 - p } else { p })
     }
 
-    /// Check if an override exists for a matchup.
+    /// Check if an override is in effect for a matchup.
     pub fn has_override(&self, name1: &str, name2: &str) -> bool {
-        let key = if name1 < name2 {
-            (name1.to_string(), name2.to_string())
-        } else {
-            (name2.to_string(), name1.to_string())
+        self.lookup(&key(name1, name2), self.current_round, self.as_of).is_some()
+    }
+
+    /// Metadata attached to a matchup's override, if any.
+    pub fn get_meta(&self, name1: &str, name2: &str) -> Option<OverrideMeta> {
+        let key = key(name1, name2);
+        self.metadata
+            .get(&key)
+            .cloned()
+            .or_else(|| self.base.as_ref().and_then(|base| base.get_meta(&key.0, &key.1)))
+    }
+
+    /// Layer this map on top of `base`.
+    pub fn set_base(&mut self, base: OverridesMap) {
+        self.base = Some(Arc::new(base));
+    }
+
+    pub fn clear_base(&mut self) {
+        self.base = None;
+    }
+
+    /// The layer beneath this one, if any.
+    #[getter]
+    pub fn base(&self) -> Option<OverridesMap> {
+        self.base.as_deref().cloned()
+    }
+
+    /// Collapse all layers into a single map (this layer wins, unless its
+    /// entry has expired under this map's clock and one beneath has not).
+    pub fn flatten(&self) -> OverridesMap {
+        let mut flat = self.flatten_at(self.current_round, self.as_of);
+        flat.current_round = self.current_round;
+        flat.as_of = self.as_of;
+        flat
+    }
+
+    /// Combine with `other` (both flattened, expired overrides dropped) into
+    /// a new map.
+    ///
+    /// `conflict_policy` decides matchups present in both with different
+    /// probabilities: `"ours"` keeps this map's value, `"theirs"` takes
+    /// `other`'s, `"newest"` takes whichever has the later metadata timestamp
+    /// (ties go to `other`), and `"error"` fails.
+    #[pyo3(signature = (other, conflict_policy = "theirs"))]
+    pub fn merge(&self, other: &OverridesMap, conflict_policy: &str) -> Result<OverridesMap> {
+        if !["ours", "theirs", "newest", "error"].contains(&conflict_policy) {
+            return Err(TourneyError::InvalidInput(format!(
+                "unknown conflict policy {conflict_policy:?}; expected \"ours\", \"theirs\", \"newest\" or \"error\""
+            )));
+        }
+        let mut merged = self.flatten();
+        merged.purge_expired();
+        let mut theirs = other.flatten();
+        theirs.purge_expired();
+        for (key, &prob) in theirs.overrides.iter() {
+            let their_meta = theirs.metadata.get(key).cloned();
+            let take_theirs = match merged.overrides.get(key) {
+                None => true,
+                Some(&ours) if (ours - prob).abs() < 1e-12 => true,
+                Some(_) => match conflict_policy {
+                    "ours" => false,
+                    "newest" => {
+                        let stamp = |meta: Option<&OverrideMeta>| meta.and_then(|m| m.timestamp);
+                        stamp(their_meta.as_ref()) >= stamp(merged.metadata.get(key))
+                    }
+                    "error" => {
+                        return Err(TourneyError::InvalidInput(format!(
+                            "conflicting overrides for {} vs {}",
+                            key.0, key.1
+                        )))
+                    }
+                    _ => true,
+                },
+            };
+            if take_theirs {
+                merged.insert(key.clone(), prob, their_meta);
+            }
+        }
+        for (key, &tempo) in theirs.tempos.iter() {
+            match merged.tempos.get(key) {
+                Some(&ours) if conflict_policy == "ours" || (ours - tempo).abs() < 1e-12 => {}
+                Some(_) if conflict_policy == "error" => {
+                    return Err(TourneyError::InvalidInput(format!(
+                        "conflicting tempo overrides for {} vs {}",
+                        key.0, key.1
+                    )))
+                }
+                _ => {
+                    Arc::make_mut(&mut merged.tempos).insert(key.clone(), tempo);
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Changes needed to turn this map into `other` (both flattened, expired
+    /// overrides dropped).
+    pub fn diff(&self, other: &OverridesMap) -> OverridesDiff {
+        let live = |map: &OverridesMap| {
+            let mut flat = map.flatten();
+            flat.purge_expired();
+            flat.overrides
         };
-        self.overrides.contains_key(&key)
+        let (ours, theirs) = (live(self), live(other));
+        let mut diff = OverridesDiff::default();
+        for (key, &prob) in theirs.iter() {
+            match ours.get(key) {
+                None => diff.added.push((key.0.clone(), key.1.clone(), prob)),
+                Some(&old) if (old - prob).abs() >= 1e-12 => {
+                    diff.changed.push((key.0.clone(), key.1.clone(), old, prob))
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, &prob) in ours.iter() {
+            if !theirs.contains_key(key) {
+                diff.removed.push((key.0.clone(), key.1.clone(), prob));
+            }
+        }
+        diff.added.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        diff.removed.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        diff.changed.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        diff
+    }
+
+    /// Set the clock used to decide which overrides have expired: the round
+    /// the tournament has reached and the time. Advance it as rounds finish;
+    /// scoring never moves it.
+    ///
+    /// `timestamp` defaults to the current time. `round` is the deprecated
+    /// name of `current_round`.
+    #[cfg(feature = "python")]
+    #[pyo3(name = "set_clock", signature = (current_round = None, timestamp = None, *, round = None))]
+    fn py_set_clock(
+        &mut self,
+        py: Python<'_>,
+        current_round: Option<Round>,
+        timestamp: Option<i64>,
+        round: Option<Round>,
+    ) -> PyResult<()> {
+        if round.is_some() {
+            let category = py.get_type_bound::<pyo3::exceptions::PyDeprecationWarning>();
+            PyErr::warn_bound(py, category.as_any(), "set_clock(round=...) is deprecated; use current_round", 1)?;
+        }
+        self.set_clock(current_round.or(round), timestamp);
+        Ok(())
+    }
+
+    /// Overrides currently in effect across all layers as (team1, team2, prob)
+    /// tuples, sorted.
+    pub fn active_overrides(&self) -> Vec<(String, String, f64)> {
+        self.flatten().entries(false)
+    }
+
+    /// Overrides across all layers that have expired under the current clock, sorted.
+    pub fn expired_overrides(&self) -> Vec<(String, String, f64)> {
+        self.flatten().entries(true)
+    }
+
+    /// Drop expired overrides from this layer, returning how many were removed.
+    pub fn purge_expired(&mut self) -> usize {
+        let expired: Vec<_> = self.overrides.keys().filter(|k| self.key_expired(k)).cloned().collect();
+        for key in &expired {
+            Arc::make_mut(&mut self.overrides).remove(key);
+            Arc::make_mut(&mut self.metadata).remove(key);
+        }
+        expired.len()
     }
 
-    /// Get the number of overrides.
+    /// Overrides in effect across layers as sorted (team1, team2, prob)
+    /// tuples, with team1 < team2. Expired entries are left out, as
+    /// `get_override` leaves them out.
+    pub fn items(&self) -> Vec<(String, String, f64)> {
+        self.flatten().entries(false)
+    }
+
+    #[cfg(feature = "python")]
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let items = self.items().into_py(py);
+        Ok(items.bind(py).iter()?.into_any().unbind())
+    }
+
+    /// Overrides as `team1,team2,probability` CSV rows (the overrides file
+    /// format), also written atomically to `path` if given.
+    #[pyo3(signature = (path = None))]
+    pub fn to_csv(&self, path: Option<&str>) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for (team1, team2, prob) in self.items() {
+            writer.write_record([team1, team2, prob.to_string()])?;
+        }
+        let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+        let text = String::from_utf8(bytes).expect("CSV output is UTF-8");
+        if let Some(path) = path {
+            crate::storage::write_atomic(std::path::Path::new(path), text.as_bytes())?;
+        }
+        Ok(text)
+    }
+
+    /// Like `from_csv`, but also accepting the contents as UTF-8 `bytes`
+    /// (e.g. an uploaded overrides file).
+    #[staticmethod]
+    pub fn from_csv_str(text: TextInput) -> Result<OverridesMap> {
+        OverridesMap::from_csv(&text.into_string()?)
+    }
+
+    /// Parse overrides written by `to_csv` (or an overrides file).
+    #[staticmethod]
+    pub fn from_csv(text: &str) -> Result<OverridesMap> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let mut map = OverridesMap::new();
+        for record in reader.records() {
+            let record = record?;
+            let (Some(team1), Some(team2), Some(prob), None) = (record.get(0), record.get(1), record.get(2), record.get(3))
+            else {
+                return Err(TourneyError::InvalidInput(format!(
+                    "expected team1,team2,probability but got {} fields",
+                    record.len()
+                )));
+            };
+            let prob = prob
+                .parse::<f64>()
+                .map_err(|_| TourneyError::InvalidInput(format!("invalid probability {prob:?}")))?;
+            map.add_override(team1, team2, prob)?;
+        }
+        Ok(map)
+    }
+
+    /// Overrides from point spreads, one `(team1, team2, spread)` per game.
+    ///
+    /// `spread` is team1's line in betting convention (-5.5 means team1 is
+    /// favored by 5.5). Margins convert to probabilities the way the model
+    /// does, with the margin standard deviation of two average teams under
+    /// `model_params` (default: the published constants).
+    #[staticmethod]
+    #[pyo3(signature = (spreads, model_params = None))]
+    pub fn from_spreads(spreads: Vec<(String, String, f64)>, model_params: Option<ModelParams>) -> Result<OverridesMap> {
+        let params = model_params.unwrap_or_default();
+        let mut map = OverridesMap::new();
+        for (team1, team2, spread) in spreads {
+            if !spread.is_finite() {
+                return Err(TourneyError::InvalidInput(format!("invalid spread {spread} for {team1} vs {team2}")));
+            }
+            map.add_override(&team1, &team2, margin_win_prob(-spread, params.scoring_stddev))?;
+        }
+        Ok(map)
+    }
+
+    /// Active overrides that differ from the rating-based probability by more
+    /// than `threshold`, largest disagreement first.
+    ///
+    /// Matchups involving a team missing from `ratings` are skipped.
+    #[pyo3(signature = (ratings, threshold = 0.25, forfeit_prob = 0.0))]
+    pub fn audit(&self, ratings: HashMap<String, Team>, threshold: f64, forfeit_prob: f64) -> Vec<OverrideAudit> {
+        let mut flagged: Vec<OverrideAudit> = self
+            .active_overrides()
+            .into_iter()
+            .filter_map(|(team1, team2, override_prob)| {
+                let model_prob = calculate_win_prob(ratings.get(&team1)?, ratings.get(&team2)?, None, forfeit_prob);
+                ((override_prob - model_prob).abs() > threshold).then_some(OverrideAudit {
+                    team1,
+                    team2,
+                    override_prob,
+                    model_prob,
+                })
+            })
+            .collect();
+        flagged.sort_by(|a, b| b.difference().abs().total_cmp(&a.difference().abs()));
+        flagged
+    }
+
+    /// `overrides[team1, team2]`: the override probability of `team1`
+    /// beating `team2`, like `get_override` but raising `KeyError` when none
+    /// is in effect.
+    #[cfg(feature = "python")]
+    fn __getitem__(&self, matchup: (String, String)) -> PyResult<f64> {
+        let (team1, team2) = &matchup;
+        self.get_override(team1, team2).ok_or_else(|| PyKeyError::new_err(matchup.clone()))
+    }
+
+    /// `overrides[team1, team2] = prob`, as `add_override`.
+    fn __setitem__(&mut self, matchup: (String, String), prob: f64) -> Result<()> {
+        self.add_override(&matchup.0, &matchup.1, prob)
+    }
+
+    /// `del overrides[team1, team2]`, as `remove_override`: raises `KeyError`
+    /// unless an override is in effect. Only this layer's entry is removed,
+    /// so one in a base layer still applies afterwards.
+    #[cfg(feature = "python")]
+    fn __delitem__(&mut self, matchup: (String, String)) -> PyResult<()> {
+        if self.take_override(&matchup.0, &matchup.1).is_none() {
+            return Err(PyKeyError::new_err(matchup));
+        }
+        Ok(())
+    }
+
+    /// `(team1, team2) in overrides`, as `has_override`.
+    fn __contains__(&self, matchup: (String, String)) -> bool {
+        self.has_override(&matchup.0, &matchup.1)
+    }
+
+    /// Number of overrides in effect across all layers, matching `items`.
     pub fn __len__(&self) -> usize {
-        self.overrides.len()
+        self.items().len()
     }
 
     fn __repr__(&self) -> String {
-        format!("OverridesMap({} overrides)", self.overrides.len())
+        format!("OverridesMap({} overrides)", self.__len__())
     }
 }
 
 impl OverridesMap {
+    /// Set the clock used to decide which overrides have expired: the round
+    /// the tournament has reached and the time (default: now). Scoring never
+    /// moves it.
+    pub fn set_clock(&mut self, current_round: Option<Round>, timestamp: Option<i64>) {
+        self.current_round = current_round;
+        self.as_of = Some(timestamp.unwrap_or_else(unix_now));
+    }
+
     /// Get override without tracking (for internal Rust use)
     pub fn get(&self, name1: &str, name2: &str) -> Option<f64> {
+        if self.is_empty() {
+            // Skip building the lookup key; scoring asks for every pairing.
+            return None;
+        }
         self.get_override(name1, name2)
     }
+
+    /// `remove_override`, returning the probability of `name1` beating
+    /// `name2` that was in effect, or `None` (removing nothing) if no override
+    /// was. Only this layer's entry is removed.
+    pub fn take_override(&mut self, name1: &str, name2: &str) -> Option<f64> {
+        let prob = self.get_override(name1, name2)?;
+        self.remove_override(name1, name2);
+        Some(prob)
+    }
+
+    /// Whether no layer holds any probability override.
+    fn is_empty(&self) -> bool {
+        self.overrides.is_empty() && self.base.as_ref().is_none_or(|base| base.is_empty())
+    }
+
+    fn validate(&self, name1: &str, name2: &str, prob: f64) -> Result<f64> {
+        if (0.0..=1.0).contains(&prob) {
+            Ok(prob)
+        } else if self.clamp && !prob.is_nan() {
+            Ok(prob.clamp(0.0, 1.0))
+        } else {
+            Err(TourneyError::InvalidInput(format!(
+                "override probability for {name1} vs {name2} must be between 0 and 1, got {prob}"
+            )))
+        }
+    }
+
+    fn insert(&mut self, key: (String, String), prob: f64, meta: Option<OverrideMeta>) {
+        match meta {
+            Some(meta) => Arc::make_mut(&mut self.metadata).insert(key.clone(), meta),
+            None => Arc::make_mut(&mut self.metadata).remove(&key),
+        };
+        Arc::make_mut(&mut self.overrides).insert(key, prob);
+    }
+
+    /// `flatten` with every layer's expiry judged by one clock, as `lookup`
+    /// judges it, so the flat map answers the same as the layers.
+    fn flatten_at(&self, round: Option<Round>, as_of: Option<i64>) -> OverridesMap {
+        let mut flat = self.base.as_ref().map(|base| base.flatten_at(round, as_of)).unwrap_or_default();
+        let expired = |map: &OverridesMap, key| map.metadata.get(key).is_some_and(|meta| meta.is_expired(round, as_of));
+        for (key, &prob) in self.overrides.iter() {
+            if expired(self, key) && flat.overrides.contains_key(key) && !expired(&flat, key) {
+                continue;
+            }
+            flat.insert(key.clone(), prob, self.metadata.get(key).cloned());
+        }
+        Arc::make_mut(&mut flat.tempos).extend(self.tempos.iter().map(|(key, &tempo)| (key.clone(), tempo)));
+        flat
+    }
+
+    /// Active probability for a canonical key, falling through to the base layer.
+    fn lookup(&self, key: &(String, String), round: Option<Round>, as_of: Option<i64>) -> Option<f64> {
+        let expired = self.metadata.get(key).is_some_and(|meta| meta.is_expired(round, as_of));
+        self.overrides
+            .get(key)
+            .copied()
+            .filter(|_| !expired)
+            .or_else(|| self.base.as_ref().and_then(|base| base.lookup(key, round, as_of)))
+    }
+
+    fn key_expired(&self, key: &(String, String)) -> bool {
+        self.metadata
+            .get(key)
+            .is_some_and(|meta| meta.is_expired(self.current_round, self.as_of))
+    }
+
+    fn entries(&self, expired: bool) -> Vec<(String, String, f64)> {
+        let mut entries: Vec<_> = self
+            .overrides
+            .iter()
+            .filter(|(key, _)| self.key_expired(key) == expired)
+            .map(|((team1, team2), &prob)| (team1.clone(), team2.clone(), prob))
+            .collect();
+        entries.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        entries
+    }
+}
+
+/// Canonical (lexicographically ordered) key for a matchup.
+fn key(name1: &str, name2: &str) -> (String, String) {
+    if name1 < name2 {
+        (name1.to_string(), name2.to_string())
+    } else {
+        (name2.to_string(), name1.to_string())
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Serialized form of a single matchup's overrides: probability of `team1`
+/// beating `team2` and/or the pairing's tempo.
+#[derive(Serialize, Deserialize)]
+struct OverrideRecord {
+    team1: String,
+    team2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prob: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tempo: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    meta: Option<OverrideMeta>,
+}
+
+impl Serialize for OverridesMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let flat = self.flatten();
+        let mut keys: Vec<&(String, String)> = flat.overrides.keys().collect();
+        keys.extend(flat.tempos.keys().filter(|key| !flat.overrides.contains_key(*key)));
+        let mut records: Vec<OverrideRecord> = keys
+            .into_iter()
+            .map(|key| OverrideRecord {
+                team1: key.0.clone(),
+                team2: key.1.clone(),
+                prob: flat.overrides.get(key).copied(),
+                tempo: flat.tempos.get(key).copied(),
+                meta: flat.metadata.get(key).cloned(),
+            })
+            .collect();
+        records.sort_by(|a, b| (&a.team1, &a.team2).cmp(&(&b.team1, &b.team2)));
+        records.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OverridesMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let records = Vec::<OverrideRecord>::deserialize(deserializer)?;
+        let mut map = OverridesMap::new();
+        for record in records {
+            if let Some(prob) = record.prob {
+                match record.meta {
+                    Some(meta) => map.add_override_with_meta(&record.team1, &record.team2, prob, meta),
+                    None => map.add_override(&record.team1, &record.team2, prob),
+                }
+                .map_err(serde::de::Error::custom)?;
+            }
+            if let Some(tempo) = record.tempo {
+                map.set_tempo_override(&record.team1, &record.team2, tempo)
+                    .map_err(serde::de::Error::custom)?;
+            }
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dict_protocol() {
+        let mut overrides = OverridesMap::new();
+        let matchup = |a: &str, b: &str| (a.to_string(), b.to_string());
+        overrides.__setitem__(matchup("UNC", "Duke"), 0.75).unwrap();
+        assert!(overrides.__setitem__(matchup("UNC", "Duke"), 1.5).is_err());
+        assert!(overrides.__contains__(matchup("Duke", "UNC")));
+        assert_eq!(overrides.get_override("Duke", "UNC"), Some(0.25));
+        assert!(!overrides.__contains__(matchup("UNC", "Kansas")));
+    }
+
+    #[test]
+    fn test_dict_protocol_sees_live_layers() {
+        let matchup = |a: &str, b: &str| (a.to_string(), b.to_string());
+        let mut base = OverridesMap::new();
+        base.add_override("A", "B", 0.6).unwrap();
+        let mut overrides = OverridesMap::new();
+        let meta = OverrideMeta::new(None, None, None, Some(Round(0)), None);
+        overrides.add_override_with_meta("C", "D", 0.9, meta).unwrap();
+        overrides.add_override("A", "C", 0.3).unwrap();
+        overrides.set_base(base);
+        overrides.set_clock(Some(Round(1)), Some(0));
+
+        // The expired C-D entry is absent everywhere; the base A-B entry is present everywhere.
+        assert_eq!(overrides.__len__(), overrides.items().len());
+        assert_eq!(overrides.__len__(), 2);
+        assert!(overrides.__contains__(matchup("B", "A")));
+        assert!(!overrides.__contains__(matchup("C", "D")));
+        assert_eq!(overrides.take_override("C", "D"), None);
+
+        assert_eq!(overrides.take_override("C", "A"), Some(0.7));
+        assert_eq!(overrides.__len__(), 1);
+        assert_eq!(overrides.take_override("A", "C"), None);
+        // Deleting a base-layer matchup leaves the base entry in effect.
+        assert_eq!(overrides.take_override("A", "B"), Some(0.6));
+        assert_eq!(overrides.get("A", "B"), Some(0.6));
+    }
+
+    #[test]
+    fn test_round_expiry_hides_override() {
+        let mut overrides = OverridesMap::new();
+        let meta = OverrideMeta::new(Some("injury feed".to_string()), None, None, Some(Round(1)), None);
+        overrides.add_override_with_meta("Duke", "UNC", 0.3, meta).unwrap();
+        overrides.add_override("Duke", "Kansas", 0.6).unwrap();
+
+        overrides.set_clock(Some(Round(1)), Some(0));
+        assert_eq!(overrides.get("UNC", "Duke"), Some(0.7));
+
+        overrides.set_clock(Some(Round(2)), Some(0));
+        assert_eq!(overrides.get("Duke", "UNC"), None);
+        assert_eq!(overrides.active_overrides(), vec![("Duke".to_string(), "Kansas".to_string(), 0.6)]);
+        assert_eq!(overrides.expired_overrides().len(), 1);
+        assert_eq!(overrides.purge_expired(), 1);
+        assert_eq!(overrides.__len__(), 1);
+    }
+
+    #[test]
+    fn test_flatten_keeps_live_base_under_expired_layer() {
+        let mut base = OverridesMap::new();
+        base.add_override("Duke", "UNC", 0.6).unwrap();
+        let mut top = OverridesMap::new();
+        let meta = OverrideMeta::new(None, None, None, Some(Round(0)), None);
+        top.add_override_with_meta("Duke", "UNC", 0.9, meta.clone()).unwrap();
+        top.add_override_with_meta("Duke", "Kansas", 0.2, meta).unwrap();
+        top.set_base(base);
+        top.set_clock(Some(Round(1)), Some(0));
+
+        assert_eq!(top.get("Duke", "UNC"), Some(0.6));
+        let flat = top.flatten();
+        assert_eq!(flat.get("Duke", "UNC"), Some(0.6));
+        assert_eq!(flat.get("Duke", "Kansas"), None);
+        assert_eq!(top.active_overrides(), vec![("Duke".to_string(), "UNC".to_string(), 0.6)]);
+        assert_eq!(top.expired_overrides(), vec![("Duke".to_string(), "Kansas".to_string(), 0.2)]);
+    }
+
+    #[test]
+    fn test_clones_share_tables_until_written() {
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("A", "B", 0.8).unwrap();
+        let mut copy = overrides.clone();
+        assert!(Arc::ptr_eq(&overrides.overrides, &copy.overrides));
+
+        copy.add_override("C", "D", 0.4).unwrap();
+        assert!(!Arc::ptr_eq(&overrides.overrides, &copy.overrides));
+        assert_eq!(overrides.__len__(), 1);
+        assert_eq!(copy.__len__(), 2);
+    }
+
+    #[test]
+    fn test_date_expiry_and_serialized_meta() {
+        let mut overrides = OverridesMap::new();
+        let meta = OverrideMeta::new(None, Some(100), Some("ankle".to_string()), None, Some(500));
+        overrides.add_override_with_meta("A", "B", 0.8, meta.clone()).unwrap();
+
+        overrides.set_clock(None, Some(500));
+        assert!(overrides.has_override("A", "B"));
+        overrides.set_clock(None, Some(501));
+        assert!(!overrides.has_override("A", "B"));
+
+        let json = serde_json::to_string(&overrides).unwrap();
+        let restored: OverridesMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get_meta("B", "A"), Some(meta));
+        // Without a clock nothing is treated as expired.
+        assert_eq!(restored.get("A", "B"), Some(0.8));
+    }
+
+    #[test]
+    fn test_invalid_probabilities_rejected_or_clamped() {
+        let mut overrides = OverridesMap::new();
+        assert!(overrides.add_override("A", "B", 1.7).is_err());
+        assert!(overrides.add_override("A", "B", -0.2).is_err());
+        assert!(serde_json::from_str::<OverridesMap>(r#"[{"team1":"A","team2":"B","prob":2.0}]"#).is_err());
+
+        overrides.clamp = true;
+        overrides.add_override("B", "A", 1.7).unwrap();
+        assert_eq!(overrides.get("A", "B"), Some(0.0));
+        assert!(overrides.add_override("A", "B", f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_audit_flags_inconsistent_overrides() {
+        let ratings: HashMap<String, Team> = [("Strong", 0.15), ("Weak", -0.15), ("Mid", 0.0)]
+            .into_iter()
+            .map(|(name, off)| (name.to_string(), Team::new(name.to_string(), off, 0.0, 68.0, false)))
+            .collect();
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("Weak", "Strong", 0.9).unwrap();
+        overrides.add_override("Mid", "Strong", 0.3).unwrap();
+        overrides.add_override("Mid", "Unknown", 0.5).unwrap();
+
+        let flagged = overrides.audit(ratings, 0.25, 0.0);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!((flagged[0].team1.as_str(), flagged[0].team2.as_str()), ("Strong", "Weak"));
+        assert!(flagged[0].difference() < -0.25);
+    }
+
+    #[test]
+    fn test_from_spreads_uses_model_margin_conversion() {
+        let params = ModelParams::default();
+        let spreads = vec![
+            ("A".to_string(), "B".to_string(), 0.0),
+            ("D".to_string(), "C".to_string(), -params.scoring_stddev),
+        ];
+        let map = OverridesMap::from_spreads(spreads, None).unwrap();
+        assert_eq!(map.get("A", "B"), Some(0.5));
+        let favorite = map.get("D", "C").unwrap();
+        assert!((favorite - 0.841_344_746).abs() < 1e-6);
+        assert!((map.get("C", "D").unwrap() - (1.0 - favorite)).abs() < 1e-12);
+
+        // A wider margin distribution pulls the same line toward 50/50.
+        let wide = ModelParams::new(params.avg_scoring, params.avg_tempo, 2.0 * params.scoring_stddev);
+        let spreads = vec![("D".to_string(), "C".to_string(), -params.scoring_stddev)];
+        assert!(OverridesMap::from_spreads(spreads, Some(wide)).unwrap().get("D", "C").unwrap() < favorite);
+        assert!(OverridesMap::from_spreads(vec![("A".to_string(), "B".to_string(), f64::NAN)], None).is_err());
+    }
+
+    #[test]
+    fn test_tempo_overrides_layer_and_serialize() {
+        let mut base = OverridesMap::new();
+        base.set_tempo_override("A", "B", 58.0).unwrap();
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("A", "C", 0.7).unwrap();
+        overrides.set_tempo_override("C", "A", 61.0).unwrap();
+        overrides.set_base(base);
+        assert_eq!(overrides.get_tempo_override("B", "A"), Some(58.0));
+        assert!(overrides.set_tempo_override("A", "D", 0.0).is_err());
+
+        let json = serde_json::to_string(&overrides).unwrap();
+        let restored: OverridesMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.get_tempo_override("A", "C"), Some(61.0));
+        assert_eq!(restored.get_tempo_override("A", "B"), Some(58.0));
+        assert_eq!(restored.get("A", "B"), None);
+        assert_eq!(restored.get("A", "C"), Some(0.7));
+        assert_eq!(restored.tempo_overrides().len(), 2);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("UNC", "Duke", 0.25).unwrap();
+        overrides.add_override("Texas A&M", "St. Mary's, CA", 0.5).unwrap();
+
+        let text = overrides.to_csv(None).unwrap();
+        assert!(text.starts_with("Duke,UNC,0.75\n"));
+        let restored = OverridesMap::from_csv(&text).unwrap();
+        assert_eq!(restored.items(), overrides.items());
+        assert!(OverridesMap::from_csv("A,B,likely\n").is_err());
+        assert!(OverridesMap::from_csv("A,B\n").is_err());
+    }
+
+    #[test]
+    fn test_expired_overrides_left_out_of_items_csv_and_merge() {
+        let mut base = OverridesMap::new();
+        base.add_override("A", "C", 0.4).unwrap();
+        let mut overrides = OverridesMap::new();
+        let meta = OverrideMeta::new(None, None, None, Some(Round(0)), None);
+        overrides.add_override_with_meta("A", "B", 0.8, meta).unwrap();
+        overrides.add_override("B", "C", 0.3).unwrap();
+        overrides.set_base(base);
+        overrides.set_clock(Some(Round(1)), Some(0));
+
+        let live = vec![("A".to_string(), "C".to_string(), 0.4), ("B".to_string(), "C".to_string(), 0.3)];
+        assert_eq!(overrides.items(), live);
+        for (team1, team2, prob) in overrides.items() {
+            assert_eq!(overrides.get_override(&team1, &team2), Some(prob));
+        }
+        let restored = OverridesMap::from_csv(&overrides.to_csv(None).unwrap()).unwrap();
+        assert_eq!(restored.items(), live);
+
+        let merged = OverridesMap::new().merge(&overrides, "theirs").unwrap();
+        assert_eq!(merged.items(), live);
+        assert!(!merged.has_override("A", "B"));
+        assert_eq!(OverridesMap::new().diff(&overrides).added, live);
+    }
+
+    #[test]
+    fn test_layered_lookup_falls_through_to_base() {
+        let mut feed = OverridesMap::new();
+        feed.add_override("A", "B", 0.9).unwrap();
+        feed.add_override("C", "D", 0.2).unwrap();
+
+        let mut session = OverridesMap::new();
+        session.add_override("B", "A", 0.5).unwrap();
+        session.set_base(feed);
+
+        assert_eq!(session.get("A", "B"), Some(0.5));
+        assert_eq!(session.get("C", "D"), Some(0.2));
+        assert_eq!(session.active_overrides().len(), 2);
+        assert_eq!(session.__len__(), 2);
+
+        // Serializing flattens the layers.
+        let restored: OverridesMap = serde_json::from_str(&serde_json::to_string(&session).unwrap()).unwrap();
+        assert_eq!(restored.get("C", "D"), Some(0.2));
+        assert!(restored.base().is_none());
+    }
+
+    #[test]
+    fn test_merge_policies_and_diff() {
+        let mut ours = OverridesMap::new();
+        ours.add_override_with_meta("A", "B", 0.6, OverrideMeta::new(None, Some(10), None, None, None)).unwrap();
+        ours.add_override("A", "C", 0.7).unwrap();
+        let mut theirs = OverridesMap::new();
+        theirs.add_override_with_meta("A", "B", 0.8, OverrideMeta::new(None, Some(5), None, None, None)).unwrap();
+        theirs.add_override("B", "C", 0.4).unwrap();
+
+        assert_eq!(ours.merge(&theirs, "theirs").unwrap().get("A", "B"), Some(0.8));
+        assert_eq!(ours.merge(&theirs, "ours").unwrap().get("A", "B"), Some(0.6));
+        assert_eq!(ours.merge(&theirs, "newest").unwrap().get("A", "B"), Some(0.6));
+        assert!(ours.merge(&theirs, "error").is_err());
+        assert_eq!(ours.merge(&theirs, "ours").unwrap().__len__(), 3);
+
+        let diff = ours.diff(&theirs);
+        assert_eq!(diff.added, vec![("B".to_string(), "C".to_string(), 0.4)]);
+        assert_eq!(diff.removed, vec![("A".to_string(), "C".to_string(), 0.7)]);
+        assert_eq!(diff.changed, vec![("A".to_string(), "B".to_string(), 0.6, 0.8)]);
+        assert!(ours.diff(&ours).is_empty());
+    }
 }
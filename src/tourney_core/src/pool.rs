@@ -0,0 +1,225 @@
+//! Pool payout structures and equity calculations.
+//!
+//! Pools pay by finishing place rather than by raw points, so the quantity worth
+//! maximizing is expected winnings. These helpers convert per-simulation entry
+//! scores into expected payout per entry.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::entry::{round_points, BracketEntry};
+use crate::error::{Result, TourneyError};
+use crate::portfolio::get_portfolio_value_ref;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// Fraction of the pot paid to each finishing place (1st, 2nd, ...).
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Payouts {
+    #[pyo3(get)]
+    pub fractions: Vec<f64>,
+}
+
+#[pymethods]
+impl Payouts {
+    /// Create a payout table. Fractions must be non-negative and sum to at most 1.
+    #[cfg(feature = "python")]
+    #[new]
+    pub fn py_new(fractions: Vec<f64>) -> PyResult<Self> {
+        Ok(Payouts::new(fractions)?)
+    }
+
+    /// Winner-take-all payout.
+    #[staticmethod]
+    pub fn winner_take_all() -> Self {
+        Payouts { fractions: vec![1.0] }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Payouts({:?})", self.fractions)
+    }
+}
+
+impl Payouts {
+    pub fn new(fractions: Vec<f64>) -> Result<Self> {
+        if fractions.iter().any(|&f| !(0.0..=1.0).contains(&f)) {
+            return Err(TourneyError::InvalidInput(
+                "payout fractions must be between 0 and 1".to_string(),
+            ));
+        }
+        if fractions.iter().sum::<f64>() > 1.0 + 1e-9 {
+            return Err(TourneyError::InvalidInput(
+                "payout fractions must sum to at most 1".to_string(),
+            ));
+        }
+        Ok(Payouts { fractions })
+    }
+
+    /// Fraction paid to 0-based `place`.
+    pub fn fraction(&self, place: usize) -> f64 {
+        self.fractions.get(place).copied().unwrap_or(0.0)
+    }
+
+    /// Payout fraction for each entry given one set of final scores.
+    ///
+    /// Entries tied on score split the payouts for the places they jointly occupy.
+    pub fn settle(&self, scores: &[f64]) -> Vec<f64> {
+        let mut order: Vec<usize> = (0..scores.len()).collect();
+        order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+        let mut result = vec![0.0; scores.len()];
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && scores[order[end]] == scores[order[start]] {
+                end += 1;
+            }
+            let pooled: f64 = (start..end).map(|place| self.fraction(place)).sum();
+            let share = pooled / (end - start) as f64;
+            for &entry in &order[start..end] {
+                result[entry] = share;
+            }
+            start = end;
+        }
+        result
+    }
+}
+
+/// Expected winnings per entry from per-simulation scores.
+///
+/// `sim_scores[s][e]` is entry `e`'s score in simulation `s`.
+pub fn expected_equity(sim_scores: &[Vec<f64>], payouts: &Payouts, pot: f64) -> Vec<f64> {
+    let n_entries = sim_scores.first().map_or(0, Vec::len);
+    let mut equity = vec![0.0; n_entries];
+    if sim_scores.is_empty() {
+        return equity;
+    }
+    for scores in sim_scores {
+        for (total, share) in equity.iter_mut().zip(payouts.settle(scores)) {
+            *total += share;
+        }
+    }
+    let scale = pot / sim_scores.len() as f64;
+    equity.iter_mut().for_each(|e| *e *= scale);
+    equity
+}
+
+/// Expected winnings for portfolio-style entries, valued as positions × team points.
+pub fn portfolio_equity_ref(
+    entries: &[HashMap<String, f64>],
+    sims: &[HashMap<String, f64>],
+    payouts: &Payouts,
+    pot: f64,
+) -> Vec<f64> {
+    let sim_scores: Vec<Vec<f64>> = sims
+        .iter()
+        .map(|sim| entries.iter().map(|e| get_portfolio_value_ref(e, sim)).collect())
+        .collect();
+    expected_equity(&sim_scores, payouts, pot)
+}
+
+/// Expected pool winnings for each portfolio entry.
+///
+/// Simulates the tournament `n_simulations` times, ranks entries by portfolio
+/// value in each simulation, and averages the resulting payouts.
+#[pyfunction]
+#[pyo3(signature = (entries, tournament, payouts, pot = 1.0, n_simulations = 10000, seed = None))]
+pub fn pool_equity(
+    entries: Vec<HashMap<String, f64>>,
+    tournament: &TournamentState,
+    payouts: &Payouts,
+    pot: f64,
+    n_simulations: usize,
+    seed: Option<u64>,
+) -> Vec<f64> {
+    let sims = tournament.run_simulations(n_simulations, seed);
+    portfolio_equity_ref(&entries, &sims, payouts, pot)
+}
+
+/// Expected pool winnings for each of `entries` in a bracket pool that also
+/// contains `opponents` (e.g. a field from `sample_field`).
+///
+/// Entries score `scoring[r]` per correct round-`r` pick (default: the
+/// tournament's round points) in each of `n_simulations` simulations.
+#[pyfunction]
+#[pyo3(signature = (
+    entries, opponents, tournament, payouts, pot = 1.0, scoring = None, n_simulations = 10000, seed = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn bracket_pool_equity(
+    entries: Vec<BracketEntry>,
+    opponents: Vec<BracketEntry>,
+    tournament: &TournamentState,
+    payouts: &Payouts,
+    pot: f64,
+    scoring: Option<Vec<f64>>,
+    n_simulations: usize,
+    seed: Option<u64>,
+) -> Vec<f64> {
+    let points = round_points(tournament, scoring.as_deref());
+    let mut rng = seeded_rng(seed);
+    let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+    let sim_scores: Vec<Vec<f64>> = seeds
+        .par_iter()
+        .map(|&sim_seed| {
+            let winners = tournament.simulate_round_winners(Some(sim_seed));
+            entries.iter().chain(&opponents).map(|entry| entry.score(&winners, &points)).collect()
+        })
+        .collect();
+    let mut equity = expected_equity(&sim_scores, payouts, pot);
+    equity.truncate(entries.len());
+    equity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_splits_ties() {
+        let payouts = Payouts::new(vec![0.7, 0.2, 0.1]).unwrap();
+        let shares = payouts.settle(&[10.0, 30.0, 30.0, 5.0]);
+        assert!((shares[1] - 0.45).abs() < 1e-12);
+        assert!((shares[2] - 0.45).abs() < 1e-12);
+        assert!((shares[0] - 0.1).abs() < 1e-12);
+        assert_eq!(shares[3], 0.0);
+    }
+
+    #[test]
+    fn test_expected_equity_sums_to_pot() {
+        let payouts = Payouts::new(vec![0.7, 0.3]).unwrap();
+        let sims = vec![vec![3.0, 1.0, 2.0], vec![0.0, 5.0, 1.0]];
+        let equity = expected_equity(&sims, &payouts, 100.0);
+        assert!((equity.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+        assert!((equity[0] - 35.0).abs() < 1e-9);
+        assert!((equity[1] - 35.0).abs() < 1e-9);
+        assert!((equity[2] - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bracket_pool_equity_against_sampled_field() {
+        let state = crate::testing::canonical_tournament(16);
+        let chalk = crate::entry::optimize_entry(&state, None, None).unwrap();
+        let field = crate::field::sample_field(&state, HashMap::new(), 20, Some(1)).unwrap();
+        let payouts = Payouts::winner_take_all();
+
+        let equity =
+            bracket_pool_equity(vec![chalk.clone()], field.clone(), &state, &payouts, 21.0, None, 500, Some(3));
+        assert_eq!(equity.len(), 1);
+        // Against coin-flip opponents, the model's best entry beats a fair share.
+        assert!(equity[0] > 1.0);
+        let everyone: Vec<BracketEntry> = std::iter::once(chalk).chain(field).collect();
+        let all = bracket_pool_equity(everyone, Vec::new(), &state, &payouts, 21.0, None, 500, Some(3));
+        assert!((all.iter().sum::<f64>() - 21.0).abs() < 1e-9);
+        assert_eq!(all[0], equity[0]);
+    }
+
+    #[test]
+    fn test_invalid_payouts_rejected() {
+        assert!(Payouts::new(vec![0.8, 0.5]).is_err());
+        assert!(Payouts::new(vec![-0.1]).is_err());
+    }
+}
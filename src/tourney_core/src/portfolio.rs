@@ -1,12 +1,40 @@
+// pyo3 0.22's generated wrappers re-convert a `PyErr` returned as `PyResult`, which
+// trips this lint outside any item an attribute could reach; the module is the tightest scope.
+#![cfg_attr(feature = "python", allow(clippy::useless_conversion))]
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyKeyError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+use rand::Rng;
 use rayon::prelude::*;
+use serde::Serialize;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
-use crate::tournament::TournamentState;
+#[cfg(feature = "python")]
+use crate::awaitable::spawn_awaitable;
+use crate::costs::TransactionCosts;
+use crate::error::{Result, TourneyError};
+use crate::exposure::ExposureReport;
+use crate::propagate::AdvancementCache;
+use crate::reduce::keyed_sum;
+use crate::team::hash_f64;
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::trace::trace_span;
 
 /// Result of a game delta calculation.
-#[pyclass]
-#[derive(Clone, Debug)]
+///
+/// Deltas order by `total_delta` (ties broken by the remaining fields), so
+/// `sorted()` puts the biggest losses first. Floats compare with `total_cmp`,
+/// so equality agrees with the order: -0.0 sorts below 0.0 and a NaN equals
+/// itself.
+#[pyclass(frozen, eq, ord, hash)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TeamDelta {
     #[pyo3(get)]
     pub team: String,
@@ -19,20 +47,59 @@ pub struct TeamDelta {
 
     #[pyo3(get)]
     pub total_delta: f64,
+
+    /// The team's expected score if `matchup.0` wins, when known.
+    #[pyo3(get)]
+    pub win_score: Option<f64>,
+
+    /// The team's expected score if `matchup.1` wins, when known.
+    #[pyo3(get)]
+    pub loss_score: Option<f64>,
+
+    /// The `(team1, team2)` game this delta was computed for.
+    #[pyo3(get)]
+    pub matchup: Option<(String, String)>,
 }
 
 #[pymethods]
 impl TeamDelta {
     #[new]
-    pub fn new(team: String, position: f64, delta_per_share: f64) -> Self {
+    #[pyo3(signature = (team, position, delta_per_share, win_score = None, loss_score = None, matchup = None))]
+    pub fn new(
+        team: String,
+        position: f64,
+        delta_per_share: f64,
+        win_score: Option<f64>,
+        loss_score: Option<f64>,
+        matchup: Option<(String, String)>,
+    ) -> Self {
         TeamDelta {
             team,
             position,
             delta_per_share,
             total_delta: position * delta_per_share,
+            win_score,
+            loss_score,
+            matchup,
         }
     }
 
+    /// The delta as a plain dict, with `team1`/`team2` in place of `matchup`.
+    #[cfg(feature = "python")]
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("team", &self.team)?;
+        dict.set_item("position", self.position)?;
+        dict.set_item("delta_per_share", self.delta_per_share)?;
+        dict.set_item("total_delta", self.total_delta)?;
+        dict.set_item("win_score", self.win_score)?;
+        dict.set_item("loss_score", self.loss_score)?;
+        let (team1, team2) = self.matchup.clone().unzip();
+        dict.set_item("team1", team1)?;
+        dict.set_item("team2", team2)?;
+        Ok(dict)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "TeamDelta({}, position={}, delta_per_share={:.4}, total_delta={:.4})",
@@ -41,6 +108,279 @@ impl TeamDelta {
     }
 }
 
+impl TeamDelta {
+    /// The delta on `team` between `team1` and `team2` winning their game,
+    /// given its expected score under each outcome.
+    pub fn for_game(team: String, position: f64, win_score: f64, loss_score: f64, team1: &str, team2: &str) -> Self {
+        let matchup = Some((team1.to_string(), team2.to_string()));
+        TeamDelta::new(team, position, win_score - loss_score, Some(win_score), Some(loss_score), matchup)
+    }
+}
+
+impl PartialEq for TeamDelta {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TeamDelta {}
+
+impl Ord for TeamDelta {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_delta
+            .total_cmp(&other.total_delta)
+            .then_with(|| self.team.cmp(&other.team))
+            .then_with(|| self.position.total_cmp(&other.position))
+            .then_with(|| self.delta_per_share.total_cmp(&other.delta_per_share))
+            .then_with(|| self.matchup.cmp(&other.matchup))
+            .then_with(|| total_cmp_opt(self.win_score, other.win_score))
+            .then_with(|| total_cmp_opt(self.loss_score, other.loss_score))
+    }
+}
+
+/// `total_cmp` for optional floats, with `None` first.
+fn total_cmp_opt(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+impl PartialOrd for TeamDelta {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for TeamDelta {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.team.hash(state);
+        for value in [self.position, self.delta_per_share, self.total_delta] {
+            hash_f64(value, state);
+        }
+        for score in [self.win_score, self.loss_score] {
+            score.is_some().hash(state);
+            hash_f64(score.unwrap_or_default(), state);
+        }
+        self.matchup.hash(state);
+    }
+}
+
+/// Summary statistics of simulated portfolio values.
+#[pyclass]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RiskSummary {
+    #[pyo3(get)]
+    pub mean: f64,
+
+    #[pyo3(get)]
+    pub stddev: f64,
+
+    /// 5th percentile of value (downside tail).
+    #[pyo3(get)]
+    pub percentile_5: f64,
+
+    /// 95th percentile of value (upside tail).
+    #[pyo3(get)]
+    pub percentile_95: f64,
+}
+
+#[pymethods]
+impl RiskSummary {
+    fn __repr__(&self) -> String {
+        format!(
+            "RiskSummary(mean={:.4}, stddev={:.4}, p5={:.4}, p95={:.4})",
+            self.mean, self.stddev, self.percentile_5, self.percentile_95
+        )
+    }
+}
+
+impl RiskSummary {
+    /// Summarize a sample of portfolio values.
+    pub fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return RiskSummary::default();
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = if values.len() > 1 {
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        RiskSummary {
+            mean,
+            stddev: variance.sqrt(),
+            percentile_5: percentile(&sorted, 0.05),
+            percentile_95: percentile(&sorted, 0.95),
+        }
+    }
+}
+
+/// One position's share of a portfolio's expected value and risk.
+///
+/// Each field is the position's component of the matching `RiskSummary`
+/// statistic, so summing a field over every position recovers the
+/// portfolio's figure (`expected_value` sums to `PortfolioState::get_value`).
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct PositionAttribution {
+    #[pyo3(get)]
+    pub team: String,
+
+    #[pyo3(get)]
+    pub position: f64,
+
+    /// Position times the team's exact expected score.
+    #[pyo3(get)]
+    pub expected_value: f64,
+
+    /// Covariance of the position's value with the portfolio's.
+    #[pyo3(get)]
+    pub variance: f64,
+
+    /// Euler component of the standard deviation: `variance / stddev`.
+    #[pyo3(get)]
+    pub stddev: f64,
+
+    /// Component 5th percentile: the position's mean value in simulations
+    /// whose portfolio value ranks near the 5th percentile.
+    #[pyo3(get)]
+    pub percentile_5: f64,
+
+    /// Component 95th percentile, as `percentile_5`.
+    #[pyo3(get)]
+    pub percentile_95: f64,
+}
+
+#[pymethods]
+impl PositionAttribution {
+    fn __repr__(&self) -> String {
+        format!(
+            "PositionAttribution({}, position={}, ev={:.4}, variance={:.4}, p5={:.4})",
+            self.team, self.position, self.expected_value, self.variance, self.percentile_5
+        )
+    }
+}
+
+/// Simulation indices whose `values` rank within a window around quantile
+/// `q`: one percent of the sample on each side, and at least one.
+fn quantile_window(values: &[f64], q: f64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let center = (q * (values.len() - 1) as f64).round() as usize;
+    let half_width = (values.len() / 100).max(1);
+    order[center.saturating_sub(half_width)..(center + half_width + 1).min(values.len())].to_vec()
+}
+
+/// Effect of a hypothetical trade on a portfolio.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct TradePreview {
+    #[pyo3(get)]
+    pub team: String,
+
+    /// Shares bought (positive) or sold (negative).
+    #[pyo3(get)]
+    pub shares: f64,
+
+    #[pyo3(get)]
+    pub price: f64,
+
+    /// Cash paid for the trade (`shares * price`; negative for sales).
+    #[pyo3(get)]
+    pub cost: f64,
+
+    #[pyo3(get)]
+    pub value_before: f64,
+
+    #[pyo3(get)]
+    pub value_after: f64,
+
+    /// Expected profit of the trade: change in value less its cost.
+    #[pyo3(get)]
+    pub edge: f64,
+
+    /// Portfolio deltas after the trade for every team whose delta changes.
+    #[pyo3(get)]
+    pub team_deltas: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub risk_before: RiskSummary,
+
+    #[pyo3(get)]
+    pub risk_after: RiskSummary,
+}
+
+#[pymethods]
+impl TradePreview {
+    /// Change in value standard deviation caused by the trade.
+    #[getter]
+    pub fn stddev_change(&self) -> f64 {
+        self.risk_after.stddev - self.risk_before.stddev
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TradePreview({} {:+} @ {:.4}, edge={:.4}, stddev {:.4} -> {:.4})",
+            self.team, self.shares, self.price, self.edge, self.risk_before.stddev, self.risk_after.stddev
+        )
+    }
+}
+
+/// A team whose market price differs from its model value.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct Edge {
+    #[pyo3(get)]
+    pub team: String,
+
+    #[pyo3(get)]
+    pub price: f64,
+
+    /// Simulated mean of expected points or championship probability.
+    #[pyo3(get)]
+    pub model_value: f64,
+
+    /// `model_value - price`; positive means the team is cheap.
+    #[pyo3(get)]
+    pub edge: f64,
+
+    /// Lower bound of the 95% confidence interval on `model_value`.
+    #[pyo3(get)]
+    pub ci_low: f64,
+
+    /// Upper bound of the 95% confidence interval on `model_value`.
+    #[pyo3(get)]
+    pub ci_high: f64,
+}
+
+#[pymethods]
+impl Edge {
+    /// Whether the price lies outside the confidence interval.
+    #[getter]
+    pub fn significant(&self) -> bool {
+        self.price < self.ci_low || self.price > self.ci_high
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Edge({}, price={:.4}, model={:.4} [{:.4}, {:.4}], edge={:+.4})",
+            self.team, self.price, self.model_value, self.ci_low, self.ci_high, self.edge
+        )
+    }
+}
+
+/// Linearly interpolated quantile `q` of an ascending, non-empty sample.
+pub fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
 /// Calculate portfolio value given positions and team values.
 ///
 /// # Arguments
@@ -62,6 +402,19 @@ pub fn get_portfolio_value_ref(positions: &HashMap<String, f64>, values: &HashMa
         .sum()
 }
 
+/// Portfolio value, summed in a fixed order when `tournament` is deterministic.
+fn portfolio_value_for(
+    tournament: &TournamentState,
+    positions: &HashMap<String, f64>,
+    values: &HashMap<String, f64>,
+) -> f64 {
+    if tournament.deterministic {
+        keyed_sum(positions.iter().map(|(team, &shares)| (team, shares * values.get(team).unwrap_or(&0.0))))
+    } else {
+        get_portfolio_value_ref(positions, values)
+    }
+}
+
 /// Calculate the impact of a game outcome on portfolio value.
 ///
 /// Returns (win_value, loss_value, team_deltas) where:
@@ -71,7 +424,7 @@ pub fn get_portfolio_value_ref(positions: &HashMap<String, f64>, values: &HashMa
 ///
 /// # Arguments
 /// * `positions` - Map of team names to shares held
-/// * `tournament` - Tournament state (will be temporarily modified)
+/// * `tournament` - Tournament state
 /// * `team1` - First team in the matchup
 /// * `team2` - Second team in the matchup
 #[pyfunction]
@@ -81,28 +434,60 @@ pub fn game_delta(
     team1: &str,
     team2: &str,
 ) -> (f64, f64, Vec<TeamDelta>) {
-    // Calculate with team1 winning (100% probability)
-    let win_state = tournament.with_override(team1, team2, 1.0);
-    let win_scores = win_state.calculate_scores_prob();
-    let win_value = get_portfolio_value_ref(&positions, &win_scores);
+    game_delta_ref(&positions, tournament, team1, team2)
+}
 
-    // Calculate with team2 winning (team1 loses, 0% probability)
-    let loss_state = tournament.with_override(team1, team2, 0.0);
-    let loss_scores = loss_state.calculate_scores_prob();
-    let loss_value = get_portfolio_value_ref(&positions, &loss_scores);
+/// Internal version of `game_delta` that borrows the positions (for Rust callers)
+pub fn game_delta_ref(
+    positions: &HashMap<String, f64>,
+    tournament: &TournamentState,
+    team1: &str,
+    team2: &str,
+) -> (f64, f64, Vec<TeamDelta>) {
+    // Scores with team1 winning and with team2 winning
+    let (win_scores, loss_scores) = tournament.calculate_scores_given_game(team1, team2);
+    let win_value = portfolio_value_for(tournament, positions, &win_scores);
+    let loss_value = portfolio_value_for(tournament, positions, &loss_scores);
 
     // Calculate per-team deltas
     let mut team_deltas = Vec::new();
-    for (team, &shares) in &positions {
-        let win_score = win_scores.get(team).unwrap_or(&0.0);
-        let loss_score = loss_scores.get(team).unwrap_or(&0.0);
-        let delta_per_share = win_score - loss_score;
-        team_deltas.push(TeamDelta::new(team.clone(), shares, delta_per_share));
+    for (team, &shares) in positions {
+        let win_score = win_scores.get(team).copied().unwrap_or(0.0);
+        let loss_score = loss_scores.get(team).copied().unwrap_or(0.0);
+        team_deltas.push(TeamDelta::for_game(team.clone(), shares, win_score, loss_score, team1, team2));
     }
 
     (win_value, loss_value, team_deltas)
 }
 
+/// Override probabilities `game_delta_curve` evaluates by default.
+pub const DEFAULT_PROB_GRID: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+/// Portfolio value as a function of the probability that `team1` beats
+/// `team2`, evaluated at each of `probs` (default: `DEFAULT_PROB_GRID`).
+///
+/// Returns `(prob, value)` pairs in the order given. `game_delta` is the two
+/// endpoints of this curve.
+#[pyfunction]
+#[pyo3(signature = (positions, tournament, team1, team2, probs = None))]
+pub fn game_delta_curve(
+    positions: HashMap<String, f64>,
+    tournament: &TournamentState,
+    team1: &str,
+    team2: &str,
+    probs: Option<Vec<f64>>,
+) -> Result<Vec<(f64, f64)>> {
+    let probs = probs.unwrap_or_else(|| DEFAULT_PROB_GRID.to_vec());
+    probs
+        .par_iter()
+        .map(|&prob| {
+            let state = tournament.with_override(team1, team2, prob)?;
+            let scores = state.calculate_scores_prob();
+            Ok((prob, portfolio_value_for(&state, &positions, &scores)))
+        })
+        .collect()
+}
+
 /// Calculate the impact of a team's rating change on tournament scores.
 ///
 /// Returns (positive_scores, negative_scores) where:
@@ -120,15 +505,26 @@ pub fn get_team_delta(
     team: &str,
     point_delta: f64,
 ) -> (HashMap<String, f64>, HashMap<String, f64>) {
-    // Calculate with improved rating
-    let positive_state = tournament.with_team_adjustment(team, point_delta);
-    let positive_scores = positive_state.calculate_scores_prob();
-
-    // Calculate with worsened rating
-    let negative_state = tournament.with_team_adjustment(team, -point_delta);
-    let negative_scores = negative_state.calculate_scores_prob();
+    adjusted_scores(tournament, AdvancementCache::new(tournament).as_ref(), team, point_delta)
+}
 
-    (positive_scores, negative_scores)
+/// Scores with `team`'s rating improved and worsened by `point_delta`,
+/// replaying only the team's path through `cache` when there is one.
+fn adjusted_scores(
+    tournament: &TournamentState,
+    cache: Option<&AdvancementCache>,
+    team: &str,
+    point_delta: f64,
+) -> (HashMap<String, f64>, HashMap<String, f64>) {
+    let index = tournament.bracket_teams().iter().position(|other| other == team);
+    let scores = |delta: f64| match (cache, index) {
+        (Some(cache), Some(index)) => {
+            let rating = tournament.ratings[team].with_adjustment(delta);
+            tournament.scores_from_advancement(&cache.with_rating(index, &rating))
+        }
+        _ => tournament.with_team_adjustment(team, delta).calculate_scores_prob(),
+    };
+    (scores(point_delta), scores(-point_delta))
 }
 
 /// Calculate portfolio delta for a team's rating change.
@@ -141,11 +537,43 @@ pub fn get_team_portfolio_delta(
     point_delta: f64,
 ) -> f64 {
     let (positive_scores, negative_scores) = get_team_delta(tournament, team, point_delta);
-    let positive_value = get_portfolio_value_ref(&positions, &positive_scores);
-    let negative_value = get_portfolio_value_ref(&positions, &negative_scores);
+    let positive_value = portfolio_value_for(tournament, &positions, &positive_scores);
+    let negative_value = portfolio_value_for(tournament, &positions, &negative_scores);
     positive_value - negative_value
 }
 
+/// Calculate the impact of moving a group of teams' ratings together.
+///
+/// Model error is often shared (e.g. a whole conference is overrated), and
+/// adjusting the teams at once captures their effect on each other's paths,
+/// which summing single-team deltas misses. Returns (positive_scores,
+/// negative_scores) like `get_team_delta`.
+#[pyfunction]
+#[pyo3(signature = (tournament, teams, point_delta = 1.0))]
+pub fn get_group_delta(
+    tournament: &TournamentState,
+    teams: Vec<String>,
+    point_delta: f64,
+) -> (HashMap<String, f64>, HashMap<String, f64>) {
+    let positive_scores = tournament.with_group_adjustment(teams.clone(), point_delta).calculate_scores_prob();
+    let negative_scores = tournament.with_group_adjustment(teams, -point_delta).calculate_scores_prob();
+    (positive_scores, negative_scores)
+}
+
+/// Calculate portfolio delta for a group of teams' joint rating change.
+#[pyfunction]
+#[pyo3(signature = (positions, tournament, teams, point_delta = 1.0))]
+pub fn get_group_portfolio_delta(
+    positions: HashMap<String, f64>,
+    tournament: &TournamentState,
+    teams: Vec<String>,
+    point_delta: f64,
+) -> f64 {
+    let (positive_scores, negative_scores) = get_group_delta(tournament, teams, point_delta);
+    portfolio_value_for(tournament, &positions, &positive_scores)
+        - portfolio_value_for(tournament, &positions, &negative_scores)
+}
+
 /// Calculate pairwise deltas for a team's rating change.
 ///
 /// Returns a map of team names to their value change when the specified team's rating changes.
@@ -159,14 +587,99 @@ pub fn get_team_pairwise_deltas(
     let (positive_scores, negative_scores) = get_team_delta(tournament, team, point_delta);
 
     let mut deltas = HashMap::new();
-    for team_name in tournament.get_bracket_teams() {
-        let pos = positive_scores.get(&team_name).unwrap_or(&0.0);
-        let neg = negative_scores.get(&team_name).unwrap_or(&0.0);
-        deltas.insert(team_name, pos - neg);
+    for team_name in tournament.bracket_teams() {
+        let pos = positive_scores.get(team_name).unwrap_or(&0.0);
+        let neg = negative_scores.get(team_name).unwrap_or(&0.0);
+        deltas.insert(team_name.clone(), pos - neg);
     }
     deltas
 }
 
+/// Portfolio and pairwise deltas for every bracket team, indexed densely.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct DeltaMatrix {
+    /// Bracket teams; row and column `i` of the matrices is `teams[i]`.
+    #[pyo3(get)]
+    pub teams: Vec<String>,
+
+    /// `team_deltas[i]`: portfolio delta for a rating change to `teams[i]`.
+    #[pyo3(get)]
+    pub team_deltas: Vec<f64>,
+
+    /// `pairwise[i][j]`: change in `teams[j]`'s expected score for a rating
+    /// change to `teams[i]`.
+    #[pyo3(get)]
+    pub pairwise: Vec<Vec<f64>>,
+}
+
+#[pymethods]
+impl DeltaMatrix {
+    fn __repr__(&self) -> String {
+        format!("DeltaMatrix({} teams)", self.teams.len())
+    }
+}
+
+impl DeltaMatrix {
+    /// The keyed form returned by `get_all_team_deltas`.
+    pub fn into_maps(self) -> (HashMap<String, f64>, HashMap<String, HashMap<String, f64>>) {
+        let team_deltas = self.teams.iter().cloned().zip(self.team_deltas).collect();
+        let pairwise_deltas = self
+            .teams
+            .iter()
+            .zip(self.pairwise)
+            .map(|(team, row)| (team.clone(), self.teams.iter().cloned().zip(row).collect()))
+            .collect();
+        (team_deltas, pairwise_deltas)
+    }
+}
+
+/// Dense portfolio and pairwise deltas for every bracket team.
+///
+/// The team list is built once and every per-team row is indexed by it, so
+/// the parallel work is two scorings per team plus a linear pass. When the
+/// bracket propagates exactly, the unadjusted bracket is played once and each
+/// scoring replays only the adjusted team's path (`AdvancementCache`).
+#[pyfunction]
+#[pyo3(signature = (positions, tournament, point_delta = 1.0))]
+pub fn get_delta_matrix(
+    positions: HashMap<String, f64>,
+    tournament: &TournamentState,
+    point_delta: f64,
+) -> DeltaMatrix {
+    delta_matrix(&positions, tournament, point_delta)
+}
+
+/// `get_delta_matrix` for Rust callers.
+pub fn delta_matrix(positions: &HashMap<String, f64>, tournament: &TournamentState, point_delta: f64) -> DeltaMatrix {
+    let teams = tournament.get_bracket_teams();
+    trace_span!(INFO, "delta_matrix", n_teams = teams.len(), n_positions = positions.len());
+
+    let cache = AdvancementCache::new(tournament);
+    let rows: Vec<(f64, Vec<f64>)> = teams
+        .par_iter()
+        .map(|team| {
+            let (positive_scores, negative_scores) = adjusted_scores(tournament, cache.as_ref(), team, point_delta);
+            let portfolio_delta = portfolio_value_for(tournament, positions, &positive_scores)
+                - portfolio_value_for(tournament, positions, &negative_scores);
+            let pairwise = teams
+                .iter()
+                .map(|other| {
+                    positive_scores.get(other).unwrap_or(&0.0) - negative_scores.get(other).unwrap_or(&0.0)
+                })
+                .collect();
+            (portfolio_delta, pairwise)
+        })
+        .collect();
+
+    let (team_deltas, pairwise) = rows.into_iter().unzip();
+    DeltaMatrix {
+        teams,
+        team_deltas,
+        pairwise,
+    }
+}
+
 /// Calculate deltas for all teams in the bracket.
 ///
 /// Uses parallel processing for better performance.
@@ -186,52 +699,240 @@ pub fn get_all_team_deltas(
     tournament: &TournamentState,
     point_delta: f64,
 ) -> (HashMap<String, f64>, HashMap<String, HashMap<String, f64>>) {
-    let teams = tournament.get_bracket_teams();
+    get_all_team_deltas_ref(&positions, tournament, point_delta)
+}
 
-    // Parallel computation over teams
-    let results: Vec<_> = teams
-        .par_iter()
-        .map(|team| {
-            let (positive_scores, negative_scores) = get_team_delta(tournament, team, point_delta);
-
-            // Calculate portfolio delta
-            let positive_value = get_portfolio_value_ref(&positions, &positive_scores);
-            let negative_value = get_portfolio_value_ref(&positions, &negative_scores);
-            let portfolio_delta = positive_value - negative_value;
-
-            // Calculate pairwise deltas
-            let mut pairwise = HashMap::new();
-            for team_name in tournament.get_bracket_teams() {
-                let pos = positive_scores.get(&team_name).unwrap_or(&0.0);
-                let neg = negative_scores.get(&team_name).unwrap_or(&0.0);
-                pairwise.insert(team_name, pos - neg);
-            }
+/// Internal version of `get_all_team_deltas` that borrows the positions (for
+/// Rust callers)
+pub fn get_all_team_deltas_ref(
+    positions: &HashMap<String, f64>,
+    tournament: &TournamentState,
+    point_delta: f64,
+) -> (HashMap<String, f64>, HashMap<String, HashMap<String, f64>>) {
+    trace_span!(INFO, "get_all_team_deltas", n_positions = positions.len());
+    delta_matrix(positions, tournament, point_delta).into_maps()
+}
+
+/// Rank teams by the gap between model value and market price.
+///
+/// `basis` selects what prices are quoted in: `"points"` for expected points or
+/// `"championship"` for probability of winning the title. Model values and
+/// their 95% confidence intervals come from `n_simulations` simulations.
+/// Teams with an absolute edge below `min_edge` are dropped; the rest are
+/// sorted by absolute edge, largest first.
+#[pyfunction]
+#[pyo3(signature = (tournament, market_prices, min_edge = 0.0, basis = "points", n_simulations = 10000, seed = None))]
+pub fn find_edges(
+    tournament: &TournamentState,
+    market_prices: HashMap<String, f64>,
+    min_edge: f64,
+    basis: &str,
+    n_simulations: usize,
+    seed: Option<u64>,
+) -> Result<Vec<Edge>> {
+    let samples: Vec<HashMap<String, f64>> = match basis {
+        "points" => tournament.run_simulations(n_simulations, seed),
+        "championship" => {
+            let mut rng = seeded_rng(seed);
+            let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+            seeds
+                .par_iter()
+                .map(|&sim_seed| {
+                    let champion = tournament.simulate_champion(Some(sim_seed));
+                    champion.into_iter().map(|team| (team, 1.0)).collect()
+                })
+                .collect()
+        }
+        other => {
+            return Err(TourneyError::InvalidInput(format!(
+                "unknown basis {other:?}; expected \"points\" or \"championship\""
+            )))
+        }
+    };
 
-            (team.clone(), portfolio_delta, pairwise)
+    let n = samples.len().max(1) as f64;
+    let mut edges: Vec<Edge> = market_prices
+        .into_iter()
+        .map(|(team, price)| {
+            let values: Vec<f64> = samples.iter().map(|sim| sim.get(&team).copied().unwrap_or(0.0)).collect();
+            let summary = RiskSummary::from_values(&values);
+            let half_width = 1.96 * summary.stddev / n.sqrt();
+            Edge {
+                edge: summary.mean - price,
+                model_value: summary.mean,
+                ci_low: summary.mean - half_width,
+                ci_high: summary.mean + half_width,
+                team,
+                price,
+            }
         })
+        .filter(|e| e.edge.abs() >= min_edge)
         .collect();
+    edges.sort_by(|a, b| b.edge.abs().total_cmp(&a.edge.abs()).then_with(|| a.team.cmp(&b.team)));
+    Ok(edges)
+}
+
+/// Trades proposed by `rebalance_to_target`.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct RebalancePlan {
+    /// Net shares to trade per team (negative to sell).
+    #[pyo3(get)]
+    pub trades: HashMap<String, f64>,
 
-    // Collect results
-    let mut team_deltas = HashMap::new();
-    let mut pairwise_deltas = HashMap::new();
+    /// Sum of `|shares| * price` over every lot traded.
+    #[pyo3(get)]
+    pub turnover: f64,
 
-    for (team, portfolio_delta, pairwise) in results {
-        team_deltas.insert(team.clone(), portfolio_delta);
-        pairwise_deltas.insert(team, pairwise);
+    /// Transaction costs of the net trades, on top of their notional.
+    #[pyo3(get)]
+    pub costs: f64,
+
+    /// Portfolio deltas after the trades for every targeted team.
+    #[pyo3(get)]
+    pub team_deltas: HashMap<String, f64>,
+
+    /// Whether every targeted delta ended up inside its band.
+    #[pyo3(get)]
+    pub within_bands: bool,
+}
+
+#[pymethods]
+impl RebalancePlan {
+    fn __repr__(&self) -> String {
+        format!(
+            "RebalancePlan({} trades, turnover={:.4}, costs={:.4}, within_bands={})",
+            self.trades.len(),
+            self.turnover,
+            self.costs,
+            self.within_bands
+        )
+    }
+}
+
+/// Most lots `rebalance_to_target` will trade before giving up.
+const MAX_REBALANCE_LOTS: usize = 10_000;
+
+/// Trades bringing each team's portfolio delta into its `target_deltas` band
+/// `(low, high)`, trading only teams in `prices` in multiples of `lot_size`
+/// and spending at most `max_turnover` (default: unlimited).
+///
+/// Deltas are linear in positions, so each lot's effect comes from the
+/// pairwise deltas. Lots are added greedily, each time taking the one that
+/// removes the most band violation per unit of turnover plus `costs`
+/// (default: free), until every band is met, the budget is spent, or no lot
+/// helps. A team's fixed cost is paid by its first lot.
+#[pyfunction]
+#[pyo3(signature = (
+    positions,
+    tournament,
+    target_deltas,
+    prices,
+    lot_size = 1.0,
+    max_turnover = None,
+    point_delta = 1.0,
+    costs = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn rebalance_to_target(
+    positions: HashMap<String, f64>,
+    tournament: &TournamentState,
+    target_deltas: HashMap<String, (f64, f64)>,
+    prices: HashMap<String, f64>,
+    lot_size: f64,
+    max_turnover: Option<f64>,
+    point_delta: f64,
+    costs: Option<TransactionCosts>,
+) -> Result<RebalancePlan> {
+    let costs = costs.unwrap_or_default();
+    if lot_size.is_nan() || lot_size <= 0.0 {
+        return Err(TourneyError::InvalidInput(format!("lot_size must be positive, got {lot_size}")));
+    }
+    if let Some((team, _)) = target_deltas.iter().find(|(_, (low, high))| low > high) {
+        return Err(TourneyError::InvalidInput(format!("delta band for {team} is empty")));
+    }
+    if let Some((team, price)) = prices.iter().find(|(_, &price)| price.is_nan() || price <= 0.0) {
+        return Err(TourneyError::InvalidInput(format!("price for {team} must be positive, got {price}")));
+    }
+
+    let (team_deltas, pairwise_deltas) = get_all_team_deltas_ref(&positions, tournament, point_delta);
+    let mut targets: Vec<(&String, f64, f64)> =
+        target_deltas.iter().map(|(team, &(low, high))| (team, low, high)).collect();
+    targets.sort_by(|a, b| a.0.cmp(b.0));
+    let mut tradeable: Vec<(&String, f64)> = prices.iter().map(|(team, &price)| (team, price)).collect();
+    tradeable.sort_by(|a, b| a.0.cmp(b.0));
+
+    // effect[j][i]: change in target i's delta per share of tradeable team j.
+    let effect: Vec<Vec<f64>> = tradeable
+        .iter()
+        .map(|(traded, _)| {
+            targets
+                .iter()
+                .map(|(target, _, _)| pairwise_deltas.get(*target).and_then(|p| p.get(*traded)).copied().unwrap_or(0.0))
+                .collect()
+        })
+        .collect();
+    let mut deltas: Vec<f64> =
+        targets.iter().map(|(team, _, _)| team_deltas.get(*team).copied().unwrap_or(0.0)).collect();
+    let violation = |deltas: &[f64]| -> f64 {
+        deltas.iter().zip(&targets).map(|(&d, &(_, low, high))| (low - d).max(0.0) + (d - high).max(0.0)).sum()
+    };
+
+    let budget = max_turnover.unwrap_or(f64::INFINITY);
+    let mut trades: HashMap<String, f64> = HashMap::new();
+    let mut turnover = 0.0;
+    for _ in 0..MAX_REBALANCE_LOTS {
+        let current = violation(&deltas);
+        if current <= 0.0 {
+            break;
+        }
+        let best = tradeable
+            .iter()
+            .zip(&effect)
+            .filter(|((_, price), _)| turnover + lot_size * price <= budget)
+            .flat_map(|(&(team, price), effect)| [1.0, -1.0].map(|side| (team, price, effect, side * lot_size)))
+            .map(|(team, price, effect, shares)| {
+                let after: Vec<f64> = deltas.iter().zip(effect).map(|(d, e)| d + shares * e).collect();
+                let traded = trades.get(team).copied().unwrap_or(0.0);
+                let friction = costs.cost(traded + shares, price) - costs.cost(traded, price);
+                let spent = (lot_size * price + friction).max(f64::EPSILON);
+                ((current - violation(&after)) / spent, team, price, shares, after)
+            })
+            .filter(|(gain, ..)| *gain > 1e-12)
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        let Some((_, team, price, shares, after)) = best else { break };
+        *trades.entry(team.clone()).or_insert(0.0) += shares;
+        turnover += lot_size * price;
+        deltas = after;
     }
+    trades.retain(|_, shares| *shares != 0.0);
+    let total_costs = trades.iter().map(|(team, &shares)| costs.cost(shares, prices[team])).sum();
 
-    (team_deltas, pairwise_deltas)
+    Ok(RebalancePlan {
+        within_bands: violation(&deltas) <= 0.0,
+        team_deltas: targets.iter().zip(&deltas).map(|((team, _, _), &d)| ((*team).clone(), d)).collect(),
+        trades,
+        turnover,
+        costs: total_costs,
+    })
 }
 
+/// Simulations keyed by the `(n_simulations, seed)` that drew them.
+type Draws = ((usize, Option<u64>), Arc<Vec<HashMap<String, f64>>>);
+
 /// Portfolio state with precomputed deltas.
+///
+/// Expected scores and the last simulations drawn are cached for valuing and
+/// previewing trades. The tournament and positions are read-only from Python;
+/// in Rust, `tournament_mut` and `positions_mut` drop the cache.
 #[pyclass]
 #[derive(Clone)]
 pub struct PortfolioState {
     #[pyo3(get)]
-    pub tournament: TournamentState,
+    tournament: TournamentState,
 
     #[pyo3(get)]
-    pub positions: HashMap<String, f64>,
+    positions: HashMap<String, f64>,
 
     #[pyo3(get)]
     pub team_deltas: HashMap<String, f64>,
@@ -241,6 +942,60 @@ pub struct PortfolioState {
 
     #[pyo3(get)]
     pub point_delta: f64,
+
+    /// `tournament.calculate_scores_prob()`, computed on first use.
+    scores: OnceLock<HashMap<String, f64>>,
+
+    /// The simulations last drawn; clones share them until either changes.
+    simulations: Arc<Mutex<Option<Draws>>>,
+}
+
+impl PortfolioState {
+    pub fn tournament(&self) -> &TournamentState {
+        &self.tournament
+    }
+
+    pub fn positions(&self) -> &HashMap<String, f64> {
+        &self.positions
+    }
+
+    /// Mutable access to the tournament. Cached scores and simulations are
+    /// dropped; stored deltas are left for the caller to recompute.
+    pub fn tournament_mut(&mut self) -> &mut TournamentState {
+        self.clear_cache();
+        &mut self.tournament
+    }
+
+    /// Mutable access to the positions, clearing the cache like
+    /// `tournament_mut`.
+    pub fn positions_mut(&mut self) -> &mut HashMap<String, f64> {
+        self.clear_cache();
+        &mut self.positions
+    }
+
+    fn clear_cache(&mut self) {
+        self.scores = OnceLock::new();
+        self.simulations = Arc::default();
+    }
+
+    fn scores(&self) -> &HashMap<String, f64> {
+        self.scores.get_or_init(|| self.tournament.calculate_scores_prob())
+    }
+
+    /// `tournament.run_simulations(n_simulations, seed)`, reused while the
+    /// same pair is asked for again. That includes `seed = None`: repeated
+    /// calls see the same draws rather than fresh ones.
+    fn simulations(&self, n_simulations: usize, seed: Option<u64>) -> Arc<Vec<HashMap<String, f64>>> {
+        let mut cached = self.simulations.lock().unwrap_or_else(PoisonError::into_inner);
+        match cached.as_ref() {
+            Some((key, sims)) if *key == (n_simulations, seed) => Arc::clone(sims),
+            _ => {
+                let sims = Arc::new(self.tournament.run_simulations(n_simulations, seed));
+                *cached = Some(((n_simulations, seed), Arc::clone(&sims)));
+                sims
+            }
+        }
+    }
 }
 
 #[pymethods]
@@ -254,28 +1009,213 @@ impl PortfolioState {
             team_deltas: HashMap::new(),
             pairwise_deltas: HashMap::new(),
             point_delta,
+            scores: OnceLock::new(),
+            simulations: Arc::default(),
         }
     }
 
     /// Compute deltas for all teams.
     pub fn compute_deltas(&mut self) {
         let (team_deltas, pairwise_deltas) =
-            get_all_team_deltas(self.positions.clone(), &self.tournament, self.point_delta);
+            get_all_team_deltas_ref(&self.positions, &self.tournament, self.point_delta);
         self.team_deltas = team_deltas;
         self.pairwise_deltas = pairwise_deltas;
     }
 
+    /// Awaitable variant of `compute_deltas` for asyncio callers.
+    ///
+    /// Deltas are computed on the background thread pool and stored on this
+    /// state once the returned future resolves.
+    #[cfg(feature = "python")]
+    pub fn compute_deltas_async(slf: Py<Self>, py: Python<'_>) -> PyResult<PyObject> {
+        let (positions, tournament, point_delta) = {
+            let this = slf.borrow(py);
+            (this.positions.clone(), this.tournament.clone(), this.point_delta)
+        };
+        spawn_awaitable(
+            py,
+            move || get_all_team_deltas_ref(&positions, &tournament, point_delta),
+            move |py, (team_deltas, pairwise_deltas)| {
+                let mut this = slf.try_borrow_mut(py)?;
+                this.team_deltas = team_deltas;
+                this.pairwise_deltas = pairwise_deltas;
+                Ok(py.None())
+            },
+        )
+    }
+
     /// Get the current portfolio value.
     pub fn get_value(&self) -> f64 {
-        let scores = self.tournament.calculate_scores_prob();
-        get_portfolio_value_ref(&self.positions, &scores)
+        portfolio_value_for(&self.tournament, &self.positions, self.scores())
+    }
+
+    /// Distribution of portfolio value over `n_simulations` simulated tournaments.
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn risk(&self, n_simulations: usize, seed: Option<u64>) -> RiskSummary {
+        let values: Vec<f64> = self
+            .simulations(n_simulations, seed)
+            .iter()
+            .map(|sim| get_portfolio_value_ref(&self.positions, sim))
+            .collect();
+        RiskSummary::from_values(&values)
+    }
+
+    /// Each position's contribution to expected value, variance, standard
+    /// deviation, and the 5th/95th value percentiles over `n_simulations`
+    /// simulated tournaments, largest variance contribution first.
+    ///
+    /// Percentile components are component VaR: a position's average value in
+    /// the simulations ranked around that percentile. They sum to the average
+    /// portfolio value over that window, which approaches the percentile as
+    /// `n_simulations` grows.
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn value_attribution(&self, n_simulations: usize, seed: Option<u64>) -> Vec<PositionAttribution> {
+        let scores = self.scores();
+        let mut teams: Vec<(&String, f64)> = self.positions.iter().map(|(team, &shares)| (team, shares)).collect();
+        teams.sort_by(|a, b| a.0.cmp(b.0));
+
+        let sims = self.simulations(n_simulations, seed);
+        // held[i][s]: position i's value in simulation s.
+        let held: Vec<Vec<f64>> = teams
+            .iter()
+            .map(|&(team, shares)| sims.iter().map(|sim| shares * sim.get(team).copied().unwrap_or(0.0)).collect())
+            .collect();
+        let totals: Vec<f64> = (0..sims.len()).map(|s| held.iter().map(|values| values[s]).sum()).collect();
+        let n = totals.len() as f64;
+        let mean = |values: &[f64]| if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / n };
+        let total_mean = mean(&totals);
+        let total_stddev = RiskSummary::from_values(&totals).stddev;
+        let (low, high) = if totals.is_empty() {
+            (Vec::new(), Vec::new())
+        } else {
+            (quantile_window(&totals, 0.05), quantile_window(&totals, 0.95))
+        };
+        let window_mean = |values: &[f64], window: &[usize]| {
+            window.iter().map(|&s| values[s]).sum::<f64>() / window.len().max(1) as f64
+        };
+
+        let mut attribution: Vec<PositionAttribution> = teams
+            .iter()
+            .zip(&held)
+            .map(|(&(team, shares), values)| {
+                let position_mean = mean(values);
+                let variance = if totals.len() > 1 {
+                    values.iter().zip(&totals).map(|(v, t)| (v - position_mean) * (t - total_mean)).sum::<f64>()
+                        / (n - 1.0)
+                } else {
+                    0.0
+                };
+                PositionAttribution {
+                    team: team.clone(),
+                    position: shares,
+                    expected_value: shares * scores.get(team).copied().unwrap_or(0.0),
+                    variance,
+                    stddev: if total_stddev > 0.0 { variance / total_stddev } else { 0.0 },
+                    percentile_5: window_mean(values, &low),
+                    percentile_95: window_mean(values, &high),
+                }
+            })
+            .collect();
+        attribution.sort_by(|a, b| b.variance.total_cmp(&a.variance));
+        attribution
+    }
+
+    /// Preview buying `shares` of `team` at `price` without changing this state.
+    ///
+    /// New deltas come from the cached pairwise deltas (computed first if
+    /// missing), since a team's portfolio delta is linear in positions. Risk
+    /// before and after is measured on the same `n_simulations` simulations,
+    /// which are kept with the expected scores so previewing more trades
+    /// against an unchanged state reuses both.
+    #[pyo3(signature = (team, shares, price, n_simulations = 1000, seed = None))]
+    pub fn preview_trade(
+        &self,
+        team: &str,
+        shares: f64,
+        price: f64,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> TradePreview {
+        let computed;
+        let (current_deltas, pairwise_deltas) = if self.pairwise_deltas.is_empty() {
+            computed = get_all_team_deltas_ref(&self.positions, &self.tournament, self.point_delta);
+            (&computed.0, &computed.1)
+        } else {
+            (&self.team_deltas, &self.pairwise_deltas)
+        };
+
+        let scores = self.scores();
+        let value_before = portfolio_value_for(&self.tournament, &self.positions, scores);
+        let value_change = shares * scores.get(team).copied().unwrap_or(0.0);
+
+        let team_deltas = pairwise_deltas
+            .iter()
+            .filter_map(|(other, pairwise)| {
+                let change = shares * pairwise.get(team).copied().unwrap_or(0.0);
+                (change != 0.0).then(|| {
+                    let current = current_deltas.get(other).copied().unwrap_or(0.0);
+                    (other.clone(), current + change)
+                })
+            })
+            .collect();
+
+        let (before, after): (Vec<f64>, Vec<f64>) = self
+            .simulations(n_simulations, seed)
+            .iter()
+            .map(|sim| {
+                let value = get_portfolio_value_ref(&self.positions, sim);
+                (value, value + shares * sim.get(team).copied().unwrap_or(0.0))
+            })
+            .unzip();
+
+        let cost = shares * price;
+        TradePreview {
+            team: team.to_string(),
+            shares,
+            price,
+            cost,
+            value_before,
+            value_after: value_before + value_change,
+            edge: value_change - cost,
+            team_deltas,
+            risk_before: RiskSummary::from_values(&before),
+            risk_after: RiskSummary::from_values(&after),
+        }
+    }
+
+    /// Expected value and delta concentration by region, seed band, and round.
+    ///
+    /// Uses the stored team deltas, computing them first if `compute_deltas`
+    /// has not been called. Regions split the bracket into equal blocks of
+    /// slots named by `region_names` (default: the tournament's `regions`, else four).
+    #[pyo3(signature = (region_names = None))]
+    pub fn exposure_report(&self, region_names: Option<Vec<String>>) -> Result<ExposureReport> {
+        let team_deltas = if self.team_deltas.is_empty() {
+            get_all_team_deltas_ref(&self.positions, &self.tournament, self.point_delta).0
+        } else {
+            self.team_deltas.clone()
+        };
+        ExposureReport::build(&self.positions, &self.tournament, &team_deltas, region_names)
+    }
+
+    /// `portfolio[team]`: shares held in `team`.
+    #[cfg(feature = "python")]
+    fn __getitem__(&self, team: &str) -> PyResult<f64> {
+        self.positions
+            .get(team)
+            .copied()
+            .ok_or_else(|| PyKeyError::new_err(team.to_string()))
+    }
+
+    fn __contains__(&self, team: &str) -> bool {
+        self.positions.contains_key(team)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PortfolioState({} positions, {} teams)",
             self.positions.len(),
-            self.tournament.get_bracket_teams().len()
+            self.tournament.bracket_teams().len()
         )
     }
 }
@@ -316,16 +1256,229 @@ mod tests {
         assert!((value - 27.5).abs() < 1e-10); // 10*2.0 + 5*1.5 = 27.5
     }
 
+    #[test]
+    fn test_team_deltas_order_by_total() {
+        let mut deltas = [
+            TeamDelta::new("A".to_string(), 2.0, 1.0, None, None, None),
+            TeamDelta::new("B".to_string(), 1.0, -3.0, None, None, None),
+            TeamDelta::new("C".to_string(), 4.0, 0.5, None, None, None),
+        ];
+        deltas.sort();
+        let order: Vec<&str> = deltas.iter().map(|delta| delta.team.as_str()).collect();
+        assert_eq!(order, ["B", "A", "C"]);
+        assert_eq!(deltas[1], TeamDelta::new("A".to_string(), 2.0, 1.0, None, None, None));
+        assert_ne!(deltas[1], deltas[2]);
+    }
+
+    #[test]
+    fn test_team_delta_equality_agrees_with_order_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        let hash = |delta: &TeamDelta| {
+            let mut hasher = DefaultHasher::new();
+            delta.hash(&mut hasher);
+            hasher.finish()
+        };
+        let delta = |per_share: f64, win_score: Option<f64>| {
+            TeamDelta::new("A".to_string(), 1.0, per_share, win_score, None, None)
+        };
+
+        let (zero, negative_zero) = (delta(0.0, None), delta(-0.0, None));
+        assert_ne!(zero, negative_zero);
+        assert!(negative_zero < zero);
+        assert_eq!(hash(&zero), hash(&delta(0.0, None)));
+
+        let nan = delta(f64::NAN, Some(f64::NAN));
+        assert_eq!(nan, nan.clone());
+        assert_eq!(nan.cmp(&nan.clone()), Ordering::Equal);
+        assert_eq!(hash(&nan), hash(&nan.clone()));
+
+        // NaN scores order consistently instead of tying with everything.
+        let (low, high) = (delta(1.0, Some(1.0)), delta(1.0, Some(f64::NAN)));
+        assert!(low < high);
+        assert_eq!(high.cmp(&low), Ordering::Greater);
+        assert_ne!(low, high);
+        assert!(delta(1.0, None) < low);
+    }
+
+    #[test]
+    fn test_risk_summary() {
+        let values: Vec<f64> = (0..=100).map(f64::from).collect();
+        let risk = RiskSummary::from_values(&values);
+        assert!((risk.mean - 50.0).abs() < 1e-12);
+        assert!((risk.percentile_5 - 5.0).abs() < 1e-12);
+        assert!((risk.percentile_95 - 95.0).abs() < 1e-12);
+        assert!(risk.stddev > 29.0 && risk.stddev < 30.0);
+    }
+
+    #[test]
+    fn test_preview_trade_matches_recomputed_state() {
+        let positions: HashMap<String, f64> = [("A".to_string(), 10.0)].into_iter().collect();
+        let state = PortfolioState::new(make_test_tournament(), positions, 1.0);
+        let preview = state.preview_trade("B", 5.0, 0.5, 500, Some(3));
+
+        // The state itself is untouched.
+        assert_eq!(state.positions.len(), 1);
+        assert!(state.team_deltas.is_empty());
+
+        let mut traded = state.clone();
+        traded.positions_mut().insert("B".to_string(), 5.0);
+        traded.compute_deltas();
+        assert!((preview.value_after - traded.get_value()).abs() < 1e-9);
+        assert!((preview.edge - (preview.value_after - preview.value_before - 2.5)).abs() < 1e-9);
+        for (team, delta) in &preview.team_deltas {
+            assert!((delta - traded.team_deltas[team]).abs() < 1e-9);
+        }
+        assert!((preview.risk_after.mean - traded.risk(500, Some(3)).mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_preview_trade_reuses_cache_until_changed() {
+        let positions: HashMap<String, f64> = [("A".to_string(), 10.0)].into_iter().collect();
+        let mut state = PortfolioState::new(make_test_tournament(), positions, 1.0);
+        let first = state.preview_trade("B", 5.0, 0.5, 200, None);
+        let sims = state.simulations(200, None);
+        let again = state.preview_trade("C", 1.0, 0.5, 200, None);
+        assert!(Arc::ptr_eq(&sims, &state.simulations(200, None)));
+        assert_eq!(first.risk_before.mean, again.risk_before.mean);
+
+        // Changing the tournament drops the cached scores and draws.
+        state.tournament_mut().overrides.add_override("A", "B", 0.0).unwrap();
+        assert!(!Arc::ptr_eq(&sims, &state.simulations(200, None)));
+        let after = state.preview_trade("B", 5.0, 0.5, 200, Some(1));
+        assert!(after.value_before < first.value_before);
+        assert_eq!(after.value_before, state.get_value());
+    }
+
+    #[test]
+    fn test_find_edges_ranks_mispricings() {
+        let tournament = make_test_tournament();
+        let scores = tournament.calculate_scores_prob();
+        let prices: HashMap<String, f64> = [
+            ("A".to_string(), scores["A"] - 0.5),
+            ("B".to_string(), scores["B"] + 0.2),
+            ("C".to_string(), scores["C"]),
+        ]
+        .into_iter()
+        .collect();
+
+        let edges = find_edges(&tournament, prices, 0.1, "points", 20000, Some(1)).unwrap();
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].team, "A");
+        assert!(edges[0].edge > 0.0 && edges[0].significant());
+        assert_eq!(edges[1].team, "B");
+        assert!(edges[1].edge < 0.0);
+
+        let prices = HashMap::from([("A".to_string(), 0.0)]);
+        let champs = find_edges(&tournament, prices, 0.0, "championship", 1000, Some(1)).unwrap();
+        assert!(champs[0].model_value > 0.0 && champs[0].model_value < 1.0);
+        assert!(find_edges(&tournament, HashMap::new(), 0.0, "odds", 10, None).is_err());
+    }
+
     #[test]
     fn test_game_delta() {
         let tournament = make_test_tournament();
         let mut positions = HashMap::new();
         positions.insert("A".to_string(), 10.0);
 
-        let (win_value, loss_value, _) = game_delta(positions, &tournament, "A", "B");
+        let (win_value, loss_value, deltas) = game_delta(positions, &tournament, "A", "B");
 
         // If A wins against B, A should have higher value
         assert!(win_value > loss_value);
+        let delta = &deltas[0];
+        assert_eq!(delta.matchup, Some(("A".to_string(), "B".to_string())));
+        assert_eq!(delta.win_score.unwrap() * 10.0, win_value);
+        assert_eq!(delta.win_score.unwrap() - delta.loss_score.unwrap(), delta.delta_per_share);
+    }
+
+    #[test]
+    fn test_value_attribution_sums_to_portfolio() {
+        let positions: HashMap<String, f64> =
+            [("A".to_string(), 10.0), ("C".to_string(), 0.5), ("D".to_string(), -2.0)].into();
+        let portfolio = PortfolioState::new(make_test_tournament(), positions, 1.0);
+        let attribution = portfolio.value_attribution(2000, Some(9));
+        let risk = portfolio.risk(2000, Some(9));
+        let total = |field: fn(&PositionAttribution) -> f64| attribution.iter().map(field).sum::<f64>();
+
+        assert!((total(|a| a.expected_value) - portfolio.get_value()).abs() < 1e-9);
+        assert!((total(|a| a.variance) - risk.stddev.powi(2)).abs() < 1e-9);
+        assert!((total(|a| a.stddev) - risk.stddev).abs() < 1e-9);
+        assert!(total(|a| a.percentile_5) < total(|a| a.percentile_95));
+        assert!((total(|a| a.percentile_5) - risk.percentile_5).abs() < 0.5);
+        // The large position drives the book.
+        assert_eq!(attribution[0].team, "A");
+
+        // Both reuse the cached draws that `preview_trade` sees.
+        let sims = portfolio.simulations(2000, Some(9));
+        portfolio.risk(2000, Some(9));
+        portfolio.value_attribution(2000, Some(9));
+        assert!(Arc::ptr_eq(&sims, &portfolio.simulations(2000, Some(9))));
+        assert_eq!(portfolio.preview_trade("B", 0.0, 0.0, 2000, Some(9)).risk_before.stddev, risk.stddev);
+    }
+
+    #[test]
+    fn test_rebalance_brings_deltas_into_bands() {
+        let tournament = make_test_tournament();
+        let positions: HashMap<String, f64> = [("A".to_string(), 10.0)].into();
+        let prices: HashMap<String, f64> = [("A".to_string(), 1.0), ("B".to_string(), 0.8)].into();
+        let targets: HashMap<String, (f64, f64)> = [("A".to_string(), (-0.05, 0.05))].into();
+
+        let rebalance = |max_turnover, costs| {
+            let (positions, targets, prices) = (positions.clone(), targets.clone(), prices.clone());
+            rebalance_to_target(positions, &tournament, targets, prices, 0.5, max_turnover, 1.0, costs).unwrap()
+        };
+        let plan = rebalance(None, None);
+        assert!(plan.within_bands);
+        assert!(plan.team_deltas["A"].abs() <= 0.05);
+        // Deltas are linear in positions, so recomputing after the trades agrees.
+        let mut rebalanced = positions.clone();
+        for (team, shares) in &plan.trades {
+            *rebalanced.entry(team.clone()).or_insert(0.0) += shares;
+        }
+        let (recomputed, _) = get_all_team_deltas(rebalanced, &tournament, 1.0);
+        assert!((recomputed["A"] - plan.team_deltas["A"]).abs() < 1e-9);
+
+        let costly = TransactionCosts::new(0.5, 0.01, 0.0).unwrap();
+        let plan_with_costs = rebalance(None, Some(costly));
+        assert!(plan_with_costs.within_bands);
+        assert!(plan_with_costs.costs >= 0.5 * plan_with_costs.trades.len() as f64);
+        assert_eq!(plan.costs, 0.0);
+
+        let capped = rebalance(Some(1.0), None);
+        assert!(!capped.within_bands);
+        assert!(capped.turnover <= 1.0);
+    }
+
+    #[test]
+    fn test_delta_matrix_matches_keyed_deltas() {
+        let tournament = make_test_tournament();
+        let positions: HashMap<String, f64> = [("A".to_string(), 3.0), ("D".to_string(), -1.0)].into();
+        let matrix = delta_matrix(&positions, &tournament, 1.0);
+        let (team_deltas, pairwise_deltas) = get_all_team_deltas(positions, &tournament, 1.0);
+
+        assert_eq!(matrix.teams, tournament.get_bracket_teams());
+        for (i, team) in matrix.teams.iter().enumerate() {
+            assert_eq!(matrix.team_deltas[i], team_deltas[team]);
+            let single = get_team_pairwise_deltas(&tournament, team, 1.0);
+            for (j, other) in matrix.teams.iter().enumerate() {
+                assert_eq!(matrix.pairwise[i][j], pairwise_deltas[team][other]);
+                assert_eq!(matrix.pairwise[i][j], single[other]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_game_delta_curve_spans_endpoints() {
+        let tournament = make_test_tournament();
+        let positions: HashMap<String, f64> = [("A".to_string(), 10.0), ("C".to_string(), -3.0)].into();
+
+        let (win_value, loss_value, _) = game_delta(positions.clone(), &tournament, "A", "B");
+        let curve = game_delta_curve(positions.clone(), &tournament, "A", "B", None).unwrap();
+        assert_eq!(curve.iter().map(|&(p, _)| p).collect::<Vec<_>>(), DEFAULT_PROB_GRID);
+        assert!((curve[0].1 - loss_value).abs() < 1e-9);
+        assert!((curve[4].1 - win_value).abs() < 1e-9);
+        // Probabilities propagate linearly, so the payoff is a straight line.
+        assert!((curve[2].1 - (win_value + loss_value) / 2.0).abs() < 1e-9);
+        assert!(game_delta_curve(positions, &tournament, "A", "B", Some(vec![1.5])).is_err());
     }
 
     #[test]
@@ -348,7 +1501,7 @@ mod tests {
         // Team B's delta may be negative because improving B hurts our larger
         // A position more than it helps our smaller B position. Just verify
         // that all deltas are finite and non-zero (rating changes have effect).
-        for (_, delta) in &team_deltas {
+        for delta in team_deltas.values() {
             assert!(delta.is_finite());
         }
 
@@ -357,4 +1510,57 @@ mod tests {
             assert!(pairwise_deltas.contains_key(team_name));
         }
     }
+
+    #[test]
+    fn test_deterministic_deltas_bit_stable() {
+        let mut tournament = crate::testing::canonical_tournament(16);
+        tournament.deterministic = true;
+        let positions: HashMap<String, f64> =
+            tournament.get_bracket_teams().into_iter().enumerate().map(|(i, t)| (t, 1.0 + i as f64 / 3.0)).collect();
+
+        // A JSON round trip rebuilds every map with a fresh hash order.
+        let rebuilt = TournamentState::from_json(&tournament.to_json().unwrap()).unwrap();
+        let run = |state: &TournamentState, threads| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| get_all_team_deltas_ref(&positions, state, 1.0))
+        };
+        let baseline = run(&tournament, 1);
+        assert_eq!(baseline, run(&tournament, 4));
+        assert_eq!(baseline, run(&rebuilt, 3));
+        let golden = crate::testing::golden_expected_scores(16).unwrap();
+        crate::testing::assert_scores_close(&tournament.calculate_scores_prob(), &golden, 1e-12);
+
+        // The single-team, game, and trade paths sum values the same way as the bulk path.
+        for state in [&tournament, &rebuilt] {
+            for (team, &delta) in &baseline.0 {
+                assert_eq!(get_team_portfolio_delta(positions.clone(), state, team, 1.0), delta, "{team}");
+            }
+            let (win_value, loss_value, _) = game_delta_ref(&positions, state, "Team 01", "Team 16");
+            let (win_scores, loss_scores) = state.calculate_scores_given_game("Team 01", "Team 16");
+            assert_eq!(win_value, portfolio_value_for(state, &positions, &win_scores));
+            assert_eq!(loss_value, portfolio_value_for(state, &positions, &loss_scores));
+            let portfolio = PortfolioState::new(state.clone(), positions.clone(), 1.0);
+            assert_eq!(portfolio.preview_trade("Team 01", 1.0, 0.5, 100, Some(1)).value_before, portfolio.get_value());
+        }
+    }
+
+    #[test]
+    fn test_group_delta_differs_from_summed_team_deltas() {
+        let tournament = make_test_tournament();
+        let group = vec!["A".to_string(), "C".to_string()];
+        let (single_pos, single_neg) = get_team_delta(&tournament, "A", 1.0);
+        let (group_pos, group_neg) = get_group_delta(&tournament, group.clone(), 1.0);
+        assert_eq!(get_group_delta(&tournament, vec!["A".to_string()], 1.0), (single_pos.clone(), single_neg.clone()));
+
+        // A and C can meet in the final, so improving C too blunts A's gain.
+        let single_gain = single_pos["A"] - single_neg["A"];
+        let group_gain = group_pos["A"] - group_neg["A"];
+        assert!(group_gain > 0.0 && group_gain < single_gain);
+
+        let positions: HashMap<String, f64> = [("A".to_string(), 1.0), ("C".to_string(), 1.0)].into();
+        let summed = get_team_portfolio_delta(positions.clone(), &tournament, "A", 1.0)
+            + get_team_portfolio_delta(positions.clone(), &tournament, "C", 1.0);
+        let joint = get_group_portfolio_delta(positions, &tournament, group, 1.0);
+        assert!(joint > 0.0 && (joint - summed).abs() > 1e-6);
+    }
 }
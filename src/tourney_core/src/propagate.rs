@@ -0,0 +1,399 @@
+//! Exact advancement probabilities over dense per-team arrays.
+//!
+//! Teams are indexed in bracket order (`TournamentState::bracket_teams`), so
+//! every slot, and every subtree above it, owns a contiguous range of
+//! indices. A round is then one pass over the pair table of each game (left
+//! range against right range), accumulating into flat arrays instead of
+//! merging per-game hash maps.
+
+use std::collections::HashMap;
+
+use crate::team::Team;
+use crate::tournament::TournamentState;
+use crate::trace::trace_span;
+use crate::win_prob::calculate_win_prob_with;
+
+/// Probability of each team winning its game in every main-bracket round:
+/// `rounds[r][i]` is for `state.bracket_teams()[i]` in round `r`.
+///
+/// Returns `None` when the bracket does not have the shape the tables need
+/// (a power-of-two slot count with no team in two slots); callers fall back
+/// to game-by-game propagation.
+pub(crate) fn advancement(state: &TournamentState) -> Option<Vec<Vec<f64>>> {
+    advancement_given(state, &[None]).map(|mut variants| variants.remove(0))
+}
+
+/// A game result to condition on: bracket teams `i` and `j` (indices into
+/// `bracket_teams`) and the probability `i` wins if they meet.
+pub(crate) type Pinned = (usize, usize, f64);
+
+/// `advancement` once per entry of `pinned`, each with that pairing's
+/// probability fixed as if overridden, or as is for `None`.
+///
+/// The variants share one pass over the bracket, so each pairing's model
+/// probability is computed once however many there are, and nothing is
+/// copied from the state.
+pub(crate) fn advancement_given(state: &TournamentState, pinned: &[Option<Pinned>]) -> Option<Vec<Vec<Vec<f64>>>> {
+    let teams = state.bracket_teams();
+    let slots = state.bracket();
+    let (reach, mut bounds) = layout(state)?;
+
+    let ratings: Vec<Option<&Team>> = teams.iter().map(|team| state.ratings.get(team)).collect();
+    let rating = |i: usize| ratings[i].unwrap_or_else(|| panic!("team not found in ratings: {}", teams[i]));
+    // Overrides keyed by index, so pairings are not looked up by name. The
+    // map is still consulted when it holds tempos, which feed the model.
+    let table = override_table(state);
+    let overridden = |i: usize, j: usize| {
+        let prob = table.as_ref()?[i * teams.len() + j];
+        (!prob.is_nan()).then_some(prob)
+    };
+    let tempos = (!state.overrides.tempo_overrides().is_empty()).then_some(&state.overrides);
+    let persistent = state.persistent_forfeits && state.forfeit_prob > 0.0;
+    // Persistent forfeits are tracked per team below, so games between teams
+    // that both showed up are played without them.
+    let forfeit_prob = if persistent { 0.0 } else { state.forfeit_prob };
+    let win_prob = |i: usize, j: usize| match overridden(i, j) {
+        Some(prob) => prob,
+        None => calculate_win_prob_with(rating(i), rating(j), tempos, forfeit_prob, &state.model_params),
+    };
+    let pin = |variant: &Option<Pinned>, i: usize, j: usize| match *variant {
+        Some((a, b, prob)) if (a, b) == (i, j) => Some(prob),
+        Some((a, b, prob)) if (a, b) == (j, i) => Some(1.0 - prob),
+        _ => None,
+    };
+
+    if persistent {
+        let variants = pinned.iter().map(|variant| {
+            persistent_rounds(state, reach.clone(), &bounds, |i, j| {
+                let seed = |i: usize| ratings[i].and_then(|team| team.seed);
+                match pin(variant, i, j).or_else(|| overridden(i, j)) {
+                    // Overrides are final, whoever shows up.
+                    Some(prob) => [prob; 4],
+                    None => [win_prob(i, j), 1.0, 0.0, state.model_params.double_forfeit.team1_share(seed(i), seed(j))],
+                }
+            })
+        });
+        return Some(variants.collect());
+    }
+
+    let mut reach = vec![reach; pinned.len()];
+    let mut rounds = vec![Vec::with_capacity(slots.len().ilog2() as usize); pinned.len()];
+    let mut opponents = vec![vec![0.0; teams.len()]; pinned.len()];
+    while bounds.len() > 2 {
+        trace_span!(TRACE, "scoring_round", round = rounds[0].len(), n_games = (bounds.len() - 1) / 2);
+        // opponents[v][i]: probability i beats whoever it meets, given it got
+        // here, in variant v.
+        opponents.iter_mut().for_each(|beats| beats.fill(0.0));
+        for game in bounds.windows(3).step_by(2) {
+            let (left, right) = (game[0]..game[1], game[1]..game[2]);
+            for i in left {
+                for j in right.clone() {
+                    let p = win_prob(i, j);
+                    for (v, variant) in pinned.iter().enumerate() {
+                        let p = pin(variant, i, j).unwrap_or(p);
+                        opponents[v][i] += reach[v][j] * p;
+                        opponents[v][j] += reach[v][i] * (1.0 - p);
+                    }
+                }
+            }
+        }
+        for ((reach, beats), rounds) in reach.iter_mut().zip(&opponents).zip(&mut rounds) {
+            reach.iter_mut().zip(beats).for_each(|(r, beats)| *r *= beats);
+            rounds.push(reach.clone());
+        }
+        bounds = bounds.into_iter().step_by(2).collect();
+    }
+    Some(rounds)
+}
+
+/// Starting probabilities and slot boundaries, indexed like `bracket_teams`;
+/// `None` when the bracket does not have the shape the tables need.
+fn layout(state: &TournamentState) -> Option<(Vec<f64>, Vec<usize>)> {
+    let slots = state.bracket();
+    if !slots.len().is_power_of_two() || slots.iter().map(HashMap::len).sum::<usize>() != state.bracket_teams().len() {
+        return None;
+    }
+
+    // `bracket_teams` lists each slot's teams together in map order, so the
+    // same walk yields starting probabilities and slot boundaries.
+    let mut reach = Vec::with_capacity(state.bracket_teams().len());
+    let mut bounds = Vec::with_capacity(slots.len() + 1);
+    bounds.push(0);
+    for slot in slots {
+        reach.extend(slot.values().copied());
+        bounds.push(reach.len());
+    }
+    Some((reach, bounds))
+}
+
+/// `advancement` with every intermediate kept, so a change to one team's
+/// rating is rescored by replaying only the games on that team's path.
+///
+/// A team's rating enters only the games of the subtrees containing it; every
+/// other subtree's advancement, and every other pairing's probability, is
+/// reused. In the game on the path each round, teams on the changed team's
+/// side keep their chances against the unchanged other side and only their
+/// reach changes, so the work per team is one model call per possible
+/// opponent and a pass over each path game's pair table. Overridden
+/// pairings keep their overrides whatever the ratings.
+pub(crate) struct AdvancementCache<'a> {
+    state: &'a TournamentState,
+    ratings: Vec<&'a Team>,
+    table: Option<Vec<f64>>,
+    /// Starting probabilities.
+    reach: Vec<f64>,
+    /// `bounds[r]`: slot boundaries going into round `r`.
+    bounds: Vec<Vec<usize>>,
+    /// `probs[i * n + j]`: probability `i` beats `j`, for teams that can meet.
+    probs: Vec<f64>,
+    /// `beats[r][i]`: probability `i` wins its round-`r` game, given it got there.
+    beats: Vec<Vec<f64>>,
+    /// As returned by `advancement`.
+    rounds: Vec<Vec<f64>>,
+}
+
+impl<'a> AdvancementCache<'a> {
+    /// `None` unless scoring `state` propagates exactly without persistent
+    /// forfeits (see `TournamentState::play_out`) and every bracket team is
+    /// rated.
+    pub(crate) fn new(state: &'a TournamentState) -> Option<Self> {
+        let persistent = state.persistent_forfeits && state.forfeit_prob > 0.0;
+        if state.reseed || state.deterministic || persistent {
+            return None;
+        }
+        let (reach, first) = layout(state)?;
+        let ratings = state.bracket_teams().iter().map(|team| state.ratings.get(team)).collect::<Option<_>>()?;
+        let n = reach.len();
+        let mut cache = AdvancementCache {
+            state,
+            ratings,
+            table: override_table(state),
+            reach,
+            bounds: vec![first],
+            probs: vec![f64::NAN; n * n],
+            beats: Vec::new(),
+            rounds: Vec::new(),
+        };
+
+        let mut reach = cache.reach.clone();
+        while cache.bounds[cache.beats.len()].len() > 2 {
+            let bounds = &cache.bounds[cache.beats.len()];
+            let mut beats = vec![0.0; n];
+            for game in bounds.windows(3).step_by(2) {
+                for i in game[0]..game[1] {
+                    for j in game[1]..game[2] {
+                        let p = cache.win_prob(i, j, None);
+                        cache.probs[i * n + j] = p;
+                        cache.probs[j * n + i] = 1.0 - p;
+                        beats[i] += reach[j] * p;
+                        beats[j] += reach[i] * (1.0 - p);
+                    }
+                }
+            }
+            reach.iter_mut().zip(&beats).for_each(|(r, beats)| *r *= beats);
+            let next = bounds.iter().copied().step_by(2).collect();
+            cache.bounds.push(next);
+            cache.beats.push(beats);
+            cache.rounds.push(reach.clone());
+        }
+        Some(cache)
+    }
+
+    /// `advancement` with `team`'s rating replaced by `rating`.
+    pub(crate) fn with_rating(&self, team: usize, rating: &Team) -> Vec<Vec<f64>> {
+        let n = self.reach.len();
+        let adjusted = Some((team, rating));
+        // Left-hand teams have the lower indices; the model is asked in that
+        // order, as in the full pass.
+        let beats_team = |j: usize| {
+            if team < j {
+                self.win_prob(team, j, adjusted)
+            } else {
+                1.0 - self.win_prob(j, team, adjusted)
+            }
+        };
+
+        let mut rounds = self.rounds.clone();
+        for r in 0..rounds.len() {
+            let bounds = &self.bounds[r];
+            let slot = bounds.partition_point(|&bound| bound <= team) - 1;
+            let game = &bounds[slot - slot % 2..slot - slot % 2 + 3];
+            let (start, middle, end) = (game[0], game[1], game[2]);
+            let (own, other) = if team < middle { (start..middle, middle..end) } else { (middle..end, start..middle) };
+            let (before, after) = rounds.split_at_mut(r);
+            let (previous, round) = (before.last().unwrap_or(&self.reach), &mut after[0]);
+
+            let odds: Vec<f64> = other.clone().map(beats_team).collect();
+            for i in own.clone() {
+                let beats = if i == team {
+                    other.clone().zip(&odds).map(|(j, p)| previous[j] * p).sum()
+                } else {
+                    self.beats[r][i]
+                };
+                round[i] = previous[i] * beats;
+            }
+            for (j, p_team) in other.zip(&odds) {
+                let beats: f64 = own
+                    .clone()
+                    .map(|i| previous[i] * if i == team { 1.0 - p_team } else { self.probs[j * n + i] })
+                    .sum();
+                round[j] = previous[j] * beats;
+            }
+        }
+        rounds
+    }
+
+    /// Probability `i` beats `j`, with `adjusted` replacing one team's rating.
+    fn win_prob(&self, i: usize, j: usize, adjusted: Option<(usize, &Team)>) -> f64 {
+        let n = self.reach.len();
+        if let Some(prob) = self.table.as_ref().map(|table| table[i * n + j]).filter(|prob| !prob.is_nan()) {
+            return prob;
+        }
+        let rating = |k: usize| match adjusted {
+            Some((team, rating)) if team == k => rating,
+            _ => self.ratings[k],
+        };
+        let state = self.state;
+        let tempos = (!state.overrides.tempo_overrides().is_empty()).then_some(&state.overrides);
+        calculate_win_prob_with(rating(i), rating(j), tempos, state.forfeit_prob, &state.model_params)
+    }
+}
+
+/// `advancement` when each team withdraws (with probability `forfeit_prob`)
+/// once for the whole event.
+///
+/// Withdrawals are independent between teams, and teams from different
+/// subtrees meet only once, so splitting each team's reach into "reached and
+/// showed up" and "reached, withdrawn" keeps the propagation exact. `outcome(i,
+/// j)` gives the probability `i` beats `j` when both show up, only `i` does,
+/// only `j` does, and neither does.
+fn persistent_rounds(
+    state: &TournamentState,
+    reach: Vec<f64>,
+    bounds: &[usize],
+    outcome: impl Fn(usize, usize) -> [f64; 4],
+) -> Vec<Vec<f64>> {
+    let forfeit_prob = state.forfeit_prob;
+    let mut present: Vec<f64> = reach.iter().map(|r| r * (1.0 - forfeit_prob)).collect();
+    let mut absent: Vec<f64> = reach.iter().map(|r| r * forfeit_prob).collect();
+    let mut bounds = bounds.to_vec();
+
+    let mut rounds = Vec::with_capacity(state.bracket().len().ilog2() as usize);
+    let (mut beats_present, mut beats_absent) = (vec![0.0; reach.len()], vec![0.0; reach.len()]);
+    while bounds.len() > 2 {
+        trace_span!(TRACE, "scoring_round", round = rounds.len(), n_games = (bounds.len() - 1) / 2);
+        // beats_*[i]: probability i beats whoever it meets, given it got here
+        // having shown up (or withdrawn).
+        beats_present.fill(0.0);
+        beats_absent.fill(0.0);
+        for game in bounds.windows(3).step_by(2) {
+            for i in game[0]..game[1] {
+                for j in game[1]..game[2] {
+                    let [both, only_i, only_j, neither] = outcome(i, j);
+                    beats_present[i] += present[j] * both + absent[j] * only_i;
+                    beats_absent[i] += present[j] * only_j + absent[j] * neither;
+                    beats_present[j] += present[i] * (1.0 - both) + absent[i] * (1.0 - only_j);
+                    beats_absent[j] += present[i] * (1.0 - only_i) + absent[i] * (1.0 - neither);
+                }
+            }
+        }
+        present.iter_mut().zip(&beats_present).for_each(|(r, beats)| *r *= beats);
+        absent.iter_mut().zip(&beats_absent).for_each(|(r, beats)| *r *= beats);
+        rounds.push(present.iter().zip(&absent).map(|(p, a)| p + a).collect());
+        bounds = bounds.into_iter().step_by(2).collect();
+    }
+    rounds
+}
+
+/// Overrides in effect between bracket teams as a square matrix indexed
+/// `[team1 * n + team2]`, NaN where there is none; `None` if there are none.
+pub(crate) fn override_table(state: &TournamentState) -> Option<Vec<f64>> {
+    let items = state.overrides.items();
+    if items.is_empty() {
+        return None;
+    }
+    let n = state.bracket_teams().len();
+    let index: HashMap<&str, usize> =
+        state.bracket_teams().iter().enumerate().map(|(i, team)| (team.as_str(), i)).collect();
+    let mut table = vec![f64::NAN; n * n];
+    for (team1, team2, _) in items {
+        let (Some(&i), Some(&j)) = (index.get(team1.as_str()), index.get(team2.as_str())) else { continue };
+        // Resolved through the map so expiry and layering apply as usual.
+        if let Some(prob) = state.overrides.get(&team1, &team2) {
+            table[i * n + j] = prob;
+            table[j * n + i] = 1.0 - prob;
+        }
+    }
+    Some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_transform::game_transform_prob_with;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_advancement_matches_game_transforms() {
+        let mut state = canonical_tournament(8);
+        state.overrides.add_override("Team 01", "Team 08", 0.3).unwrap();
+        // A two-team slot, as after a play-in.
+        let extra = state.ratings["Team 08"].clone();
+        state.ratings.insert("Team 09".to_string(), crate::team::Team { name: "Team 09".to_string(), ..extra });
+        state.bracket_mut()[1] = [("Team 08".to_string(), 0.6), ("Team 09".to_string(), 0.4)].into();
+
+        let rounds = advancement(&state).unwrap();
+        let teams = state.bracket_teams();
+        let mut games = state.bracket().to_vec();
+        for round in &rounds {
+            games = games
+                .chunks(2)
+                .map(|pair| {
+                    let params = &state.model_params;
+                    game_transform_prob_with(&pair[0], &pair[1], &state.ratings, Some(&state.overrides), 0.0, params)
+                })
+                .collect();
+            for game in &games {
+                for (team, &prob) in game {
+                    let i = teams.iter().position(|t| t == team).unwrap();
+                    assert!((round[i] - prob).abs() < 1e-12);
+                }
+            }
+        }
+        assert_eq!(rounds.len(), 3);
+        assert!((rounds[2].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cache_replays_rating_changes() {
+        let mut state = canonical_tournament(16);
+        state.overrides.add_override("Team 03", "Team 14", 0.2).unwrap();
+        state.overrides.set_tempo_override("Team 01", "Team 09", 75.0).unwrap();
+        // A two-team slot, as after a play-in.
+        let last = state.bracket()[15].keys().next().unwrap().clone();
+        let extra = state.ratings[&last].clone();
+        state.ratings.insert("Team 17".to_string(), crate::team::Team { name: "Team 17".to_string(), ..extra });
+        state.bracket_mut()[15] = [(last, 0.7), ("Team 17".to_string(), 0.3)].into();
+
+        let cache = AdvancementCache::new(&state).unwrap();
+        assert_eq!(cache.rounds, advancement(&state).unwrap());
+        for (index, team) in state.bracket_teams().iter().enumerate() {
+            let rating = state.ratings[team].with_adjustment(-2.5);
+            let expected = advancement(&state.with_team_adjustment(team, -2.5)).unwrap();
+            for (round, expected) in cache.with_rating(index, &rating).iter().zip(&expected) {
+                assert!(round.iter().zip(expected).all(|(a, b)| (a - b).abs() < 1e-12), "{team}");
+            }
+        }
+
+        state.deterministic = true;
+        assert!(AdvancementCache::new(&state).is_none());
+    }
+
+    #[test]
+    fn test_advancement_rejects_repeated_teams() {
+        let mut state = canonical_tournament(4);
+        let first = state.bracket()[0].clone();
+        state.bracket_mut()[1] = first;
+        assert!(advancement(&state).is_none());
+    }
+}
@@ -0,0 +1,383 @@
+//! Elo ratings built from a season's game results.
+//!
+//! For users without efficiency ratings, `elo_from_games` plays through a game
+//! log in order, moving both teams' Elo ratings by `k` times the difference
+//! between the result and the expected result. `EloRatings::to_teams`
+//! converts the ratings to `Team`s for the rest of the library: each team gets
+//! the offense and defense (split evenly, at average tempo) whose win
+//! probability against an average team matches its Elo win probability
+//! against the mean rating. Matchups between two non-average teams follow the
+//! normal margin model from there, so they agree with Elo only approximately.
+//!
+//! With possessions in the log, `adjusted_ratings` instead derives efficiency
+//! ratings directly, adjusted for opponents the way tempo-free ratings are:
+//! each team's adjusted offense is the mean over its games of its points per
+//! 100 possessions, scaled by how much better than average the opponent's
+//! adjusted defense is, and likewise for defense and tempo, iterated until the
+//! ratings stop changing.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::team::Team;
+use crate::win_prob::ModelParams;
+
+/// Rating every team starts from.
+pub const INITIAL_ELO: f64 = 1500.0;
+
+/// One completed game.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GameResult {
+    /// Home team, unless `neutral`.
+    #[pyo3(get)]
+    pub team1: String,
+
+    #[pyo3(get)]
+    pub team2: String,
+
+    #[pyo3(get)]
+    pub score1: f64,
+
+    #[pyo3(get)]
+    pub score2: f64,
+
+    #[pyo3(get)]
+    pub neutral: bool,
+
+    /// Possessions each team had, for `adjusted_ratings`.
+    #[pyo3(get)]
+    pub possessions: Option<f64>,
+}
+
+#[pymethods]
+impl GameResult {
+    #[new]
+    #[pyo3(signature = (team1, team2, score1, score2, neutral = false, possessions = None))]
+    pub fn new(
+        team1: String,
+        team2: String,
+        score1: f64,
+        score2: f64,
+        neutral: bool,
+        possessions: Option<f64>,
+    ) -> Self {
+        GameResult {
+            team1,
+            team2,
+            score1,
+            score2,
+            neutral,
+            possessions,
+        }
+    }
+
+    /// Parse a CSV game log with a header naming `team1`, `team2`, `score1`,
+    /// and `score2` columns, plus optional `neutral` (`true`/`1` for a
+    /// neutral site) and `possessions` columns, one game per row in date
+    /// order.
+    #[staticmethod]
+    pub fn parse_log(text: &str) -> Result<Vec<GameResult>> {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(text.as_bytes());
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+                .ok_or_else(|| TourneyError::InvalidInput(format!("game log has no {name} column")))
+        };
+        let (team1, team2, score1, score2) = (column("team1")?, column("team2")?, column("score1")?, column("score2")?);
+        let (neutral, possessions) = (column("neutral").ok(), column("possessions").ok());
+        reader
+            .records()
+            .enumerate()
+            .map(|(i, record)| {
+                let record = record?;
+                let field = |col: usize| record.get(col).unwrap_or_default();
+                let number = |col: usize, what: &str| {
+                    let value = field(col);
+                    value
+                        .parse::<f64>()
+                        .map_err(|_| TourneyError::InvalidInput(format!("row {}: invalid {what} {value:?}", i + 1)))
+                };
+                let neutral =
+                    neutral.is_some_and(|col| matches!(field(col).to_ascii_lowercase().as_str(), "true" | "1"));
+                Ok(GameResult {
+                    team1: field(team1).to_string(),
+                    team2: field(team2).to_string(),
+                    score1: number(score1, "score")?,
+                    score2: number(score2, "score")?,
+                    neutral,
+                    possessions: match possessions.filter(|&col| !field(col).is_empty()) {
+                        Some(col) => Some(number(col, "possessions")?),
+                        None => None,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let site = if self.neutral { " (neutral)" } else { "" };
+        format!("GameResult({} {} - {} {}{site})", self.team1, self.score1, self.score2, self.team2)
+    }
+}
+
+/// Elo ratings from `elo_from_games`.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct EloRatings {
+    #[pyo3(get)]
+    pub ratings: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub n_games: usize,
+
+    #[pyo3(get)]
+    pub k: f64,
+
+    #[pyo3(get)]
+    pub home_adv: f64,
+}
+
+#[pymethods]
+impl EloRatings {
+    /// Elo probability that `team1` beats `team2`, with `team1` at home
+    /// unless `neutral`.
+    #[pyo3(signature = (team1, team2, neutral = true))]
+    pub fn win_prob(&self, team1: &str, team2: &str, neutral: bool) -> Result<f64> {
+        let home = if neutral { 0.0 } else { self.home_adv };
+        Ok(elo_win_prob(self.rating(team1)? + home - self.rating(team2)?))
+    }
+
+    /// Ratings as `Team`s (see the module docs), at `tempo` possessions per
+    /// game (default: the model's average tempo).
+    #[pyo3(signature = (params = None, tempo = None))]
+    pub fn to_teams(&self, params: Option<ModelParams>, tempo: Option<f64>) -> HashMap<String, Team> {
+        let params = params.unwrap_or_default();
+        let tempo = tempo.unwrap_or(params.avg_tempo);
+        let mean = self.ratings.values().sum::<f64>() / self.ratings.len().max(1) as f64;
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        // Against an average team at average tempo the margin is net * avg_scoring / 100 * avg_tempo
+        // with standard deviation scoring_stddev; solve for net.
+        let points_per_net = params.avg_scoring / 100.0 * params.avg_tempo;
+        self.ratings
+            .iter()
+            .map(|(name, &elo)| {
+                let p = elo_win_prob(elo - mean).clamp(1e-9, 1.0 - 1e-9);
+                let net = params.scoring_stddev * normal.inverse_cdf(p) / points_per_net;
+                (name.clone(), Team::new(name.clone(), net / 2.0, -net / 2.0, tempo, false))
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.ratings.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EloRatings({} teams, {} games, k={})", self.ratings.len(), self.n_games, self.k)
+    }
+}
+
+impl EloRatings {
+    fn rating(&self, team: &str) -> Result<f64> {
+        self.ratings
+            .get(team)
+            .copied()
+            .ok_or_else(|| TourneyError::InvalidInput(format!("no Elo rating for {team}")))
+    }
+}
+
+/// Elo win probability for a rating difference (including home advantage).
+pub fn elo_win_prob(diff: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-diff / 400.0))
+}
+
+/// Play through `game_log` in order from `INITIAL_ELO`, updating both teams
+/// by `k` times the result's surprise. `team1` gets `home_adv` Elo points
+/// in games not at a neutral site. Ties count as half a win.
+#[pyfunction]
+#[pyo3(signature = (game_log, k = 20.0, home_adv = 100.0))]
+pub fn elo_from_games(game_log: Vec<GameResult>, k: f64, home_adv: f64) -> Result<EloRatings> {
+    if !(k > 0.0 && k.is_finite() && home_adv.is_finite()) {
+        return Err(TourneyError::InvalidInput(format!("invalid Elo parameters k={k}, home_adv={home_adv}")));
+    }
+    let mut ratings: HashMap<String, f64> = HashMap::new();
+    for game in &game_log {
+        if game.team1 == game.team2 {
+            return Err(TourneyError::InvalidInput(format!("{} cannot play itself", game.team1)));
+        }
+        let r1 = *ratings.entry(game.team1.clone()).or_insert(INITIAL_ELO);
+        let r2 = *ratings.entry(game.team2.clone()).or_insert(INITIAL_ELO);
+        let home = if game.neutral { 0.0 } else { home_adv };
+        let expected = elo_win_prob(r1 + home - r2);
+        let actual = match game.score1.total_cmp(&game.score2) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Equal => 0.5,
+            std::cmp::Ordering::Less => 0.0,
+        };
+        let shift = k * (actual - expected);
+        *ratings.get_mut(&game.team1).expect("rated above") += shift;
+        *ratings.get_mut(&game.team2).expect("rated above") -= shift;
+    }
+    Ok(EloRatings {
+        ratings,
+        n_games: game_log.len(),
+        k,
+        home_adv,
+    })
+}
+
+/// Opponent-adjusted offense, defense, and tempo for every team in
+/// `game_log`, which must give `possessions` for every game. Ratings are
+/// relative to the log's own average efficiency, so they plug into the model
+/// as they are. Iterates until no rating moves by more than `tolerance`
+/// (points per 100 possessions), or `max_iterations`.
+#[pyfunction]
+#[pyo3(signature = (game_log, max_iterations = 100, tolerance = 1e-6))]
+pub fn adjusted_ratings(
+    game_log: Vec<GameResult>,
+    max_iterations: usize,
+    tolerance: f64,
+) -> Result<HashMap<String, Team>> {
+    let mut names: Vec<String> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut id = |name: &str| {
+        *index.entry(name.to_string()).or_insert_with(|| {
+            names.push(name.to_string());
+            names.len() - 1
+        })
+    };
+    // One entry per team per game: (team, opponent, points for, points against, possessions).
+    let mut lines: Vec<(usize, usize, f64, f64, f64)> = Vec::with_capacity(2 * game_log.len());
+    for game in &game_log {
+        let possessions = game
+            .possessions
+            .filter(|&p| p > 0.0)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("{game:?} has no possessions")))?;
+        if game.team1 == game.team2 {
+            return Err(TourneyError::InvalidInput(format!("{} cannot play itself", game.team1)));
+        }
+        let (team1, team2) = (id(&game.team1), id(&game.team2));
+        lines.push((team1, team2, game.score1, game.score2, possessions));
+        lines.push((team2, team1, game.score2, game.score1, possessions));
+    }
+    if lines.is_empty() {
+        return Err(TourneyError::InvalidInput("game log is empty".to_string()));
+    }
+
+    let total_possessions: f64 = lines.iter().map(|line| line.4).sum();
+    let avg_efficiency = 100.0 * lines.iter().map(|line| line.2).sum::<f64>() / total_possessions;
+    let avg_tempo = total_possessions / lines.len() as f64;
+    let n = names.len();
+    let (mut offense, mut defense, mut tempo) = (vec![avg_efficiency; n], vec![avg_efficiency; n], vec![avg_tempo; n]);
+    for _ in 0..max_iterations {
+        let mut sums = vec![(0.0, 0.0, 0.0, 0usize); n];
+        for &(team, opponent, points_for, points_against, possessions) in &lines {
+            let entry = &mut sums[team];
+            entry.0 += 100.0 * points_for / possessions * avg_efficiency / defense[opponent];
+            entry.1 += 100.0 * points_against / possessions * avg_efficiency / offense[opponent];
+            entry.2 += possessions * avg_tempo / tempo[opponent];
+            entry.3 += 1;
+        }
+        let mut change: f64 = 0.0;
+        for (team, &(off, def, pace, games)) in sums.iter().enumerate() {
+            let games = games as f64;
+            change = change.max((off / games - offense[team]).abs()).max((def / games - defense[team]).abs());
+            (offense[team], defense[team], tempo[team]) = (off / games, def / games, pace / games);
+        }
+        if change <= tolerance {
+            break;
+        }
+    }
+
+    Ok(names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let team = Team::new(
+                name.clone(),
+                offense[i] / avg_efficiency - 1.0,
+                defense[i] / avg_efficiency - 1.0,
+                tempo[i],
+                false,
+            );
+            (name, team)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::win_prob::calculate_win_prob;
+
+    fn game(team1: &str, team2: &str, score1: f64, score2: f64) -> GameResult {
+        GameResult::new(team1.to_string(), team2.to_string(), score1, score2, true, None)
+    }
+
+    #[test]
+    fn test_elo_updates() {
+        let log = vec![game("A", "B", 80.0, 70.0), game("B", "C", 60.0, 60.0), game("A", "C", 75.0, 50.0)];
+        let elo = elo_from_games(log, 20.0, 100.0).unwrap();
+        assert_eq!(elo.n_games, 3);
+        // Updates are zero-sum.
+        assert!((elo.ratings.values().sum::<f64>() - 3.0 * INITIAL_ELO).abs() < 1e-9);
+        assert!(elo.ratings["A"] > elo.ratings["B"] && elo.ratings["A"] > elo.ratings["C"]);
+        assert!((elo.ratings["A"] - 1519.70).abs() < 0.01);
+
+        // Home advantage: winning at home earns less than winning on the road.
+        let home_win = GameResult::new("A".into(), "B".into(), 1.0, 0.0, false, None);
+        let home = elo_from_games(vec![home_win], 20.0, 100.0).unwrap();
+        assert!(home.ratings["A"] - INITIAL_ELO < 10.0);
+        let road_diff = home.ratings["A"] + 100.0 - home.ratings["B"];
+        assert!((home.win_prob("A", "B", false).unwrap() - elo_win_prob(road_diff)).abs() < 1e-12);
+        assert!(elo_from_games(vec![game("A", "A", 1.0, 0.0)], 20.0, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_to_teams_matches_elo_against_average() {
+        let text = "team1,team2,score1,score2,neutral\nA,B,70,60,true\nA,C,70,60,1\nB,C,70,60,false\n";
+        let log = GameResult::parse_log(text).unwrap();
+        assert_eq!(log.len(), 3);
+        assert!(log[1].neutral && !log[2].neutral);
+        let elo = elo_from_games(log, 32.0, 100.0).unwrap();
+
+        let teams = elo.to_teams(None, None);
+        let average = Team::new("Avg".to_string(), 0.0, 0.0, ModelParams::default().avg_tempo, false);
+        let mean = elo.ratings.values().sum::<f64>() / 3.0;
+        for (name, team) in &teams {
+            let expected = elo_win_prob(elo.ratings[name] - mean);
+            assert!((calculate_win_prob(team, &average, None, 0.0) - expected).abs() < 1e-9);
+        }
+        assert!(calculate_win_prob(&teams["A"], &teams["C"], None, 0.0) > 0.5);
+        assert!(GameResult::parse_log("team1,team2,score1\nA,B,1\n").is_err());
+        assert!(GameResult::parse_log("team1,team2,score1,score2\nA,B,1,x\n").is_err());
+    }
+
+    #[test]
+    fn test_adjusted_ratings_account_for_opponents() {
+        // B and C score the same against A, but B does it in fewer possessions
+        // and C also beats D, the weakest team.
+        let text = "team1,team2,score1,score2,possessions\n\
+                    A,B,80,70,70\nA,C,80,70,70\nC,D,75,60,70\nB,D,70,65,70\nA,D,90,55,70\nB,C,70,70,70\n";
+        let log = GameResult::parse_log(text).unwrap();
+        let teams = adjusted_ratings(log.clone(), 100, 1e-9).unwrap();
+        assert_eq!(teams.len(), 4);
+        let net = |team: &str| teams[team].offense - teams[team].defense;
+        assert!(net("A") > net("C") && net("C") > net("B") && net("B") > net("D"));
+        // Relative to the log's average, offense and defense each average zero.
+        let mean_offense = teams.values().map(|t| t.offense).sum::<f64>() / 4.0;
+        assert!(mean_offense.abs() < 0.02);
+        assert!(teams.values().all(|t| (t.tempo - 70.0).abs() < 1e-9));
+
+        let mut missing = log;
+        missing[0].possessions = None;
+        assert!(adjusted_ratings(missing, 100, 1e-9).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! Order-independent floating point reductions.
+//!
+//! Floating point addition is not associative, so sums taken in hash-map order
+//! or over thread-count-dependent splits can differ in the last bits from run to
+//! run. These helpers fix the order: keyed terms are sorted by key, parallel
+//! sums use fixed-size chunks, and every sum is pairwise, which also keeps the
+//! rounding error at O(log n). `TournamentState::deterministic` routes scoring
+//! and delta computations through them so CI comparisons are bit-stable.
+
+use rayon::prelude::*;
+
+/// Chunk size for `par_sum`; fixed so results do not depend on thread count.
+pub const DETERMINISTIC_CHUNK: usize = 1024;
+
+/// Below this length `pairwise_sum` adds sequentially.
+const PAIRWISE_BASE: usize = 8;
+
+/// Pairwise (cascade) sum of `values`, in slice order.
+pub fn pairwise_sum(values: &[f64]) -> f64 {
+    if values.len() <= PAIRWISE_BASE {
+        values.iter().sum()
+    } else {
+        let (left, right) = values.split_at(values.len() / 2);
+        pairwise_sum(left) + pairwise_sum(right)
+    }
+}
+
+/// Parallel sum over fixed `DETERMINISTIC_CHUNK`-sized chunks, combined in
+/// chunk order; bit-identical for any thread count.
+pub fn par_sum(values: &[f64]) -> f64 {
+    let partials: Vec<f64> = values.par_chunks(DETERMINISTIC_CHUNK).map(pairwise_sum).collect();
+    pairwise_sum(&partials)
+}
+
+/// Pairwise sum of keyed terms in key order, so hash-map iteration order does
+/// not matter.
+pub fn keyed_sum<K: Ord>(terms: impl IntoIterator<Item = (K, f64)>) -> f64 {
+    let mut terms: Vec<(K, f64)> = terms.into_iter().collect();
+    terms.sort_by(|a, b| a.0.cmp(&b.0));
+    let values: Vec<f64> = terms.into_iter().map(|(_, v)| v).collect();
+    pairwise_sum(&values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_sum_independent_of_thread_count() {
+        let values: Vec<f64> = (0..10_000).map(|i| 1.0 / (1.0 + i as f64)).collect();
+        let sum_with = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            pool.install(|| par_sum(&values))
+        };
+        let single = sum_with(1);
+        assert_eq!(single.to_bits(), sum_with(3).to_bits());
+        assert_eq!(single.to_bits(), sum_with(8).to_bits());
+        assert!((single - values.iter().sum::<f64>()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_keyed_sum_ignores_input_order() {
+        let terms = [("c", 1e16), ("a", 1.0), ("b", -1e16), ("d", 3.0)];
+        let forward = keyed_sum(terms);
+        let reverse = keyed_sum(terms.into_iter().rev());
+        assert_eq!(forward.to_bits(), reverse.to_bits());
+        assert_eq!(pairwise_sum(&[]), 0.0);
+    }
+}
@@ -0,0 +1,250 @@
+//! Human-readable tournament and portfolio reports.
+//!
+//! A report lists every bracket team's expected points and championship odds,
+//! plus position, value, and delta columns when a portfolio is supplied, and a
+//! region breakdown of portfolio exposure. It renders as a plain-text table
+//! for terminals or as a standalone HTML fragment.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::fmt::Write;
+
+use crate::error::{Result, TourneyError};
+use crate::export::bracket_levels;
+use crate::exposure::ExposureReport;
+use crate::portfolio::PortfolioState;
+use crate::tournament::TournamentState;
+
+/// One team's line in a report.
+#[derive(Clone, Debug)]
+struct ReportRow {
+    label: String,
+    expected_points: f64,
+    championship: f64,
+    /// (position, position value, portfolio delta), when a portfolio is given.
+    holding: Option<(f64, f64, f64)>,
+}
+
+/// Report contents, ready to render.
+#[derive(Clone, Debug)]
+struct Report {
+    rows: Vec<ReportRow>,
+    total_value: Option<f64>,
+    /// Region name, value share, delta share.
+    regions: Vec<(String, f64, f64)>,
+}
+
+impl Report {
+    fn build(state: &TournamentState, portfolio: Option<&PortfolioState>) -> Result<Self> {
+        let scores = state.calculate_scores_prob();
+        let levels = bracket_levels(state)?;
+        let champion_odds = levels.last().and_then(|level| level.first()).cloned().unwrap_or_default();
+
+        let mut rows: Vec<ReportRow> = state
+            .get_bracket_teams()
+            .into_iter()
+            .map(|team| {
+                let expected_points = scores.get(&team).copied().unwrap_or(0.0);
+                let holding = portfolio.map(|p| {
+                    let position = p.positions().get(&team).copied().unwrap_or(0.0);
+                    let delta = p.team_deltas.get(&team).copied().unwrap_or(0.0);
+                    (position, position * expected_points, delta)
+                });
+                ReportRow {
+                    label: state.ratings.get(&team).map_or_else(|| team.clone(), |t| t.label()),
+                    expected_points,
+                    championship: champion_odds.get(&team).copied().unwrap_or(0.0),
+                    holding,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| b.expected_points.total_cmp(&a.expected_points).then_with(|| a.label.cmp(&b.label)));
+
+        // Region breakdown only applies to brackets that split into four regions.
+        let mut regions: Vec<(String, f64, f64)> = portfolio
+            .and_then(|p| ExposureReport::build(p.positions(), state, &p.team_deltas, None).ok())
+            .map(|exposure| {
+                exposure
+                    .by_region
+                    .into_iter()
+                    .map(|(name, bucket)| (name, bucket.value_share, bucket.delta_share))
+                    .collect()
+            })
+            .unwrap_or_default();
+        regions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(Report {
+            total_value: portfolio.map(|_| rows.iter().filter_map(|r| r.holding).map(|h| h.1).sum()),
+            rows,
+            regions,
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let width = self.rows.iter().map(|r| r.label.len()).max().unwrap_or(0).max(4);
+        let mut out = format!("{:<width$}  {:>8}  {:>7}", "Team", "Exp Pts", "Champ%");
+        if self.total_value.is_some() {
+            out.push_str(&format!("  {:>9}  {:>9}  {:>9}", "Position", "Value", "Delta"));
+        }
+        out.push('\n');
+        let rule_len = out.trim_end().len();
+        out.push_str(&"-".repeat(rule_len));
+        out.push('\n');
+
+        for row in &self.rows {
+            let _ = write!(
+                out,
+                "{:<width$}  {:>8.3}  {:>6.2}%",
+                row.label,
+                row.expected_points,
+                100.0 * row.championship
+            );
+            if let Some((position, value, delta)) = row.holding {
+                let _ = write!(out, "  {position:>9.2}  {value:>9.3}  {delta:>9.3}");
+            }
+            out.push('\n');
+        }
+
+        if let Some(total) = self.total_value {
+            let _ = writeln!(out, "\nPortfolio value: {total:.3}");
+        }
+        if !self.regions.is_empty() {
+            out.push_str("\nExposure by region:\n");
+            for (name, value_share, delta_share) in &self.regions {
+                let _ = writeln!(
+                    out,
+                    "  {name:<12} {:>6.1}% of value  {:>6.1}% of delta",
+                    100.0 * value_share,
+                    100.0 * delta_share
+                );
+            }
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = String::from("<table class=\"tourney-report\">\n<thead><tr><th>Team</th><th>Exp Pts</th><th>Champ%</th>");
+        if self.total_value.is_some() {
+            out.push_str("<th>Position</th><th>Value</th><th>Delta</th>");
+        }
+        out.push_str("</tr></thead>\n<tbody>\n");
+
+        for row in &self.rows {
+            let _ = write!(
+                out,
+                "<tr><td>{}</td><td>{:.3}</td><td>{:.2}%</td>",
+                escape_html(&row.label),
+                row.expected_points,
+                100.0 * row.championship
+            );
+            if let Some((position, value, delta)) = row.holding {
+                let _ = write!(out, "<td>{position:.2}</td><td>{value:.3}</td><td>{delta:.3}</td>");
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</tbody>\n</table>\n");
+
+        if let Some(total) = self.total_value {
+            let _ = writeln!(out, "<p>Portfolio value: {total:.3}</p>");
+        }
+        if !self.regions.is_empty() {
+            out.push_str("<table class=\"tourney-exposure\">\n<thead><tr><th>Region</th><th>Value share</th><th>Delta share</th></tr></thead>\n<tbody>\n");
+            for (name, value_share, delta_share) in &self.regions {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{:.1}%</td><td>{:.1}%</td></tr>",
+                    escape_html(name),
+                    100.0 * value_share,
+                    100.0 * delta_share
+                );
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        out
+    }
+}
+
+/// Plain-text report of expected scores, championship odds, and (optionally)
+/// portfolio holdings and exposure.
+pub fn text_report(state: &TournamentState, portfolio: Option<&PortfolioState>) -> Result<String> {
+    Ok(Report::build(state, portfolio)?.to_text())
+}
+
+/// HTML fragment with the same contents as `text_report`.
+pub fn html_report(state: &TournamentState, portfolio: Option<&PortfolioState>) -> Result<String> {
+    Ok(Report::build(state, portfolio)?.to_html())
+}
+
+/// Formatted bracket report as `"text"` or `"html"`.
+///
+/// Portfolio columns use the portfolio's stored deltas; call `compute_deltas`
+/// first to populate them.
+#[pyfunction]
+#[pyo3(signature = (tournament, portfolio = None, format = "text"))]
+pub fn bracket_report(tournament: &TournamentState, portfolio: Option<&PortfolioState>, format: &str) -> Result<String> {
+    match format {
+        "text" => text_report(tournament, portfolio),
+        "html" => html_report(tournament, portfolio),
+        other => Err(TourneyError::InvalidInput(format!(
+            "unknown report format {other:?}; expected \"text\" or \"html\""
+        ))),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+    use std::collections::HashMap;
+
+    /// The canonical 4-team field with its weakest team renamed to one that
+    /// needs HTML escaping.
+    fn state_with_ampersand() -> TournamentState {
+        let canonical = canonical_tournament(4);
+        let rename = |name: &String| if name == "Team 04" { "A&M".to_string() } else { name.clone() };
+        let bracket = canonical
+            .bracket()
+            .iter()
+            .map(|slot| slot.iter().map(|(team, p)| (rename(team), *p)).collect())
+            .collect();
+        let ratings = canonical
+            .ratings
+            .values()
+            .map(|team| {
+                let mut team = team.clone();
+                team.name = rename(&team.name);
+                (team.name.clone(), team)
+            })
+            .collect();
+        TournamentState::new(bracket, ratings, canonical.scoring.clone(), None, 0.0, None)
+    }
+
+    #[test]
+    fn test_text_report_lists_teams_by_expected_points() {
+        let text = text_report(&canonical_tournament(4), None).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("Team"));
+        assert!(!lines[0].contains("Position"));
+        // Strongest team first, four team rows after the header and rule.
+        assert!(lines[2].starts_with("(1) Team 01"));
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn test_html_report_with_portfolio() {
+        let state = state_with_ampersand();
+        let positions: HashMap<String, f64> = [("Team 01".to_string(), 10.0)].into_iter().collect();
+        let mut portfolio = PortfolioState::new(state.clone(), positions, 1.0);
+        portfolio.compute_deltas();
+
+        let html = bracket_report(&state, Some(&portfolio), "html").unwrap();
+        assert!(html.contains("<td>(4) A&amp;M</td>"));
+        assert!(html.contains("<th>Delta</th>"));
+        assert!(html.contains("<td>Region 1</td><td>100.0%</td>"));
+        assert!(bracket_report(&state, None, "pdf").is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Named random streams derived from one master seed.
+//!
+//! Drawing every kind of randomness from a single RNG ties each feature's
+//! draws to how many numbers the features before it consumed: adding a draw
+//! anywhere shifts everything after it, and reproducible results silently
+//! change. `RngStreams` gives each subsystem its own ChaCha stream of the
+//! master seed instead, so a subsystem's draws depend only on the seed and on
+//! its own use of them.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// A subsystem with its own random stream. The discriminant is the ChaCha
+/// stream id: never renumber a variant, only add new ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    /// Play-in resolution and game winners.
+    GameOutcomes = 1,
+    /// Whether each team shows up for a game.
+    Forfeits = 2,
+    /// Sampling model parameters and ratings around their estimates.
+    ParameterUncertainty = 3,
+    /// Resampling simulations for confidence intervals.
+    Bootstrap = 4,
+}
+
+/// Independent RNG streams for one master seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RngStreams {
+    seed: u64,
+}
+
+impl RngStreams {
+    /// Streams of `seed`, or of a master seed drawn from system entropy when
+    /// none is given (so the streams are still independent of each other).
+    pub fn new(seed: Option<u64>) -> Self {
+        RngStreams { seed: seed.unwrap_or_else(rand::random) }
+    }
+
+    /// The master seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// A fresh RNG for `stream`, starting from the beginning of the stream.
+    pub fn stream(&self, stream: RngStream) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(stream as u64);
+        rng
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(rng: &mut ChaCha8Rng) -> Vec<u64> {
+        (0..8).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_streams_reproduce_from_seed() {
+        let (a, b) = (RngStreams::new(Some(7)), RngStreams::new(Some(7)));
+        assert_eq!(draws(&mut a.stream(RngStream::Forfeits)), draws(&mut b.stream(RngStream::Forfeits)));
+        assert_ne!(
+            draws(&mut a.stream(RngStream::Forfeits)),
+            draws(&mut RngStreams::new(Some(8)).stream(RngStream::Forfeits))
+        );
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let streams = RngStreams::new(Some(7));
+        let outcomes = draws(&mut streams.stream(RngStream::GameOutcomes));
+        assert_ne!(outcomes, draws(&mut streams.stream(RngStream::Forfeits)));
+        assert_ne!(outcomes, draws(&mut streams.stream(RngStream::ParameterUncertainty)));
+        // Consuming one stream leaves the others where they were.
+        let mut forfeits = streams.stream(RngStream::Forfeits);
+        draws(&mut forfeits);
+        assert_eq!(outcomes, draws(&mut streams.stream(RngStream::GameOutcomes)));
+    }
+}
@@ -0,0 +1,187 @@
+//! Rooting interests: who to cheer for in the games about to be played.
+//!
+//! A game is upcoming once both of its participants are settled (every game
+//! feeding it is decided, usually by certain overrides for results already
+//! in) and it is not yet decided itself. For each upcoming game, the holder's
+//! value is recomputed with each side winning, exactly like `game_delta`; the
+//! side worth more is the one to root for, and the gap is the stake. Play-in
+//! games are resolved through slot probabilities rather than overrides and
+//! are not listed.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::entry::BracketEntry;
+use crate::error::Result;
+use crate::export::bracket_levels;
+use crate::game_id::{GameId, Round};
+use crate::portfolio::get_portfolio_value_ref;
+use crate::tournament::TournamentState;
+
+/// Tolerance for treating a node's probability as certain, and a stake as
+/// zero.
+const CERTAIN: f64 = 1e-9;
+
+/// The stakes of one upcoming game.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootingInterest {
+    #[pyo3(get)]
+    pub team1: String,
+
+    #[pyo3(get)]
+    pub team2: String,
+
+    /// Main-bracket round (0-based) of the game.
+    #[pyo3(get)]
+    pub round: Round,
+
+    /// The game itself.
+    #[pyo3(get)]
+    pub game: GameId,
+
+    #[pyo3(get)]
+    pub value_if_team1: f64,
+
+    #[pyo3(get)]
+    pub value_if_team2: f64,
+
+    /// The side worth more, or `None` if the outcome does not matter.
+    #[pyo3(get)]
+    pub root_for: Option<String>,
+
+    /// Absolute value difference between the two outcomes.
+    #[pyo3(get)]
+    pub stake: f64,
+}
+
+#[pymethods]
+impl RootingInterest {
+    fn __repr__(&self) -> String {
+        format!(
+            "RootingInterest({} vs {}, root_for={:?}, stake={:.4})",
+            self.team1, self.team2, self.root_for, self.stake
+        )
+    }
+}
+
+/// The settled team of a node, if one is certain to be there.
+fn settled(node: &HashMap<String, f64>) -> Option<&String> {
+    node.iter().find(|(_, &p)| p >= 1.0 - CERTAIN).map(|(team, _)| team)
+}
+
+/// Upcoming games as `(team1, team2, game)`, in bracket order.
+#[pyfunction]
+pub fn upcoming_games(tournament: &TournamentState) -> Result<Vec<(String, String, GameId)>> {
+    let levels = bracket_levels(tournament)?;
+    let mut games = Vec::new();
+    for (round, pair) in (0..).map(Round).zip(levels.windows(2)) {
+        let (feeders, nodes) = (&pair[0], &pair[1]);
+        for (game, node) in nodes.iter().enumerate() {
+            if let (Some(team1), Some(team2), None) =
+                (settled(&feeders[2 * game]), settled(&feeders[2 * game + 1]), settled(node))
+            {
+                games.push((team1.clone(), team2.clone(), GameId::new(round, game)));
+            }
+        }
+    }
+    Ok(games)
+}
+
+/// Rooting interests for the holder valued by `value`, largest stakes first.
+fn rooting_report(
+    tournament: &TournamentState,
+    value: impl Fn(&TournamentState) -> Result<f64> + Sync,
+) -> Result<Vec<RootingInterest>> {
+    let mut report: Vec<RootingInterest> = upcoming_games(tournament)?
+        .into_par_iter()
+        .map(|(team1, team2, game)| {
+            let outcome = |prob: f64| {
+                let state = tournament
+                    .with_override(&team1, &team2, prob)
+                    .expect("certain outcomes are valid probabilities");
+                value(&state)
+            };
+            let (value_if_team1, value_if_team2) = (outcome(1.0)?, outcome(0.0)?);
+            let stake = (value_if_team1 - value_if_team2).abs();
+            let root_for = if stake < CERTAIN {
+                None
+            } else if value_if_team1 > value_if_team2 {
+                Some(team1.clone())
+            } else {
+                Some(team2.clone())
+            };
+            Ok(RootingInterest {
+                team1,
+                team2,
+                round: game.round,
+                game,
+                value_if_team1,
+                value_if_team2,
+                root_for,
+                stake,
+            })
+        })
+        .collect::<Result<_>>()?;
+    report.sort_by(|a, b| b.stake.total_cmp(&a.stake).then_with(|| a.team1.cmp(&b.team1)));
+    Ok(report)
+}
+
+/// Who a portfolio of `positions` should root for in each upcoming game,
+/// valued by expected portfolio points.
+#[pyfunction]
+pub fn rooting_interests(positions: HashMap<String, f64>, tournament: &TournamentState) -> Result<Vec<RootingInterest>> {
+    rooting_report(tournament, |state| Ok(get_portfolio_value_ref(&positions, &state.calculate_scores_prob())))
+}
+
+/// Who `entry` should root for in each upcoming game, valued by its expected
+/// score with `scoring[r]` points per correct round-`r` pick (default: the
+/// tournament's round points).
+#[pyfunction]
+#[pyo3(signature = (entry, tournament, scoring = None))]
+pub fn entry_rooting_interests(
+    entry: &BracketEntry,
+    tournament: &TournamentState,
+    scoring: Option<Vec<f64>>,
+) -> Result<Vec<RootingInterest>> {
+    rooting_report(tournament, |state| entry.expected_score(state, scoring.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::optimize_entry;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_upcoming_games_advance_with_results() {
+        let state = canonical_tournament(8);
+        let upcoming = upcoming_games(&state).unwrap();
+        assert_eq!(upcoming.len(), 4);
+        assert!(upcoming.iter().all(|(_, _, game)| game.round == Round::FIRST));
+
+        // Deciding both games feeding a semifinal makes it upcoming instead.
+        let (a, b, _) = upcoming[0].clone();
+        let (c, d, _) = upcoming[1].clone();
+        let played = state.with_override(&a, &b, 1.0).unwrap().with_override(&c, &d, 0.0).unwrap();
+        let upcoming = upcoming_games(&played).unwrap();
+        assert_eq!(upcoming.len(), 3);
+        assert!(upcoming.contains(&(a, d, GameId::new(Round(1), 0))));
+    }
+
+    #[test]
+    fn test_portfolio_and_entry_root_for_their_teams() {
+        let state = canonical_tournament(8);
+        let positions: HashMap<String, f64> = [("Team 01".to_string(), 10.0)].into();
+        let report = rooting_interests(positions, &state).unwrap();
+        assert_eq!(report[0].root_for.as_deref(), Some("Team 01"));
+        assert!(report.windows(2).all(|w| w[0].stake >= w[1].stake));
+
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        for interest in entry_rooting_interests(&chalk, &state, None).unwrap() {
+            assert!(chalk.picks[0].contains(interest.root_for.as_ref().unwrap()));
+        }
+    }
+}
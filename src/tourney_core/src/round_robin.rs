@@ -0,0 +1,266 @@
+//! Round-robin (conference race) standings.
+//!
+//! Each scheduled game is independent, so a team's expected wins and its
+//! distribution of win totals are exact (a sum of Bernoulli trials). Finishing
+//! places depend on how the whole table falls out, so they are simulated, and
+//! ties are broken like `GroupStage`: wins, then wins against the other tied
+//! teams, then a random draw.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::win_prob::calculate_win_prob_with;
+
+/// A league schedule: every team plays its listed games once each.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct RoundRobin {
+    #[pyo3(get)]
+    pub teams: Vec<String>,
+
+    /// Games as (team1, team2) pairs; a pair listed twice is played twice.
+    #[pyo3(get)]
+    pub schedule: Vec<(String, String)>,
+}
+
+/// Expected standings for a `RoundRobin` under a tournament's ratings.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct RoundRobinStandings {
+    /// Expected wins per team.
+    #[pyo3(get)]
+    pub expected_wins: HashMap<String, f64>,
+
+    /// Probability of each win total per team (index = wins).
+    #[pyo3(get)]
+    pub win_probs: HashMap<String, Vec<f64>>,
+
+    /// Probability of each finishing place per team (index 0 = first),
+    /// after tiebreakers.
+    #[pyo3(get)]
+    pub finish_probs: HashMap<String, Vec<f64>>,
+}
+
+#[pymethods]
+impl RoundRobinStandings {
+    fn __repr__(&self) -> String {
+        format!("RoundRobinStandings({} teams)", self.expected_wins.len())
+    }
+}
+
+#[pymethods]
+impl RoundRobin {
+    /// Create a league. Without a `schedule`, every team plays every other
+    /// team `games_per_pair` times.
+    #[cfg(feature = "python")]
+    #[new]
+    #[pyo3(signature = (teams, schedule = None, games_per_pair = 1))]
+    pub fn py_new(
+        teams: Vec<String>,
+        schedule: Option<Vec<(String, String)>>,
+        games_per_pair: usize,
+    ) -> PyResult<Self> {
+        Ok(RoundRobin::new(teams, schedule, games_per_pair)?)
+    }
+
+    /// Expected wins, win-total distributions, and finishing-place
+    /// probabilities, using `tournament` for ratings, overrides, and model.
+    /// Finishing places are estimated from `n_simulations` simulated seasons.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn standings(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<RoundRobinStandings> {
+        let games = self.game_probs(tournament)?;
+        let n = self.teams.len();
+
+        let mut win_dists = vec![vec![1.0]; n];
+        for &(i, j, p) in &games {
+            add_game(&mut win_dists[i], p);
+            add_game(&mut win_dists[j], 1.0 - p);
+        }
+
+        let mut rng = seeded_rng(seed);
+        let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+        let place_counts = seeds
+            .par_iter()
+            .fold(
+                || vec![vec![0u32; n]; n],
+                |mut counts, &sim_seed| {
+                    let order = simulate_order(n, &games, &mut ChaCha8Rng::seed_from_u64(sim_seed));
+                    for (place, &team) in order.iter().enumerate() {
+                        counts[team][place] += 1;
+                    }
+                    counts
+                },
+            )
+            .reduce(
+                || vec![vec![0u32; n]; n],
+                |mut a, b| {
+                    for (row_a, row_b) in a.iter_mut().zip(b) {
+                        row_a.iter_mut().zip(row_b).for_each(|(x, y)| *x += y);
+                    }
+                    a
+                },
+            );
+
+        let expected_wins: Vec<f64> = win_dists
+            .iter()
+            .map(|dist| dist.iter().enumerate().map(|(wins, p)| wins as f64 * p).sum())
+            .collect();
+        let finish_probs: Vec<Vec<f64>> = place_counts
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| f64::from(c) / n_simulations as f64).collect())
+            .collect();
+        Ok(RoundRobinStandings {
+            expected_wins: self.by_team(expected_wins),
+            win_probs: self.by_team(win_dists),
+            finish_probs: self.by_team(finish_probs),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RoundRobin({} teams, {} games)", self.teams.len(), self.schedule.len())
+    }
+}
+
+impl RoundRobin {
+    pub fn new(teams: Vec<String>, schedule: Option<Vec<(String, String)>>, games_per_pair: usize) -> Result<Self> {
+        if let Some(team) = teams.iter().enumerate().find_map(|(i, t)| teams[..i].contains(t).then_some(t)) {
+            return Err(TourneyError::InvalidInput(format!("{team} is listed twice")));
+        }
+        let schedule = schedule.unwrap_or_else(|| {
+            let pairs = teams
+                .iter()
+                .enumerate()
+                .flat_map(|(i, team1)| teams[i + 1..].iter().map(move |team2| (team1.clone(), team2.clone())));
+            pairs.flat_map(|pair| std::iter::repeat_n(pair, games_per_pair)).collect()
+        });
+        if let Some((team1, team2)) = schedule
+            .iter()
+            .find(|(team1, team2)| team1 == team2 || !teams.contains(team1) || !teams.contains(team2))
+        {
+            return Err(TourneyError::InvalidInput(format!(
+                "scheduled game {team1} vs {team2} is not between two different league teams"
+            )));
+        }
+        Ok(RoundRobin { teams, schedule })
+    }
+
+    fn by_team<T>(&self, values: Vec<T>) -> HashMap<String, T> {
+        self.teams.iter().cloned().zip(values).collect()
+    }
+
+    /// Scheduled games as (team1 index, team2 index, team1 win probability).
+    fn game_probs(&self, tournament: &TournamentState) -> Result<Vec<(usize, usize, f64)>> {
+        let index: HashMap<&str, usize> = self.teams.iter().enumerate().map(|(i, t)| (t.as_str(), i)).collect();
+        let rating = |name: &str| {
+            tournament
+                .ratings
+                .get(name)
+                .ok_or_else(|| TourneyError::InvalidInput(format!("no rating for {name}")))
+        };
+        self.schedule
+            .iter()
+            .map(|(team1, team2)| {
+                let p = calculate_win_prob_with(
+                    rating(team1)?,
+                    rating(team2)?,
+                    Some(&tournament.overrides),
+                    tournament.forfeit_prob,
+                    &tournament.model_params,
+                );
+                Ok((index[team1.as_str()], index[team2.as_str()], p))
+            })
+            .collect()
+    }
+}
+
+/// Fold one game won with probability `p` into a win-total distribution.
+fn add_game(dist: &mut Vec<f64>, p: f64) {
+    dist.push(0.0);
+    for wins in (1..dist.len()).rev() {
+        dist[wins] = dist[wins] * (1.0 - p) + dist[wins - 1] * p;
+    }
+    dist[0] *= 1.0 - p;
+}
+
+/// Play every game once and return team indices from first place to last.
+fn simulate_order<R: Rng>(n: usize, games: &[(usize, usize, f64)], rng: &mut R) -> Vec<usize> {
+    let mut wins = vec![0u32; n];
+    let mut beat = vec![vec![0u32; n]; n];
+    for &(i, j, p) in games {
+        let (winner, loser) = if rng.gen::<f64>() < p { (i, j) } else { (j, i) };
+        wins[winner] += 1;
+        beat[winner][loser] += 1;
+    }
+    let draw: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+
+    let head_to_head = |x: usize| -> u32 { (0..n).filter(|&o| wins[o] == wins[x]).map(|o| beat[x][o]).sum() };
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        wins[b]
+            .cmp(&wins[a])
+            .then_with(|| head_to_head(b).cmp(&head_to_head(a)))
+            .then_with(|| draw[b].total_cmp(&draw[a]))
+    });
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{canonical_ratings, canonical_team_name};
+
+    /// Tournament over an `n`-team canonical field, with its names strongest first.
+    fn make_tournament(n: usize) -> (TournamentState, Vec<String>) {
+        let tournament = TournamentState::new(Vec::new(), canonical_ratings(n), vec![1.0], None, 0.0, None);
+        (tournament, (0..n).map(canonical_team_name).collect())
+    }
+
+    #[test]
+    fn test_exact_wins_and_finish_probs() {
+        let (tournament, teams) = make_tournament(4);
+        let league = RoundRobin::new(teams.clone(), None, 2).unwrap();
+        assert_eq!(league.schedule.len(), 12);
+        let standings = league.standings(&tournament, 2000, Some(3)).unwrap();
+
+        // Every game produces one win.
+        assert!((standings.expected_wins.values().sum::<f64>() - 12.0).abs() < 1e-9);
+        for team in &teams {
+            let dist = &standings.win_probs[team];
+            assert_eq!(dist.len(), 7);
+            assert!((dist.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            assert!((standings.finish_probs[team].iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+        assert!(standings.expected_wins["Team 01"] > standings.expected_wins["Team 04"]);
+        assert!(standings.finish_probs["Team 01"][0] > standings.finish_probs["Team 04"][0]);
+    }
+
+    #[test]
+    fn test_overrides_and_schedule_validation() {
+        let (mut tournament, teams) = make_tournament(2);
+        tournament.overrides.add_override("Team 02", "Team 01", 0.75).unwrap();
+        let schedule = vec![(teams[0].clone(), teams[1].clone())];
+        let standings = RoundRobin::new(teams.clone(), Some(schedule), 1)
+            .unwrap()
+            .standings(&tournament, 10, Some(1))
+            .unwrap();
+        assert!((standings.expected_wins["Team 02"] - 0.75).abs() < 1e-12);
+        assert_eq!(standings.win_probs["Team 01"], vec![0.75, 0.25]);
+
+        let unknown = canonical_team_name(2);
+        assert!(RoundRobin::new(teams.clone(), Some(vec![(teams[0].clone(), unknown.clone())]), 1).is_err());
+        assert!(RoundRobin::new(vec![teams[0].clone(), teams[1].clone(), teams[0].clone()], None, 1).is_err());
+        let unrated = RoundRobin::new(vec![teams[0].clone(), unknown], None, 1).unwrap();
+        assert!(unrated.standings(&tournament, 10, None).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Scenario trees over the next few games.
+//!
+//! Before a day's games, the useful question is not just what the book is
+//! worth but how that value moves game by game, and what trade would neutralize
+//! each game before it tips. `scenario_tree` takes the next `n_games` upcoming
+//! games (see `upcoming_games`), branches on each in turn, and at every node
+//! records the probability of reaching it, the portfolio's expected value
+//! there, and the hedge for the next game.
+//!
+//! The hedge is the position in the next game's first team, traded at its
+//! current model value, that leaves portfolio value the same whichever side
+//! wins; that team's value moves the most with the game, so it is the most
+//! direct instrument.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::portfolio::get_portfolio_value_ref;
+use crate::rooting::upcoming_games;
+use crate::tournament::TournamentState;
+use crate::win_prob::calculate_win_prob_with;
+
+/// Most games `scenario_tree` will branch on (2^games leaves).
+pub const MAX_TREE_GAMES: usize = 12;
+
+/// One node of a scenario tree.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ScenarioNode {
+    /// Team whose win led here (`None` at the root).
+    #[pyo3(get)]
+    pub winner: Option<String>,
+
+    /// Probability of reaching this node from the root.
+    #[pyo3(get)]
+    pub prob: f64,
+
+    /// Expected portfolio value at this node.
+    #[pyo3(get)]
+    pub value: f64,
+
+    /// The game this node branches on (`None` at leaves).
+    #[pyo3(get)]
+    pub game: Option<(String, String)>,
+
+    /// Probability the game's first team wins.
+    #[pyo3(get)]
+    pub win_prob: Option<f64>,
+
+    /// Shares of the game's first team to trade (negative to sell) so the
+    /// portfolio is indifferent to the game.
+    #[pyo3(get)]
+    pub hedge_shares: Option<f64>,
+
+    /// Child for the first team winning, then for the second.
+    #[pyo3(get)]
+    pub children: Vec<ScenarioNode>,
+}
+
+#[pymethods]
+impl ScenarioNode {
+    /// Leaves of the subtree rooted here, in outcome order.
+    pub fn leaves(&self) -> Vec<ScenarioNode> {
+        if self.children.is_empty() {
+            return vec![self.clone()];
+        }
+        self.children.iter().flat_map(ScenarioNode::leaves).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ScenarioNode(winner={:?}, prob={:.4}, value={:.4}, game={:?}, hedge_shares={:?})",
+            self.winner, self.prob, self.value, self.game, self.hedge_shares
+        )
+    }
+}
+
+/// Scenario tree of `positions` over the next `n_games` upcoming games.
+#[pyfunction]
+pub fn scenario_tree(
+    positions: HashMap<String, f64>,
+    tournament: &TournamentState,
+    n_games: usize,
+) -> Result<ScenarioNode> {
+    if n_games > MAX_TREE_GAMES {
+        return Err(TourneyError::InvalidInput(format!(
+            "scenario trees are limited to {MAX_TREE_GAMES} games, got {n_games}"
+        )));
+    }
+    let games: Vec<(String, String)> =
+        upcoming_games(tournament)?.into_iter().take(n_games).map(|(team1, team2, _)| (team1, team2)).collect();
+    if games.len() < n_games {
+        return Err(TourneyError::InvalidInput(format!(
+            "only {} games are upcoming, asked for {n_games}",
+            games.len()
+        )));
+    }
+    let scores = tournament.calculate_scores_prob();
+    Ok(grow(&positions, tournament, &scores, &games, None, 1.0))
+}
+
+/// The subtree at `state`, whose expected scores are `scores`.
+fn grow(
+    positions: &HashMap<String, f64>,
+    state: &TournamentState,
+    scores: &HashMap<String, f64>,
+    games: &[(String, String)],
+    winner: Option<String>,
+    prob: f64,
+) -> ScenarioNode {
+    let value = get_portfolio_value_ref(positions, scores);
+    let Some(((team1, team2), rest)) = games.split_first() else {
+        return ScenarioNode {
+            winner,
+            prob,
+            value,
+            game: None,
+            win_prob: None,
+            hedge_shares: None,
+            children: Vec::new(),
+        };
+    };
+
+    let win_prob = calculate_win_prob_with(
+        &state.ratings[team1],
+        &state.ratings[team2],
+        Some(&state.overrides),
+        state.forfeit_prob,
+        &state.model_params,
+    );
+    // Each branch also reports team1's expected score in it.
+    let branch = |team: &String, team1_wins: f64, p: f64| {
+        let child = state.with_override(team1, team2, team1_wins).expect("certain outcomes are valid probabilities");
+        let child_scores = child.calculate_scores_prob();
+        let team1_score = child_scores.get(team1).copied().unwrap_or(0.0);
+        (grow(positions, &child, &child_scores, rest, Some(team.clone()), prob * p), team1_score)
+    };
+    let ((if_team1, score_if_team1), (if_team2, score_if_team2)) =
+        rayon::join(|| branch(team1, 1.0, win_prob), || branch(team2, 0.0, 1.0 - win_prob));
+
+    // Buying h shares of team1 at its current value changes each child's
+    // value by h * (its team1 score - current); pick h to equalize them.
+    let swing = score_if_team1 - score_if_team2;
+    let hedge_shares = (swing != 0.0).then(|| -(if_team1.value - if_team2.value) / swing);
+
+    ScenarioNode {
+        winner,
+        prob,
+        value,
+        game: Some((team1.clone(), team2.clone())),
+        win_prob: Some(win_prob),
+        hedge_shares,
+        children: vec![if_team1, if_team2],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_tree_values_are_consistent() {
+        let state = canonical_tournament(8);
+        let positions: HashMap<String, f64> = [("Team 01".to_string(), 5.0), ("Team 03".to_string(), 2.0)].into();
+        let root = scenario_tree(positions, &state, 3).unwrap();
+
+        let leaves = root.leaves();
+        assert_eq!(leaves.len(), 8);
+        assert!((leaves.iter().map(|l| l.prob).sum::<f64>() - 1.0).abs() < 1e-9);
+        // Expected value is a martingale: each node is its children's average.
+        let averaged: f64 = leaves.iter().map(|l| l.prob * l.value).sum();
+        assert!((averaged - root.value).abs() < 1e-9);
+        let p = root.win_prob.unwrap();
+        let children = &root.children;
+        assert!((p * children[0].value + (1.0 - p) * children[1].value - root.value).abs() < 1e-9);
+        assert!(leaves.iter().all(|l| l.game.is_none() && l.hedge_shares.is_none()));
+    }
+
+    #[test]
+    fn test_hedge_neutralizes_next_game() {
+        let state = canonical_tournament(8);
+        let (team1, team2, _) = upcoming_games(&state).unwrap().remove(0);
+        let positions: HashMap<String, f64> = [(team1.clone(), 4.0)].into();
+        let root = scenario_tree(positions, &state, 1).unwrap();
+        // Holding only team1, the hedge sells the whole position.
+        assert!((root.hedge_shares.unwrap() + 4.0).abs() < 1e-9);
+        assert_eq!(root.children[1].winner.as_deref(), Some(team2.as_str()));
+        assert!(scenario_tree(HashMap::new(), &state, 5).is_err());
+        assert!(scenario_tree(HashMap::new(), &state, MAX_TREE_GAMES + 1).is_err());
+    }
+}
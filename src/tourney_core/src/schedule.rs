@@ -0,0 +1,112 @@
+//! Strength of a team's remaining path through the bracket.
+//!
+//! A team's opponent in round `r` comes from the sibling subtree of its own
+//! subtree at that depth, so the chance of meeting each possible opponent is
+//! (chance the team gets there) x (chance the opponent wins its side). The
+//! resulting probability-weighted opponent ratings quantify "easy draw" and
+//! "brutal region" claims from the bracket itself.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use crate::error::{Result, TourneyError};
+use crate::export::bracket_levels;
+use crate::tournament::TournamentState;
+
+/// Expected opponent quality over a team's remaining main-bracket games.
+///
+/// Ratings are `Team::net_rating` under the state's `avg_scoring`.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct ScheduleStrength {
+    #[pyo3(get)]
+    pub team: String,
+
+    /// Probability of playing each remaining round.
+    #[pyo3(get)]
+    pub play_prob: Vec<f64>,
+
+    /// Expected rating of the opponent in each round, given the team plays it.
+    #[pyo3(get)]
+    pub opponent_rating: Vec<f64>,
+
+    /// `opponent_rating` averaged over rounds, weighted by `play_prob`.
+    #[pyo3(get)]
+    pub expected_opponent_rating: f64,
+}
+
+#[pymethods]
+impl ScheduleStrength {
+    fn __repr__(&self) -> String {
+        format!(
+            "ScheduleStrength({}, expected_opponent_rating={:.2}, rounds={})",
+            self.team,
+            self.expected_opponent_rating,
+            self.play_prob.len()
+        )
+    }
+}
+
+/// Strength of `team`'s remaining schedule in `state`.
+pub fn strength_of_remaining_schedule(state: &TournamentState, team: &str) -> Result<ScheduleStrength> {
+    if state.reseed {
+        return Err(TourneyError::InvalidInput(
+            "remaining schedule follows the fixed bracket and is undefined when reseeding".to_string(),
+        ));
+    }
+    let slot = state
+        .bracket()
+        .iter()
+        .position(|slot| slot.contains_key(team))
+        .ok_or_else(|| TourneyError::InvalidInput(format!("team not in bracket: {team}")))?;
+    let rating = |name: &str| {
+        let t = state.ratings.get(name).unwrap_or_else(|| panic!("team not found in ratings: {name}"));
+        t.net_rating(state.model_params.avg_scoring)
+    };
+
+    let levels = bracket_levels(state)?;
+    let (mut play_prob, mut opponent_rating) = (Vec::new(), Vec::new());
+    for (depth, level) in levels.iter().take(levels.len() - 1).enumerate() {
+        let node = slot >> depth;
+        play_prob.push(level[node].get(team).copied().unwrap_or(0.0));
+        opponent_rating.push(level[node ^ 1].iter().map(|(name, p)| p * rating(name)).sum());
+    }
+
+    let total_prob: f64 = play_prob.iter().sum();
+    let weighted: f64 = play_prob.iter().zip(&opponent_rating).map(|(p, r)| p * r).sum();
+    Ok(ScheduleStrength {
+        team: team.to_string(),
+        expected_opponent_rating: if total_prob > 0.0 { weighted / total_prob } else { 0.0 },
+        play_prob,
+        opponent_rating,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_first_round_opponent_is_known() {
+        let state = canonical_tournament(16);
+        let top = strength_of_remaining_schedule(&state, "Team 01").unwrap();
+        assert_eq!(top.play_prob.len(), 4);
+        assert_eq!(top.play_prob[0], 1.0);
+
+        let expected = state.ratings["Team 13"].net_rating(state.model_params.avg_scoring);
+        assert!((top.opponent_rating[0] - expected).abs() < 1e-12);
+        // Later rounds are less likely to be played.
+        assert!(top.play_prob.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_underdog_faces_harder_path() {
+        let state = canonical_tournament(16);
+        let top = strength_of_remaining_schedule(&state, "Team 01").unwrap();
+        let bottom = strength_of_remaining_schedule(&state, "Team 16").unwrap();
+        assert!(bottom.expected_opponent_rating > top.expected_opponent_rating);
+        assert!(strength_of_remaining_schedule(&state, "Nobody").is_err());
+    }
+}
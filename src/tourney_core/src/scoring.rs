@@ -0,0 +1,227 @@
+//! Pool scoring rules.
+//!
+//! Most pools pay a fixed number of points per round, which a bare
+//! `Vec<f64>` captures. Dynasty-style pools also discount later rounds (points
+//! earned weeks from now are worth less than points earned this weekend), so a
+//! `ScoringRule` pairs the nominal points with a per-round discount factor and
+//! produces the effective points that expected values should be computed with.
+//!
+//! Pools often stack several awards on each correct pick: base points, a bonus
+//! per seed of the winner, and a bonus for upsets. Each is a rule of its own,
+//! and `ScoringRule.combine` (or `+` in Python) sums them into one.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TourneyError};
+use crate::tournament::TournamentState;
+
+/// Points for a correct pick in each round, plus a compounding per-round
+/// discount.
+///
+/// A correct round-`r` pick of a winner seeded `w` over a loser seeded `l`
+/// earns `points[r] + seed_bonus[r] * w + upset_bonus[r] * max(w - l, 0)`,
+/// scaled by `discount^r`. Missing rounds earn nothing from that component.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoringRule {
+    /// Nominal points per round, indexed like `TournamentState::scoring`.
+    #[pyo3(get)]
+    pub points: Vec<f64>,
+
+    /// Value of a point one round later relative to now; round `r` points
+    /// are worth `discount^r`. 1.0 means no discounting.
+    #[pyo3(get)]
+    pub discount: f64,
+
+    /// Points per seed of the winner, per round.
+    #[pyo3(get)]
+    #[serde(default)]
+    pub seed_bonus: Vec<f64>,
+
+    /// Points per seed line of upset margin, per round.
+    #[pyo3(get)]
+    #[serde(default)]
+    pub upset_bonus: Vec<f64>,
+}
+
+#[pymethods]
+impl ScoringRule {
+    #[cfg(feature = "python")]
+    #[new]
+    #[pyo3(signature = (points, discount = 1.0, seed_bonus = None, upset_bonus = None))]
+    pub fn py_new(
+        points: Vec<f64>,
+        discount: f64,
+        seed_bonus: Option<Vec<f64>>,
+        upset_bonus: Option<Vec<f64>>,
+    ) -> PyResult<Self> {
+        let mut rule = Self::new(points, discount)?;
+        rule.seed_bonus = seed_bonus.unwrap_or_default();
+        rule.upset_bonus = upset_bonus.unwrap_or_default();
+        Ok(rule)
+    }
+
+    /// A rule awarding only `per_seed[r]` points per seed of a round-`r` winner.
+    #[staticmethod]
+    pub fn seed_bonus_rule(per_seed: Vec<f64>) -> Self {
+        ScoringRule {
+            seed_bonus: per_seed,
+            ..ScoringRule::default()
+        }
+    }
+
+    /// A rule awarding only `per_seed[r]` points per seed line a round-`r`
+    /// winner was seeded below its opponent.
+    #[staticmethod]
+    pub fn upset_bonus_rule(per_seed: Vec<f64>) -> Self {
+        ScoringRule {
+            upset_bonus: per_seed,
+            ..ScoringRule::default()
+        }
+    }
+
+    /// The sum of `rules`: every pick earns what each rule would award it.
+    /// Discounts are folded into the components, so the result is undiscounted.
+    #[staticmethod]
+    pub fn combine(rules: Vec<ScoringRule>) -> Self {
+        let mut total = ScoringRule::default();
+        for rule in &rules {
+            let factors = rule.factors(rule.points.len().max(rule.seed_bonus.len()).max(rule.upset_bonus.len()));
+            add_scaled(&mut total.points, &rule.points, &factors);
+            add_scaled(&mut total.seed_bonus, &rule.seed_bonus, &factors);
+            add_scaled(&mut total.upset_bonus, &rule.upset_bonus, &factors);
+        }
+        total
+    }
+
+    fn __add__(&self, other: &ScoringRule) -> ScoringRule {
+        ScoringRule::combine(vec![self.clone(), other.clone()])
+    }
+
+    /// Base points per round after discounting.
+    pub fn effective_points(&self) -> Vec<f64> {
+        let factors = self.factors(self.points.len());
+        self.points.iter().zip(factors).map(|(points, factor)| points * factor).collect()
+    }
+
+    /// Points for a correct round-`round` pick of a `winner_seed` team over a
+    /// `loser_seed` team.
+    pub fn pick_points(&self, round: usize, winner_seed: u32, loser_seed: u32) -> f64 {
+        let at = |values: &[f64]| values.get(round).copied().unwrap_or(0.0);
+        let upset_margin = winner_seed.saturating_sub(loser_seed) as f64;
+        let nominal =
+            at(&self.points) + at(&self.seed_bonus) * winner_seed as f64 + at(&self.upset_bonus) * upset_margin;
+        nominal * self.discount.powi(round as i32)
+    }
+
+    /// A copy of `tournament` scored with this rule's effective points.
+    ///
+    /// Seed and upset bonuses depend on who a team beats, which per-round
+    /// team scores cannot express, so rules with bonuses are rejected.
+    pub fn apply(&self, tournament: &TournamentState) -> Result<TournamentState> {
+        if !self.seed_bonus.is_empty() || !self.upset_bonus.is_empty() {
+            return Err(TourneyError::InvalidInput(
+                "seed and upset bonuses only apply to bracket entries".to_string(),
+            ));
+        }
+        let mut scored = tournament.clone();
+        scored.scoring = self.effective_points();
+        Ok(scored)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ScoringRule(points={:?}, discount={}, seed_bonus={:?}, upset_bonus={:?})",
+            self.points, self.discount, self.seed_bonus, self.upset_bonus
+        )
+    }
+}
+
+impl Default for ScoringRule {
+    fn default() -> Self {
+        ScoringRule {
+            points: Vec::new(),
+            discount: 1.0,
+            seed_bonus: Vec::new(),
+            upset_bonus: Vec::new(),
+        }
+    }
+}
+
+impl ScoringRule {
+    pub fn new(points: Vec<f64>, discount: f64) -> Result<Self> {
+        if !(discount > 0.0 && discount <= 1.0) {
+            return Err(TourneyError::InvalidInput(format!(
+                "discount must be in (0, 1], got {discount}"
+            )));
+        }
+        Ok(ScoringRule {
+            points,
+            discount,
+            ..ScoringRule::default()
+        })
+    }
+
+    /// Discount factors for the first `n` rounds.
+    fn factors(&self, n: usize) -> Vec<f64> {
+        std::iter::successors(Some(1.0), |factor| Some(factor * self.discount)).take(n).collect()
+    }
+}
+
+/// `total[r] += values[r] * factors[r]`, growing `total` as needed.
+fn add_scaled(total: &mut Vec<f64>, values: &[f64], factors: &[f64]) {
+    if total.len() < values.len() {
+        total.resize(values.len(), 0.0);
+    }
+    for ((sum, value), factor) in total.iter_mut().zip(values).zip(factors) {
+        *sum += value * factor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ROUND_POINTS;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_effective_points_compound() {
+        let rule = ScoringRule::new(vec![1.0, 2.0, 4.0, 8.0], 0.5).unwrap();
+        assert_eq!(rule.effective_points(), [1.0, 1.0, 1.0, 1.0]);
+        let flat = ScoringRule::new(ROUND_POINTS.to_vec(), 1.0).unwrap();
+        assert_eq!(flat.effective_points(), ROUND_POINTS.to_vec());
+        assert!(ScoringRule::new(vec![1.0], 0.0).is_err());
+        assert!(ScoringRule::new(vec![1.0], 1.5).is_err());
+    }
+
+    #[test]
+    fn test_discount_shifts_value_toward_early_rounds() {
+        let state = canonical_tournament(16);
+        let points = vec![1.0, 2.0, 4.0, 8.0];
+        let nominal = ScoringRule::new(points.clone(), 1.0).unwrap().apply(&state).unwrap().calculate_scores_prob();
+        let discounted = ScoringRule::new(points, 0.8).unwrap().apply(&state).unwrap().calculate_scores_prob();
+        // Favorites earn more of their value late, so they lose a larger share.
+        let ratio = |team: &str| discounted[team] / nominal[team];
+        assert!(ratio("Team 01") < ratio("Team 16"));
+        assert!(discounted.values().sum::<f64>() < nominal.values().sum::<f64>());
+    }
+
+    #[test]
+    fn test_combined_rule_sums_components() {
+        let base = ScoringRule::new(vec![1.0, 2.0], 0.5).unwrap();
+        let combined = ScoringRule::combine(vec![
+            base.clone(),
+            ScoringRule::seed_bonus_rule(vec![1.0]),
+            ScoringRule::upset_bonus_rule(vec![0.5, 0.5]),
+        ]);
+        // Round 0: 1 base + 12 seed + 0.5 * (12 - 5) upset.
+        assert_eq!(combined.pick_points(0, 12, 5), 16.5);
+        // Round 1: discounted base only; favorites earn no upset bonus.
+        assert_eq!(combined.pick_points(1, 1, 4), 1.0);
+        assert_eq!(combined.pick_points(1, 1, 4), base.pick_points(1, 1, 4));
+        assert!(combined.apply(&canonical_tournament(4)).is_err());
+        assert_eq!(base.__add__(&ScoringRule::default()), ScoringRule::combine(vec![base]));
+    }
+}
@@ -0,0 +1,62 @@
+//! Helpers for placing seeded teams into bracket slots.
+
+/// Bracket slot order for `n` seeds (a power of two), as 0-based seed indices.
+///
+/// Follows the standard layout where the top seed meets the lowest seed in the
+/// first round and the top two seeds can only meet in the final, e.g. for 8
+/// teams: 1v8, 4v5, 2v7, 3v6 (returned as `[0, 7, 3, 4, 1, 6, 2, 5]`).
+pub fn standard_seed_order(n: usize) -> Vec<usize> {
+    assert!(n.is_power_of_two(), "bracket size must be a power of two, got {n}");
+    let mut order = vec![0];
+    while order.len() < n {
+        let size = order.len() * 2;
+        order = order.iter().flat_map(|&seed| [seed, size - 1 - seed]).collect();
+    }
+    order
+}
+
+/// 1-based seeds of a 16-slot NCAA region, top to bottom.
+///
+/// Every round pairs the same seeds as `standard_seed_order(16)`, but the
+/// printed bracket puts the 5/12 game above the 4/13 game and the 2 seed at
+/// the bottom of the region.
+pub const NCAA_REGION_SEEDS: [usize; 16] = [1, 16, 8, 9, 5, 12, 4, 13, 6, 11, 3, 14, 7, 10, 2, 15];
+
+/// 1-based seed of bracket `slot` when the bracket is split into consecutive
+/// regions of `region_size` slots. 16-slot regions follow the NCAA layout
+/// (`NCAA_REGION_SEEDS`); other sizes follow standard seed order.
+pub fn seed_for_slot(slot: usize, region_size: usize) -> usize {
+    if region_size == NCAA_REGION_SEEDS.len() {
+        return NCAA_REGION_SEEDS[slot % region_size];
+    }
+    standard_seed_order(region_size)[slot % region_size] + 1
+}
+
+/// Label for the band of four seeds containing `seed`, e.g. 6 -> "5-8".
+pub fn seed_band(seed: usize) -> String {
+    let low = (seed - 1) / 4 * 4 + 1;
+    format!("{}-{}", low, low + 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_seed_order() {
+        assert_eq!(standard_seed_order(1), vec![0]);
+        assert_eq!(standard_seed_order(4), vec![0, 3, 1, 2]);
+        assert_eq!(standard_seed_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+    }
+
+    #[test]
+    fn test_seed_for_slot_and_band() {
+        let seeds: Vec<usize> = (0..8).map(|slot| seed_for_slot(slot, 4)).collect();
+        assert_eq!(seeds, vec![1, 4, 2, 3, 1, 4, 2, 3]);
+        let seeds: Vec<usize> = (16..32).map(|slot| seed_for_slot(slot, 16)).collect();
+        assert_eq!(seeds, vec![1, 16, 8, 9, 5, 12, 4, 13, 6, 11, 3, 14, 7, 10, 2, 15]);
+        assert_eq!(seed_band(1), "1-4");
+        assert_eq!(seed_band(8), "5-8");
+        assert_eq!(seed_band(13), "13-16");
+    }
+}
@@ -0,0 +1,186 @@
+//! Sensitivity of model outputs to the win probability model's constants.
+//!
+//! `SCORING_STDDEV` in particular is an estimate, and it sets how often
+//! favorites win. `sensitivity_report` re-runs the bracket under each
+//! `ModelParams` in a grid so conclusions can be checked against plausible
+//! alternative values.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::export::bracket_levels;
+use crate::portfolio::{get_portfolio_value_ref, PortfolioState};
+use crate::tournament::TournamentState;
+use crate::win_prob::ModelParams;
+
+/// Model outputs under one set of parameters.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SensitivityPoint {
+    #[pyo3(get)]
+    pub params: ModelParams,
+
+    /// Probability each bracket team wins the championship.
+    #[pyo3(get)]
+    pub championship: HashMap<String, f64>,
+
+    #[pyo3(get)]
+    pub expected_points: HashMap<String, f64>,
+
+    /// Portfolio value at `expected_points`, when a portfolio was given.
+    #[pyo3(get)]
+    pub portfolio_value: Option<f64>,
+}
+
+#[pymethods]
+impl SensitivityPoint {
+    fn __repr__(&self) -> String {
+        format!(
+            "SensitivityPoint(scoring_stddev={}, avg_scoring={}, portfolio_value={:?})",
+            self.params.scoring_stddev, self.params.avg_scoring, self.portfolio_value
+        )
+    }
+}
+
+impl SensitivityPoint {
+    fn evaluate(tournament: &TournamentState, params: ModelParams, portfolio: Option<&PortfolioState>) -> Result<Self> {
+        let mut state = tournament.clone();
+        state.model_params = params.clone();
+        let expected_points = state.calculate_scores_prob();
+        Ok(SensitivityPoint {
+            params,
+            championship: bracket_levels(&state)?
+                .last()
+                .and_then(|level| level.first())
+                .cloned()
+                .unwrap_or_default(),
+            portfolio_value: portfolio.map(|p| get_portfolio_value_ref(p.positions(), &expected_points)),
+            expected_points,
+        })
+    }
+}
+
+/// Model outputs across a grid of parameters, alongside the tournament's own.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SensitivityReport {
+    /// Outputs under the tournament's current `model_params`.
+    #[pyo3(get)]
+    pub baseline: SensitivityPoint,
+
+    /// Outputs for each grid entry, in grid order.
+    #[pyo3(get)]
+    pub points: Vec<SensitivityPoint>,
+}
+
+#[pymethods]
+impl SensitivityReport {
+    /// Largest absolute change in each team's championship odds from the
+    /// baseline across the grid.
+    pub fn max_championship_shift(&self) -> HashMap<String, f64> {
+        self.baseline
+            .championship
+            .iter()
+            .map(|(team, &base)| {
+                let shift = self
+                    .points
+                    .iter()
+                    .map(|point| (point.championship.get(team).copied().unwrap_or(0.0) - base).abs())
+                    .fold(0.0, f64::max);
+                (team.clone(), shift)
+            })
+            .collect()
+    }
+
+    /// (min, max) of `team`'s championship odds over the baseline and grid.
+    pub fn championship_range(&self, team: &str) -> (f64, f64) {
+        self.range(|point| point.championship.get(team).copied().unwrap_or(0.0))
+    }
+
+    /// (min, max) portfolio value over the baseline and grid, if a portfolio
+    /// was given.
+    pub fn portfolio_value_range(&self) -> Option<(f64, f64)> {
+        self.baseline.portfolio_value?;
+        Some(self.range(|point| point.portfolio_value.unwrap_or(0.0)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SensitivityReport({} grid points)", self.points.len())
+    }
+}
+
+impl SensitivityReport {
+    fn range(&self, value: impl Fn(&SensitivityPoint) -> f64) -> (f64, f64) {
+        std::iter::once(&self.baseline)
+            .chain(&self.points)
+            .map(value)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+    }
+}
+
+/// Championship odds, expected points, and (optionally) portfolio value under
+/// each `ModelParams` in `params_grid`.
+///
+/// `ModelParams.grid` builds a grid over scoring stddev and average scoring.
+#[pyfunction]
+#[pyo3(signature = (tournament, params_grid, portfolio = None))]
+pub fn sensitivity_report(
+    tournament: &TournamentState,
+    params_grid: Vec<ModelParams>,
+    portfolio: Option<&PortfolioState>,
+) -> Result<SensitivityReport> {
+    if let Some(bad) = params_grid
+        .iter()
+        .find(|p| !(p.avg_scoring > 0.0 && p.avg_tempo > 0.0 && p.scoring_stddev > 0.0))
+    {
+        return Err(TourneyError::InvalidInput(format!("model parameters must be positive, got {bad:?}")));
+    }
+    Ok(SensitivityReport {
+        baseline: SensitivityPoint::evaluate(tournament, tournament.model_params.clone(), portfolio)?,
+        points: params_grid
+            .into_par_iter()
+            .map(|params| SensitivityPoint::evaluate(tournament, params, portfolio))
+            .collect::<Result<_>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_higher_stddev_flattens_championship_odds() {
+        let state = canonical_tournament(16);
+        let grid = ModelParams::grid(vec![8.0, 11.0, 14.0], None);
+        let report = sensitivity_report(&state, grid, None).unwrap();
+        assert_eq!(report.points.len(), 3);
+
+        let favorite = |point: &SensitivityPoint| point.championship["Team 01"];
+        assert!(favorite(&report.points[0]) > favorite(&report.points[1]));
+        assert!(favorite(&report.points[1]) > favorite(&report.points[2]));
+        // The middle grid point matches the default constants.
+        assert!((favorite(&report.points[1]) - favorite(&report.baseline)).abs() < 1e-12);
+        assert!(report.max_championship_shift()["Team 01"] > 0.0);
+        assert!(report.portfolio_value_range().is_none());
+    }
+
+    #[test]
+    fn test_portfolio_value_tracked() {
+        let state = canonical_tournament(4);
+        let positions = HashMap::from([("Team 01".to_string(), 2.0)]);
+        let portfolio = PortfolioState::new(state.clone(), positions, 1.0);
+        let report = sensitivity_report(&state, ModelParams::grid(vec![9.0, 13.0], None), Some(&portfolio)).unwrap();
+
+        let (lo, hi) = report.portfolio_value_range().unwrap();
+        assert!(lo < hi);
+        let base = report.baseline.portfolio_value.unwrap();
+        assert!((base - 2.0 * state.calculate_scores_prob()["Team 01"]).abs() < 1e-12);
+
+        let bad = vec![ModelParams::new(104.6, 67.7, 0.0)];
+        assert!(sensitivity_report(&state, bad, None).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! HTTP scoring service (enabled by the `server` feature).
+//!
+//! Every endpoint takes a JSON body containing a serialized `TournamentState`
+//! (the same format as `TournamentState::to_json`) and returns JSON. Posted
+//! states are checked with `TournamentState::validate` first, and rejected
+//! with 400 and the violations found. Scoring runs on tokio's blocking pool so
+//! slow requests don't stall the reactor.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use crate::portfolio::{game_delta, get_all_team_deltas, TeamDelta};
+use crate::tournament::TournamentState;
+
+/// Upper bound on simulations per request, to keep one call from monopolizing the host.
+pub const MAX_SIMULATIONS: usize = 1_000_000;
+
+/// Error response carrying an HTTP status and a plain-text message.
+#[derive(Debug)]
+pub struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+/// Reject a posted state that cannot be scored.
+fn check(tournament: &TournamentState) -> std::result::Result<(), ApiError> {
+    tournament
+        .validate()
+        .map_err(|err| ApiError(StatusCode::BAD_REQUEST, err.to_string()))
+}
+
+/// Run CPU-bound work on the blocking pool.
+async fn blocking<T, F>(work: F) -> ApiResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+#[derive(Deserialize)]
+pub struct SimulationRequest {
+    pub tournament: TournamentState,
+    pub n_simulations: usize,
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct DeltasRequest {
+    pub tournament: TournamentState,
+    pub positions: HashMap<String, f64>,
+    #[serde(default = "default_point_delta")]
+    pub point_delta: f64,
+}
+
+fn default_point_delta() -> f64 {
+    1.0
+}
+
+#[derive(Serialize)]
+pub struct DeltasResponse {
+    pub team_deltas: HashMap<String, f64>,
+    pub pairwise_deltas: HashMap<String, HashMap<String, f64>>,
+}
+
+#[derive(Deserialize)]
+pub struct GameDeltaRequest {
+    pub tournament: TournamentState,
+    pub positions: HashMap<String, f64>,
+    pub team1: String,
+    pub team2: String,
+}
+
+#[derive(Serialize)]
+pub struct GameDeltaResponse {
+    pub win_value: f64,
+    pub loss_value: f64,
+    pub team_deltas: Vec<TeamDelta>,
+}
+
+async fn health() -> &'static str {
+    "ok"
+}
+
+/// `POST /scores`: expected scores for the posted tournament.
+pub async fn scores(Json(tournament): Json<TournamentState>) -> ApiResult<HashMap<String, f64>> {
+    check(&tournament)?;
+    blocking(move || tournament.calculate_scores_prob()).await
+}
+
+/// `POST /simulations`: per-simulation Monte Carlo scores.
+pub async fn simulations(Json(req): Json<SimulationRequest>) -> ApiResult<Vec<HashMap<String, f64>>> {
+    if req.n_simulations > MAX_SIMULATIONS {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("n_simulations must be at most {MAX_SIMULATIONS}"),
+        ));
+    }
+    check(&req.tournament)?;
+    blocking(move || req.tournament.run_simulations(req.n_simulations, req.seed)).await
+}
+
+/// `POST /deltas`: portfolio and pairwise deltas for every team's rating change.
+pub async fn deltas(Json(req): Json<DeltasRequest>) -> ApiResult<DeltasResponse> {
+    check(&req.tournament)?;
+    blocking(move || {
+        let (team_deltas, pairwise_deltas) =
+            get_all_team_deltas(req.positions, &req.tournament, req.point_delta);
+        DeltasResponse {
+            team_deltas,
+            pairwise_deltas,
+        }
+    })
+    .await
+}
+
+/// `POST /game-delta`: portfolio impact of a single game's outcome.
+pub async fn game_delta_handler(Json(req): Json<GameDeltaRequest>) -> ApiResult<GameDeltaResponse> {
+    check(&req.tournament)?;
+    blocking(move || {
+        let (win_value, loss_value, team_deltas) =
+            game_delta(req.positions, &req.tournament, &req.team1, &req.team2);
+        GameDeltaResponse {
+            win_value,
+            loss_value,
+            team_deltas,
+        }
+    })
+    .await
+}
+
+/// Build the service's router.
+pub fn router() -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/scores", post(scores))
+        .route("/simulations", post(simulations))
+        .route("/deltas", post(deltas))
+        .route("/game-delta", post(game_delta_handler))
+}
+
+/// Serve the API on `addr` until the process is stopped.
+pub async fn serve(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[tokio::test]
+    async fn test_scores_endpoint() {
+        let Json(scores) = scores(Json(canonical_tournament(2))).await.unwrap();
+        assert!(scores["Team 01"] > scores["Team 02"]);
+    }
+
+    #[tokio::test]
+    async fn test_simulation_limit() {
+        let req = SimulationRequest {
+            tournament: canonical_tournament(2),
+            n_simulations: MAX_SIMULATIONS + 1,
+            seed: None,
+        };
+        let err = simulations(Json(req)).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_states_rejected() {
+        let mut unrated = canonical_tournament(2);
+        unrated.ratings.remove("Team 02");
+        let err = scores(Json(unrated.clone())).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        assert!(err.1.contains("without ratings"), "{}", err.1);
+
+        let req = DeltasRequest {
+            tournament: unrated,
+            positions: HashMap::new(),
+            point_delta: 1.0,
+        };
+        assert_eq!(deltas(Json(req)).await.err().unwrap().0, StatusCode::BAD_REQUEST);
+
+        let mut three_slots = canonical_tournament(2);
+        three_slots.bracket_mut().push([("Team 01".to_string(), 1.0)].into_iter().collect());
+        let req = GameDeltaRequest {
+            tournament: three_slots,
+            positions: HashMap::new(),
+            team1: "Team 01".to_string(),
+            team2: "Team 02".to_string(),
+        };
+        let err = game_delta_handler(Json(req)).await.err().unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        assert!(err.1.contains("power of 2"), "{}", err.1);
+    }
+}
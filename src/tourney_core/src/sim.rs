@@ -0,0 +1,348 @@
+//! Reusable state for Monte Carlo tournament simulation.
+//!
+//! Playing a simulation game by game over bracket maps allocates a copy of
+//! the bracket and a fresh map for every game, in every simulated
+//! tournament. A `SimContext` resolves the bracket to team indices once and
+//! reuses its buffers across simulations, along with a memo of the win
+//! probabilities it has needed so far. Forfeits and game outcomes draw from
+//! separate streams of the seed (`RngStreams`), so enabling forfeits does not
+//! reshuffle the outcomes of games both teams show up for.
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, VecDeque};
+
+use crate::overrides::OverridesMap;
+use crate::propagate::override_table;
+use crate::rng::{RngStream, RngStreams};
+use crate::team::Team;
+use crate::tournament::TournamentState;
+use crate::win_prob::{calculate_win_prob_with, draw_forfeits};
+
+/// Buffers for simulating one `TournamentState` repeatedly. Create one per
+/// thread (e.g. with rayon's `map_init`) and call it once per seed.
+pub struct SimContext<'a> {
+    state: &'a TournamentState,
+    teams: &'a [String],
+    /// Each slot's `(team, probability)` entries, sorted by team name, the
+    /// order play-in draws are resolved in.
+    slots: Vec<Vec<(usize, f64)>>,
+    ratings: Vec<Option<&'a Team>>,
+    multipliers: Vec<f64>,
+    overrides: Option<Vec<f64>>,
+    tempos: Option<&'a OverridesMap>,
+    /// Win probabilities computed so far, `[team1 * n + team2]`; NaN if not yet.
+    win_probs: Vec<f64>,
+    reseed_keys: Vec<(u32, usize)>,
+    /// With persistent forfeits, whether each team withdrew this simulation.
+    withdrawn: Vec<bool>,
+    /// Survivors of the current round.
+    games: Vec<usize>,
+    next: Vec<usize>,
+    scores: Vec<f64>,
+    scored: Vec<bool>,
+}
+
+impl<'a> SimContext<'a> {
+    pub fn new(state: &'a TournamentState) -> Self {
+        let teams = state.bracket_teams();
+        let index: HashMap<&str, usize> = teams.iter().enumerate().map(|(i, team)| (team.as_str(), i)).collect();
+        let slots = state
+            .bracket()
+            .iter()
+            .map(|slot| {
+                let mut entries: Vec<(usize, f64)> = slot.iter().map(|(team, &p)| (index[team.as_str()], p)).collect();
+                entries.sort_by(|a, b| teams[a.0].cmp(&teams[b.0]));
+                entries
+            })
+            .collect();
+        let reseed_keys = if state.reseed {
+            let keys = state.reseed_keys();
+            teams.iter().map(|team| keys[team]).collect()
+        } else {
+            Vec::new()
+        };
+        let n = teams.len();
+        SimContext {
+            state,
+            teams,
+            slots,
+            ratings: teams.iter().map(|team| state.ratings.get(team)).collect(),
+            multipliers: teams.iter().map(|team| state.team_multiplier(team)).collect(),
+            overrides: override_table(state),
+            tempos: (!state.overrides.tempo_overrides().is_empty()).then_some(&state.overrides),
+            win_probs: vec![f64::NAN; n * n],
+            reseed_keys,
+            withdrawn: vec![false; n],
+            games: Vec::with_capacity(state.bracket().len()),
+            next: Vec::with_capacity(state.bracket().len() / 2),
+            scores: vec![0.0; n],
+            scored: vec![false; n],
+        }
+    }
+
+    /// Teams in index order (`TournamentState::bracket_teams`).
+    pub fn teams(&self) -> &'a [String] {
+        self.teams
+    }
+
+    /// Scores of one simulated tournament, like
+    /// `TournamentState::calculate_scores_sim`: only teams that won a game
+    /// are present.
+    pub fn simulate_scores(&mut self, seed: Option<u64>) -> HashMap<String, f64> {
+        self.score(seed, |_, _| {});
+        let teams = self.teams;
+        (0..teams.len()).filter(|&i| self.scored[i]).map(|i| (teams[i].clone(), self.scores[i])).collect()
+    }
+
+    /// Scores of one simulated tournament, indexed like `teams`, with zeros
+    /// for teams that won nothing.
+    pub fn simulate_dense(&mut self, seed: Option<u64>) -> &[f64] {
+        self.score(seed, |_, _| {});
+        &self.scores
+    }
+
+    /// `simulate_dense`, also reporting every `(team, scoring round)` win to
+    /// `observe`.
+    pub(crate) fn simulate_dense_observed(&mut self, seed: Option<u64>, observe: impl FnMut(usize, usize)) -> &[f64] {
+        self.score(seed, observe);
+        &self.scores
+    }
+
+    fn score(&mut self, seed: Option<u64>, mut observe: impl FnMut(usize, usize)) {
+        let mut scores = std::mem::take(&mut self.scores);
+        let mut scored = std::mem::take(&mut self.scored);
+        scores.fill(0.0);
+        scored.fill(false);
+        let (scoring, multipliers) = (&self.state.scoring, std::mem::take(&mut self.multipliers));
+        self.play(seed, |team, scoring_round| {
+            scores[team] += scoring.get(scoring_round).copied().unwrap_or(1.0) * multipliers[team];
+            scored[team] = true;
+            observe(team, scoring_round);
+        });
+        (self.scores, self.scored, self.multipliers) = (scores, scored, multipliers);
+    }
+
+    /// Play one tournament, reporting every `(team, scoring round)` win to
+    /// `award`.
+    pub(crate) fn play(&mut self, seed: Option<u64>, award: impl FnMut(usize, usize)) {
+        self.play_games(seed, award, |_, _, _| {});
+    }
+
+    /// `play`, also reporting every main-bracket game as `(team1, team2,
+    /// winner)` to `on_game`, round by round in bracket order (`GameId`
+    /// order). Round 0's pairs are the teams drawn into each slot.
+    pub(crate) fn play_games(
+        &mut self,
+        seed: Option<u64>,
+        mut award: impl FnMut(usize, usize),
+        mut on_game: impl FnMut(usize, usize, usize),
+    ) {
+        let streams = RngStreams::new(seed);
+        let (mut rng, mut forfeits) = (streams.stream(RngStream::GameOutcomes), streams.stream(RngStream::Forfeits));
+        let offset = usize::from(self.state.play_in_round);
+        if self.persistent_forfeits() {
+            let forfeit_prob = self.state.forfeit_prob;
+            self.withdrawn.iter_mut().for_each(|withdrawn| *withdrawn = forfeits.gen::<f64>() < forfeit_prob);
+        }
+        let mut games = std::mem::take(&mut self.games);
+        let mut next = std::mem::take(&mut self.next);
+
+        games.clear();
+        if self.state.play_in_round {
+            for slot in 0..self.slots.len() {
+                let winner = self.resolve_slot(slot, &mut rng);
+                if self.slots[slot].len() > 1 {
+                    award(winner, 0);
+                }
+                games.push(winner);
+            }
+        }
+
+        let mut round = 0;
+        let mut n_games = self.slots.len();
+        while n_games > 1 {
+            if !self.reseed_keys.is_empty() && round > 0 {
+                self.reseed(&mut games);
+            }
+            next.clear();
+            for game in 0..n_games / 2 {
+                let (team1, team2) = if games.is_empty() {
+                    // Unresolved first round: each slot is drawn just before its game.
+                    (self.resolve_slot(2 * game, &mut rng), self.resolve_slot(2 * game + 1, &mut rng))
+                } else {
+                    (games[2 * game], games[2 * game + 1])
+                };
+                let winner = self.play_game(team1, team2, &mut rng, &mut forfeits);
+                on_game(team1, team2, winner);
+                award(winner, round + offset);
+                next.push(winner);
+            }
+            std::mem::swap(&mut games, &mut next);
+            n_games = games.len();
+            round += 1;
+        }
+
+        (self.games, self.next) = (games, next);
+    }
+
+    /// Draw a slot's team by its probabilities; single-team slots draw nothing.
+    fn resolve_slot(&self, slot: usize, rng: &mut ChaCha8Rng) -> usize {
+        let entries = &self.slots[slot];
+        if entries.len() == 1 {
+            return entries[0].0;
+        }
+        let r: f64 = rng.gen();
+        let mut cumulative = 0.0;
+        for &(team, prob) in entries {
+            cumulative += prob;
+            if r < cumulative {
+                return team;
+            }
+        }
+        entries[entries.len() - 1].0
+    }
+
+    /// Winner of one game. An override is the final word on a pairing, as in
+    /// `calculate_win_prob_with`, so overridden games draw no forfeits.
+    fn play_game(&mut self, team1: usize, team2: usize, rng: &mut ChaCha8Rng, forfeits: &mut ChaCha8Rng) -> usize {
+        let forfeit_prob = self.state.forfeit_prob;
+        if forfeit_prob > 0.0 && self.overridden(team1, team2).is_none() {
+            let seed = |i: usize| self.ratings[i].and_then(|team| team.seed);
+            let policy = self.state.model_params.double_forfeit;
+            let forfeited = if self.persistent_forfeits() {
+                // Withdrawals are fixed for the simulation, so there is
+                // nothing to redraw: a double forfeit is settled directly.
+                match (self.withdrawn[team1], self.withdrawn[team2]) {
+                    (false, false) => None,
+                    (true, false) => Some(false),
+                    (false, true) => Some(true),
+                    (true, true) => Some(forfeits.gen::<f64>() < policy.team1_share(seed(team1), seed(team2))),
+                }
+            } else {
+                draw_forfeits(forfeits, forfeit_prob, policy, seed(team1), seed(team2))
+            };
+            match forfeited {
+                Some(true) => return team1,
+                Some(false) => return team2,
+                None => {}
+            }
+        }
+        let prob = self.win_prob(team1, team2);
+        if rng.gen::<f64>() < prob {
+            team1
+        } else {
+            team2
+        }
+    }
+
+    /// Probability `team1` beats `team2` once both show up (forfeits are drawn
+    /// separately), memoized.
+    fn win_prob(&mut self, team1: usize, team2: usize) -> f64 {
+        let at = team1 * self.teams.len() + team2;
+        if self.win_probs[at].is_nan() {
+            self.win_probs[at] = match self.overridden(team1, team2) {
+                Some(prob) => prob,
+                None => {
+                    let rating = |i: usize| {
+                        self.ratings[i].unwrap_or_else(|| panic!("team not found in ratings: {}", self.teams[i]))
+                    };
+                    calculate_win_prob_with(rating(team1), rating(team2), self.tempos, 0.0, &self.state.model_params)
+                }
+            };
+        }
+        self.win_probs[at]
+    }
+
+    fn persistent_forfeits(&self) -> bool {
+        self.state.persistent_forfeits && self.state.forfeit_prob > 0.0
+    }
+
+    /// The override between two teams, if any.
+    fn overridden(&self, team1: usize, team2: usize) -> Option<f64> {
+        let prob = self.overrides.as_ref()?[team1 * self.teams.len() + team2];
+        (!prob.is_nan()).then_some(prob)
+    }
+
+    /// `reseed_games` over team indices: best remaining seed meets the worst.
+    fn reseed(&self, games: &mut Vec<usize>) {
+        games.sort_by_key(|&team| self.reseed_keys[team]);
+        let mut ranked: VecDeque<usize> = games.drain(..).collect();
+        while let (Some(best), Some(worst)) = (ranked.pop_front(), ranked.pop_back()) {
+            games.push(best);
+            games.push(worst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    /// A 16-team field with an override and a two-team play-in slot.
+    fn tournament() -> TournamentState {
+        let mut state = canonical_tournament(16);
+        state.overrides.add_override("Team 01", "Team 16", 0.2).unwrap();
+        let extra = state.ratings["Team 16"].clone();
+        state.ratings.insert("Team 17".to_string(), Team { name: "Team 17".to_string(), ..extra });
+        state.bracket_mut()[1] = [("Team 16".to_string(), 0.5), ("Team 17".to_string(), 0.5)].into();
+        state
+    }
+
+    #[test]
+    fn test_simulated_means_match_exact_scores() {
+        let mut state = tournament();
+        let cases = [
+            (false, 0.0, "coin_flip", false),
+            (true, 0.0, "coin_flip", false),
+            (false, 0.3, "redraw", false),
+            (true, 0.3, "higher_seed", false),
+            (false, 0.3, "coin_flip", true),
+            (true, 0.3, "higher_seed", true),
+        ];
+        for (play_in_round, forfeit_prob, policy, persistent_forfeits) in cases {
+            state.play_in_round = play_in_round;
+            state.persistent_forfeits = persistent_forfeits;
+            state.forfeit_prob = forfeit_prob;
+            state.model_params.double_forfeit = policy.parse().unwrap();
+            let exact = state.calculate_scores_prob();
+            let mut context = SimContext::new(&state);
+            let n_sims = 4000;
+            let mut means: HashMap<String, f64> = HashMap::new();
+            for seed in 0..n_sims {
+                for (team, score) in context.simulate_scores(Some(seed)) {
+                    *means.entry(team).or_insert(0.0) += score / n_sims as f64;
+                }
+            }
+            for (team, expected) in exact {
+                let mean = means.get(&team).copied().unwrap_or(0.0);
+                assert!((mean - expected).abs() < 0.1 * expected.max(1.0), "{team}: {mean} vs {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_forfeits_do_not_reshuffle_outcomes() {
+        // Forfeits that never happen still draw from their own stream only,
+        // so every game is decided exactly as without them.
+        let state = tournament();
+        let mut rare = state.clone();
+        rare.forfeit_prob = 1e-300;
+        let (mut without, mut with) = (SimContext::new(&state), SimContext::new(&rare));
+        for seed in 0..50 {
+            assert_eq!(without.simulate_scores(Some(seed)), with.simulate_scores(Some(seed)));
+        }
+    }
+
+    #[test]
+    fn test_dense_scores_fill_zeros() {
+        let state = canonical_tournament(8);
+        let mut context = SimContext::new(&state);
+        let sparse = context.simulate_scores(Some(3));
+        let dense = context.simulate_dense(Some(3)).to_vec();
+        for (team, score) in context.teams().iter().zip(dense) {
+            assert_eq!(score, sparse.get(team).copied().unwrap_or(0.0));
+        }
+    }
+}
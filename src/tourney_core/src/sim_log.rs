@@ -0,0 +1,222 @@
+//! Game-by-game simulation logs.
+//!
+//! `run_simulations` keeps only each team's points, which cannot answer
+//! questions about specific paths ("sims where a 12 seed reached the Elite
+//! Eight", "sims where both 1 seeds in the top half lost early"). A
+//! `SimulationLog` keeps every game's result instead, compactly: each
+//! simulation is a fixed-width row of `u16` team indices, first the team drawn
+//! into each bracket slot, then the winner of every main-bracket game in
+//! `GameId` order. A 64-team simulation takes 254 bytes.
+//!
+//! With `reseed`, later-round pairings depend on the results so far, and a
+//! game's index is its position after re-pairing.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::game_id::{GameId, Round};
+use crate::sim::SimContext;
+use crate::tournament::{seeded_rng, TournamentState};
+
+/// Every game's winner in each of a set of simulations.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SimulationLog {
+    /// Bracket teams, in index order.
+    #[pyo3(get)]
+    pub teams: Vec<String>,
+
+    #[pyo3(get)]
+    pub n_slots: usize,
+
+    /// `n_slots` slot teams then `n_slots - 1` game winners per simulation.
+    rows: Vec<u16>,
+}
+
+#[pymethods]
+impl SimulationLog {
+    #[getter]
+    pub fn n_simulations(&self) -> usize {
+        self.rows.len() / self.row_len().max(1)
+    }
+
+    fn __len__(&self) -> usize {
+        self.n_simulations()
+    }
+
+    /// Winner of `game` in simulation `sim`.
+    pub fn winner(&self, sim: usize, game: GameId) -> Result<String> {
+        let column = self.game_column(game)?;
+        Ok(self.teams[usize::from(self.row(sim)?[column])].clone())
+    }
+
+    /// Team drawn into bracket slot `slot` in simulation `sim` (the play-in
+    /// winner for play-in slots).
+    pub fn slot_team(&self, sim: usize, slot: usize) -> Result<String> {
+        if slot >= self.n_slots {
+            return Err(TourneyError::InvalidInput(format!("slot {slot} is out of range")));
+        }
+        Ok(self.teams[usize::from(self.row(sim)?[slot])].clone())
+    }
+
+    /// Winner of every game in simulation `sim`.
+    pub fn path(&self, sim: usize) -> Result<HashMap<GameId, String>> {
+        let row = self.row(sim)?;
+        Ok(self
+            .games()
+            .zip(&row[self.n_slots..])
+            .map(|(game, &winner)| (game, self.teams[usize::from(winner)].clone()))
+            .collect())
+    }
+
+    /// Indices of the simulations in which `team` won its game in `round`
+    /// (for example, round 2 of a 64-team bracket for reaching the Elite Eight).
+    pub fn filter_wins(&self, team: &str, round: Round) -> Result<Vec<usize>> {
+        let index = self
+            .teams
+            .iter()
+            .position(|t| t == team)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("{team} is not in the bracket")))?;
+        let n_games = round.n_games(self.n_slots);
+        if n_games == 0 {
+            return Err(TourneyError::InvalidInput(format!("{round} is out of range")));
+        }
+        let start = self.game_column(GameId::new(round, 0))?;
+        Ok((0..self.n_simulations())
+            .filter(|&sim| {
+                let row = &self.rows[sim * self.row_len()..][..self.row_len()];
+                row[start..start + n_games].iter().any(|&winner| usize::from(winner) == index)
+            })
+            .collect())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SimulationLog(teams={}, n_simulations={})", self.teams.len(), self.n_simulations())
+    }
+}
+
+impl SimulationLog {
+    fn row_len(&self) -> usize {
+        2 * self.n_slots - 1
+    }
+
+    fn row(&self, sim: usize) -> Result<&[u16]> {
+        if sim >= self.n_simulations() {
+            return Err(TourneyError::InvalidInput(format!(
+                "simulation {sim} is out of range for {} simulations",
+                self.n_simulations()
+            )));
+        }
+        Ok(&self.rows[sim * self.row_len()..][..self.row_len()])
+    }
+
+    /// Main-bracket games in row order.
+    fn games(&self) -> impl Iterator<Item = GameId> + '_ {
+        let n_rounds = self.n_slots.max(1).ilog2() as u8;
+        (0..n_rounds).flat_map(move |round| {
+            (0..Round(round).n_games(self.n_slots)).map(move |index| GameId::new(Round(round), index))
+        })
+    }
+
+    /// Column of `game`'s winner within a row.
+    fn game_column(&self, game: GameId) -> Result<usize> {
+        if game.index >= game.round.n_games(self.n_slots) {
+            return Err(TourneyError::InvalidInput(format!("no {game} in a {}-slot bracket", self.n_slots)));
+        }
+        // Rounds before `game.round` hold n/2 + n/4 + ... games.
+        let earlier = self.n_slots - (self.n_slots >> game.round.0);
+        Ok(self.n_slots + earlier + game.index)
+    }
+}
+
+/// Play `n_simulations` of `state` with the draws `run_simulations` uses for
+/// the same seed, keeping every game's result.
+pub fn simulation_log(state: &TournamentState, n_simulations: usize, seed: Option<u64>) -> Result<SimulationLog> {
+    let teams = state.bracket_teams().to_vec();
+    let n_slots = state.bracket().len();
+    if teams.len() > usize::from(u16::MAX) || n_slots < 2 || !n_slots.is_power_of_two() {
+        return Err(TourneyError::InvalidInput(format!(
+            "cannot log a bracket of {n_slots} slots and {} teams",
+            teams.len()
+        )));
+    }
+    let seeds: Vec<u64> = {
+        let mut rng = seeded_rng(seed);
+        (0..n_simulations).map(|_| rng.gen::<u64>()).collect()
+    };
+    let rows: Vec<Vec<u16>> = seeds
+        .par_iter()
+        .map_init(
+            || SimContext::new(state),
+            |context, &sim_seed| {
+                let mut slots = Vec::with_capacity(n_slots);
+                let mut winners = Vec::with_capacity(n_slots - 1);
+                context.play_games(
+                    Some(sim_seed),
+                    |_, _| {},
+                    |team1, team2, winner| {
+                        if slots.len() < n_slots {
+                            slots.extend([team1 as u16, team2 as u16]);
+                        }
+                        winners.push(winner as u16);
+                    },
+                );
+                slots.extend(winners);
+                slots
+            },
+        )
+        .collect();
+    Ok(SimulationLog {
+        teams,
+        n_slots,
+        rows: rows.concat(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_log_matches_simulated_scores() {
+        let state = canonical_tournament(16);
+        let log = simulation_log(&state, 50, Some(11)).unwrap();
+        assert_eq!(log.n_simulations(), 50);
+        let sims = state.run_simulations(50, Some(11));
+        for (i, scores) in sims.iter().enumerate() {
+            let path = log.path(i).unwrap();
+            assert_eq!(path.len(), 15);
+            let champion = log.winner(i, GameId::new(Round(3), 0)).unwrap();
+            assert_eq!(path[&GameId::new(Round(3), 0)], champion);
+            let best = scores.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0;
+            assert_eq!(&champion, best);
+            // Every round-0 winner is one of the two teams in its slots.
+            for game in 0..8 {
+                let winner = log.winner(i, GameId::new(Round(0), game)).unwrap();
+                let slot_teams = [log.slot_team(i, 2 * game).unwrap(), log.slot_team(i, 2 * game + 1).unwrap()];
+                assert!(slot_teams.contains(&winner));
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_wins() {
+        let state = canonical_tournament(16);
+        let log = simulation_log(&state, 200, Some(4)).unwrap();
+        let champion_sims = log.filter_wins("Team 01", Round(3)).unwrap();
+        let semifinal = log.filter_wins("Team 01", Round(1)).unwrap();
+        assert!(!champion_sims.is_empty());
+        assert!(champion_sims.iter().all(|sim| semifinal.contains(sim)));
+        for &sim in &champion_sims {
+            assert_eq!(log.winner(sim, GameId::new(Round(3), 0)).unwrap(), "Team 01");
+        }
+        assert!(log.filter_wins("Nobody", Round(0)).is_err());
+        assert!(log.filter_wins("Team 01", Round(4)).is_err());
+        assert!(log.winner(200, GameId::new(Round(0), 0)).is_err());
+    }
+}
@@ -0,0 +1,960 @@
+//! Pool standings midway through a tournament.
+//!
+//! Once games have been played, a pool's entries have banked some points and
+//! lost the chance at others. `project_standings` reports, per entry, what it
+//! has, the most it could still reach, and what it should expect, by
+//! simulating only the games still to be played.
+//!
+//! Completed games are given as `results[r]`, the teams that have won
+//! main-bracket round `r` so far (rounds may be partially complete). The
+//! remaining games are simulated from `tournament` with each result pinned by
+//! a certain override, so every simulation agrees with what has happened.
+//!
+//! Simulation can say an entry wins 0.01% of the time, but not whether it is
+//! truly out. `entry_status` answers that exactly by walking every way the
+//! remaining games can go, ignoring the model's probabilities entirely.
+//!
+//! Late in a tournament the same walk is cheap enough to replace simulation:
+//! with at most `MAX_OUTCOME_GAMES` games left, `project_standings` weighs
+//! every outcome by its model probability instead, so expected scores and
+//! win probabilities carry no simulation error. `outcome_matrix` returns the
+//! walk itself, every outcome's probability and entry scores. The walk plays
+//! each game independently, so it cannot model persistent forfeits (a team
+//! that withdraws forfeits every later game); those states are simulated.
+//!
+//! `score_completed_bracket` is the commissioner's view of one entry: points
+//! earned and still possible, round by round, under any `ScoringRule`.
+//! `score_entries_batch` scores a whole pool's earned points at once.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::entry::{round_points, BracketEntry};
+use crate::error::{Result, TourneyError};
+use crate::game_id::{GameId, Round};
+use crate::pool::{expected_equity, Payouts};
+use crate::scoring::ScoringRule;
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::win_matrix::{win_prob_matrix, WinProbMatrix};
+
+/// One entry's position in a pool in progress.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryStanding {
+    /// Points from games already played.
+    #[pyo3(get)]
+    pub current_score: f64,
+
+    /// Points if every remaining pick still alive comes in.
+    #[pyo3(get)]
+    pub max_score: f64,
+
+    /// Mean final score over the simulations.
+    #[pyo3(get)]
+    pub expected_score: f64,
+
+    /// Share of simulations this entry finishes first, ties split.
+    #[pyo3(get)]
+    pub win_prob: f64,
+}
+
+#[pymethods]
+impl EntryStanding {
+    fn __repr__(&self) -> String {
+        format!(
+            "EntryStanding(current={:.1}, max={:.1}, expected={:.2}, win_prob={:.4})",
+            self.current_score, self.max_score, self.expected_score, self.win_prob
+        )
+    }
+}
+
+/// Most undecided games `entry_status` will enumerate (2^games outcomes).
+pub const MAX_EXACT_GAMES: usize = 20;
+
+/// Most undecided games `outcome_matrix` will enumerate, and the most
+/// `project_standings` evaluates exactly rather than by simulation.
+pub const MAX_OUTCOME_GAMES: usize = 16;
+
+/// Best and worst possible finish for an entry over every remaining outcome.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryStatus {
+    /// Best possible place (1-based); ties count in the entry's favor.
+    #[pyo3(get)]
+    pub best_place: usize,
+
+    /// Worst possible place (1-based); ties count against the entry.
+    #[pyo3(get)]
+    pub worst_place: usize,
+
+    /// Finishes in a paid place however the remaining games go.
+    #[pyo3(get)]
+    pub clinched: bool,
+
+    /// Cannot finish in a paid place however the remaining games go.
+    #[pyo3(get)]
+    pub eliminated: bool,
+}
+
+#[pymethods]
+impl EntryStatus {
+    fn __repr__(&self) -> String {
+        format!(
+            "EntryStatus(best_place={}, worst_place={}, clinched={}, eliminated={})",
+            self.best_place, self.worst_place, self.clinched, self.eliminated
+        )
+    }
+}
+
+/// Every way the remaining games can go, with each entry's final score.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutcomeMatrix {
+    /// Undecided games, in the order `outcomes` lists their winners.
+    #[pyo3(get)]
+    pub games: Vec<GameId>,
+
+    /// Winners of `games` in each outcome.
+    #[pyo3(get)]
+    pub outcomes: Vec<Vec<String>>,
+
+    /// Model probability of each outcome.
+    #[pyo3(get)]
+    pub probs: Vec<f64>,
+
+    /// `scores[o][e]`: entry `e`'s final score in outcome `o`.
+    #[pyo3(get)]
+    pub scores: Vec<Vec<f64>>,
+
+    /// Probability-weighted final score per entry.
+    #[pyo3(get)]
+    pub expected_scores: Vec<f64>,
+
+    /// Probability each entry finishes first, ties split.
+    #[pyo3(get)]
+    pub win_probs: Vec<f64>,
+}
+
+#[pymethods]
+impl OutcomeMatrix {
+    fn __len__(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OutcomeMatrix({} games, {} outcomes, {} entries)",
+            self.games.len(),
+            self.outcomes.len(),
+            self.expected_scores.len()
+        )
+    }
+}
+
+/// An entry's points under a scoring rule, split by round.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BracketScore {
+    /// Points from correct picks of completed games.
+    #[pyo3(get)]
+    pub earned: f64,
+
+    /// Points still available from undecided games whose picked winner is
+    /// alive.
+    #[pyo3(get)]
+    pub remaining: f64,
+
+    /// `earned` per main-bracket round.
+    #[pyo3(get)]
+    pub round_earned: Vec<f64>,
+
+    /// `remaining` per main-bracket round.
+    #[pyo3(get)]
+    pub round_remaining: Vec<f64>,
+
+    /// Correct picks per main-bracket round.
+    #[pyo3(get)]
+    pub round_correct: Vec<usize>,
+}
+
+#[pymethods]
+impl BracketScore {
+    /// The most the entry can finish with.
+    #[getter]
+    pub fn max_score(&self) -> f64 {
+        self.earned + self.remaining
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BracketScore(earned={:.1}, remaining={:.1}, round_correct={:?})",
+            self.earned, self.remaining, self.round_correct
+        )
+    }
+}
+
+/// Completed games of a bracket, validated against its tree.
+pub(crate) struct Results {
+    /// Winner of each decided game, keyed by (round, game).
+    pub decided: HashMap<(usize, usize), String>,
+    /// Winners by round, for scoring.
+    pub winners: Vec<HashSet<String>>,
+    /// Bracket slot of every team.
+    pub slots: HashMap<String, usize>,
+}
+
+impl Results {
+    pub fn new(tournament: &TournamentState, results: &[Vec<String>]) -> Result<Self> {
+        if tournament.reseed {
+            return Err(TourneyError::InvalidInput(
+                "bracket entries follow the fixed tree and are undefined when reseeding".to_string(),
+            ));
+        }
+        let n_rounds = tournament.bracket().len().max(1).ilog2() as usize;
+        if results.len() > n_rounds {
+            return Err(TourneyError::InvalidInput(format!(
+                "{} rounds of results for a bracket with {n_rounds} rounds",
+                results.len()
+            )));
+        }
+        let slots: HashMap<String, usize> = tournament
+            .bracket()
+            .iter()
+            .enumerate()
+            .flat_map(|(slot, game)| game.keys().map(move |team| (team.clone(), slot)))
+            .collect();
+        let mut decided = HashMap::new();
+        for (round, winners) in results.iter().enumerate() {
+            for team in winners {
+                let slot = *slots
+                    .get(team)
+                    .ok_or_else(|| TourneyError::InvalidInput(format!("result for team not in bracket: {team}")))?;
+                if round > 0 && !results[round - 1].contains(team) {
+                    return Err(TourneyError::InvalidInput(format!(
+                        "{team} won round {round} without winning round {}",
+                        round - 1
+                    )));
+                }
+                if let Some(other) = decided.insert((round, slot >> (round + 1)), team.clone()) {
+                    return Err(TourneyError::InvalidInput(format!(
+                        "{team} and {other} both won the same round {round} game"
+                    )));
+                }
+            }
+        }
+        let winners = (0..n_rounds)
+            .map(|round| results.get(round).map(|teams| teams.iter().cloned().collect()).unwrap_or_default())
+            .collect();
+        Ok(Results {
+            decided,
+            winners,
+            slots,
+        })
+    }
+
+    /// Whether `team` has lost a completed game.
+    pub fn eliminated(&self, team: &str) -> bool {
+        let Some(&slot) = self.slots.get(team) else { return true };
+        (0..self.winners.len())
+            .any(|round| self.decided.get(&(round, slot >> (round + 1))).is_some_and(|winner| winner != team))
+    }
+
+    /// Main-bracket games of `tournament` not yet decided.
+    fn remaining(&self, tournament: &TournamentState) -> usize {
+        tournament.bracket().len().saturating_sub(1) - self.decided.len()
+    }
+
+    /// Whether `team` has lost no completed game before `round`.
+    fn alive_through(&self, team: &str, round: usize) -> bool {
+        let Some(&slot) = self.slots.get(team) else { return false };
+        (0..round).all(|r| self.decided.get(&(r, slot >> (r + 1))).is_none_or(|winner| winner == team))
+    }
+
+    /// Points under `rule` for `team` winning its round-`round` game, against
+    /// the opponent that would earn the most of those still possible (the
+    /// actual opponent once the feeder game is decided). Unseeded teams count
+    /// as seed 0.
+    fn pick_points(&self, tournament: &TournamentState, team: &str, round: usize, rule: &ScoringRule) -> f64 {
+        let seed = |name: &str| tournament.ratings.get(name).and_then(|t| t.seed).unwrap_or(0);
+        let side = self.slots[team] >> round;
+        self.slots
+            .iter()
+            .filter(|(other, &slot)| slot >> round == side ^ 1 && self.alive_through(other, round))
+            .map(|(other, _)| rule.pick_points(round, seed(team), seed(other)))
+            .fold(0.0, f64::max)
+    }
+
+    /// `tournament` with every completed game's winner certain to beat any
+    /// team it could have faced there.
+    pub fn pin(&self, tournament: &TournamentState) -> Result<TournamentState> {
+        let mut pinned = tournament.clone();
+        for ((round, game), winner) in &self.decided {
+            let side = self.slots[winner] >> round;
+            for (team, &slot) in &self.slots {
+                if slot >> (round + 1) == *game && slot >> round != side {
+                    pinned.overrides.add_override(winner, team, 1.0)?;
+                }
+            }
+        }
+        Ok(pinned)
+    }
+
+    /// Points `entry` has banked, and the most it can still finish with.
+    pub fn score_range(&self, entry: &BracketEntry, points: &[f64]) -> (f64, f64) {
+        let mut current = 0.0;
+        let mut max = 0.0;
+        for (round, picks) in entry.picks.iter().enumerate() {
+            for (game, team) in picks.iter().enumerate() {
+                match self.decided.get(&(round, game)) {
+                    Some(winner) if winner == team => {
+                        current += points[round];
+                        max += points[round];
+                    }
+                    Some(_) => {}
+                    None if !self.eliminated(team) => max += points[round],
+                    None => {}
+                }
+            }
+        }
+        (current, max)
+    }
+}
+
+/// Current, maximum, and expected score plus win probability for each of
+/// `entries`, given `results` so far and the remaining games simulated
+/// `n_sims` times from `tournament`. With at most `MAX_OUTCOME_GAMES` games
+/// left, every outcome is weighed exactly instead and `n_sims` and `seed` are
+/// unused.
+///
+/// Entries score `scoring[r]` per correct round-`r` pick (default: the
+/// tournament's round points).
+#[pyfunction]
+#[pyo3(signature = (entries, tournament, n_sims = 10000, results = None, scoring = None, seed = None))]
+pub fn project_standings(
+    entries: Vec<BracketEntry>,
+    tournament: &TournamentState,
+    n_sims: usize,
+    results: Option<Vec<Vec<String>>>,
+    scoring: Option<Vec<f64>>,
+    seed: Option<u64>,
+) -> Result<Vec<EntryStanding>> {
+    let results = Results::new(tournament, &results.unwrap_or_default())?;
+    let points = round_points(tournament, scoring.as_deref());
+
+    let (expected, win_prob) = if enumerable(tournament) && results.remaining(tournament) <= MAX_OUTCOME_GAMES {
+        let (mut expected, mut win_prob) = (vec![0.0; entries.len()], vec![0.0; entries.len()]);
+        OutcomeSearch::new(tournament, &results, &entries, &points, |scores: &[f64], prob, _: &[Vec<String>]| {
+            credit_outcome(scores, prob, &mut expected, &mut win_prob)
+        })?
+        .explore(0, 1.0);
+        (expected, win_prob)
+    } else {
+        let remaining = results.pin(tournament)?;
+        let mut rng = seeded_rng(seed);
+        let seeds: Vec<u64> = (0..n_sims).map(|_| rng.gen()).collect();
+        let sim_scores: Vec<Vec<f64>> = seeds
+            .par_iter()
+            .map(|&sim_seed| {
+                let winners = remaining.simulate_round_winners(Some(sim_seed));
+                entries.iter().map(|entry| entry.score(&winners, &points)).collect()
+            })
+            .collect();
+        let expected = (0..entries.len())
+            .map(|index| sim_scores.iter().map(|scores| scores[index]).sum::<f64>() / sim_scores.len().max(1) as f64)
+            .collect();
+        (expected, expected_equity(&sim_scores, &Payouts::winner_take_all(), 1.0))
+    };
+
+    Ok(entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let (current_score, max_score) = results.score_range(entry, &points);
+            EntryStanding {
+                current_score,
+                max_score,
+                expected_score: expected[index],
+                win_prob: win_prob[index],
+            }
+        })
+        .collect())
+}
+
+/// Every outcome of the games left after `results`, with its model
+/// probability and each of `entries`' final score (`scoring[r]` per correct
+/// round-`r` pick, default: the tournament's round points). Fails if more
+/// than `MAX_OUTCOME_GAMES` games remain.
+#[pyfunction]
+#[pyo3(signature = (entries, tournament, results = None, scoring = None))]
+pub fn outcome_matrix(
+    entries: Vec<BracketEntry>,
+    tournament: &TournamentState,
+    results: Option<Vec<Vec<String>>>,
+    scoring: Option<Vec<f64>>,
+) -> Result<OutcomeMatrix> {
+    if !enumerable(tournament) {
+        return Err(TourneyError::InvalidInput(
+            "outcome matrices play games independently and cannot model persistent forfeits".to_string(),
+        ));
+    }
+    let results = Results::new(tournament, &results.unwrap_or_default())?;
+    let remaining = results.remaining(tournament);
+    if remaining > MAX_OUTCOME_GAMES {
+        return Err(TourneyError::InvalidInput(format!(
+            "{remaining} games remain; the outcome matrix is limited to {MAX_OUTCOME_GAMES}"
+        )));
+    }
+    let points = round_points(tournament, scoring.as_deref());
+    let n_slots = tournament.bracket().len();
+    let games: Vec<GameId> = (0..points.len())
+        .flat_map(|r| (0..n_slots >> (r + 1)).map(move |g| (r, g)))
+        .filter(|game| !results.decided.contains_key(game))
+        .map(|(round, game)| Ok(GameId::new(Round::try_from(round)?, game)))
+        .collect::<Result<_>>()?;
+
+    let mut matrix = OutcomeMatrix {
+        games: games.clone(),
+        outcomes: Vec::new(),
+        probs: Vec::new(),
+        scores: Vec::new(),
+        expected_scores: vec![0.0; entries.len()],
+        win_probs: vec![0.0; entries.len()],
+    };
+    OutcomeSearch::new(tournament, &results, &entries, &points, |scores: &[f64], prob, winners: &[Vec<String>]| {
+        matrix.outcomes.push(games.iter().map(|game| winners[game.round.index()][game.index].clone()).collect());
+        matrix.probs.push(prob);
+        matrix.scores.push(scores.to_vec());
+        credit_outcome(scores, prob, &mut matrix.expected_scores, &mut matrix.win_probs);
+    })?
+    .explore(0, 1.0);
+    Ok(matrix)
+}
+
+/// Whether the outcome walk models `tournament`'s games: it weighs each game
+/// on its own, which persistent forfeits break. (Reseeding is rejected by
+/// `Results::new`; deterministic sums need nothing, as the walk is sequential.)
+fn enumerable(tournament: &TournamentState) -> bool {
+    !(tournament.persistent_forfeits && tournament.forfeit_prob > 0.0)
+}
+
+/// Add an outcome of probability `prob` with final `scores` to each entry's
+/// `expected` score and `win_prob`, splitting first place among ties.
+fn credit_outcome(scores: &[f64], prob: f64, expected: &mut [f64], win_prob: &mut [f64]) {
+    let top = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let n_top = scores.iter().filter(|&&score| score == top).count();
+    for ((score, expected), win_prob) in scores.iter().zip(expected).zip(win_prob) {
+        *expected += prob * score;
+        if *score == top {
+            *win_prob += prob / n_top as f64;
+        }
+    }
+}
+
+/// Whether each of `entries` has clinched or been eliminated from a paid place
+/// (default: first only) given `results` so far, checked exactly over every
+/// outcome of the remaining games.
+///
+/// A place is paid if `payouts` pays it anything; an entry tied across the
+/// paid cutoff shares in the payout, so it counts as paid. Fails if more than
+/// `MAX_EXACT_GAMES` games remain.
+#[pyfunction]
+#[pyo3(signature = (entries, tournament, results = None, payouts = None, scoring = None))]
+pub fn entry_status(
+    entries: Vec<BracketEntry>,
+    tournament: &TournamentState,
+    results: Option<Vec<Vec<String>>>,
+    payouts: Option<Payouts>,
+    scoring: Option<Vec<f64>>,
+) -> Result<Vec<EntryStatus>> {
+    let results = Results::new(tournament, &results.unwrap_or_default())?;
+    let points = round_points(tournament, scoring.as_deref());
+    let remaining = results.remaining(tournament);
+    if remaining > MAX_EXACT_GAMES {
+        return Err(TourneyError::InvalidInput(format!(
+            "{remaining} games remain; exact status is limited to {MAX_EXACT_GAMES}"
+        )));
+    }
+    let payouts = payouts.unwrap_or_else(Payouts::winner_take_all);
+    let paid_places = payouts.fractions.iter().rposition(|&f| f > 0.0).map_or(0, |last| last + 1);
+
+    let (mut best, mut worst) = (vec![usize::MAX; entries.len()], vec![0; entries.len()]);
+    OutcomeSearch::new(tournament, &results, &entries, &points, |scores: &[f64], _, _: &[Vec<String>]| {
+        for (entry, &score) in scores.iter().enumerate() {
+            let above = scores.iter().filter(|&&other| other > score).count();
+            let tied = scores.iter().filter(|&&other| other == score).count();
+            best[entry] = best[entry].min(above + 1);
+            worst[entry] = worst[entry].max(above + tied);
+        }
+    })?
+    .explore(0, 1.0);
+
+    Ok(best
+        .iter()
+        .zip(&worst)
+        .map(|(&best_place, &worst_place)| EntryStatus {
+            best_place,
+            worst_place,
+            clinched: worst_place <= paid_places,
+            eliminated: best_place > paid_places,
+        })
+        .collect())
+}
+
+/// Points `entry` has earned and can still earn given `results` so far, under
+/// `rule` (default: the tournament's round points). Rule rounds are
+/// main-bracket rounds.
+///
+/// With seed or upset bonuses, an undecided pick is counted against its best
+/// possible opponent, so `remaining` is an upper bound that different picks
+/// may not reach together.
+#[pyfunction]
+#[pyo3(signature = (entry, tournament, results = None, rule = None))]
+pub fn score_completed_bracket(
+    entry: &BracketEntry,
+    tournament: &TournamentState,
+    results: Option<Vec<Vec<String>>>,
+    rule: Option<ScoringRule>,
+) -> Result<BracketScore> {
+    let results = Results::new(tournament, &results.unwrap_or_default())?;
+    let rule = rule.unwrap_or_else(|| ScoringRule {
+        points: round_points(tournament, None),
+        ..ScoringRule::default()
+    });
+    check_shape(entry, tournament.bracket().len())?;
+    let n_rounds = results.winners.len();
+
+    let mut score = BracketScore {
+        earned: 0.0,
+        remaining: 0.0,
+        round_earned: vec![0.0; n_rounds],
+        round_remaining: vec![0.0; n_rounds],
+        round_correct: vec![0; n_rounds],
+    };
+    for (round, picks) in entry.picks.iter().enumerate() {
+        for (game, team) in picks.iter().enumerate() {
+            if results.slots.get(team).is_none_or(|slot| slot >> (round + 1) != game) {
+                return Err(TourneyError::InvalidInput(format!(
+                    "{team} cannot win round {round} game {game}"
+                )));
+            }
+            match results.decided.get(&(round, game)) {
+                Some(winner) if winner == team => {
+                    score.round_earned[round] += results.pick_points(tournament, team, round, &rule);
+                    score.round_correct[round] += 1;
+                }
+                None if results.alive_through(team, round) => {
+                    score.round_remaining[round] += results.pick_points(tournament, team, round, &rule);
+                }
+                _ => {}
+            }
+        }
+    }
+    score.earned = score.round_earned.iter().sum();
+    score.remaining = score.round_remaining.iter().sum();
+    Ok(score)
+}
+
+/// Earned points of many entries, by round.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryScoreTable {
+    /// Points per main-bracket round, one run of entries per round: entry
+    /// `e`'s points in round `r` are at `r * n_entries + e`.
+    scores: Vec<f64>,
+
+    /// Total points per entry.
+    #[pyo3(get)]
+    pub totals: Vec<f64>,
+}
+
+impl EntryScoreTable {
+    /// Points each entry earned in main-bracket `round`.
+    pub fn round_scores(&self, round: usize) -> &[f64] {
+        let n_entries = self.totals.len();
+        &self.scores[round * n_entries..(round + 1) * n_entries]
+    }
+}
+
+#[pymethods]
+impl EntryScoreTable {
+    /// Number of main-bracket rounds scored.
+    #[getter]
+    pub fn n_rounds(&self) -> usize {
+        self.scores.len().checked_div(self.totals.len()).unwrap_or(0)
+    }
+
+    /// Per-round points as one buffer of native-endian float64, round by
+    /// round, without building a list per round:
+    /// `numpy.frombuffer(table.round_scores, dtype=numpy.float64).reshape(table.n_rounds, -1)`.
+    #[cfg(feature = "python")]
+    #[getter(round_scores)]
+    fn py_round_scores<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let bytes: Vec<u8> = self.scores.iter().flat_map(|points| points.to_ne_bytes()).collect();
+        PyBytes::new_bound(py, &bytes)
+    }
+
+    fn __len__(&self) -> usize {
+        self.totals.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EntryScoreTable({} entries, {} rounds)", self.totals.len(), self.n_rounds())
+    }
+}
+
+/// Points each of `entries` has earned given `results` so far, under `rule`
+/// (default: the tournament's round points), scored in parallel.
+///
+/// Each completed game's points are worked out once and shared by every
+/// entry that picked its winner, as in `score_completed_bracket`.
+#[pyfunction]
+#[pyo3(signature = (entries, tournament, results = None, rule = None))]
+pub fn score_entries_batch(
+    entries: Vec<BracketEntry>,
+    tournament: &TournamentState,
+    results: Option<Vec<Vec<String>>>,
+    rule: Option<ScoringRule>,
+) -> Result<EntryScoreTable> {
+    let results = Results::new(tournament, &results.unwrap_or_default())?;
+    let rule = rule.unwrap_or_else(|| ScoringRule {
+        points: round_points(tournament, None),
+        ..ScoringRule::default()
+    });
+    let n_slots = tournament.bracket().len();
+    for entry in &entries {
+        check_shape(entry, n_slots)?;
+    }
+    let n_rounds = results.winners.len();
+    let payouts: Vec<Vec<Option<(&String, f64)>>> = (0..n_rounds)
+        .map(|round| {
+            (0..n_slots >> (round + 1))
+                .map(|game| {
+                    let winner = results.decided.get(&(round, game))?;
+                    Some((winner, results.pick_points(tournament, winner, round, &rule)))
+                })
+                .collect()
+        })
+        .collect();
+
+    let rows: Vec<Vec<f64>> = entries
+        .par_iter()
+        .map(|entry| {
+            entry
+                .picks
+                .iter()
+                .zip(&payouts)
+                .map(|(picks, games)| {
+                    picks
+                        .iter()
+                        .zip(games)
+                        .filter_map(|(team, game)| game.filter(|(winner, _)| *winner == team).map(|(_, points)| points))
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+    Ok(EntryScoreTable {
+        scores: (0..n_rounds).flat_map(|round| rows.iter().map(move |row| row[round])).collect(),
+        totals: rows.iter().map(|row| row.iter().sum()).collect(),
+    })
+}
+
+/// Fail unless `entry` picks every main-bracket game of an `n_slots` bracket.
+fn check_shape(entry: &BracketEntry, n_slots: usize) -> Result<()> {
+    let n_rounds = n_slots.max(1).ilog2() as usize;
+    let shape: Vec<usize> = (0..n_rounds).map(|round| n_slots >> (round + 1)).collect();
+    if entry.picks.iter().map(Vec::len).ne(shape.iter().copied()) {
+        return Err(TourneyError::InvalidInput(format!(
+            "entry picks {:?} games per round for a bracket with {shape:?}",
+            entry.picks.iter().map(Vec::len).collect::<Vec<_>>()
+        )));
+    }
+    Ok(())
+}
+
+/// Depth-first walk over every completion of the bracket, game by game in
+/// round order, passing each to `visit` with the entries' final scores, the
+/// outcome's model probability, and the winners by round.
+struct OutcomeSearch<'a, V> {
+    tournament: &'a TournamentState,
+    results: &'a Results,
+    entries: &'a [BracketEntry],
+    points: &'a [f64],
+    /// Every main-bracket game as (round, game), earlier rounds first.
+    games: Vec<(usize, usize)>,
+    /// Winner of each game assigned so far on the current path.
+    winners: Vec<Vec<String>>,
+    scores: Vec<f64>,
+    /// Pairwise win probabilities of the bracket teams.
+    matrix: WinProbMatrix,
+    index: HashMap<String, usize>,
+    visit: V,
+}
+
+impl<'a, V: FnMut(&[f64], f64, &[Vec<String>])> OutcomeSearch<'a, V> {
+    fn new(
+        tournament: &'a TournamentState,
+        results: &'a Results,
+        entries: &'a [BracketEntry],
+        points: &'a [f64],
+        visit: V,
+    ) -> Result<Self> {
+        let n_slots = tournament.bracket().len();
+        let matrix = win_prob_matrix(tournament, None)?;
+        Ok(OutcomeSearch {
+            tournament,
+            results,
+            entries,
+            points,
+            games: (0..points.len()).flat_map(|r| (0..n_slots >> (r + 1)).map(move |g| (r, g))).collect(),
+            winners: (0..points.len()).map(|r| vec![String::new(); n_slots >> (r + 1)]).collect(),
+            scores: vec![0.0; entries.len()],
+            index: matrix.teams.iter().enumerate().map(|(i, team)| (team.clone(), i)).collect(),
+            matrix,
+            visit,
+        })
+    }
+
+    fn explore(&mut self, index: usize, prob: f64) {
+        let Some(&(round, game)) = self.games.get(index) else {
+            (self.visit)(&self.scores, prob, &self.winners);
+            return;
+        };
+        let candidates: Vec<(String, f64)> = match self.results.decided.get(&(round, game)) {
+            Some(winner) => vec![(winner.clone(), 1.0)],
+            None if round == 0 => {
+                // Slot weights are the chances each team fills the slot.
+                let search = &*self;
+                let slots = &search.tournament.bracket()[2 * game..2 * game + 2];
+                let mut candidates: Vec<(String, f64)> = slots
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(side, slot)| {
+                        slot.iter().map(move |(team, &weight)| {
+                            let versus: f64 =
+                                slots[side ^ 1].iter().map(|(other, &w)| w * search.win_prob(team, other)).sum();
+                            (team.clone(), weight * versus)
+                        })
+                    })
+                    .collect();
+                candidates.sort_by(|a, b| a.0.cmp(&b.0));
+                candidates
+            }
+            None => {
+                let (team1, team2) = (&self.winners[round - 1][2 * game], &self.winners[round - 1][2 * game + 1]);
+                vec![(team1.clone(), self.win_prob(team1, team2)), (team2.clone(), self.win_prob(team2, team1))]
+            }
+        };
+        for (winner, win_prob) in candidates {
+            let hits: Vec<usize> =
+                (0..self.entries.len()).filter(|&e| self.entries[e].picks[round].get(game) == Some(&winner)).collect();
+            for &entry in &hits {
+                self.scores[entry] += self.points[round];
+            }
+            self.winners[round][game] = winner;
+            self.explore(index + 1, prob * win_prob);
+            for &entry in &hits {
+                self.scores[entry] -= self.points[round];
+            }
+        }
+    }
+
+    fn win_prob(&self, team1: &str, team2: &str) -> f64 {
+        self.matrix.probs[self.index[team1]][self.index[team2]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::{optimize_entry, PickConstraints};
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_standings_after_first_round() {
+        let state = canonical_tournament(8);
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        // Lock the team chalk's first pick beats as champion, then let it lose.
+        let underdog = state.bracket()[0..2].iter().flat_map(|slot| slot.keys()).find(|t| **t != chalk.picks[0][0]);
+        let locks = PickConstraints::new(Some(vec![(underdog.unwrap().clone(), Round(2))]), None);
+        let busted = optimize_entry(&state, None, Some(locks)).unwrap();
+
+        let results = vec![chalk.picks[0].clone()];
+        let standings =
+            project_standings(vec![chalk.clone(), busted], &state, 2000, Some(results), None, Some(5)).unwrap();
+        let points = round_points(&state, None);
+        let perfect: f64 = chalk.picks.iter().zip(&points).map(|(picks, p)| picks.len() as f64 * p).sum();
+        assert_eq!(standings[0].current_score, 4.0 * points[0]);
+        assert_eq!(standings[0].max_score, perfect);
+        // The busted entry's two late picks are dead.
+        assert_eq!(standings[1].max_score, perfect - points[0] - points[1] - points[2]);
+        for standing in &standings {
+            assert!(standing.current_score <= standing.expected_score);
+            assert!(standing.expected_score <= standing.max_score);
+        }
+        assert!(standings[0].win_prob > standings[1].win_prob);
+        assert!((standings.iter().map(|s| s.win_prob).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_finished_pool_is_settled_and_bad_results_rejected() {
+        let state = canonical_tournament(4);
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let standings = project_standings(vec![chalk.clone()], &state, 50, Some(chalk.picks.clone()), None, None);
+        let standing = &standings.unwrap()[0];
+        assert_eq!(standing.current_score, standing.max_score);
+        assert_eq!(standing.expected_score, standing.max_score);
+
+        // A champion that did not win its semifinal.
+        let loser = state.get_bracket_teams().into_iter().find(|t| !chalk.picks[0].contains(t)).unwrap();
+        let bad = vec![chalk.picks[0].clone(), vec![loser]];
+        assert!(project_standings(vec![chalk], &state, 10, Some(bad), None, None).is_err());
+    }
+
+    #[test]
+    fn test_entry_status_is_exact() {
+        let state = canonical_tournament(8);
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let champion = chalk.champion().unwrap();
+        let faded = PickConstraints::new(None, Some(vec![(champion.clone(), Round(0))]));
+        let fader = optimize_entry(&state, None, Some(faded)).unwrap();
+        let entries = vec![chalk.clone(), fader];
+
+        // Before any games, either entry can finish first or last.
+        let open = entry_status(entries.clone(), &state, None, None, None).unwrap();
+        assert!(open.iter().all(|s| s.best_place == 1 && s.worst_place == 2 && !s.clinched && !s.eliminated));
+
+        // With flat scoring, two rounds of chalk leave the fader too far back.
+        let results = chalk.picks[..2].to_vec();
+        let flat = Some(vec![1.0; 3]);
+        let status = entry_status(entries.clone(), &state, Some(results), None, flat).unwrap();
+        assert!(status[0].clinched && !status[0].eliminated);
+        assert!(status[1].eliminated && status[1].best_place == 2);
+        // Paying two places, second is enough.
+        let two = Payouts::new(vec![0.7, 0.3]).unwrap();
+        assert!(entry_status(entries, &state, None, Some(two), None).unwrap().iter().all(|s| s.clinched));
+
+        let big = canonical_tournament(64);
+        assert!(entry_status(vec![], &big, None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_score_completed_bracket() {
+        let state = canonical_tournament(8);
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let underdog = state.bracket()[0..2].iter().flat_map(|slot| slot.keys()).find(|t| **t != chalk.picks[0][0]);
+        let locks = PickConstraints::new(Some(vec![(underdog.unwrap().clone(), Round(2))]), None);
+        let busted = optimize_entry(&state, None, Some(locks)).unwrap();
+        let results = vec![chalk.picks[0].clone()];
+
+        // Flat points agree with the standings' current and maximum scores.
+        let points = round_points(&state, None);
+        let settled = Results::new(&state, &results).unwrap();
+        for entry in [&chalk, &busted] {
+            let score = score_completed_bracket(entry, &state, Some(results.clone()), None).unwrap();
+            assert_eq!((score.earned, score.max_score()), settled.score_range(entry, &points));
+            assert_eq!(score.round_earned.iter().sum::<f64>(), score.earned);
+        }
+        let score = score_completed_bracket(&busted, &state, Some(results.clone()), None).unwrap();
+        assert_eq!(score.round_correct, vec![3, 0, 0]);
+        assert_eq!(score.round_remaining, vec![0.0, points[1], 0.0]);
+
+        // Upset bonuses on earned picks match scoring the entry directly.
+        let rule = ScoringRule::combine(vec![
+            ScoringRule::new(points.clone(), 1.0).unwrap(),
+            ScoringRule::upset_bonus_rule(vec![1.0; 3]),
+        ]);
+        let upsets = vec![busted.picks[0].clone()];
+        let winners: Vec<HashSet<String>> = vec![upsets[0].iter().cloned().collect(), HashSet::new(), HashSet::new()];
+        let score = score_completed_bracket(&busted, &state, Some(upsets), Some(rule.clone())).unwrap();
+        assert_eq!(score.earned, busted.score_with_rule(&state, &winners, &rule));
+        assert!(score.earned > 4.0 * points[0]);
+
+        let small = optimize_entry(&canonical_tournament(4), None, None).unwrap();
+        assert!(score_completed_bracket(&small, &state, None, None).is_err());
+    }
+
+    #[test]
+    fn test_batch_matches_single_entry_scores() {
+        let state = canonical_tournament(16);
+        let ownership: HashMap<String, Vec<f64>> =
+            state.get_bracket_teams().into_iter().map(|team| (team, vec![0.5; 4])).collect();
+        let entries = crate::field::sample_field(&state, ownership, 200, Some(3)).unwrap();
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let results = chalk.picks[..2].to_vec();
+        let rule = ScoringRule::combine(vec![
+            ScoringRule::new(round_points(&state, None), 1.0).unwrap(),
+            ScoringRule::upset_bonus_rule(vec![1.0; 4]),
+        ]);
+
+        let table = score_entries_batch(entries.clone(), &state, Some(results.clone()), Some(rule.clone())).unwrap();
+        assert_eq!(table.n_rounds(), 4);
+        for (e, entry) in entries.iter().enumerate() {
+            let single = score_completed_bracket(entry, &state, Some(results.clone()), Some(rule.clone())).unwrap();
+            assert_eq!(table.totals[e], single.earned);
+            for round in 0..4 {
+                assert_eq!(table.round_scores(round)[e], single.round_earned[round]);
+            }
+        }
+        assert!(table.totals.iter().any(|&total| total > 0.0));
+
+        let small = optimize_entry(&canonical_tournament(8), None, None).unwrap();
+        assert!(score_entries_batch(vec![small], &state, None, None).is_err());
+    }
+
+    #[test]
+    fn test_outcome_matrix_is_exact() {
+        let state = canonical_tournament(8);
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let faded = PickConstraints::new(None, Some(vec![(chalk.champion().unwrap(), Round(1))]));
+        let fader = optimize_entry(&state, None, Some(faded)).unwrap();
+        let entries = vec![chalk.clone(), fader];
+        let results = vec![chalk.picks[0].clone()];
+
+        let matrix = outcome_matrix(entries.clone(), &state, Some(results.clone()), None).unwrap();
+        let games = vec![GameId::new(Round(1), 0), GameId::new(Round(1), 1), GameId::new(Round(2), 0)];
+        assert_eq!(matrix.games, games);
+        assert_eq!(matrix.outcomes.len(), 8);
+        assert!((matrix.probs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!((matrix.win_probs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        let pinned = Results::new(&state, &results).unwrap().pin(&state).unwrap();
+        for (e, entry) in entries.iter().enumerate() {
+            assert!((matrix.expected_scores[e] - entry.expected_score(&pinned, None).unwrap()).abs() < 1e-9);
+            let weighted: f64 = matrix.probs.iter().zip(&matrix.scores).map(|(p, scores)| p * scores[e]).sum();
+            assert!((weighted - matrix.expected_scores[e]).abs() < 1e-12);
+        }
+
+        // Few enough games left, standings are the exact values.
+        let standings = project_standings(entries.clone(), &state, 10, Some(results), None, Some(1)).unwrap();
+        for (e, standing) in standings.iter().enumerate() {
+            assert_eq!(standing.expected_score, matrix.expected_scores[e]);
+            assert_eq!(standing.win_prob, matrix.win_probs[e]);
+        }
+        assert!(outcome_matrix(entries, &canonical_tournament(64), None, None).is_err());
+    }
+
+    #[test]
+    fn test_persistent_forfeits_are_simulated() {
+        let mut state = canonical_tournament(8);
+        state.forfeit_prob = 0.2;
+        state.persistent_forfeits = true;
+        let chalk = optimize_entry(&state, None, None).unwrap();
+        let results = vec![chalk.picks[0].clone()];
+        assert!(outcome_matrix(vec![chalk.clone()], &state, Some(results.clone()), None).is_err());
+
+        // Standings match simulations of the pinned state, not the walk.
+        let standings = project_standings(vec![chalk.clone()], &state, 20000, Some(results.clone()), None, Some(3));
+        let pinned = Results::new(&state, &results).unwrap().pin(&state).unwrap();
+        let expected = chalk.expected_score(&pinned, None).unwrap();
+        assert!((standings.unwrap()[0].expected_score - expected).abs() < 0.05 * expected);
+
+        state.persistent_forfeits = false;
+        let independent = project_standings(vec![chalk.clone()], &state, 10, Some(results), None, Some(3)).unwrap();
+        assert!((independent[0].expected_score - expected).abs() > 1e-6);
+    }
+}
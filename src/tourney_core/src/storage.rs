@@ -0,0 +1,427 @@
+//! Persistence of tournament snapshots, override history, and trade ledgers.
+//!
+//! Everything is keyed by a Unix timestamp (seconds), so the state the model saw
+//! at any past moment can be rebuilt: the latest snapshot at or before that time,
+//! plus the override changes recorded after it.
+//!
+//! Single-file snapshots (`TournamentState.save`) are written atomically: the
+//! new contents go to a temporary file beside the target, which is fsync'd and
+//! then renamed over it, so a crash mid-save leaves the previous copy intact.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::{Result, TourneyError};
+use crate::tournament::TournamentState;
+
+/// First line of a checksummed snapshot file, before the hex checksum.
+const CHECKSUM_HEADER: &str = "#tourney-state fnv1a64=";
+
+/// A change to the override set. `prob` of `None` records a removal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OverrideEvent {
+    pub timestamp: i64,
+    pub team1: String,
+    pub team2: String,
+    pub prob: Option<f64>,
+}
+
+/// A fill in the trade ledger. Negative `shares` are sales.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub timestamp: i64,
+    pub team: String,
+    pub shares: f64,
+    pub price: f64,
+    pub note: Option<String>,
+}
+
+/// Storage backend for timestamped tournament history.
+pub trait StateStore {
+    /// Persist a full snapshot of `state` taken at `timestamp`.
+    fn save_snapshot(&mut self, timestamp: i64, state: &TournamentState) -> Result<()>;
+
+    /// Latest snapshot taken at or before `timestamp`, with its timestamp.
+    fn snapshot_at(&self, timestamp: i64) -> Result<Option<(i64, TournamentState)>>;
+
+    /// Append an override change to the history.
+    fn record_override(&mut self, event: &OverrideEvent) -> Result<()>;
+
+    /// Override changes with `after < timestamp <= until`, oldest first.
+    fn override_events(&self, after: i64, until: i64) -> Result<Vec<OverrideEvent>>;
+
+    /// Append a trade to the ledger.
+    fn record_trade(&mut self, trade: &Trade) -> Result<()>;
+
+    /// Trades executed at or before `until`, oldest first.
+    fn trades_until(&self, until: i64) -> Result<Vec<Trade>>;
+
+    /// Reconstruct the tournament as of `timestamp`: the latest snapshot with
+    /// any later override changes replayed on top.
+    fn state_at(&self, timestamp: i64) -> Result<Option<TournamentState>> {
+        let Some((taken, mut state)) = self.snapshot_at(timestamp)? else {
+            return Ok(None);
+        };
+        for event in self.override_events(taken, timestamp)? {
+            match event.prob {
+                Some(prob) => state.overrides.add_override(&event.team1, &event.team2, prob)?,
+                None => state.overrides.remove_override(&event.team1, &event.team2),
+            }
+        }
+        Ok(Some(state))
+    }
+
+    /// Net positions implied by the ledger as of `timestamp`.
+    fn positions_at(&self, timestamp: i64) -> Result<HashMap<String, f64>> {
+        let mut positions = HashMap::new();
+        for trade in self.trades_until(timestamp)? {
+            *positions.entry(trade.team).or_insert(0.0) += trade.shares;
+        }
+        Ok(positions)
+    }
+}
+
+/// In-memory `StateStore`, useful for tests and short-lived sessions.
+#[derive(Default)]
+pub struct MemoryStore {
+    snapshots: Vec<(i64, TournamentState)>,
+    overrides: Vec<OverrideEvent>,
+    trades: Vec<Trade>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn save_snapshot(&mut self, timestamp: i64, state: &TournamentState) -> Result<()> {
+        self.snapshots.push((timestamp, state.clone()));
+        Ok(())
+    }
+
+    fn snapshot_at(&self, timestamp: i64) -> Result<Option<(i64, TournamentState)>> {
+        // Later inserts win ties, matching the SQLite store's ordering.
+        Ok(self
+            .snapshots
+            .iter()
+            .enumerate()
+            .filter(|(_, (taken, _))| *taken <= timestamp)
+            .max_by_key(|(i, (taken, _))| (*taken, *i))
+            .map(|(_, snapshot)| snapshot.clone()))
+    }
+
+    fn record_override(&mut self, event: &OverrideEvent) -> Result<()> {
+        self.overrides.push(event.clone());
+        Ok(())
+    }
+
+    fn override_events(&self, after: i64, until: i64) -> Result<Vec<OverrideEvent>> {
+        let mut events: Vec<_> = self
+            .overrides
+            .iter()
+            .filter(|e| e.timestamp > after && e.timestamp <= until)
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.timestamp);
+        Ok(events)
+    }
+
+    fn record_trade(&mut self, trade: &Trade) -> Result<()> {
+        self.trades.push(trade.clone());
+        Ok(())
+    }
+
+    fn trades_until(&self, until: i64) -> Result<Vec<Trade>> {
+        let mut trades: Vec<_> = self.trades.iter().filter(|t| t.timestamp <= until).cloned().collect();
+        trades.sort_by_key(|t| t.timestamp);
+        Ok(trades)
+    }
+}
+
+/// Replace the file at `path` with `contents` so that readers (and a crash)
+/// only ever see the old or the new file, never a partial write.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .ok_or_else(|| TourneyError::InvalidInput(format!("{}: not a file path", path.display())))?;
+    let tmp = dir.join(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+
+    let written = File::create(&tmp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| std::fs::rename(&tmp, path)) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(std::io::Error::new(err.kind(), format!("{}: {err}", path.display())).into());
+    }
+    // Persist the rename itself; directories cannot be opened for syncing on Windows.
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+/// 64-bit FNV-1a hash, used to detect truncated or corrupted snapshots (not
+/// tampering).
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Atomically write `state` to `path` as JSON, preceded by a checksum header
+/// line if `checksum` is set.
+pub fn save_state(path: &Path, state: &TournamentState, checksum: bool) -> Result<()> {
+    let json = state.to_json()?;
+    let contents = if checksum {
+        format!("{CHECKSUM_HEADER}{:016x}\n{json}", fnv1a64(json.as_bytes()))
+    } else {
+        json
+    };
+    write_atomic(path, contents.as_bytes())
+}
+
+/// Read a state written by `save_state` (or plain `to_json` output),
+/// verifying the checksum header if present.
+pub fn load_state(path: &Path) -> Result<TournamentState> {
+    let text = crate::data_files::read_file(&path.to_string_lossy())?;
+    let Some(rest) = text.strip_prefix(CHECKSUM_HEADER) else {
+        return TournamentState::from_json(&text);
+    };
+    let corrupt = |what: &str| TourneyError::InvalidInput(format!("{}: {what}", path.display()));
+    let (expected, json) = rest.split_once('\n').ok_or_else(|| corrupt("truncated snapshot"))?;
+    let expected = u64::from_str_radix(expected.trim(), 16).map_err(|_| corrupt("invalid checksum header"))?;
+    if fnv1a64(json.as_bytes()) != expected {
+        return Err(corrupt("checksum mismatch; the snapshot is corrupt"));
+    }
+    TournamentState::from_json(json)
+}
+
+#[cfg(feature = "storage")]
+pub use sqlite::SqliteStore;
+
+#[cfg(feature = "storage")]
+mod sqlite {
+    use rusqlite::{params, Connection, OptionalExtension};
+    use std::path::Path;
+
+    use super::{OverrideEvent, StateStore, Trade};
+    use crate::error::Result;
+    use crate::tournament::TournamentState;
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            state TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS snapshots_timestamp ON snapshots (timestamp);
+        CREATE TABLE IF NOT EXISTS override_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            team1 TEXT NOT NULL,
+            team2 TEXT NOT NULL,
+            prob REAL
+        );
+        CREATE TABLE IF NOT EXISTS trades (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            team TEXT NOT NULL,
+            shares REAL NOT NULL,
+            price REAL NOT NULL,
+            note TEXT
+        );
+    ";
+
+    /// SQLite-backed `StateStore`. Snapshots are stored as `TournamentState` JSON.
+    pub struct SqliteStore {
+        conn: Connection,
+    }
+
+    impl SqliteStore {
+        /// Open (creating if needed) a store at `path`.
+        pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+            Self::with_connection(Connection::open(path)?)
+        }
+
+        /// Open a private in-memory store.
+        pub fn open_in_memory() -> Result<Self> {
+            Self::with_connection(Connection::open_in_memory()?)
+        }
+
+        fn with_connection(conn: Connection) -> Result<Self> {
+            conn.execute_batch(SCHEMA)?;
+            Ok(SqliteStore { conn })
+        }
+    }
+
+    impl StateStore for SqliteStore {
+        fn save_snapshot(&mut self, timestamp: i64, state: &TournamentState) -> Result<()> {
+            self.conn.execute(
+                "INSERT INTO snapshots (timestamp, state) VALUES (?1, ?2)",
+                params![timestamp, state.to_json()?],
+            )?;
+            Ok(())
+        }
+
+        fn snapshot_at(&self, timestamp: i64) -> Result<Option<(i64, TournamentState)>> {
+            let row: Option<(i64, String)> = self
+                .conn
+                .query_row(
+                    "SELECT timestamp, state FROM snapshots WHERE timestamp <= ?1
+                     ORDER BY timestamp DESC, id DESC LIMIT 1",
+                    params![timestamp],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            row.map(|(taken, json)| Ok((taken, TournamentState::from_json(&json)?)))
+                .transpose()
+        }
+
+        fn record_override(&mut self, event: &OverrideEvent) -> Result<()> {
+            self.conn.execute(
+                "INSERT INTO override_events (timestamp, team1, team2, prob) VALUES (?1, ?2, ?3, ?4)",
+                params![event.timestamp, event.team1, event.team2, event.prob],
+            )?;
+            Ok(())
+        }
+
+        fn override_events(&self, after: i64, until: i64) -> Result<Vec<OverrideEvent>> {
+            let mut stmt = self.conn.prepare(
+                "SELECT timestamp, team1, team2, prob FROM override_events
+                 WHERE timestamp > ?1 AND timestamp <= ?2 ORDER BY timestamp, id",
+            )?;
+            let events = stmt
+                .query_map(params![after, until], |row| {
+                    Ok(OverrideEvent {
+                        timestamp: row.get(0)?,
+                        team1: row.get(1)?,
+                        team2: row.get(2)?,
+                        prob: row.get(3)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(events)
+        }
+
+        fn record_trade(&mut self, trade: &Trade) -> Result<()> {
+            self.conn.execute(
+                "INSERT INTO trades (timestamp, team, shares, price, note) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![trade.timestamp, trade.team, trade.shares, trade.price, trade.note],
+            )?;
+            Ok(())
+        }
+
+        fn trades_until(&self, until: i64) -> Result<Vec<Trade>> {
+            let mut stmt = self.conn.prepare(
+                "SELECT timestamp, team, shares, price, note FROM trades
+                 WHERE timestamp <= ?1 ORDER BY timestamp, id",
+            )?;
+            let trades = stmt
+                .query_map(params![until], |row| {
+                    Ok(Trade {
+                        timestamp: row.get(0)?,
+                        team: row.get(1)?,
+                        shares: row.get(2)?,
+                        price: row.get(3)?,
+                        note: row.get(4)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(trades)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    fn exercise_store(store: &mut dyn StateStore) {
+        store.save_snapshot(100, &canonical_tournament(2)).unwrap();
+        store
+            .record_override(&OverrideEvent {
+                timestamp: 150,
+                team1: "Team 01".to_string(),
+                team2: "Team 02".to_string(),
+                prob: Some(0.8),
+            })
+            .unwrap();
+        store
+            .record_override(&OverrideEvent {
+                timestamp: 250,
+                team1: "Team 01".to_string(),
+                team2: "Team 02".to_string(),
+                prob: None,
+            })
+            .unwrap();
+        for (timestamp, shares) in [(120, 10.0), (220, -4.0)] {
+            store
+                .record_trade(&Trade {
+                    timestamp,
+                    team: "Team 01".to_string(),
+                    shares,
+                    price: 0.5,
+                    note: None,
+                })
+                .unwrap();
+        }
+
+        assert!(store.state_at(50).unwrap().is_none());
+        assert_eq!(store.state_at(120).unwrap().unwrap().overrides.get("Team 01", "Team 02"), None);
+        let during = store.state_at(200).unwrap().unwrap();
+        assert!((during.overrides.get("Team 02", "Team 01").unwrap() - 0.2).abs() < 1e-12);
+        assert_eq!(store.state_at(300).unwrap().unwrap().overrides.get("Team 01", "Team 02"), None);
+
+        assert_eq!(store.positions_at(200).unwrap()["Team 01"], 10.0);
+        assert_eq!(store.positions_at(300).unwrap()["Team 01"], 6.0);
+    }
+
+    #[test]
+    fn test_memory_store_point_in_time() {
+        exercise_store(&mut MemoryStore::new());
+    }
+
+    #[test]
+    fn test_state_file_round_trip_and_corruption() {
+        let dir = std::env::temp_dir().join(format!("tourney_state_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        let mut state = canonical_tournament(2);
+        state.overrides.add_override("Team 01", "Team 02", 0.7).unwrap();
+
+        for checksum in [true, false] {
+            save_state(&path, &state, checksum).unwrap();
+            let loaded = load_state(&path).unwrap();
+            assert_eq!(loaded.overrides.get("Team 01", "Team 02"), Some(0.7));
+        }
+        // Only the target remains; the temporary file was renamed over it.
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        save_state(&path, &state, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, text.replace("0.7", "0.8")).unwrap();
+        let Err(err) = load_state(&path) else {
+            panic!("corrupt snapshot accepted");
+        };
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+        std::fs::write(&path, &text[..text.len() / 2]).unwrap();
+        assert!(load_state(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn test_sqlite_store_point_in_time() {
+        exercise_store(&mut SqliteStore::open_in_memory().unwrap());
+    }
+}
@@ -0,0 +1,329 @@
+//! Trading strategies and a step-by-step backtester.
+//!
+//! A `Strategy` observes the tournament, current positions, and market prices,
+//! and emits actions. The same strategy can be driven by `backtest` over a
+//! recorded sequence of states or called directly against live state.
+
+use std::collections::HashMap;
+
+use crate::costs::TransactionCosts;
+use crate::portfolio::get_portfolio_value_ref;
+use crate::tournament::TournamentState;
+
+/// Everything a strategy can see at one decision point.
+pub struct Observation<'a> {
+    pub tournament: &'a TournamentState,
+    pub positions: &'a HashMap<String, f64>,
+    pub prices: &'a HashMap<String, f64>,
+    /// Model expected scores for `tournament`, computed once per step.
+    pub scores: &'a HashMap<String, f64>,
+}
+
+impl<'a> Observation<'a> {
+    pub fn new(
+        tournament: &'a TournamentState,
+        positions: &'a HashMap<String, f64>,
+        prices: &'a HashMap<String, f64>,
+        scores: &'a HashMap<String, f64>,
+    ) -> Self {
+        Observation {
+            tournament,
+            positions,
+            prices,
+            scores,
+        }
+    }
+}
+
+/// A decision emitted by a strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    /// Trade `shares` of `team` (negative to sell) at up to `price` per share.
+    Trade { team: String, shares: f64, price: f64 },
+    /// Pick `team` to win its game in `round` of a bracket entry.
+    Pick { team: String, round: usize },
+}
+
+/// Decision logic that maps observations to actions.
+pub trait Strategy {
+    /// Short identifier used in reports.
+    fn name(&self) -> &str;
+
+    /// Decide what to do given the current observation.
+    fn decide(&mut self, obs: &Observation) -> Vec<Action>;
+}
+
+/// Buy teams priced below model value and sell teams priced above it.
+pub struct BuyUndervalued {
+    /// Minimum |value - price| before trading.
+    pub min_edge: f64,
+    /// Largest absolute position per team.
+    pub max_position: f64,
+    /// Shares traded per signal.
+    pub lot_size: f64,
+    /// Costs charged on every trade; edges are measured net of them.
+    pub costs: TransactionCosts,
+}
+
+impl Strategy for BuyUndervalued {
+    fn name(&self) -> &str {
+        "buy_undervalued"
+    }
+
+    fn decide(&mut self, obs: &Observation) -> Vec<Action> {
+        let mut teams: Vec<&String> = obs.prices.keys().collect();
+        teams.sort();
+
+        let mut actions = Vec::new();
+        for team in teams {
+            let price = obs.prices[team];
+            let value = obs.scores.get(team).copied().unwrap_or(0.0);
+            let held = obs.positions.get(team).copied().unwrap_or(0.0);
+            let edge = value - price;
+
+            let shares = if edge > self.min_edge {
+                self.lot_size.min(self.max_position - held).max(0.0)
+            } else if -edge > self.min_edge {
+                -self.lot_size.min(self.max_position + held).max(0.0)
+            } else {
+                0.0
+            };
+            // Trade at the all-in price, and only if the edge survives costs.
+            let all_in = self.costs.effective_price(shares, price);
+            let net_edge = (value - all_in) * shares.signum();
+            if shares != 0.0 && net_edge > self.min_edge {
+                actions.push(Action::Trade {
+                    team: team.clone(),
+                    shares,
+                    price: all_in,
+                });
+            }
+        }
+        actions
+    }
+}
+
+/// Scale the whole book down when simulated value volatility exceeds a target.
+pub struct HedgeToTargetVariance {
+    /// Acceptable standard deviation of final portfolio value.
+    pub target_stddev: f64,
+    /// Simulations used to estimate the current standard deviation.
+    pub n_simulations: usize,
+    pub seed: Option<u64>,
+    /// Costs charged on every hedge trade, folded into its price.
+    pub costs: TransactionCosts,
+}
+
+impl Strategy for HedgeToTargetVariance {
+    fn name(&self) -> &str {
+        "hedge_to_target_variance"
+    }
+
+    fn decide(&mut self, obs: &Observation) -> Vec<Action> {
+        let sims = obs.tournament.run_simulations(self.n_simulations, self.seed);
+        let stddev = value_stddev(obs.positions, &sims);
+        if stddev <= self.target_stddev || stddev == 0.0 {
+            return Vec::new();
+        }
+
+        // Portfolio value is linear in positions, so scaling every position by
+        // target/current scales the standard deviation by the same factor.
+        let keep = self.target_stddev / stddev;
+        let mut teams: Vec<&String> = obs.positions.keys().collect();
+        teams.sort();
+        teams
+            .into_iter()
+            .filter_map(|team| {
+                let shares = -obs.positions[team] * (1.0 - keep);
+                let price = self.costs.effective_price(shares, *obs.prices.get(team)?);
+                (shares != 0.0).then(|| Action::Trade {
+                    team: team.clone(),
+                    shares,
+                    price,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Sample standard deviation of portfolio value across simulations.
+pub fn value_stddev(positions: &HashMap<String, f64>, sims: &[HashMap<String, f64>]) -> f64 {
+    if sims.len() < 2 {
+        return 0.0;
+    }
+    let values: Vec<f64> = sims.iter().map(|s| get_portfolio_value_ref(positions, s)).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    var.sqrt()
+}
+
+/// One decision point in a backtest.
+pub struct BacktestStep {
+    pub tournament: TournamentState,
+    pub prices: HashMap<String, f64>,
+}
+
+/// Outcome of running a strategy over a sequence of steps.
+#[derive(Clone, Debug)]
+pub struct BacktestReport {
+    pub strategy: String,
+    pub positions: HashMap<String, f64>,
+    /// Cash after all fills (starts at zero; buying spends cash).
+    pub cash: f64,
+    /// Model value of the final positions under the last step's tournament.
+    pub final_value: f64,
+    pub n_trades: usize,
+}
+
+impl BacktestReport {
+    /// Cash plus marked-to-model positions.
+    pub fn total_equity(&self) -> f64 {
+        self.cash + self.final_value
+    }
+}
+
+/// Run `strategy` over `steps`, filling every trade at its stated price.
+pub fn backtest(
+    strategy: &mut dyn Strategy,
+    steps: &[BacktestStep],
+    initial_positions: HashMap<String, f64>,
+) -> BacktestReport {
+    let mut positions = initial_positions;
+    let mut cash = 0.0;
+    let mut n_trades = 0;
+    let mut final_value = 0.0;
+
+    for step in steps {
+        let scores = step.tournament.calculate_scores_prob();
+        let actions = strategy.decide(&Observation::new(&step.tournament, &positions, &step.prices, &scores));
+        for action in actions {
+            if let Action::Trade { team, shares, price } = action {
+                cash -= shares * price;
+                *positions.entry(team).or_insert(0.0) += shares;
+                n_trades += 1;
+            }
+        }
+        final_value = get_portfolio_value_ref(&positions, &scores);
+    }
+
+    BacktestReport {
+        strategy: strategy.name().to_string(),
+        positions,
+        cash,
+        final_value,
+        n_trades,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_buy_undervalued_direction() {
+        let tournament = canonical_tournament(4);
+        let scores = tournament.calculate_scores_prob();
+        let prices: HashMap<String, f64> = [("Team 01".to_string(), 0.1), ("Team 02".to_string(), 5.0)].into_iter().collect();
+        let positions = HashMap::new();
+
+        let mut strategy = BuyUndervalued {
+            min_edge: 0.05,
+            max_position: 10.0,
+            lot_size: 2.0,
+            costs: TransactionCosts::default(),
+        };
+        let actions = strategy.decide(&Observation::new(&tournament, &positions, &prices, &scores));
+        assert_eq!(
+            actions,
+            vec![
+                Action::Trade {
+                    team: "Team 01".to_string(),
+                    shares: 2.0,
+                    price: 0.1
+                },
+                Action::Trade {
+                    team: "Team 02".to_string(),
+                    shares: -2.0,
+                    price: 5.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_costs_suppress_thin_edges() {
+        let tournament = canonical_tournament(4);
+        let scores = tournament.calculate_scores_prob();
+        let price = scores["Team 01"] - 0.1;
+        let prices: HashMap<String, f64> = [("Team 01".to_string(), price)].into_iter().collect();
+        let positions = HashMap::new();
+        let strategy = |costs| BuyUndervalued {
+            min_edge: 0.05,
+            max_position: 10.0,
+            lot_size: 2.0,
+            costs,
+        };
+        let observation = Observation::new(&tournament, &positions, &prices, &scores);
+
+        let cheap = strategy(TransactionCosts::new(0.0, 0.0, 0.02).unwrap()).decide(&observation);
+        assert_eq!(
+            cheap,
+            vec![Action::Trade {
+                team: "Team 01".to_string(),
+                shares: 2.0,
+                price: price + 0.01
+            }]
+        );
+        // A 0.1 edge per share does not pay a 0.2 fee on a two-share lot.
+        assert!(strategy(TransactionCosts::new(0.2, 0.0, 0.0).unwrap()).decide(&observation).is_empty());
+    }
+
+    #[test]
+    fn test_hedge_reduces_stddev_to_target() {
+        let tournament = canonical_tournament(4);
+        let scores = tournament.calculate_scores_prob();
+        let positions: HashMap<String, f64> = [("Team 01".to_string(), 100.0)].into_iter().collect();
+        let prices: HashMap<String, f64> = [("Team 01".to_string(), 1.0)].into_iter().collect();
+
+        let mut strategy = HedgeToTargetVariance {
+            target_stddev: 10.0,
+            n_simulations: 500,
+            seed: Some(7),
+            costs: TransactionCosts::default(),
+        };
+        let actions = strategy.decide(&Observation::new(&tournament, &positions, &prices, &scores));
+        let Some(Action::Trade { shares, .. }) = actions.first() else {
+            panic!("expected a hedge trade");
+        };
+
+        let hedged: HashMap<String, f64> = [("Team 01".to_string(), 100.0 + shares)].into_iter().collect();
+        let sims = tournament.run_simulations(500, Some(7));
+        assert!((value_stddev(&hedged, &sims) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_backtest_tracks_cash_and_positions() {
+        let tournament = canonical_tournament(4);
+        let prices: HashMap<String, f64> = [("Team 01".to_string(), 0.1)].into_iter().collect();
+        let steps = vec![
+            BacktestStep {
+                tournament: tournament.clone(),
+                prices: prices.clone(),
+            },
+            BacktestStep { tournament, prices },
+        ];
+
+        let mut strategy = BuyUndervalued {
+            min_edge: 0.05,
+            max_position: 3.0,
+            lot_size: 2.0,
+            costs: TransactionCosts::default(),
+        };
+        let report = backtest(&mut strategy, &steps, HashMap::new());
+        assert_eq!(report.positions["Team 01"], 3.0);
+        assert!((report.cash + 0.3).abs() < 1e-12);
+        assert_eq!(report.n_trades, 2);
+        assert!(report.total_equity() > 0.0);
+    }
+}
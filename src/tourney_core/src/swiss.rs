@@ -0,0 +1,309 @@
+//! Swiss-system stage feeding a knockout bracket.
+//!
+//! Each round pairs teams with the same (or nearest) record, avoiding rematches
+//! where possible. After the last round teams are ranked by wins, then Buchholz
+//! score (total wins of opponents faced), then a random draw, and the top
+//! `advance` are seeded into the knockout bracket 1 v N, 2 v N-1, ...
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::seeding::standard_seed_order;
+use crate::tournament::{seeded_rng, TournamentState};
+use crate::win_prob::calculate_win_prob_with;
+
+/// Final table of one simulated Swiss stage, best team first.
+#[derive(Clone, Debug)]
+pub struct SwissStanding {
+    pub teams: Vec<String>,
+    pub wins: Vec<u32>,
+    pub buchholz: Vec<u32>,
+}
+
+/// Swiss stage configuration.
+///
+/// Pairings are adjacent rather than folded: within a group of tied records
+/// the teams are taken in `teams` order and paired 1 v 2, 3 v 4, ..., not
+/// 1 v 3, 2 v 4 as in Dutch-system Swiss. A team skips ahead to the next
+/// unpaired team it has not yet played.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SwissStage {
+    /// Participants in initial seed order (used to break first-round pairings).
+    #[pyo3(get)]
+    pub teams: Vec<String>,
+
+    #[pyo3(get)]
+    pub rounds: usize,
+
+    /// Number of teams advancing to the knockout bracket.
+    #[pyo3(get)]
+    pub advance: usize,
+
+    /// Points awarded per Swiss-round win.
+    #[pyo3(get, set)]
+    pub win_points: f64,
+}
+
+#[pymethods]
+impl SwissStage {
+    #[cfg(feature = "python")]
+    #[new]
+    #[pyo3(signature = (teams, rounds, advance, win_points = 0.0))]
+    pub fn py_new(teams: Vec<String>, rounds: usize, advance: usize, win_points: f64) -> PyResult<Self> {
+        Ok(SwissStage::new(teams, rounds, advance, win_points)?)
+    }
+
+    /// Probability of each team finishing with each win total (index = wins).
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn record_probs(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<HashMap<String, Vec<f64>>> {
+        let mut rng = seeded_rng(seed);
+        let mut probs: HashMap<String, Vec<f64>> = self
+            .teams
+            .iter()
+            .map(|team| (team.clone(), vec![0.0; self.rounds + 1]))
+            .collect();
+        for _ in 0..n_simulations {
+            let standing = self.simulate_stage(tournament, &mut rng)?;
+            for (team, &wins) in standing.teams.iter().zip(&standing.wins) {
+                probs.get_mut(team).unwrap()[wins as usize] += 1.0 / n_simulations as f64;
+            }
+        }
+        Ok(probs)
+    }
+
+    /// Simulate the Swiss stage followed by `tournament`'s knockout scoring.
+    ///
+    /// The bracket stored on `tournament` is replaced by the advancing teams.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn run_simulations(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<HashMap<String, f64>>> {
+        let mut rng = seeded_rng(seed);
+        let seeds: Vec<u64> = (0..n_simulations).map(|_| rng.gen()).collect();
+        seeds
+            .par_iter()
+            .map(|&sim_seed| self.simulate_scores(tournament, sim_seed))
+            .collect()
+    }
+
+    /// Expected scores across Swiss and knockout stages, estimated by simulation.
+    #[pyo3(signature = (tournament, n_simulations, seed = None))]
+    pub fn expected_scores(
+        &self,
+        tournament: &TournamentState,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> Result<HashMap<String, f64>> {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for sim in self.run_simulations(tournament, n_simulations, seed)? {
+            for (team, score) in sim {
+                *totals.entry(team).or_insert(0.0) += score / n_simulations as f64;
+            }
+        }
+        Ok(totals)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SwissStage({} teams, {} rounds, top {} advance)",
+            self.teams.len(),
+            self.rounds,
+            self.advance
+        )
+    }
+}
+
+impl SwissStage {
+    pub fn new(teams: Vec<String>, rounds: usize, advance: usize, win_points: f64) -> Result<Self> {
+        if !teams.len().is_multiple_of(2) {
+            return Err(TourneyError::InvalidInput(
+                "Swiss stage needs an even number of teams".to_string(),
+            ));
+        }
+        if rounds >= teams.len() {
+            return Err(TourneyError::InvalidInput(
+                "Swiss stage must have fewer rounds than teams".to_string(),
+            ));
+        }
+        if !advance.is_power_of_two() || advance > teams.len() {
+            return Err(TourneyError::InvalidInput(
+                "advancing teams must be a power of two no larger than the field".to_string(),
+            ));
+        }
+        Ok(SwissStage {
+            teams,
+            rounds,
+            advance,
+            win_points,
+        })
+    }
+
+    /// Simulate all Swiss rounds once. Fails if a team has no rating.
+    pub fn simulate_stage<R: Rng>(&self, tournament: &TournamentState, rng: &mut R) -> Result<SwissStanding> {
+        let ratings = tournament.ratings_of(&self.teams)?;
+        let n = self.teams.len();
+        let mut wins = vec![0u32; n];
+        let mut opponents: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+        for _ in 0..self.rounds {
+            for (a, b) in pair_round(&wins, &opponents) {
+                let p = calculate_win_prob_with(
+                    ratings[a],
+                    ratings[b],
+                    Some(&tournament.overrides),
+                    tournament.forfeit_prob,
+                    &tournament.model_params,
+                );
+                if rng.gen::<f64>() < p {
+                    wins[a] += 1;
+                } else {
+                    wins[b] += 1;
+                }
+                opponents[a].push(b);
+                opponents[b].push(a);
+            }
+        }
+
+        let buchholz: Vec<u32> = opponents.iter().map(|opps| opps.iter().map(|&o| wins[o]).sum()).collect();
+        let draw: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| {
+            wins[b]
+                .cmp(&wins[a])
+                .then_with(|| buchholz[b].cmp(&buchholz[a]))
+                .then_with(|| draw[b].total_cmp(&draw[a]))
+        });
+
+        Ok(SwissStanding {
+            teams: order.iter().map(|&i| self.teams[i].clone()).collect(),
+            wins: order.iter().map(|&i| wins[i]).collect(),
+            buchholz: order.iter().map(|&i| buchholz[i]).collect(),
+        })
+    }
+
+    /// Knockout bracket seeded from a final Swiss table.
+    pub fn knockout_bracket(&self, standing: &SwissStanding) -> Vec<HashMap<String, f64>> {
+        standard_seed_order(self.advance)
+            .into_iter()
+            .map(|seed| [(standing.teams[seed].clone(), 1.0)].into_iter().collect())
+            .collect()
+    }
+
+    /// One full simulation: Swiss win points plus knockout scores.
+    pub fn simulate_scores(&self, tournament: &TournamentState, seed: u64) -> Result<HashMap<String, f64>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let standing = self.simulate_stage(tournament, &mut rng)?;
+
+        let mut knockout = tournament.clone();
+        knockout.set_bracket(self.knockout_bracket(&standing));
+        let mut scores = if self.advance > 1 {
+            knockout.calculate_scores_sim(Some(rng.gen()))
+        } else {
+            HashMap::new()
+        };
+
+        for (team, &wins) in standing.teams.iter().zip(&standing.wins) {
+            *scores.entry(team.clone()).or_insert(0.0) += f64::from(wins) * self.win_points;
+        }
+        Ok(scores)
+    }
+}
+
+/// Pair teams for one round: best record first, each taking the next-best
+/// unpaired team it has not yet played (or the next-best at all if every
+/// remaining team is a rematch). Ties keep initial seed order.
+fn pair_round(wins: &[u32], opponents: &[Vec<usize>]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..wins.len()).collect();
+    order.sort_by(|&a, &b| wins[b].cmp(&wins[a]).then(a.cmp(&b)));
+
+    let mut paired = vec![false; wins.len()];
+    let mut pairs = Vec::with_capacity(wins.len() / 2);
+    for (pos, &a) in order.iter().enumerate() {
+        if paired[a] {
+            continue;
+        }
+        let mut unpaired = order[pos + 1..].iter().copied().filter(|&b| !paired[b]);
+        let Some(first) = unpaired.clone().next() else {
+            break;
+        };
+        let b = unpaired.find(|b| !opponents[a].contains(b)).unwrap_or(first);
+        paired[a] = true;
+        paired[b] = true;
+        pairs.push((a, b));
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{canonical_ratings, canonical_team_name};
+
+    /// Tournament over an `n`-team canonical field, with its names strongest first.
+    fn make_tournament(n: usize) -> (TournamentState, Vec<String>) {
+        let tournament = TournamentState::new(Vec::new(), canonical_ratings(n), vec![1.0, 1.0, 1.0], None, 0.0, None);
+        (tournament, (0..n).map(canonical_team_name).collect())
+    }
+
+    #[test]
+    fn test_pairing_avoids_rematches() {
+        let wins = vec![1, 1, 0, 0];
+        let opponents = vec![vec![2], vec![3], vec![0], vec![1]];
+        assert_eq!(pair_round(&wins, &opponents), vec![(0, 1), (2, 3)]);
+
+        // Tied teams pair adjacently in seed order, not folded.
+        let no_games = vec![Vec::new(); 4];
+        assert_eq!(pair_round(&[0, 0, 0, 0], &no_games), vec![(0, 1), (2, 3)]);
+
+        // 0 already played 1, so it drops to the next available opponent.
+        let opponents = vec![vec![1], vec![0], vec![3], vec![2]];
+        assert_eq!(pair_round(&[1, 1, 0, 0], &opponents), vec![(0, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn test_record_probs_and_scores() {
+        let (tournament, names) = make_tournament(8);
+        let stage = SwissStage::new(names, 3, 4, 0.5).unwrap();
+
+        let probs = stage.record_probs(&tournament, 500, Some(2)).unwrap();
+        for dist in probs.values() {
+            assert!((dist.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+
+        for sim in stage.run_simulations(&tournament, 20, Some(4)).unwrap() {
+            // 12 Swiss games at 0.5 plus 4-team knockout (2 + 1 games at 1.0)
+            assert!((sim.values().sum::<f64>() - 9.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_unrated_team_rejected() {
+        let (mut tournament, names) = make_tournament(8);
+        tournament.ratings.remove("Team 06");
+        let stage = SwissStage::new(names, 3, 4, 0.0).unwrap();
+        let err = stage.expected_scores(&tournament, 10, Some(1)).err().unwrap();
+        assert!(err.to_string().contains("Team 06"), "{err}");
+        assert!(stage.record_probs(&tournament, 10, Some(1)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_configuration_rejected() {
+        let (_, names) = make_tournament(6);
+        assert!(SwissStage::new(names.clone(), 3, 3, 0.0).is_err());
+        assert!(SwissStage::new(names[..5].to_vec(), 3, 2, 0.0).is_err());
+    }
+}
@@ -1,12 +1,20 @@
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 
 use crate::constants::AVG_SCORING;
 
 /// Team with offensive/defensive efficiency ratings and tempo.
 ///
 /// Ratings are stored as relative efficiency (e.g., 0.05 means 5% above average).
-#[pyclass]
-#[derive(Clone, Debug)]
+///
+/// Teams compare and hash by value, so they work as set members and dict keys
+/// from Python; don't mutate one while it is being used as a key.
+#[pyclass(eq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Team {
     #[pyo3(get, set)]
     pub name: String,
@@ -22,6 +30,34 @@ pub struct Team {
     /// Expected possessions per game
     #[pyo3(get, set)]
     pub tempo: f64,
+
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conference: Option<String>,
+
+    /// Tournament seed (1-16)
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
+
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wins: Option<u32>,
+
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub losses: Option<u32>,
+
+    /// Abbreviated name for compact displays (e.g. "UNC")
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+
+    /// Named style features (e.g. three-point attempt rate) for a
+    /// `MatchupAdjuster` to read.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub style: BTreeMap<String, f64>,
 }
 
 #[pymethods]
@@ -44,6 +80,12 @@ impl Team {
             offense: off,
             defense: def,
             tempo,
+            conference: None,
+            seed: None,
+            wins: None,
+            losses: None,
+            short_name: None,
+            style: BTreeMap::new(),
         }
     }
 
@@ -52,6 +94,40 @@ impl Team {
         self.clone()
     }
 
+    /// Short name if set, otherwise the full name.
+    pub fn display_name(&self) -> String {
+        self.short_name.clone().unwrap_or_else(|| self.name.clone())
+    }
+
+    /// Win-loss record as "W-L", if known.
+    pub fn record(&self) -> Option<String> {
+        Some(format!("{}-{}", self.wins?, self.losses?))
+    }
+
+    /// Net efficiency: points per 100 possessions better than an average team.
+    #[pyo3(signature = (avg_scoring = AVG_SCORING))]
+    pub fn net_rating(&self, avg_scoring: f64) -> f64 {
+        (self.offense - self.defense) * avg_scoring
+    }
+
+    /// Display label with seed and record when known, e.g. "(1) UNC 29-4".
+    pub fn label(&self) -> String {
+        let mut label = self.display_name();
+        if let Some(seed) = self.seed {
+            label = format!("({seed}) {label}");
+        }
+        if let Some(record) = self.record() {
+            label = format!("{label} {record}");
+        }
+        label
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
     fn __str__(&self) -> String {
         format!("{}: {} | {} | {}", self.name, self.offense, self.defense, self.tempo)
     }
@@ -61,15 +137,76 @@ impl Team {
     }
 }
 
+impl Hash for Team {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        for rating in [self.offense, self.defense, self.tempo] {
+            hash_f64(rating, state);
+        }
+        (&self.conference, self.seed, self.wins, self.losses, &self.short_name).hash(state);
+        for (feature, &value) in &self.style {
+            feature.hash(state);
+            hash_f64(value, state);
+        }
+    }
+}
+
+/// Hash `value` consistently with `==`, which treats 0.0 and -0.0 as equal,
+/// and with `total_cmp`: every NaN hashes alike, so equal NaNs do too.
+pub(crate) fn hash_f64<H: Hasher>(value: f64, state: &mut H) {
+    let value = if value.is_nan() { f64::NAN } else { value + 0.0 };
+    value.to_bits().hash(state);
+}
+
 impl Team {
     /// Create a team with adjusted ratings (internal use)
     pub fn with_adjustment(&self, point_adjustment: f64) -> Self {
         let adj = point_adjustment / AVG_SCORING;
         Team {
-            name: self.name.clone(),
             offense: self.offense + adj,
             defense: self.defense - adj,
-            tempo: self.tempo,
+            ..self.clone()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unc() -> Team {
+        let mut team = Team::new("North Carolina".to_string(), 0.1, -0.05, 70.0, false);
+        team.seed = Some(1);
+        team.wins = Some(29);
+        team.losses = Some(4);
+        team.short_name = Some("UNC".to_string());
+        team
+    }
+
+    #[test]
+    fn test_metadata_survives_adjustment_and_json() {
+        let team = unc();
+        let adjusted = team.with_adjustment(2.0);
+        assert_eq!(adjusted.seed, Some(1));
+        assert_eq!(adjusted.label(), "(1) UNC 29-4");
+
+        let restored: Team = serde_json::from_str(&serde_json::to_string(&team).unwrap()).unwrap();
+        assert_eq!(restored.short_name.as_deref(), Some("UNC"));
+        let bare: Team =
+            serde_json::from_str(r#"{"name":"X","offense":0.0,"defense":0.0,"tempo":68.0}"#).unwrap();
+        assert_eq!(bare.label(), "X");
+    }
+
+    #[test]
+    fn test_equal_teams_hash_equal() {
+        let mut copy = unc();
+        assert_eq!(copy, unc());
+        assert_eq!(copy.__hash__(), unc().__hash__());
+        copy.defense = 0.0;
+        let mut zero = unc();
+        zero.defense = -0.0;
+        assert_eq!(copy.__hash__(), zero.__hash__());
+        copy.seed = None;
+        assert_ne!(copy, zero);
+    }
+}
@@ -0,0 +1,167 @@
+//! Fixtures for regression tests against model changes.
+//!
+//! `canonical_tournament` builds deterministic 4-, 16-, and 64-team brackets
+//! (any power of two works) with fixed ratings, seeds, and standard scoring.
+//! Their expected scores are checked in as golden fixtures, so a change to
+//! the win probability model or bracket walk shows up as a golden diff.
+//! `assert_snapshot` gives downstream users the same workflow for their own
+//! states: snapshots are written on first run (or when `UPDATE_SNAPSHOTS` is
+//! set) and compared afterwards.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::constants::ROUND_POINTS;
+use crate::seeding::standard_seed_order;
+use crate::team::Team;
+use crate::tournament::TournamentState;
+
+/// Environment variable that makes `assert_snapshot` rewrite snapshots.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Default tolerance for comparing expected scores.
+pub const SCORE_TOLERANCE: f64 = 1e-9;
+
+/// Name of the team ranked `rank` (0 = strongest) in a canonical field.
+pub fn canonical_team_name(rank: usize) -> String {
+    format!("Team {:02}", rank + 1)
+}
+
+/// Deterministic ratings for an `n`-team field, strongest first.
+///
+/// Efficiency margins fall linearly from best to worst, tempos cycle through a
+/// small range, and each team's seed is its rank within a group of four.
+pub fn canonical_ratings(n: usize) -> HashMap<String, Team> {
+    (0..n)
+        .map(|rank| {
+            let strength = if n > 1 { 1.0 - 2.0 * rank as f64 / (n - 1) as f64 } else { 0.0 };
+            let mut team = Team::new(
+                canonical_team_name(rank),
+                0.08 * strength,
+                -0.04 * strength,
+                64.0 + (rank % 7) as f64,
+                false,
+            );
+            team.seed = Some((rank / regions(n)) as u32 + 1);
+            (team.name.clone(), team)
+        })
+        .collect()
+}
+
+/// Deterministic `n`-team tournament (`n` a power of two).
+///
+/// Fields of 16 or more are split into four regions filled in rank order
+/// (ranks 1-4 are the four 1 seeds, and so on), each laid out in standard seed
+/// order. Scoring uses `ROUND_POINTS` for as many rounds as the bracket has.
+pub fn canonical_tournament(n: usize) -> TournamentState {
+    assert!(n.is_power_of_two() && n >= 2, "canonical tournaments need a power-of-2 field, got {n}");
+    let n_regions = regions(n);
+    let region_size = n / n_regions;
+
+    let bracket = (0..n_regions)
+        .flat_map(|region| {
+            standard_seed_order(region_size)
+                .into_iter()
+                .map(move |seed_line| seed_line * n_regions + region)
+        })
+        .map(|rank| [(canonical_team_name(rank), 1.0)].into_iter().collect())
+        .collect();
+
+    let n_rounds = n.ilog2() as usize;
+    let scoring = (0..n_rounds).map(|r| ROUND_POINTS.get(r).copied().unwrap_or(1.0)).collect();
+    TournamentState::new(bracket, canonical_ratings(n), scoring, None, 0.0, None)
+}
+
+/// Checked-in expected scores for `canonical_tournament(n)`, for n = 4, 16, 64.
+pub fn golden_expected_scores(n: usize) -> Option<HashMap<String, f64>> {
+    let json = match n {
+        4 => include_str!("../fixtures/golden_4.json"),
+        16 => include_str!("../fixtures/golden_16.json"),
+        64 => include_str!("../fixtures/golden_64.json"),
+        _ => return None,
+    };
+    Some(serde_json::from_str(json).expect("golden fixtures are valid JSON"))
+}
+
+/// Panic with a per-team report if `actual` and `expected` differ by more
+/// than `tolerance` for any team (or cover different teams).
+pub fn assert_scores_close(actual: &HashMap<String, f64>, expected: &HashMap<String, f64>, tolerance: f64) {
+    let mut teams: Vec<&String> = actual.keys().chain(expected.keys()).collect();
+    teams.sort();
+    teams.dedup();
+
+    let mismatches: Vec<String> = teams
+        .into_iter()
+        .filter_map(|team| match (actual.get(team), expected.get(team)) {
+            (Some(a), Some(e)) if (a - e).abs() <= tolerance => None,
+            (a, e) => Some(format!("  {team}: got {a:?}, expected {e:?}")),
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "{} team scores differ (tolerance {tolerance}):\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+/// Compare `scores` against the JSON snapshot at `path`.
+///
+/// Writes the snapshot instead when it does not exist yet or when the
+/// `UPDATE_SNAPSHOTS` environment variable is set.
+pub fn assert_snapshot(path: impl AsRef<Path>, scores: &HashMap<String, f64>, tolerance: f64) {
+    let path = path.as_ref();
+    if !path.exists() || std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        let sorted: BTreeMap<&String, &f64> = scores.iter().collect();
+        let json = serde_json::to_string_pretty(&sorted).expect("scores serialize to JSON");
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("snapshot directory is writable");
+        }
+        std::fs::write(path, json + "\n").expect("snapshot file is writable");
+        return;
+    }
+    let text = std::fs::read_to_string(path).expect("snapshot file is readable");
+    let expected: HashMap<String, f64> = serde_json::from_str(&text).expect("snapshot file is valid JSON");
+    assert_scores_close(scores, &expected, tolerance);
+}
+
+fn regions(n: usize) -> usize {
+    if n >= 16 {
+        4
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_tournaments_match_golden_scores() {
+        for n in [4, 16, 64] {
+            let state = canonical_tournament(n);
+            assert_eq!(state.bracket().len(), n);
+            let expected = golden_expected_scores(n).unwrap();
+            assert_scores_close(&state.calculate_scores_prob(), &expected, SCORE_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_canonical_bracket_layout() {
+        let state = canonical_tournament(16);
+        // Region 1 opens with its 1 seed (rank 1) against its 4 seed (rank 13).
+        assert!(state.bracket()[0].contains_key("Team 01"));
+        assert!(state.bracket()[1].contains_key("Team 13"));
+        assert_eq!(state.ratings["Team 13"].seed, Some(4));
+        assert!(golden_expected_scores(8).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "team scores differ")]
+    fn test_assert_scores_close_reports_mismatch() {
+        let actual = HashMap::from([("A".to_string(), 1.0)]);
+        let expected = HashMap::from([("A".to_string(), 1.5)]);
+        assert_scores_close(&actual, &expected, 1e-6);
+    }
+}
@@ -1,22 +1,73 @@
+// pyo3 0.22's generated wrappers re-convert a `PyErr` returned as `PyResult`, which
+// trips this lint outside any item an attribute could reach; the module is the tightest scope.
+#![cfg_attr(feature = "python", allow(clippy::useless_conversion))]
+
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 
-use crate::game_transform::{game_transform_prob, game_transform_sim};
+use crate::archive;
+use crate::builder::TournamentStateBuilder;
+#[cfg(feature = "python")]
+use crate::awaitable::spawn_awaitable;
+use crate::calibration;
+use crate::conditional::{self, ConditionalSimulations};
+use crate::constants::ROUND_POINTS;
+use crate::convergence::{self, ConvergenceReport};
+use crate::data_files;
+use crate::error::{Result, TourneyError};
+use crate::export;
+use crate::game_id::{GameId, Round};
+use crate::game_transform::{
+    game_transform_prob_deterministic, game_transform_prob_with,
+};
+use crate::history::RatedTeamSeries;
+use crate::intervals::{self, IntervalOptions, ScoreIntervals};
+use crate::invariants::verify_invariants;
 use crate::overrides::OverridesMap;
+use crate::propagate;
+use crate::schedule::{self, ScheduleStrength};
+use crate::sim::SimContext;
+use crate::sim_log::{self, SimulationLog};
+use crate::storage;
 use crate::team::Team;
+use crate::trace::trace_span;
+#[cfg(feature = "python")]
+use crate::views::{BracketView, RatingsView};
+use crate::win_matrix::{self, WinProbMatrix};
+use crate::win_prob::{self, calculate_win_prob_with, MatchupProjection, ModelParams};
+
+/// Simulations averaged for probabilistic scoring when it cannot be
+/// propagated exactly (`reseed`, or persistent forfeits in an irregular or
+/// deterministic bracket).
+pub const RESEED_SIMULATIONS: u64 = 4096;
+
+/// RNG seeded from `seed`, or from system entropy when no seed is given.
+pub(crate) fn seeded_rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(s) => ChaCha8Rng::seed_from_u64(s),
+        None => ChaCha8Rng::from_entropy(),
+    }
+}
 
 /// Tournament state containing bracket, ratings, and scoring rules.
 #[pyclass]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TournamentState {
     /// Bracket represented as games, each game is a map of team names to probabilities
-    pub bracket: Vec<HashMap<String, f64>>,
+    bracket: Vec<HashMap<String, f64>>,
+
+    /// Distinct bracket teams in first-appearance order, built on first use
+    /// and reset whenever the bracket is replaced or mutated.
+    #[serde(skip)]
+    bracket_teams: OnceLock<Vec<String>>,
 
     /// Team ratings lookup
-    #[pyo3(get)]
     pub ratings: HashMap<String, Team>,
 
     /// Points awarded per round
@@ -24,11 +75,72 @@ pub struct TournamentState {
     pub scoring: Vec<f64>,
 
     /// Manual probability overrides
+    #[serde(default)]
     pub overrides: OverridesMap,
 
     /// Probability of a team forfeiting
     #[pyo3(get)]
+    #[serde(default)]
     pub forfeit_prob: f64,
+
+    /// Whether play-in (First Four) games are scored as their own round.
+    ///
+    /// When set, `scoring[0]` is awarded to play-in winners and main-bracket
+    /// round `r` is scored with `scoring[r + 1]`.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub play_in_round: bool,
+
+    /// Per-team scaling of round points (e.g. seed for points-per-seed pools).
+    ///
+    /// Teams without an entry score at 1x.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub team_multipliers: HashMap<String, f64>,
+
+    /// Win probability model constants; defaults to the published values.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub model_params: ModelParams,
+
+    /// Sum in a fixed order (sorted keys, fixed chunks, pairwise sums) so
+    /// scores and deltas are bit-identical across runs and thread counts.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub deterministic: bool,
+
+    /// Re-pair survivors after every main-bracket round, highest remaining
+    /// seed against lowest, instead of following the fixed tree.
+    ///
+    /// Ties between equal seeds go to the team from the earlier bracket slot,
+    /// and unseeded teams rank below every seed. Pairings then depend on which
+    /// teams survive, so `calculate_scores_prob` averages `RESEED_SIMULATIONS`
+    /// fixed-seed simulations instead of propagating probabilities exactly.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub reseed: bool,
+
+    /// Decide forfeits once per team per simulation instead of per game: a
+    /// team withdraws from the whole event with probability `forfeit_prob`
+    /// and forfeits every game it reaches.
+    ///
+    /// Probabilistic scoring tracks each team's chance of reaching a round
+    /// with and without having withdrawn, which is exact for fixed-tree
+    /// brackets; brackets it cannot propagate average simulations instead.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub persistent_forfeits: bool,
+
+    /// Region names in bracket order, each covering an equal block of slots.
+    ///
+    /// Empty when unknown; set from version 2 bracket files.
+    #[pyo3(get, set)]
+    #[serde(default)]
+    pub regions: Vec<String>,
+
+    /// Name variants passed to `new`, re-applied by `update_ratings`.
+    #[serde(default)]
+    equivalence_classes: Vec<Vec<String>>,
 }
 
 #[pymethods]
@@ -43,54 +155,135 @@ impl TournamentState {
         forfeit_prob: f64,
         equivalence_classes: Option<Vec<Vec<String>>>,
     ) -> Self {
-        let mut expanded_ratings = ratings;
-
-        // Expand ratings to include all equivalent name variants
-        if let Some(classes) = equivalence_classes {
-            for class in &classes {
-                // Find which name in the class exists in ratings
-                let mut found_team: Option<Team> = None;
-                for name in class {
-                    if let Some(team) = expanded_ratings.get(name) {
-                        found_team = Some(team.clone());
-                        break;
-                    }
-                }
-                // Add all other variant names pointing to cloned Teams
-                if let Some(team) = found_team {
-                    for name in class {
-                        if !expanded_ratings.contains_key(name) {
-                            let mut alias = team.clone();
-                            alias.name = name.clone();
-                            expanded_ratings.insert(name.clone(), alias);
-                        }
-                    }
-                }
-            }
-        }
-
+        let equivalence_classes = equivalence_classes.unwrap_or_default();
         TournamentState {
             bracket,
-            ratings: expanded_ratings,
+            bracket_teams: OnceLock::new(),
+            ratings: expand_equivalents(ratings, &equivalence_classes),
             scoring,
             overrides: overrides.unwrap_or_default(),
             forfeit_prob,
+            play_in_round: false,
+            team_multipliers: HashMap::new(),
+            model_params: ModelParams::default(),
+            deterministic: false,
+            reseed: false,
+            persistent_forfeits: false,
+            regions: Vec::new(),
+            equivalence_classes,
         }
     }
 
-    /// Get the bracket
-    #[getter]
-    pub fn bracket(&self) -> Vec<HashMap<String, f64>> {
-        self.bracket.clone()
+    /// Read-only view of the bracket slots; see `BracketView`.
+    #[cfg(feature = "python")]
+    #[getter(bracket)]
+    fn py_bracket(slf: Py<Self>) -> BracketView {
+        BracketView::new(slf)
+    }
+
+    /// Read-only view of the ratings; see `RatingsView`.
+    #[cfg(feature = "python")]
+    #[getter(ratings)]
+    fn py_ratings(slf: Py<Self>) -> RatingsView {
+        RatingsView::new(slf)
     }
 
     /// Calculate expected scores using probabilistic method.
     ///
     /// Returns a map of team names to their expected tournament scores.
     pub fn calculate_scores_prob(&self) -> HashMap<String, f64> {
+        trace_span!(DEBUG, "calculate_scores_prob", n_slots = self.bracket.len());
         self.calculate_scores_internal(false, None)
     }
 
+    /// Expected points per team earned in each round.
+    ///
+    /// Vectors are indexed like `scoring` (index 0 is the play-in round when
+    /// `play_in_round` is set) and sum to `calculate_scores_prob`.
+    pub fn calculate_scores_by_round(&self) -> HashMap<String, Vec<f64>> {
+        self.scores_by_round_internal(false, None)
+    }
+
+    /// Probability-weighted opponent ratings over `team`'s remaining games.
+    pub fn strength_of_remaining_schedule(&self, team: &str) -> Result<ScheduleStrength> {
+        schedule::strength_of_remaining_schedule(self, team)
+    }
+
+    /// Pairwise win probabilities among `teams` (default: the bracket teams)
+    /// under the current ratings, overrides, and model.
+    #[pyo3(signature = (teams = None))]
+    pub fn win_prob_matrix(&self, teams: Option<Vec<String>>) -> Result<WinProbMatrix> {
+        win_matrix::win_prob_matrix(self, teams)
+    }
+
+    /// How much the members of `model_params.ensemble` disagree about
+    /// `team1` beating `team2` (weighted standard deviation of their
+    /// probabilities); 0 without an ensemble or when an override decides the
+    /// game.
+    pub fn model_disagreement(&self, team1: &str, team2: &str) -> Result<f64> {
+        let rating = |name: &str| {
+            self.ratings
+                .get(name)
+                .ok_or_else(|| TourneyError::InvalidInput(format!("no rating for {name}")))
+        };
+        let (team1, team2) = (rating(team1)?, rating(team2)?);
+        Ok(self.model_params.ensemble.as_ref().map_or(0.0, |ensemble| {
+            ensemble.disagreement(team1, team2, Some(&self.overrides), self.forfeit_prob)
+        }))
+    }
+
+    /// Expected scores from the ratings model alone, ignoring all overrides.
+    pub fn calculate_scores_prob_raw(&self) -> HashMap<String, f64> {
+        let raw = TournamentState {
+            overrides: OverridesMap::default(),
+            ..self.clone()
+        };
+        raw.calculate_scores_prob()
+    }
+
+    /// Per-team `(with_overrides, raw)` expected scores, so the value manual
+    /// overrides move for each team is `with_overrides - raw`.
+    pub fn override_impact(&self) -> HashMap<String, (f64, f64)> {
+        let with_overrides = self.calculate_scores_prob();
+        let raw = self.calculate_scores_prob_raw();
+        self.get_bracket_teams()
+            .into_iter()
+            .map(|team| {
+                let scores = (
+                    with_overrides.get(&team).copied().unwrap_or(0.0),
+                    raw.get(&team).copied().unwrap_or(0.0),
+                );
+                (team, scores)
+            })
+            .collect()
+    }
+
+    /// Replace the ratings with `new_ratings`, returning each bracket team's
+    /// `(before, after)` expected score.
+    ///
+    /// `new_ratings` is expanded with the equivalence classes given to `new`,
+    /// then checked like `TournamentStateBuilder::build` checks ratings: every
+    /// bracket team must be rated, with finite values. Otherwise nothing
+    /// changes.
+    pub fn update_ratings(&mut self, new_ratings: HashMap<String, Team>) -> Result<HashMap<String, (f64, f64)>> {
+        let new_ratings = expand_equivalents(new_ratings, &self.equivalence_classes);
+        check_ratings(&self.bracket, &new_ratings)?;
+        let before = self.calculate_scores_prob();
+        self.ratings = new_ratings;
+        let after = self.calculate_scores_prob();
+        Ok(self
+            .get_bracket_teams()
+            .into_iter()
+            .map(|team| {
+                let scores = (
+                    before.get(&team).copied().unwrap_or(0.0),
+                    after.get(&team).copied().unwrap_or(0.0),
+                );
+                (team, scores)
+            })
+            .collect())
+    }
+
     /// Simulate tournament once using Monte Carlo method.
     ///
     /// Returns a map of team names to their scores in this simulation.
@@ -105,36 +298,207 @@ impl TournamentState {
     /// Uses all available CPU cores for maximum throughput.
     #[pyo3(signature = (n_simulations, seed = None))]
     pub fn run_simulations(&self, n_simulations: usize, seed: Option<u64>) -> Vec<HashMap<String, f64>> {
+        trace_span!(INFO, "run_simulations", n_simulations, n_slots = self.bracket.len());
         // Generate seeds upfront (sequential for reproducibility)
         let seeds: Vec<u64> = {
-            let mut rng = match seed {
-                Some(s) => ChaCha8Rng::seed_from_u64(s),
-                None => ChaCha8Rng::from_entropy(),
-            };
+            let mut rng = seeded_rng(seed);
             (0..n_simulations).map(|_| rng.gen::<u64>()).collect()
         };
 
         // Run simulations in parallel
         seeds
             .par_iter()
-            .map(|&sim_seed| self.calculate_scores_internal(true, Some(sim_seed)))
+            .map_init(|| SimContext::new(self), |context, &sim_seed| context.simulate_scores(Some(sim_seed)))
             .collect()
     }
 
+    /// `run_simulations` keeping every game's result instead of team scores;
+    /// see `SimulationLog`. The same seed gives the same simulations.
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn run_simulation_log(&self, n_simulations: usize, seed: Option<u64>) -> Result<SimulationLog> {
+        sim_log::simulation_log(self, n_simulations, seed)
+    }
+
+    /// `run_simulations` summarized as expected scores and championship
+    /// probabilities with their Monte Carlo standard errors. With
+    /// `target_std_error`, stops as soon as every team's standard error for
+    /// `metric` (`"points"` or `"champion"`) is below it, so `n_simulations`
+    /// becomes a cap: `target_std_error=0.001` simulates until championship
+    /// probabilities are within 0.1 percentage points.
+    #[pyo3(signature = (n_simulations, target_std_error = None, metric = "champion", batch_size = 10000, seed = None))]
+    pub fn simulate_with_diagnostics(
+        &self,
+        n_simulations: usize,
+        target_std_error: Option<f64>,
+        metric: &str,
+        batch_size: usize,
+        seed: Option<u64>,
+    ) -> Result<ConvergenceReport> {
+        convergence::simulate_with_diagnostics(self, n_simulations, target_std_error, metric, batch_size, seed)
+    }
+
+    /// Bootstrapped confidence intervals on every bracket team's expected
+    /// score and, with `positions`, on the portfolio's expected value. With
+    /// `rating_sd` above 0, the intervals also reflect rating uncertainty:
+    /// simulations are spread over `n_rating_draws` copies of the ratings with
+    /// each team's offense and defense perturbed by that standard deviation.
+    /// See `ScoreIntervals`.
+    #[pyo3(signature = (
+        n_simulations = 10000,
+        positions = None,
+        rating_sd = 0.0,
+        n_rating_draws = 100,
+        level = 0.95,
+        n_resamples = 1000,
+        seed = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn score_intervals(
+        &self,
+        n_simulations: usize,
+        positions: Option<HashMap<String, f64>>,
+        rating_sd: f64,
+        n_rating_draws: usize,
+        level: f64,
+        n_resamples: usize,
+        seed: Option<u64>,
+    ) -> Result<ScoreIntervals> {
+        let options = IntervalOptions {
+            rating_sd,
+            n_rating_draws,
+            level,
+            n_resamples,
+        };
+        intervals::score_intervals(self, n_simulations, positions.as_ref(), options, seed)
+    }
+
+    /// Up to `n_simulations` simulations in which every `(team, round)` in
+    /// `condition` holds, that is, each team wins its game in that
+    /// main-bracket round. See `ConditionalSimulations`.
+    #[pyo3(signature = (n_simulations, condition, seed = None, max_attempts = None))]
+    pub fn simulate_conditional(
+        &self,
+        n_simulations: usize,
+        condition: Vec<(String, Round)>,
+        seed: Option<u64>,
+        max_attempts: Option<usize>,
+    ) -> Result<ConditionalSimulations> {
+        conditional::simulate_conditional(self, n_simulations, &condition, seed, max_attempts)
+    }
+
+    /// `run_simulations`, streaming results to a binary archive at `path`
+    /// instead of returning them. Open the file with `SimulationArchive`.
+    #[pyo3(signature = (path, n_simulations, seed = None))]
+    pub fn run_simulations_to_file(&self, path: &str, n_simulations: usize, seed: Option<u64>) -> Result<()> {
+        archive::write_simulations(self, std::path::Path::new(path), n_simulations, seed)
+    }
+
+    /// `run_simulations` on the GPU (requires the `gpu` feature).
+    ///
+    /// Same inputs and output shape; the GPU uses its own RNG stream, so a
+    /// given seed produces different (but reproducible) draws than the CPU.
+    #[cfg(feature = "gpu")]
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn run_simulations_gpu(&self, n_simulations: usize, seed: Option<u64>) -> Result<Vec<HashMap<String, f64>>> {
+        crate::gpu::shared_simulator()?.run_simulations(self, n_simulations, seed)
+    }
+
+    /// Per-team (mean, standard deviation) of simulated scores, reduced on the
+    /// GPU so 10M+ simulation runs never materialize individual results.
+    #[cfg(feature = "gpu")]
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn simulation_stats_gpu(&self, n_simulations: usize, seed: Option<u64>) -> Result<HashMap<String, (f64, f64)>> {
+        crate::gpu::shared_simulator()?.simulation_stats(self, n_simulations, seed)
+    }
+
+    /// Awaitable variant of `run_simulations` for asyncio callers.
+    ///
+    /// Simulations run on the background thread pool, so the caller's event loop
+    /// keeps serving requests while they complete.
+    #[cfg(feature = "python")]
+    #[pyo3(signature = (n_simulations, seed = None))]
+    pub fn run_simulations_async(
+        &self,
+        py: Python<'_>,
+        n_simulations: usize,
+        seed: Option<u64>,
+    ) -> PyResult<PyObject> {
+        let state = self.clone();
+        spawn_awaitable(
+            py,
+            move || state.run_simulations(n_simulations, seed),
+            |py, results| Ok(results.into_py(py)),
+        )
+    }
+
     /// Get all teams in the bracket.
     pub fn get_bracket_teams(&self) -> Vec<String> {
-        let mut teams = Vec::new();
-        for game in &self.bracket {
-            for team in game.keys() {
-                if !teams.contains(team) {
-                    teams.push(team.clone());
+        self.bracket_teams().to_vec()
+    }
+
+    /// Indices of bracket slots that are filled by a play-in game.
+    pub fn play_in_games(&self) -> Vec<usize> {
+        self.bracket
+            .iter()
+            .enumerate()
+            .filter(|(_, game)| game.len() > 1)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Points awarded for winning a game in main-bracket round `round` (0-based),
+    /// accounting for a scored play-in round.
+    pub fn round_points(&self, round: Round) -> f64 {
+        self.scoring.get(round.scoring_index(self.play_in_round)).copied().unwrap_or(1.0)
+    }
+
+    /// The game in which `team1` and `team2` would meet, or `None` if either
+    /// is not in the bracket or both are in the same slot.
+    pub fn game_between(&self, team1: &str, team2: &str) -> Option<GameId> {
+        let slot = |team: &str| self.bracket.iter().position(|game| game.contains_key(team));
+        let (slot1, slot2) = (slot(team1)?, slot(team2)?);
+        let round = Round::try_from((slot1 ^ slot2).checked_ilog2()? as usize).ok()?;
+        Some(GameId::for_slot(slot1, round))
+    }
+
+    /// Teams that can play in `game`, in bracket order.
+    pub fn game_teams(&self, game: GameId) -> Result<Vec<String>> {
+        let slots = game.slot_range();
+        let n_slots = self.bracket.len();
+        if slots.end > n_slots || !n_slots.is_power_of_two() {
+            return Err(TourneyError::InvalidInput(format!(
+                "{game} is not in this {n_slots}-slot bracket"
+            )));
+        }
+        let mut teams: Vec<String> = Vec::new();
+        for slot in &self.bracket[slots] {
+            let mut names: Vec<&String> = slot.keys().collect();
+            names.sort();
+            for name in names {
+                if !teams.contains(name) {
+                    teams.push(name.clone());
                 }
             }
         }
-        teams
+        Ok(teams)
+    }
+
+    /// Points awarded for winning a play-in game, or 0 if play-ins are unscored.
+    pub fn play_in_points(&self) -> f64 {
+        if self.play_in_round {
+            self.scoring.first().copied().unwrap_or(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Scoring multiplier applied to `team`'s round points.
+    pub fn team_multiplier(&self, team: &str) -> f64 {
+        self.team_multipliers.get(team).copied().unwrap_or(1.0)
     }
 
-    /// Get the overrides map
+    /// Get the overrides map. The copy shares its tables with the state until
+    /// either side is modified, so this is cheap however many overrides exist.
     #[getter]
     pub fn overrides(&self) -> OverridesMap {
         self.overrides.clone()
@@ -146,6 +510,241 @@ impl TournamentState {
         self.overrides = overrides;
     }
 
+    /// `model_params.confidence`: how far model probabilities are trusted,
+    /// as a scale on their log-odds (below 1 shrinks them toward 0.5).
+    #[getter]
+    pub fn confidence(&self) -> f64 {
+        self.model_params.confidence
+    }
+
+    #[setter]
+    pub fn set_confidence(&mut self, confidence: f64) -> Result<()> {
+        self.model_params.set_confidence(confidence)
+    }
+
+    /// Maximum likelihood `confidence` for this state's ratings model given
+    /// completed games as `(winner, loser)`, e.g. `HistoricalSeason.results`.
+    /// Predictions ignore overrides and the current confidence.
+    pub fn fit_confidence(&self, games: Vec<(String, String)>) -> Result<f64> {
+        let mut params = self.model_params.clone();
+        params.confidence = 1.0;
+        let probs = games
+            .iter()
+            .map(|(winner, loser)| {
+                let rating = |name: &String| {
+                    self.ratings
+                        .get(name)
+                        .ok_or_else(|| TourneyError::InvalidInput(format!("no rating for {name}")))
+                };
+                Ok(calculate_win_prob_with(rating(winner)?, rating(loser)?, None, 0.0, &params))
+            })
+            .collect::<Result<Vec<f64>>>()?;
+        calibration::fit_confidence(probs, vec![true; games.len()])
+    }
+
+    /// Project a matchup between two rated teams with this state's model,
+    /// overrides, and forfeit probability, so `win_prob` matches scoring.
+    pub fn project_matchup(&self, team1: &str, team2: &str) -> Result<MatchupProjection> {
+        let teams = self.ratings_of(&[team1.to_string(), team2.to_string()])?;
+        Ok(win_prob::project_matchup(
+            teams[0],
+            teams[1],
+            Some(&self.overrides),
+            self.forfeit_prob,
+            Some(&self.model_params),
+        ))
+    }
+
+    /// Exchange the contents of bracket slots `slot_a` and `slot_b`.
+    pub fn swap_teams(&mut self, slot_a: usize, slot_b: usize) -> Result<()> {
+        let n_slots = self.bracket.len();
+        if slot_a >= n_slots || slot_b >= n_slots {
+            return Err(TourneyError::InvalidInput(format!(
+                "cannot swap slots {slot_a} and {slot_b} of a {n_slots}-slot bracket"
+            )));
+        }
+        self.bracket_mut().swap(slot_a, slot_b);
+        Ok(())
+    }
+
+    /// Put `new` in `old`'s place in the bracket, with the same slot
+    /// probability.
+    ///
+    /// `rating` is stored for `new` (under that name); without one, `new`
+    /// must already be rated. `old` keeps its rating, but overrides and
+    /// multipliers naming it no longer apply to the bracket.
+    #[pyo3(signature = (old, new, rating = None))]
+    pub fn replace_team(&mut self, old: &str, new: &str, rating: Option<Team>) -> Result<()> {
+        let slot = self.slot_of(old)?;
+        if old != new && self.bracket_teams().iter().any(|team| team == new) {
+            return Err(TourneyError::InvalidInput(format!("{new} is already in the bracket")));
+        }
+        match rating {
+            Some(rating) => {
+                self.ratings.insert(new.to_string(), Team { name: new.to_string(), ..rating });
+            }
+            None if !self.ratings.contains_key(new) => {
+                return Err(TourneyError::InvalidInput(format!("no rating for replacement team {new}")));
+            }
+            None => {}
+        }
+        let game = &mut self.bracket_mut()[slot];
+        let prob = game.remove(old).expect("slot_of found the team");
+        game.insert(new.to_string(), prob);
+        Ok(())
+    }
+
+    /// Take `name` out of the tournament.
+    ///
+    /// `policy` is `"renormalize"` to drop it from a play-in slot it shares,
+    /// rescaling the other teams' probabilities (an error if it is alone in
+    /// its slot), or `"walkover"` to leave the slot in place and have `name`
+    /// lose its first game to whoever it meets, via certain overrides.
+    #[pyo3(signature = (name, policy = "walkover"))]
+    pub fn remove_team(&mut self, name: &str, policy: &str) -> Result<()> {
+        let slot = self.slot_of(name)?;
+        match policy {
+            "renormalize" => {
+                let game = &self.bracket[slot];
+                let remaining = 1.0 - game[name];
+                if game.len() < 2 || remaining <= 0.0 {
+                    return Err(TourneyError::InvalidInput(format!(
+                        "cannot renormalize slot {slot} without {name}; use the \"walkover\" policy"
+                    )));
+                }
+                let game = &mut self.bracket_mut()[slot];
+                game.remove(name);
+                game.values_mut().for_each(|prob| *prob /= remaining);
+                Ok(())
+            }
+            "walkover" => {
+                let opponents: Vec<String> =
+                    self.bracket.get(slot ^ 1).into_iter().flat_map(|game| game.keys().cloned()).collect();
+                if opponents.is_empty() {
+                    return Err(TourneyError::InvalidInput(format!("{name} has no first-round opponent")));
+                }
+                for opponent in opponents {
+                    self.overrides.add_override(&opponent, name, 1.0)?;
+                }
+                Ok(())
+            }
+            _ => Err(TourneyError::InvalidInput(format!(
+                "unknown removal policy {policy:?}; expected \"renormalize\" or \"walkover\""
+            ))),
+        }
+    }
+
+    /// Graphviz DOT graph of the bracket annotated with advancement probabilities.
+    pub fn to_dot(&self) -> Result<String> {
+        export::to_dot(self)
+    }
+
+    /// Bracket as a nested JSON tree annotated with advancement probabilities.
+    pub fn to_bracket_json(&self) -> Result<String> {
+        Ok(export::to_bracket_json(self)?.to_string())
+    }
+
+    /// Serialize this state (bracket, ratings, scoring, overrides) to JSON.
+    #[cfg(feature = "python")]
+    #[pyo3(name = "to_json")]
+    pub fn py_to_json(&self) -> Result<String> {
+        self.to_json()
+    }
+
+    /// Build a state from JSON produced by `to_json`.
+    #[cfg(feature = "python")]
+    #[staticmethod]
+    #[pyo3(name = "from_json")]
+    pub fn py_from_json(json: &str) -> Result<Self> {
+        Self::from_json(json)
+    }
+
+    /// Save this state to `path` as JSON, atomically replacing any existing
+    /// file. With `checksum`, a header line lets `load` detect corruption.
+    #[pyo3(signature = (path, checksum = true))]
+    pub fn save(&self, path: &str, checksum: bool) -> Result<()> {
+        storage::save_state(std::path::Path::new(path), self, checksum)
+    }
+
+    /// Load a state written by `save` (or `to_json`), verifying its checksum.
+    #[staticmethod]
+    pub fn load(path: &str) -> Result<Self> {
+        storage::load_state(std::path::Path::new(path))
+    }
+
+    /// Load a tournament from the data files: bracket, ratings, and
+    /// optionally overrides and rating adjustments (see `data_files` for the
+    /// formats). `scoring` defaults to `ROUND_POINTS`.
+    ///
+    /// Ratings are expanded with `equivalence_classes` before the bracket is
+    /// read, so bracket names may use any variant. Seeds and regions from a
+    /// version 2 bracket file are set on the ratings and `regions`. The result must pass
+    /// `verify_invariants`; every violation is reported in the error.
+    #[staticmethod]
+    #[pyo3(signature = (
+        bracket_path, ratings_path, overrides_path = None, scoring = None, adjustments_path = None,
+        equivalence_classes = None
+    ))]
+    pub fn from_files(
+        bracket_path: &str,
+        ratings_path: &str,
+        overrides_path: Option<&str>,
+        scoring: Option<Vec<f64>>,
+        adjustments_path: Option<&str>,
+        equivalence_classes: Option<Vec<Vec<String>>>,
+    ) -> Result<Self> {
+        let adjustments = adjustments_path
+            .map(|path| data_files::parse_adjustments(&data_files::read_file(path)?))
+            .transpose()?;
+        let ratings = data_files::parse_ratings(&data_files::read_file(ratings_path)?, adjustments.as_ref())?;
+        let overrides = overrides_path
+            .map(|path| OverridesMap::from_csv(&data_files::read_file(path)?))
+            .transpose()?;
+        let scoring = scoring.unwrap_or_else(|| ROUND_POINTS.to_vec());
+        let mut state = TournamentState::new(Vec::new(), ratings, scoring, overrides, 0.0, equivalence_classes);
+
+        let bracket_text = data_files::read_file(bracket_path)?;
+        let file = data_files::parse_bracket_file(&bracket_text)?;
+        state.set_bracket(file.slot_probabilities(&state.ratings, &state.overrides)?);
+        for (team, &seed) in &file.seeds {
+            if let Some(rating) = state.ratings.get_mut(team) {
+                rating.seed = Some(seed);
+            }
+        }
+        state.regions = file.regions;
+        state
+            .validate()
+            .map_err(|err| TourneyError::InvalidInput(format!("{bracket_path}: {err}")))?;
+        Ok(state)
+    }
+
+    /// Build a state from keyword arguments. `scoring` defaults to
+    /// `ROUND_POINTS` and `model` to the default `ModelParams`. Every bracket
+    /// team must have a rating, `scoring` must cover every round, and the
+    /// result must pass `verify_invariants`.
+    #[staticmethod]
+    #[pyo3(signature = (*, bracket, ratings, scoring = None, overrides = None, forfeit_prob = 0.0, model = None))]
+    pub fn create(
+        bracket: Vec<HashMap<String, f64>>,
+        ratings: HashMap<String, Team>,
+        scoring: Option<Vec<f64>>,
+        overrides: Option<OverridesMap>,
+        forfeit_prob: f64,
+        model: Option<ModelParams>,
+    ) -> Result<Self> {
+        let mut builder = TournamentState::builder().bracket(bracket).ratings(ratings).forfeit_prob(forfeit_prob);
+        if let Some(scoring) = scoring {
+            builder = builder.scoring(scoring);
+        }
+        if let Some(overrides) = overrides {
+            builder = builder.overrides(overrides);
+        }
+        if let Some(model) = model {
+            builder = builder.model(model);
+        }
+        builder.build()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "TournamentState({} teams, {} rounds)",
@@ -155,10 +754,27 @@ impl TournamentState {
     }
 
     /// Create a modified copy with an override added
-    pub fn with_override(&self, team1: &str, team2: &str, prob: f64) -> Self {
+    pub fn with_override(&self, team1: &str, team2: &str, prob: f64) -> Result<Self> {
         let mut new_state = self.clone();
-        new_state.overrides.add_override(team1, team2, prob);
-        new_state
+        new_state.overrides.add_override(team1, team2, prob)?;
+        Ok(new_state)
+    }
+
+    /// Copy of this state with ratings taken from `series` as of `timestamp`.
+    ///
+    /// With `half_life` (seconds) ratings are time-decayed averages of past
+    /// observations; otherwise they are interpolated. Teams without an
+    /// observation by `timestamp` keep their current ratings. Fails if
+    /// `half_life` is not positive.
+    #[pyo3(signature = (series, timestamp, half_life = None))]
+    pub fn as_of(&self, series: &RatedTeamSeries, timestamp: i64, half_life: Option<f64>) -> Result<Self> {
+        let mut new_state = self.clone();
+        let ratings = match half_life {
+            Some(half_life) => series.decayed_ratings(timestamp, half_life)?,
+            None => series.ratings_at(timestamp, true),
+        };
+        new_state.ratings.extend(ratings);
+        Ok(new_state)
     }
 
     /// Create a modified copy with a team's rating adjusted
@@ -170,6 +786,18 @@ impl TournamentState {
         new_state
     }
 
+    /// Create a modified copy with every team in `team_names` adjusted by the
+    /// same `point_delta` (names missing from the ratings are ignored).
+    pub fn with_group_adjustment(&self, team_names: Vec<String>, point_delta: f64) -> Self {
+        let mut new_state = self.clone();
+        for name in &team_names {
+            if let Some(team) = new_state.ratings.get_mut(name) {
+                *team = team.with_adjustment(point_delta);
+            }
+        }
+        new_state
+    }
+
     /// Calculate scores for multiple override scenarios in parallel.
     ///
     /// Takes a list of override scenarios, where each scenario is a list of
@@ -181,61 +809,326 @@ impl TournamentState {
     pub fn calculate_scores_prob_batch(
         &self,
         override_scenarios: Vec<Vec<(String, String, f64)>>,
-    ) -> Vec<HashMap<String, f64>> {
+    ) -> Result<Vec<HashMap<String, f64>>> {
         override_scenarios
             .par_iter()
             .map(|overrides| {
                 // Create modified state with these overrides
                 let mut state = self.clone();
                 for (team1, team2, prob) in overrides {
-                    state.overrides.add_override(team1, team2, *prob);
+                    state.overrides.add_override(team1, team2, *prob)?;
                 }
                 // Calculate scores
-                state.calculate_scores_internal(false, None)
+                Ok(state.calculate_scores_internal(false, None))
             })
             .collect()
     }
 }
 
 impl TournamentState {
+    /// A `TournamentStateBuilder`, naming each input and validating the
+    /// result instead of taking them positionally like `new`.
+    pub fn builder() -> TournamentStateBuilder {
+        TournamentStateBuilder::new()
+    }
+
+    /// Reseeding rank of every bracket team: (seed, original slot), with
+    /// unseeded teams after all seeds.
+    pub(crate) fn reseed_keys(&self) -> HashMap<String, (u32, usize)> {
+        let mut keys = HashMap::new();
+        for (slot, game) in self.bracket.iter().enumerate() {
+            for team in game.keys() {
+                let seed = self.ratings.get(team).and_then(|t| t.seed).unwrap_or(u32::MAX);
+                keys.insert(team.clone(), (seed, slot));
+            }
+        }
+        keys
+    }
+
+    /// Bracket slots, in bracket order.
+    pub fn bracket(&self) -> &[HashMap<String, f64>] {
+        &self.bracket
+    }
+
+    /// Replace the bracket.
+    pub fn set_bracket(&mut self, bracket: Vec<HashMap<String, f64>>) {
+        self.bracket = bracket;
+        self.bracket_teams = OnceLock::new();
+    }
+
+    /// Index of the bracket slot holding `team`.
+    fn slot_of(&self, team: &str) -> Result<usize> {
+        self.bracket
+            .iter()
+            .position(|game| game.contains_key(team))
+            .ok_or_else(|| TourneyError::InvalidInput(format!("team not in bracket: {team}")))
+    }
+
+    /// Ratings of `teams`, in the same order. Fails on the first team without
+    /// a rating.
+    pub(crate) fn ratings_of(&self, teams: &[String]) -> Result<Vec<&Team>> {
+        teams
+            .iter()
+            .map(|team| {
+                self.ratings
+                    .get(team)
+                    .ok_or_else(|| TourneyError::InvalidInput(format!("team not found in ratings: {team}")))
+            })
+            .collect()
+    }
+
+    /// Mutable access to the bracket slots. The cached team list is reset, so
+    /// it is rebuilt from whatever the caller leaves behind.
+    pub fn bracket_mut(&mut self) -> &mut Vec<HashMap<String, f64>> {
+        self.bracket_teams = OnceLock::new();
+        &mut self.bracket
+    }
+
+    /// Distinct teams in the bracket, in order of first appearance. Built
+    /// once and cached, so repeated calls are free.
+    pub fn bracket_teams(&self) -> &[String] {
+        self.bracket_teams.get_or_init(|| {
+            let mut seen = HashSet::new();
+            self.bracket
+                .iter()
+                .flat_map(|game| game.keys())
+                .filter(|team| seen.insert(*team))
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Serialize this state to a JSON string.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize a state from a JSON string, checked with `validate` so a
+    /// well-typed but unscorable document is an error rather than a panic.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let state: Self = serde_json::from_str(json)?;
+        state.validate()?;
+        Ok(state)
+    }
+
+    /// Check that this state can be scored: the forfeit probability is a
+    /// probability, the model passes `ModelParams::validate`, and
+    /// `verify_invariants` finds nothing wrong (which covers unrated teams and
+    /// brackets without a power-of-2 slot count). Every invariant violation is
+    /// reported in the error.
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.forfeit_prob) {
+            return Err(TourneyError::InvalidInput(format!(
+                "forfeit_prob must be between 0 and 1, got {}",
+                self.forfeit_prob
+            )));
+        }
+        self.model_params.validate()?;
+        let violations = verify_invariants(self, 1e-9);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = violations.into_iter().map(|v| v.detail).collect();
+        Err(TourneyError::InvalidInput(details.join("; ")))
+    }
+
     /// Internal scoring implementation.
     fn calculate_scores_internal(&self, simulate: bool, seed: Option<u64>) -> HashMap<String, f64> {
+        self.total_scores(|award| self.play_out(simulate, seed, award))
+    }
+
+    /// Expected points per team from the advancements `play` reports.
+    fn total_scores(&self, play: impl FnOnce(&mut dyn FnMut(&str, usize, f64))) -> HashMap<String, f64> {
         let mut total_scores: HashMap<String, f64> = HashMap::new();
+        play(&mut |team, scoring_round, win_prob| {
+            let points = self.scoring.get(scoring_round).copied().unwrap_or(1.0);
+            let score = win_prob * points * self.team_multiplier(team);
+            // Every team is awarded once per round; only the first allocates.
+            match total_scores.get_mut(team) {
+                Some(total) => *total += score,
+                None => {
+                    total_scores.insert(team.to_string(), score);
+                }
+            }
+        });
+        total_scores
+    }
+
+    /// Expected scores if `team1` beats `team2` and if it loses:
+    /// `calculate_scores_prob` of `with_override(team1, team2, 1.0)` and of
+    /// `with_override(team1, team2, 0.0)`.
+    ///
+    /// With exact propagation both come from one pass over the bracket that
+    /// conditions only the pairing's games, without copying the state.
+    pub fn calculate_scores_given_game(
+        &self,
+        team1: &str,
+        team2: &str,
+    ) -> (HashMap<String, f64>, HashMap<String, f64>) {
+        let persistent = self.persistent_forfeits && self.forfeit_prob > 0.0;
+        let teams = self.bracket_teams();
+        let index = |name: &str| teams.iter().position(|team| team == name);
+        if !self.reseed && (!self.deterministic || persistent) {
+            let conditioned = match (index(team1), index(team2)) {
+                (Some(i), Some(j)) => propagate::advancement_given(self, &[Some((i, j, 1.0)), Some((i, j, 0.0))]),
+                // A pairing that cannot happen conditions nothing.
+                _ => propagate::advancement(self).map(|rounds| vec![rounds.clone(), rounds]),
+            };
+            if let Some([win, loss]) = conditioned.and_then(|variants| <[_; 2]>::try_from(variants).ok()) {
+                return (self.scores_from_advancement(&win), self.scores_from_advancement(&loss));
+            }
+        }
+        let scores = |prob| {
+            let state = self.with_override(team1, team2, prob).expect("certain outcomes are valid probabilities");
+            state.calculate_scores_prob()
+        };
+        (scores(1.0), scores(0.0))
+    }
+
+    /// Expected points per team broken down by scoring round.
+    ///
+    /// Each vector is indexed like `scoring` (so index 0 is the play-in round
+    /// when `play_in_round` is set) and has one entry per round played.
+    pub fn scores_by_round_internal(&self, simulate: bool, seed: Option<u64>) -> HashMap<String, Vec<f64>> {
+        let offset = usize::from(self.play_in_round);
+        let n_rounds = self.bracket.len().max(1).ilog2() as usize + offset;
+        let mut by_round: HashMap<String, Vec<f64>> = HashMap::new();
+        self.play_out(simulate, seed, |team, scoring_round, win_prob| {
+            let points = self.scoring.get(scoring_round).copied().unwrap_or(1.0);
+            by_round.entry(team.to_string()).or_insert_with(|| vec![0.0; n_rounds])[scoring_round] +=
+                win_prob * points * self.team_multiplier(team);
+        });
+        by_round
+    }
+
+    /// Simulate the tournament once and return the champion.
+    pub fn simulate_champion(&self, seed: Option<u64>) -> Option<String> {
+        let mut champion = None;
+        self.play_out(true, seed, |team, _, _| champion = Some(team.to_string()));
+        champion
+    }
+
+    /// Teams winning each main-bracket round (0-based) in one simulation.
+    pub(crate) fn simulate_round_winners(&self, seed: Option<u64>) -> Vec<HashSet<String>> {
+        let offset = usize::from(self.play_in_round);
+        let mut winners = vec![HashSet::new(); self.bracket.len().max(1).ilog2() as usize];
+        self.play_out(true, seed, |team, scoring_round, _| {
+            if scoring_round >= offset {
+                winners[scoring_round - offset].insert(team.to_string());
+            }
+        });
+        winners
+    }
+
+    /// Play the bracket forward, reporting every (team, scoring round, win
+    /// probability) advancement to `award`.
+    fn play_out(&self, simulate: bool, seed: Option<u64>, mut award: impl FnMut(&str, usize, f64)) {
+        let persistent = self.persistent_forfeits && self.forfeit_prob > 0.0;
+        // Exact propagation sums in a fixed order, so it also serves
+        // deterministic scoring when the tree path cannot model forfeits.
+        if !simulate && !self.reseed && (!self.deterministic || persistent) {
+            if let Some(rounds) = propagate::advancement(self) {
+                self.award_rounds(&rounds, award);
+                return;
+            }
+        }
+
+        if (self.reseed || persistent) && !simulate {
+            // Simulations run in parallel; their advancements are replayed in
+            // seed order so the averages are reproducible.
+            let runs: Vec<Vec<(usize, usize)>> = (0..RESEED_SIMULATIONS)
+                .into_par_iter()
+                .map_init(
+                    || SimContext::new(self),
+                    |context, sim_seed| {
+                        let mut advanced = Vec::new();
+                        context.play(Some(sim_seed), |team, round| advanced.push((team, round)));
+                        advanced
+                    },
+                )
+                .collect();
+            let weight = 1.0 / RESEED_SIMULATIONS as f64;
+            let teams = self.bracket_teams();
+            for &(team, round) in runs.iter().flatten() {
+                award(&teams[team], round, weight);
+            }
+        } else {
+            self.play_out_tree(simulate, seed, award);
+        }
+    }
+
+    /// Expected points per team from exact main-bracket advancement
+    /// (`propagate::advancement` output).
+    pub(crate) fn scores_from_advancement(&self, rounds: &[Vec<f64>]) -> HashMap<String, f64> {
+        self.total_scores(|award| self.award_rounds(rounds, award))
+    }
+
+    /// Report exact main-bracket advancement (`propagate::advancement`
+    /// output) to `award`, after the play-in slots if scored.
+    fn award_rounds(&self, rounds: &[Vec<f64>], mut award: impl FnMut(&str, usize, f64)) {
+        let offset = usize::from(self.play_in_round);
+        if self.play_in_round {
+            for (team, &win_prob) in self.bracket.iter().filter(|game| game.len() > 1).flatten() {
+                award(team, 0, win_prob);
+            }
+        }
+        for (round, probs) in rounds.iter().enumerate() {
+            for (team, &win_prob) in self.bracket_teams().iter().zip(probs) {
+                award(team, round + offset, win_prob);
+            }
+        }
+    }
+
+    /// `play_out` for a single pass over the bracket. `play_out` averages
+    /// simulations for reseeded brackets, so the probability pass here never
+    /// reseeds.
+    fn play_out_tree(&self, simulate: bool, seed: Option<u64>, mut award: impl FnMut(&str, usize, f64)) {
+        if simulate {
+            let teams = self.bracket_teams();
+            SimContext::new(self).play(seed, |team, round| award(&teams[team], round, 1.0));
+            return;
+        }
+
         let mut games = self.bracket.clone();
         let mut round = 0;
+        let offset = usize::from(self.play_in_round);
 
-        let mut rng = match seed {
-            Some(s) => ChaCha8Rng::seed_from_u64(s),
-            None => ChaCha8Rng::from_entropy(),
-        };
+        if self.play_in_round {
+            for game in games.iter().filter(|game| game.len() > 1) {
+                for (team, &win_prob) in game.iter() {
+                    award(team, 0, win_prob);
+                }
+            }
+        }
 
         while games.len() > 1 {
+            trace_span!(TRACE, "scoring_round", round, n_games = games.len() / 2);
             let mut new_games = Vec::new();
 
             for i in (0..games.len()).step_by(2) {
-                let parent = if simulate {
-                    game_transform_sim(
+                let parent = if self.deterministic {
+                    game_transform_prob_deterministic(
                         &games[i],
                         &games[i + 1],
                         &self.ratings,
                         Some(&self.overrides),
                         self.forfeit_prob,
-                        &mut rng,
+                        &self.model_params,
                     )
                 } else {
-                    game_transform_prob(
+                    game_transform_prob_with(
                         &games[i],
                         &games[i + 1],
                         &self.ratings,
                         Some(&self.overrides),
                         self.forfeit_prob,
+                        &self.model_params,
                     )
                 };
 
                 // Add scores for this round
-                let round_points = self.scoring.get(round).copied().unwrap_or(1.0);
-                for (team, win_prob) in &parent {
-                    *total_scores.entry(team.clone()).or_insert(0.0) += win_prob * round_points;
+                for (team, &win_prob) in &parent {
+                    award(team, round + offset, win_prob);
                 }
 
                 new_games.push(parent);
@@ -244,9 +1137,74 @@ impl TournamentState {
             games = new_games;
             round += 1;
         }
+    }
+}
 
-        total_scores
+/// Reorder resolved games so the best remaining seed meets the worst, the
+/// second best the second worst, and so on. Empty games (double forfeits) rank
+/// last.
+pub(crate) fn reseed_games(
+    games: Vec<HashMap<String, f64>>,
+    keys: &HashMap<String, (u32, usize)>,
+) -> Vec<HashMap<String, f64>> {
+    let key = |game: &HashMap<String, f64>| {
+        game.keys()
+            .map(|team| keys.get(team).copied().unwrap_or((u32::MAX, usize::MAX)))
+            .min()
+            .unwrap_or((u32::MAX, usize::MAX))
+    };
+    let mut ranked = games;
+    ranked.sort_by_key(|game| key(game));
+    let mut ranked: std::collections::VecDeque<_> = ranked.into();
+    let mut paired = Vec::with_capacity(ranked.len());
+    while let (Some(best), Some(worst)) = (ranked.pop_front(), ranked.pop_back()) {
+        paired.push(best);
+        paired.push(worst);
+    }
+    paired
+}
+
+/// `ratings` plus an entry for every unrated name in each equivalence class,
+/// copied from the first rated name in that class.
+fn expand_equivalents(mut ratings: HashMap<String, Team>, classes: &[Vec<String>]) -> HashMap<String, Team> {
+    for class in classes {
+        let Some(team) = class.iter().find_map(|name| ratings.get(name)).cloned() else {
+            continue;
+        };
+        for name in class {
+            if !ratings.contains_key(name) {
+                ratings.insert(name.clone(), Team { name: name.clone(), ..team.clone() });
+            }
+        }
     }
+    ratings
+}
+
+/// Check that every team in `bracket` has a rating in `ratings` and that
+/// those ratings are finite.
+pub(crate) fn check_ratings(bracket: &[HashMap<String, f64>], ratings: &HashMap<String, Team>) -> Result<()> {
+    let mut unrated: Vec<&str> = bracket
+        .iter()
+        .flat_map(HashMap::keys)
+        .filter(|team| !ratings.contains_key(*team))
+        .map(String::as_str)
+        .collect();
+    unrated.sort_unstable();
+    if !unrated.is_empty() {
+        return Err(TourneyError::InvalidInput(format!("no ratings for {}", unrated.join(", "))));
+    }
+    let mut non_finite: Vec<&str> = bracket
+        .iter()
+        .flat_map(HashMap::keys)
+        .map(|team| &ratings[team])
+        .filter(|team| !(team.offense.is_finite() && team.defense.is_finite() && team.tempo.is_finite()))
+        .map(|team| team.name.as_str())
+        .collect();
+    non_finite.sort_unstable();
+    if !non_finite.is_empty() {
+        return Err(TourneyError::InvalidInput(format!("non-finite ratings for {}", non_finite.join(", "))));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -326,4 +1284,370 @@ mod tests {
         assert!(teams.contains(&"C".to_string()));
         assert!(teams.contains(&"D".to_string()));
     }
+
+    #[test]
+    fn test_bracket_teams_cache_follows_mutation() {
+        let (bracket, ratings) = make_simple_bracket();
+        let mut state = TournamentState::new(bracket, ratings, vec![1.0, 1.0], None, 0.0, None);
+        assert_eq!(state.bracket_teams(), ["A", "B", "C", "D"]);
+
+        state.bracket_mut()[3].insert("E".to_string(), 0.5);
+        assert_eq!(state.bracket_teams().len(), 5);
+        assert_eq!(state.bracket_teams()[..3], ["A", "B", "C"]);
+
+        let reversed: Vec<_> = state.bracket().iter().rev().cloned().collect();
+        state.set_bracket(reversed);
+        assert_eq!(&state.bracket_teams()[2..], ["C", "B", "A"]);
+    }
+
+    #[test]
+    fn test_play_in_round_scoring() {
+        let (mut bracket, mut ratings) = make_simple_bracket();
+        ratings.insert("E".to_string(), Team::new("E".to_string(), 0.0, 0.01, 67.0, false));
+        bracket[3] = [("D".to_string(), 0.7), ("E".to_string(), 0.3)].into_iter().collect();
+
+        let mut state = TournamentState::new(bracket, ratings, vec![0.5, 1.0, 2.0], None, 0.0, None);
+        assert_eq!(state.play_in_games(), vec![3]);
+        let flattened: f64 = state.calculate_scores_prob().values().sum();
+        assert!((flattened - 2.0).abs() < 1e-9, "main rounds use scoring[0..2] without play-in");
+
+        state.play_in_round = true;
+        let scores = state.calculate_scores_prob();
+        let total: f64 = scores.values().sum();
+        // Play-in 0.5 + round one 2 * 1.0 + final 2.0
+        assert!((total - 4.5).abs() < 1e-9, "got {}", total);
+
+        let sim = state.calculate_scores_sim(Some(3));
+        let sim_total: f64 = sim.values().sum();
+        assert!((sim_total - 4.5).abs() < 1e-9);
+        assert!(sim.contains_key("D") ^ sim.contains_key("E"));
+    }
+
+    #[test]
+    fn test_team_multipliers_scale_points() {
+        let (bracket, ratings) = make_simple_bracket();
+        let mut state = TournamentState::new(bracket, ratings, vec![1.0, 2.0], None, 0.0, None);
+        let base = state.calculate_scores_prob();
+
+        state.team_multipliers.insert("C".to_string(), 3.0);
+        let scaled = state.calculate_scores_prob();
+
+        assert!((scaled["C"] - 3.0 * base["C"]).abs() < 1e-12);
+        assert!((scaled["A"] - base["A"]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_project_matchup_uses_state_model() {
+        let (bracket, ratings) = make_simple_bracket();
+        let mut state = TournamentState::new(bracket, ratings, vec![1.0, 2.0], None, 0.02, None);
+        state.model_params.confidence = 0.6;
+        state.overrides.set_tempo_override("A", "B", 60.0).unwrap();
+
+        let projection = state.project_matchup("A", "B").unwrap();
+        let expected = calculate_win_prob_with(
+            &state.ratings["A"],
+            &state.ratings["B"],
+            Some(&state.overrides),
+            state.forfeit_prob,
+            &state.model_params,
+        );
+        assert_eq!(projection.win_prob, expected);
+        let default = win_prob::project_matchup(&state.ratings["A"], &state.ratings["B"], None, 0.0, None);
+        assert_ne!(projection.win_prob, default.win_prob);
+        assert!(projection.total < default.total);
+        assert!(state.project_matchup("A", "Z").is_err());
+    }
+
+    #[test]
+    fn test_as_of_uses_series_ratings() {
+        let (bracket, ratings) = make_simple_bracket();
+        let state = TournamentState::new(bracket, ratings, vec![1.0, 2.0], None, 0.0, None);
+        let mut series = RatedTeamSeries::new();
+        series.add(100, Team::new("A".to_string(), 0.5, -0.5, 68.0, false));
+
+        assert_eq!(state.as_of(&series, 50, None).unwrap().ratings["A"].offense, state.ratings["A"].offense);
+        let later = state.as_of(&series, 150, None).unwrap();
+        assert_eq!(later.ratings["A"].offense, 0.5);
+        assert_eq!(later.ratings["B"].offense, state.ratings["B"].offense);
+        assert!(later.calculate_scores_prob()["A"] > state.calculate_scores_prob()["A"]);
+        assert!(state.as_of(&series, 150, Some(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_scores_by_round_sum_to_totals() {
+        let (mut bracket, mut ratings) = make_simple_bracket();
+        ratings.insert("E".to_string(), Team::new("E".to_string(), 0.0, 0.01, 67.0, false));
+        bracket[3] = [("D".to_string(), 0.6), ("E".to_string(), 0.4)].into_iter().collect();
+        let mut state = TournamentState::new(bracket, ratings, vec![0.5, 1.0, 2.0], None, 0.0, None);
+        state.play_in_round = true;
+
+        let totals = state.calculate_scores_prob();
+        let by_round = state.calculate_scores_by_round();
+        assert_eq!(by_round.len(), totals.len());
+        for (team, rounds) in &by_round {
+            assert_eq!(rounds.len(), 3);
+            assert!((rounds.iter().sum::<f64>() - totals[team]).abs() < 1e-12);
+        }
+        assert!((by_round["E"][0] - 0.2).abs() < 1e-12);
+        assert_eq!(by_round["A"][0], 0.0);
+    }
+
+    #[test]
+    fn test_override_impact() {
+        let (bracket, ratings) = make_simple_bracket();
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("A", "B", 0.1).unwrap();
+        let state = TournamentState::new(bracket, ratings, vec![1.0, 2.0], Some(overrides), 0.0, None);
+
+        let plain = TournamentState {
+            overrides: OverridesMap::new(),
+            ..state.clone()
+        };
+        assert_eq!(state.calculate_scores_prob_raw(), plain.calculate_scores_prob());
+
+        let impact = state.override_impact();
+        let (with_overrides, raw) = impact["A"];
+        assert!(with_overrides < raw);
+        // Overrides move value between teams; the total is unchanged.
+        let net: f64 = impact.values().map(|(w, r)| w - r).sum();
+        assert!(net.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reseed_games_pairs_best_with_worst() {
+        let keys: HashMap<String, (u32, usize)> =
+            [("A", (1, 0)), ("B", (4, 1)), ("C", (2, 2)), ("D", (1, 3))].map(|(t, k)| (t.to_string(), k)).into();
+        let games: Vec<HashMap<String, f64>> =
+            ["A", "B", "C", "D"].iter().map(|t| [(t.to_string(), 1.0)].into_iter().collect()).collect();
+        let order: Vec<String> = reseed_games(games, &keys).iter().map(|g| g.keys().next().unwrap().clone()).collect();
+        assert_eq!(order, ["A", "B", "D", "C"]);
+    }
+
+    #[test]
+    fn test_games_are_named_by_round_and_position() {
+        let state = crate::testing::canonical_tournament(16);
+        let teams = state.bracket_teams();
+        let game = state.game_between(&teams[0], &teams[3]).unwrap();
+        assert_eq!(game, GameId::new(Round(1), 0));
+        assert_eq!(state.game_teams(game).unwrap(), teams[..4]);
+        assert_eq!(state.game_between(&teams[5], &teams[4]), Some(GameId::new(Round::FIRST, 2)));
+        assert_eq!(state.game_between(&teams[0], "Nobody"), None);
+        assert!(state.game_teams(GameId::new(Round(4), 0)).is_err());
+        assert_eq!(state.round_points(Round(1)), state.scoring[1]);
+    }
+
+    #[test]
+    fn test_persistent_forfeits_favor_deep_runs() {
+        // Per game, a title takes showing up four times; a team that has not
+        // withdrawn shows up to all of them.
+        let per_game = TournamentState {
+            forfeit_prob: 0.3,
+            ..crate::testing::canonical_tournament(16)
+        };
+        let persistent = TournamentState {
+            persistent_forfeits: true,
+            ..per_game.clone()
+        };
+        let (per_game_rounds, persistent_rounds) =
+            (per_game.calculate_scores_by_round(), persistent.calculate_scores_by_round());
+        assert!(persistent_rounds["Team 01"][3] > per_game_rounds["Team 01"][3] + 0.05);
+
+        // Deterministic scoring propagates the same way; an irregular bracket
+        // falls back to averaging simulations.
+        let deterministic = TournamentState {
+            deterministic: true,
+            ..persistent.clone()
+        };
+        assert_eq!(deterministic.calculate_scores_by_round()["Team 01"], persistent_rounds["Team 01"]);
+        let mut irregular = persistent.clone();
+        let mut bracket = irregular.bracket().to_vec();
+        bracket.truncate(12);
+        irregular.set_bracket(bracket);
+        let total: f64 = irregular.calculate_scores_by_round().values().map(|rounds| rounds[0]).sum();
+        assert!((total - 6.0 * irregular.scoring[0]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scores_given_game_match_overrides() {
+        let mut state = crate::testing::canonical_tournament(16);
+        state.overrides.add_override("Team 02", "Team 15", 0.4).unwrap();
+        let persistent = TournamentState {
+            forfeit_prob: 0.1,
+            persistent_forfeits: true,
+            ..state.clone()
+        };
+        let reseeded = TournamentState {
+            reseed: true,
+            ..state.clone()
+        };
+        let close = |a: &HashMap<String, f64>, b: &HashMap<String, f64>| {
+            a.len() == b.len() && a.iter().all(|(team, score)| (score - b[team]).abs() < 1e-12)
+        };
+        for state in [&state, &persistent, &reseeded] {
+            for (team1, team2) in [("Team 01", "Team 05"), ("Team 15", "Team 02"), ("Team 01", "Nobody")] {
+                let (win, loss) = state.calculate_scores_given_game(team1, team2);
+                assert!(close(&win, &state.with_override(team1, team2, 1.0).unwrap().calculate_scores_prob()));
+                assert!(close(&loss, &state.with_override(team1, team2, 0.0).unwrap().calculate_scores_prob()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bracket_edits() {
+        let mut state = crate::testing::canonical_tournament(4);
+        let first = state.bracket()[0].keys().next().unwrap().clone();
+        state.swap_teams(0, 3).unwrap();
+        assert!(state.bracket()[3].contains_key(&first));
+        assert!(state.swap_teams(0, 4).is_err());
+
+        let rating = state.ratings[&first].clone();
+        assert!(state.replace_team(&first, "Replacement", None).is_err());
+        state.replace_team(&first, "Replacement", Some(rating)).unwrap();
+        assert!(state.bracket_teams().iter().any(|team| team == "Replacement"));
+        assert_eq!(state.ratings["Replacement"].name, "Replacement");
+        assert!(state.replace_team(&first, "Other", None).is_err());
+    }
+
+    #[test]
+    fn test_update_ratings_reports_changes() {
+        let mut state = crate::testing::canonical_tournament(4);
+        let mut ratings = state.ratings.clone();
+        let underdog = state.bracket()[1].keys().next().unwrap().clone();
+        ratings.get_mut(&underdog).unwrap().offense += 0.2;
+
+        let mut partial = ratings.clone();
+        partial.remove(&underdog);
+        assert!(state.update_ratings(partial).is_err());
+        assert_ne!(state.ratings[&underdog].offense, ratings[&underdog].offense);
+
+        let mut broken = ratings.clone();
+        broken.get_mut(&underdog).unwrap().tempo = f64::NAN;
+        let err = state.update_ratings(broken).err().unwrap();
+        assert!(err.to_string().contains(&underdog), "{err}");
+
+        let changes = state.update_ratings(ratings).unwrap();
+        assert_eq!(changes.len(), 4);
+        let (before, after) = changes[&underdog];
+        assert!(after > before);
+        assert_eq!(after, state.calculate_scores_prob()[&underdog]);
+    }
+
+    #[test]
+    fn test_update_ratings_keeps_equivalence_classes() {
+        let (bracket, ratings) = make_simple_bracket();
+        let classes = vec![vec!["A".to_string(), "Team A".to_string()]];
+        let mut state = TournamentState::new(bracket, ratings.clone(), vec![1.0, 2.0], None, 0.0, Some(classes));
+        assert_eq!(state.ratings["Team A"].name, "Team A");
+
+        let mut new_ratings = ratings;
+        new_ratings.get_mut("A").unwrap().offense += 0.1;
+        state.update_ratings(new_ratings.clone()).unwrap();
+        let alias = &state.ratings["Team A"];
+        assert_eq!(alias.name, "Team A");
+        assert_eq!(alias.offense, new_ratings["A"].offense);
+    }
+
+    #[test]
+    fn test_from_files() {
+        let dir = std::env::temp_dir().join(format!("tourney_files_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, text: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, text).unwrap();
+            path.to_str().unwrap().to_string()
+        };
+        let ratings = write("ratings.txt", "UConn|120|90|66|Big East|1\nTexas|115|95|68\nMaryland|110|100|70\n");
+        let overrides = write("overrides.txt", "Texas,Maryland,0.4\n");
+        let bracket = write("bracket.txt", "Connecticut\nTexas,Maryland\n");
+        let classes = vec![vec!["UConn".to_string(), "Connecticut".to_string()]];
+
+        let state =
+            TournamentState::from_files(&bracket, &ratings, Some(&overrides), None, None, Some(classes.clone())).unwrap();
+        assert_eq!(state.bracket()[1]["Texas"], 0.4);
+        assert_eq!(state.ratings["Connecticut"].seed, Some(1));
+        assert_eq!(state.scoring, ROUND_POINTS.to_vec());
+
+        // Without the alias, "Connecticut" has no rating.
+        let Err(err) = TournamentState::from_files(&bracket, &ratings, None, None, None, None) else {
+            panic!("unrated bracket team accepted");
+        };
+        assert!(err.to_string().contains("Connecticut"), "{err}");
+        let bracket_v2 = write(
+            "bracket_v2.txt",
+            "# bracket v2\nteam,seed,region\nConnecticut,2,East\nTexas,3,West\n",
+        );
+        let state = TournamentState::from_files(&bracket_v2, &ratings, None, None, None, Some(classes)).unwrap();
+        assert_eq!(state.ratings["Connecticut"].seed, Some(2));
+        assert_eq!(state.ratings["Texas"].seed, Some(3));
+        assert_eq!(state.regions, ["East", "West"]);
+
+        let missing = dir.join("missing.txt");
+        assert!(TournamentState::from_files(missing.to_str().unwrap(), &ratings, None, None, None, None).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_remove_team_policies() {
+        let mut state = crate::testing::canonical_tournament(4);
+        let alone = state.bracket()[0].keys().next().unwrap().clone();
+        let opponent = state.bracket()[1].keys().next().unwrap().clone();
+        assert!(state.remove_team(&alone, "renormalize").is_err());
+        assert!(state.remove_team(&alone, "forfeit").is_err());
+        state.remove_team(&alone, "walkover").unwrap();
+        assert_eq!(state.calculate_scores_by_round()[&alone][0], 0.0);
+        assert_eq!(state.overrides.get(&opponent, &alone), Some(1.0));
+
+        // A play-in slot shares its probability among the teams left.
+        let mut state = crate::testing::canonical_tournament(4);
+        let play_in = state.bracket()[2].keys().next().unwrap().clone();
+        let rating = Team { name: "Play In".to_string(), ..state.ratings[&play_in].clone() };
+        state.ratings.insert(rating.name.clone(), rating);
+        state.bracket_mut()[2] = [(play_in.clone(), 0.25), ("Play In".to_string(), 0.75)].into_iter().collect();
+        state.remove_team(&play_in, "renormalize").unwrap();
+        assert_eq!(state.bracket()[2], [("Play In".to_string(), 1.0)].into_iter().collect());
+    }
+
+    #[test]
+    fn test_reseed_changes_second_round_opponents() {
+        // Seeds 1-8 laid out so the fixed tree meets 1 v 2 in round two.
+        let mut ratings = crate::testing::canonical_ratings(8);
+        for (rank, team) in ratings.values_mut().map(|t| (t.name[5..].parse::<u32>().unwrap(), t)) {
+            team.seed = Some(rank);
+        }
+        let bracket: Vec<HashMap<String, f64>> = [1, 8, 2, 7, 3, 6, 4, 5]
+            .iter()
+            .map(|&seed| [(crate::testing::canonical_team_name(seed - 1), 1.0)].into_iter().collect())
+            .collect();
+        let fixed = TournamentState::new(bracket, ratings, vec![1.0, 2.0, 4.0], None, 0.0, None);
+        let reseeded = TournamentState {
+            reseed: true,
+            ..fixed.clone()
+        };
+
+        let fixed_rounds = fixed.calculate_scores_by_round();
+        let reseeded_rounds = reseeded.calculate_scores_by_round();
+        // Re-paired, the 2 seed avoids the 1 seed until the final.
+        assert!(reseeded_rounds["Team 02"][1] > fixed_rounds["Team 02"][1] + 0.05);
+        let total = |scores: HashMap<String, f64>| scores.values().sum::<f64>();
+        assert!((total(reseeded.calculate_scores_prob()) - 12.0).abs() < 1e-9);
+        assert!((total(reseeded.calculate_scores_sim(Some(5))) - 12.0).abs() < 1e-9);
+        assert_eq!(reseeded.calculate_scores_prob(), reseeded.calculate_scores_prob());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let (bracket, ratings) = make_simple_bracket();
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("B", "A", 0.3).unwrap();
+        let state = TournamentState::new(bracket, ratings, vec![1.0, 2.0], Some(overrides), 0.01, None);
+
+        let restored = TournamentState::from_json(&state.to_json().unwrap()).unwrap();
+        assert_eq!(restored.scoring, state.scoring);
+        assert_eq!(restored.overrides.get("A", "B"), Some(0.7));
+
+        let original = state.calculate_scores_prob();
+        for (team, score) in restored.calculate_scores_prob() {
+            assert!((score - original[&team]).abs() < 1e-12);
+        }
+    }
 }
@@ -0,0 +1,101 @@
+//! Optional `tracing` instrumentation (the `tracing` feature).
+//!
+//! `trace_span!(LEVEL, "name", field = value, ...)` opens a span covering the
+//! rest of the enclosing block. When the block ends, a `done` event carrying
+//! `elapsed_us` is emitted inside the span, so any subscriber sees both the
+//! sizes recorded on the span and how long the work took. Without the feature
+//! the macro expands to nothing.
+
+#[cfg(feature = "tracing")]
+pub(crate) struct Timed {
+    span: tracing::span::EnteredSpan,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl Timed {
+    pub(crate) fn new(span: tracing::Span) -> Self {
+        Timed {
+            span: span.entered(),
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Timed {
+    fn drop(&mut self) {
+        let elapsed_us = self.start.elapsed().as_micros() as u64;
+        tracing::event!(parent: self.span.id(), tracing::Level::TRACE, elapsed_us, "done");
+    }
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($level:ident, $name:expr $(, $($fields:tt)*)?) => {
+        let _trace = $crate::trace::Timed::new(tracing::span!(tracing::Level::$level, $name $(, $($fields)*)?));
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($args:tt)*) => {};
+}
+
+pub(crate) use trace_span;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::portfolio::get_all_team_deltas;
+    use crate::testing::canonical_tournament;
+
+    /// Counts spans by name and `done` events.
+    #[derive(Default)]
+    struct Recorder {
+        next_id: AtomicU64,
+        spans: Arc<Mutex<HashMap<String, usize>>>,
+        done: Arc<AtomicU64>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            *self.spans.lock().unwrap().entry(attrs.metadata().name().to_string()).or_default() += 1;
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            if event.metadata().fields().field("elapsed_us").is_some() {
+                self.done.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_spans_emitted() {
+        let recorder = Recorder::default();
+        let (spans, done) = (recorder.spans.clone(), recorder.done.clone());
+        tracing::subscriber::with_default(recorder, || {
+            let state = canonical_tournament(4);
+            state.calculate_scores_prob();
+            get_all_team_deltas(HashMap::new(), &state, 1.0);
+        });
+        let spans = spans.lock().unwrap();
+        // Per-team work runs on rayon threads, outside this thread's subscriber.
+        assert!(spans["calculate_scores_prob"] >= 1);
+        assert_eq!(spans["get_all_team_deltas"], 1);
+        assert!(spans["scoring_round"] >= 2);
+        assert!(done.load(Ordering::Relaxed) >= 4);
+    }
+}
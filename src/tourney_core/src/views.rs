@@ -0,0 +1,138 @@
+//! Read-only Python views of a `TournamentState`'s bracket and ratings.
+//!
+//! Returning `bracket` or `ratings` by value converts every slot map or team
+//! into fresh Python objects on each attribute access, which dominates loops
+//! that only look at one slot or one team. The views hold a reference to the
+//! state and convert just the item asked for; `to_list` / `to_dict` give a
+//! full copy when one is really wanted.
+
+use pyo3::exceptions::{PyIndexError, PyKeyError};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::team::Team;
+use crate::tournament::TournamentState;
+
+/// Resolve a Python-style (possibly negative) index against `len`.
+fn resolve_index(index: isize, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { index + len as isize } else { index };
+    (0..len as isize).contains(&resolved).then_some(resolved as usize)
+}
+
+/// The bracket slots of a state, as a read-only sequence of
+/// `{team: probability}` dicts.
+#[pyclass(sequence)]
+pub struct BracketView {
+    state: Py<TournamentState>,
+}
+
+impl BracketView {
+    pub fn new(state: Py<TournamentState>) -> Self {
+        BracketView { state }
+    }
+}
+
+#[pymethods]
+impl BracketView {
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.state.borrow(py).bracket().len()
+    }
+
+    fn __getitem__(&self, py: Python<'_>, index: isize) -> PyResult<HashMap<String, f64>> {
+        let state = self.state.borrow(py);
+        resolve_index(index, state.bracket().len())
+            .map(|slot| state.bracket()[slot].clone())
+            .ok_or_else(|| PyIndexError::new_err("bracket slot out of range"))
+    }
+
+    /// A full copy of the bracket as a list of dicts.
+    fn to_list(&self, py: Python<'_>) -> Vec<HashMap<String, f64>> {
+        self.state.borrow(py).bracket().to_vec()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!("BracketView(n_slots={})", self.__len__(py))
+    }
+}
+
+/// The ratings of a state, as a read-only mapping of team name to `Team`.
+#[pyclass(mapping)]
+pub struct RatingsView {
+    state: Py<TournamentState>,
+}
+
+impl RatingsView {
+    pub fn new(state: Py<TournamentState>) -> Self {
+        RatingsView { state }
+    }
+}
+
+#[pymethods]
+impl RatingsView {
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.state.borrow(py).ratings.len()
+    }
+
+    fn __getitem__(&self, py: Python<'_>, name: &str) -> PyResult<Team> {
+        self.get(py, name).ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
+
+    fn __contains__(&self, py: Python<'_>, name: &str) -> bool {
+        self.state.borrow(py).ratings.contains_key(name)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let keys = self.keys(py).into_py(py);
+        Ok(keys.bind(py).iter()?.into_any().unbind())
+    }
+
+    /// The team rated under `name`, or `None`.
+    pub fn get(&self, py: Python<'_>, name: &str) -> Option<Team> {
+        self.state.borrow(py).ratings.get(name).cloned()
+    }
+
+    /// Rated team names, sorted.
+    pub fn keys(&self, py: Python<'_>) -> Vec<String> {
+        let mut keys: Vec<String> = self.state.borrow(py).ratings.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Rated teams, sorted by name.
+    fn values(&self, py: Python<'_>) -> Vec<Team> {
+        self.items(py).into_iter().map(|(_, team)| team).collect()
+    }
+
+    /// `(name, team)` pairs, sorted by name.
+    fn items(&self, py: Python<'_>) -> Vec<(String, Team)> {
+        let state = self.state.borrow(py);
+        let mut items: Vec<(String, Team)> =
+            state.ratings.iter().map(|(name, team)| (name.clone(), team.clone())).collect();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        items
+    }
+
+    /// A full copy of the ratings as a dict.
+    fn to_dict(&self, py: Python<'_>) -> HashMap<String, Team> {
+        self.state.borrow(py).ratings.clone()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!("RatingsView(n_teams={})", self.__len__(py))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_index_counts_from_either_end() {
+        assert_eq!(resolve_index(0, 4), Some(0));
+        assert_eq!(resolve_index(-1, 4), Some(3));
+        assert_eq!(resolve_index(-4, 4), Some(0));
+        assert_eq!(resolve_index(4, 4), None);
+        assert_eq!(resolve_index(-5, 4), None);
+        assert_eq!(resolve_index(0, 0), None);
+    }
+}
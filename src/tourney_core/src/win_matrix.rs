@@ -0,0 +1,115 @@
+//! All-pairs win probabilities under a tournament's ratings and overrides.
+//!
+//! Entries use the same model as bracket scoring (`model_params`, overrides,
+//! and `forfeit_prob`), so they are the raw game probabilities the bracket
+//! propagates, laid out densely for heatmaps and external consumers.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::tournament::TournamentState;
+use crate::win_prob::calculate_win_prob_with;
+
+/// Pairwise win probabilities for a list of teams.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct WinProbMatrix {
+    /// Row and column `i` of `probs` is `teams[i]`.
+    #[pyo3(get)]
+    pub teams: Vec<String>,
+
+    /// `probs[i][j]`: probability `teams[i]` beats `teams[j]` (0.5 on the
+    /// diagonal).
+    #[pyo3(get)]
+    pub probs: Vec<Vec<f64>>,
+}
+
+#[pymethods]
+impl WinProbMatrix {
+    /// Probability `team1` beats `team2`, or `None` if either is not in the matrix.
+    pub fn get(&self, team1: &str, team2: &str) -> Option<f64> {
+        let index = |team: &str| self.teams.iter().position(|t| t == team);
+        Some(self.probs[index(team1)?][index(team2)?])
+    }
+
+    /// Nested `{team1: {team2: probability}}` form.
+    pub fn to_dict(&self) -> HashMap<String, HashMap<String, f64>> {
+        self.teams
+            .iter()
+            .zip(&self.probs)
+            .map(|(team, row)| (team.clone(), self.teams.iter().cloned().zip(row.iter().copied()).collect()))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("WinProbMatrix({} teams)", self.teams.len())
+    }
+}
+
+/// Win probability matrix for `teams` (default: the bracket teams) in `state`.
+pub fn win_prob_matrix(state: &TournamentState, teams: Option<Vec<String>>) -> Result<WinProbMatrix> {
+    let teams = teams.unwrap_or_else(|| state.get_bracket_teams());
+    let ratings = teams
+        .iter()
+        .map(|team| {
+            state
+                .ratings
+                .get(team)
+                .ok_or_else(|| TourneyError::InvalidInput(format!("no rating for {team}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (overrides, params) = (Some(&state.overrides), &state.model_params);
+    let probs = ratings
+        .par_iter()
+        .enumerate()
+        .map(|(i, team1)| {
+            ratings
+                .iter()
+                .enumerate()
+                .map(|(j, team2)| {
+                    if i == j {
+                        return 0.5;
+                    }
+                    calculate_win_prob_with(team1, team2, overrides, state.forfeit_prob, params)
+                })
+                .collect()
+        })
+        .collect();
+    Ok(WinProbMatrix { teams, probs })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::canonical_tournament;
+
+    #[test]
+    fn test_matrix_is_complementary() {
+        let state = canonical_tournament(8);
+        let matrix = win_prob_matrix(&state, None).unwrap();
+        assert_eq!(matrix.teams, state.get_bracket_teams());
+        for i in 0..8 {
+            assert_eq!(matrix.probs[i][i], 0.5);
+            for j in 0..8 {
+                assert!((matrix.probs[i][j] + matrix.probs[j][i] - 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_uses_overrides_and_subsets() {
+        let mut state = canonical_tournament(4);
+        let teams = state.get_bracket_teams();
+        state.overrides.add_override(&teams[3], &teams[0], 0.9).unwrap();
+        let matrix = win_prob_matrix(&state, Some(vec![teams[0].clone(), teams[3].clone()])).unwrap();
+        assert!((matrix.get(&teams[3], &teams[0]).unwrap() - 0.9).abs() < 1e-12);
+        assert_eq!(matrix.get(&teams[1], &teams[0]), None);
+        assert!((matrix.to_dict()[&teams[0]][&teams[3]] - 0.1).abs() < 1e-12);
+        assert!(win_prob_matrix(&state, Some(vec!["Nobody".to_string()])).is_err());
+    }
+}
@@ -1,9 +1,301 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use statrs::distribution::{ContinuousCDF, Normal};
 
 use crate::constants::{AVG_SCORING, AVG_TEMPO, SCORING_STDDEV};
+use crate::ensemble::EnsembleModel;
+use crate::error::{Result, TourneyError};
+use crate::matchup::MatchupAdjuster;
 use crate::overrides::OverridesMap;
 use crate::team::Team;
 
+/// Constants of the win probability model.
+///
+/// Defaults are the published constants (`AVG_SCORING`, `AVG_TEMPO`,
+/// `SCORING_STDDEV`); a `TournamentState` carries its own copy so the model
+/// can be re-run under alternative values.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelParams {
+    /// National average scoring (points per 100 possessions).
+    #[pyo3(get, set)]
+    pub avg_scoring: f64,
+
+    /// National average tempo (possessions per game).
+    #[pyo3(get, set)]
+    pub avg_tempo: f64,
+
+    /// Standard deviation of the scoring margin at average tempo and scoring.
+    #[pyo3(get, set)]
+    pub scoring_stddev: f64,
+
+    /// Who advances when both teams forfeit a game.
+    #[serde(default)]
+    pub double_forfeit: DoubleForfeit,
+
+    /// Models whose blended probabilities replace this model's own win
+    /// probabilities; see `EnsembleModel`.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ensemble: Option<EnsembleModel>,
+
+    /// Style-based tweaks to each matchup's margin and its standard
+    /// deviation; see `MatchupAdjuster`.
+    #[pyo3(get, set)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matchup_adjuster: Option<MatchupAdjuster>,
+
+    /// Scale on the log-odds of every model probability (overrides are left
+    /// alone): 1 keeps them, values below 1 shrink them toward 0.5, 0 makes
+    /// every game a coin flip. See `apply_confidence` and `fit_confidence`.
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+
+    /// `(floor, ceiling)` every model probability is clamped to after
+    /// `confidence`, so extreme rating gaps cannot produce certain games;
+    /// `None` (the default) turns the clamp off. Overrides are left alone.
+    ///
+    /// The same bounds apply in every round: a game's probability depends
+    /// only on its two teams (`AdvancementCache` and the pairwise checks in
+    /// `verify_invariants` rely on that), so it cannot vary with the round
+    /// the pairing is played in. Pin individual late-round games with
+    /// overrides instead.
+    #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "deserialize_prob_bounds")]
+    prob_bounds: Option<(f64, f64)>,
+}
+
+fn default_confidence() -> f64 {
+    1.0
+}
+
+fn check_prob_bounds(bounds: Option<(f64, f64)>) -> Result<()> {
+    match bounds {
+        Some((floor, ceiling)) if !(0.0 <= floor && floor <= ceiling && ceiling <= 1.0) => {
+            Err(TourneyError::InvalidInput(format!(
+                "prob_bounds must satisfy 0 <= floor <= ceiling <= 1, got ({floor}, {ceiling})"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn deserialize_prob_bounds<'de, D>(deserializer: D) -> std::result::Result<Option<(f64, f64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let bounds = Option::deserialize(deserializer)?;
+    check_prob_bounds(bounds).map_err(serde::de::Error::custom)?;
+    Ok(bounds)
+}
+
+/// Resolution of a game both teams forfeit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoubleForfeit {
+    /// Either team advances with probability 1/2.
+    #[default]
+    CoinFlip,
+    /// The better (lower) seed advances; a coin flip if the seeds are equal
+    /// or either team is unseeded.
+    HigherSeed,
+    /// Forfeits are drawn again until at least one team shows up. With a
+    /// forfeit probability of 1 nobody ever does, so this falls back to a
+    /// coin flip.
+    Redraw,
+}
+
+impl DoubleForfeit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DoubleForfeit::CoinFlip => "coin_flip",
+            DoubleForfeit::HigherSeed => "higher_seed",
+            DoubleForfeit::Redraw => "redraw",
+        }
+    }
+
+    /// Probability team1 advances from a double forfeit, for the policies
+    /// that decide it directly (`Redraw` counts as a coin flip here).
+    pub(crate) fn team1_share(&self, seed1: Option<u32>, seed2: Option<u32>) -> f64 {
+        match (self, seed1, seed2) {
+            (DoubleForfeit::HigherSeed, Some(seed1), Some(seed2)) if seed1 != seed2 => {
+                if seed1 < seed2 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.5,
+        }
+    }
+}
+
+impl FromStr for DoubleForfeit {
+    type Err = TourneyError;
+
+    fn from_str(policy: &str) -> Result<Self> {
+        match policy {
+            "coin_flip" => Ok(DoubleForfeit::CoinFlip),
+            "higher_seed" => Ok(DoubleForfeit::HigherSeed),
+            "redraw" => Ok(DoubleForfeit::Redraw),
+            _ => Err(TourneyError::InvalidInput(format!(
+                "unknown double forfeit policy {policy:?}; expected \"coin_flip\", \"higher_seed\" or \"redraw\""
+            ))),
+        }
+    }
+}
+
+/// Draw whether either team forfeits a game: `Some(true)` if team1 advances
+/// without playing, `Some(false)` if team2 does, `None` if the game is played.
+/// Double forfeits are resolved by `policy`.
+pub(crate) fn draw_forfeits<R: Rng>(
+    rng: &mut R,
+    forfeit_prob: f64,
+    policy: DoubleForfeit,
+    seed1: Option<u32>,
+    seed2: Option<u32>,
+) -> Option<bool> {
+    loop {
+        match (rng.gen::<f64>() < forfeit_prob, rng.gen::<f64>() < forfeit_prob) {
+            (false, false) => return None,
+            (true, false) => return Some(false),
+            (false, true) => return Some(true),
+            (true, true) if policy == DoubleForfeit::Redraw && forfeit_prob < 1.0 => continue,
+            (true, true) => return Some(rng.gen::<f64>() < policy.team1_share(seed1, seed2)),
+        }
+    }
+}
+
+impl Default for ModelParams {
+    fn default() -> Self {
+        ModelParams {
+            avg_scoring: AVG_SCORING,
+            avg_tempo: AVG_TEMPO,
+            scoring_stddev: SCORING_STDDEV,
+            double_forfeit: DoubleForfeit::default(),
+            ensemble: None,
+            matchup_adjuster: None,
+            confidence: 1.0,
+            prob_bounds: None,
+        }
+    }
+}
+
+impl ModelParams {
+    /// Check that the model can produce probabilities: scoring, tempo and
+    /// the margin standard deviation are positive and finite, `confidence`
+    /// is non-negative and finite, and `prob_bounds` are ordered within
+    /// [0, 1]. The public fields can be set to anything, so states check
+    /// their model before scoring with it.
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("avg_scoring", self.avg_scoring),
+            ("avg_tempo", self.avg_tempo),
+            ("scoring_stddev", self.scoring_stddev),
+        ] {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(TourneyError::InvalidInput(format!("{name} must be positive, got {value}")));
+            }
+        }
+        if !(self.confidence >= 0.0 && self.confidence.is_finite()) {
+            return Err(TourneyError::InvalidInput(format!(
+                "confidence must be non-negative, got {}",
+                self.confidence
+            )));
+        }
+        check_prob_bounds(self.prob_bounds)
+    }
+}
+
+#[pymethods]
+impl ModelParams {
+    #[new]
+    #[pyo3(signature = (avg_scoring = AVG_SCORING, avg_tempo = AVG_TEMPO, scoring_stddev = SCORING_STDDEV))]
+    pub fn new(avg_scoring: f64, avg_tempo: f64, scoring_stddev: f64) -> Self {
+        ModelParams {
+            avg_scoring,
+            avg_tempo,
+            scoring_stddev,
+            double_forfeit: DoubleForfeit::default(),
+            ensemble: None,
+            matchup_adjuster: None,
+            confidence: 1.0,
+            prob_bounds: None,
+        }
+    }
+
+    /// Every combination of `scoring_stddevs` and `avg_scorings` (default:
+    /// just `AVG_SCORING`), stddev-major, at the default tempo.
+    #[staticmethod]
+    #[pyo3(signature = (scoring_stddevs, avg_scorings = None))]
+    pub fn grid(scoring_stddevs: Vec<f64>, avg_scorings: Option<Vec<f64>>) -> Vec<ModelParams> {
+        let avg_scorings = avg_scorings.unwrap_or_else(|| vec![AVG_SCORING]);
+        scoring_stddevs
+            .iter()
+            .flat_map(|&stddev| avg_scorings.iter().map(move |&scoring| ModelParams::new(scoring, AVG_TEMPO, stddev)))
+            .collect()
+    }
+
+    /// Double forfeit policy: `"coin_flip"` (default), `"higher_seed"` or
+    /// `"redraw"`; see `DoubleForfeit`.
+    #[cfg(feature = "python")]
+    #[getter(double_forfeit)]
+    fn py_double_forfeit(&self) -> &'static str {
+        self.double_forfeit.name()
+    }
+
+    #[cfg(feature = "python")]
+    #[setter(double_forfeit)]
+    fn py_set_double_forfeit(&mut self, policy: &str) -> Result<()> {
+        self.double_forfeit = policy.parse()?;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    #[setter]
+    pub fn set_confidence(&mut self, confidence: f64) -> Result<()> {
+        if !(confidence >= 0.0 && confidence.is_finite()) {
+            return Err(TourneyError::InvalidInput(format!(
+                "confidence must be non-negative, got {confidence}"
+            )));
+        }
+        self.confidence = confidence;
+        Ok(())
+    }
+
+    #[getter]
+    pub fn prob_bounds(&self) -> Option<(f64, f64)> {
+        self.prob_bounds
+    }
+
+    #[setter]
+    pub fn set_prob_bounds(&mut self, bounds: Option<(f64, f64)>) -> Result<()> {
+        check_prob_bounds(bounds)?;
+        self.prob_bounds = bounds;
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ModelParams(avg_scoring={}, avg_tempo={}, scoring_stddev={}, double_forfeit={:?}, confidence={}{}{}{})",
+            self.avg_scoring,
+            self.avg_tempo,
+            self.scoring_stddev,
+            self.double_forfeit.name(),
+            self.confidence,
+            self.prob_bounds.map_or(String::new(), |(lo, hi)| format!(", prob_bounds=({lo}, {hi})")),
+            self.ensemble.as_ref().map_or(String::new(), |e| format!(", ensemble of {}", e.members.len())),
+            self.matchup_adjuster.as_ref().map_or(String::new(), |a| format!(", {} matchup terms", a.__len__()))
+        )
+    }
+}
+
 /// Calculate the probability of team1 beating team2.
 ///
 /// Uses KenPom-style efficiency ratings with normal distribution
@@ -22,6 +314,17 @@ pub fn calculate_win_prob(
     team2: &Team,
     overrides: Option<&OverridesMap>,
     forfeit_prob: f64,
+) -> f64 {
+    calculate_win_prob_with(team1, team2, overrides, forfeit_prob, &ModelParams::default())
+}
+
+/// `calculate_win_prob` under the given model constants.
+pub fn calculate_win_prob_with(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: &ModelParams,
 ) -> f64 {
     // Check for manual override first
     if let Some(ovr) = overrides {
@@ -30,56 +333,248 @@ pub fn calculate_win_prob(
         }
     }
 
-    // Calculate expected possessions per team
-    let tempo = (team1.tempo * team2.tempo) / AVG_TEMPO;
-
-    // Teams' points per possession as percentage of national average
-    let team1_scoring = 1.0 + team1.offense + team2.defense;
-    let team2_scoring = 1.0 + team2.offense + team1.defense;
-
-    // Teams' actual points per possession
-    let team1_ppp = team1_scoring * (AVG_SCORING / 100.0);
-    let team2_ppp = team2_scoring * (AVG_SCORING / 100.0);
+    let game_win_prob = match &params.ensemble {
+        Some(ensemble) => ensemble.win_prob(team1, team2, overrides, 0.0),
+        None => {
+            // Expected point differential, at any overridden pace for this pairing
+            let tempo = matchup_tempo(team1, team2, overrides, params);
+            let (team1_score, team2_score) = expected_scores_at(team1, team2, tempo, params);
+            let point_diff = team1_score - team2_score;
 
-    // Expected point differential
-    let team1_score = team1_ppp * tempo;
-    let team2_score = team2_ppp * tempo;
-    let point_diff = team1_score - team2_score;
+            // Standard deviation scales with tempo and scoring rates
+            let stddev = margin_stddev_at(team1, team2, tempo, params);
 
-    // Standard deviation scales with tempo and scoring rates
-    let stddev = ((team1_scoring + team2_scoring) / 2.0) * (tempo / AVG_TEMPO) * SCORING_STDDEV;
-
-    // Use normal CDF to convert point differential to win probability
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    let game_win_prob = normal.cdf(point_diff / stddev);
+            match &params.matchup_adjuster {
+                Some(adjuster) => {
+                    let (point_diff, stddev) = adjuster.adjust(team1, team2, point_diff, stddev);
+                    margin_win_prob(point_diff, stddev)
+                }
+                None => margin_win_prob(point_diff, stddev),
+            }
+        }
+    };
+    let game_win_prob = apply_confidence(game_win_prob, params.confidence);
+    let game_win_prob = match params.prob_bounds {
+        Some((floor, ceiling)) => game_win_prob.clamp(floor, ceiling),
+        None => game_win_prob,
+    };
 
-    // Forfeit probability adjustments
+    // Forfeit probability adjustments, matching `draw_forfeits`
     if forfeit_prob > 0.0 {
         let forfeit_win_prob = forfeit_prob * (1.0 - forfeit_prob);
         let forfeit_tie_prob = forfeit_prob * forfeit_prob;
         let game_play_prob = 1.0 - (2.0 * forfeit_win_prob + forfeit_tie_prob);
+        let decided = forfeit_win_prob + game_play_prob * game_win_prob;
 
-        forfeit_win_prob + (0.5 * forfeit_tie_prob) + (game_play_prob * game_win_prob)
+        match params.double_forfeit {
+            DoubleForfeit::Redraw if forfeit_prob < 1.0 => decided / (1.0 - forfeit_tie_prob),
+            policy => decided + policy.team1_share(team1.seed, team2.seed) * forfeit_tie_prob,
+        }
     } else {
         game_win_prob
     }
 }
 
+/// Scale the log-odds of `prob` by `confidence`, keeping 0 and 1 (which have
+/// infinite log-odds) fixed unless `confidence` is 0.
+pub fn apply_confidence(prob: f64, confidence: f64) -> f64 {
+    if confidence == 1.0 || (confidence > 0.0 && (prob <= 0.0 || prob >= 1.0)) {
+        return prob;
+    }
+    let logit = (prob / (1.0 - prob)).ln();
+    1.0 / (1.0 + (-confidence * logit).exp())
+}
+
+/// Probability that a normally distributed final margin with mean `margin`
+/// and standard deviation `stddev` is positive.
+pub fn margin_win_prob(margin: f64, stddev: f64) -> f64 {
+    Normal::new(0.0, 1.0).unwrap().cdf(margin / stddev)
+}
+
 /// Calculate expected scores for a matchup.
 ///
 /// Returns (team1_expected_score, team2_expected_score)
 pub fn calculate_expected_scores(team1: &Team, team2: &Team) -> (f64, f64) {
-    let tempo = (team1.tempo * team2.tempo) / AVG_TEMPO;
+    expected_scores_with(team1, team2, &ModelParams::default())
+}
+
+/// `calculate_expected_scores` under the given model constants.
+pub fn expected_scores_with(team1: &Team, team2: &Team, params: &ModelParams) -> (f64, f64) {
+    expected_scores_at(team1, team2, rated_tempo(team1, team2, params), params)
+}
+
+/// Expected scores at the pace `overrides` sets for this pairing, if any.
+pub fn matchup_expected_scores(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    params: &ModelParams,
+) -> (f64, f64) {
+    expected_scores_at(team1, team2, matchup_tempo(team1, team2, overrides, params), params)
+}
 
+/// Expected possessions for a matchup: the tempo override for the pairing if
+/// `overrides` has one, otherwise the ratings-based pace.
+pub fn matchup_tempo(team1: &Team, team2: &Team, overrides: Option<&OverridesMap>, params: &ModelParams) -> f64 {
+    overrides
+        .and_then(|ovr| ovr.get_tempo_override(&team1.name, &team2.name))
+        .unwrap_or_else(|| rated_tempo(team1, team2, params))
+}
+
+fn rated_tempo(team1: &Team, team2: &Team, params: &ModelParams) -> f64 {
+    (team1.tempo * team2.tempo) / params.avg_tempo
+}
+
+fn expected_scores_at(team1: &Team, team2: &Team, tempo: f64, params: &ModelParams) -> (f64, f64) {
     let team1_scoring = 1.0 + team1.offense + team2.defense;
     let team2_scoring = 1.0 + team2.offense + team1.defense;
 
-    let team1_ppp = team1_scoring * (AVG_SCORING / 100.0);
-    let team2_ppp = team2_scoring * (AVG_SCORING / 100.0);
+    let team1_ppp = team1_scoring * (params.avg_scoring / 100.0);
+    let team2_ppp = team2_scoring * (params.avg_scoring / 100.0);
 
     (team1_ppp * tempo, team2_ppp * tempo)
 }
 
+/// Standard deviation of the final margin for a matchup.
+///
+/// Scales with tempo and the teams' scoring rates.
+pub fn margin_stddev(team1: &Team, team2: &Team) -> f64 {
+    margin_stddev_with(team1, team2, &ModelParams::default())
+}
+
+/// `margin_stddev` under the given model constants.
+pub fn margin_stddev_with(team1: &Team, team2: &Team, params: &ModelParams) -> f64 {
+    margin_stddev_at(team1, team2, rated_tempo(team1, team2, params), params)
+}
+
+fn margin_stddev_at(team1: &Team, team2: &Team, tempo: f64, params: &ModelParams) -> f64 {
+    let team1_scoring = 1.0 + team1.offense + team2.defense;
+    let team2_scoring = 1.0 + team2.offense + team1.defense;
+    ((team1_scoring + team2_scoring) / 2.0) * (tempo / params.avg_tempo) * params.scoring_stddev
+}
+
+/// Quantiles of a matchup's margin and total distributions.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct MatchupPercentiles {
+    #[pyo3(get)]
+    pub quantiles: Vec<f64>,
+
+    /// Team1-minus-team2 margin at each quantile.
+    #[pyo3(get)]
+    pub margin: Vec<f64>,
+
+    /// Combined score at each quantile.
+    #[pyo3(get)]
+    pub total: Vec<f64>,
+}
+
+#[pymethods]
+impl MatchupPercentiles {
+    fn __repr__(&self) -> String {
+        format!("MatchupPercentiles(quantiles={:?}, margin={:?}, total={:?})", self.quantiles, self.margin, self.total)
+    }
+}
+
+/// Analytic quantiles of the margin and total for a matchup.
+///
+/// The margin is normal with the same mean and spread used for win
+/// probabilities. Treating the two scores as independent with equal variance,
+/// the total has the same spread around the expected total. `overrides` and
+/// `params` are read as in `project_matchup`, so the median matches its
+/// margin and total.
+pub fn matchup_percentiles(
+    team1: &Team,
+    team2: &Team,
+    quantiles: Vec<f64>,
+    overrides: Option<&OverridesMap>,
+    params: Option<&ModelParams>,
+) -> Result<MatchupPercentiles> {
+    if let Some(q) = quantiles.iter().find(|&&q| !(q > 0.0 && q < 1.0)) {
+        return Err(TourneyError::InvalidInput(format!(
+            "quantiles must be strictly between 0 and 1, got {q}"
+        )));
+    }
+    let default_params = ModelParams::default();
+    let params = params.unwrap_or(&default_params);
+    let tempo = matchup_tempo(team1, team2, overrides, params);
+    let (team1_score, team2_score) = expected_scores_at(team1, team2, tempo, params);
+    let stddev = margin_stddev_at(team1, team2, tempo, params);
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let z: Vec<f64> = quantiles.iter().map(|&q| normal.inverse_cdf(q) * stddev).collect();
+    Ok(MatchupPercentiles {
+        margin: z.iter().map(|dz| team1_score - team2_score + dz).collect(),
+        total: z.iter().map(|dz| team1_score + team2_score + dz).collect(),
+        quantiles,
+    })
+}
+
+/// Projected outcome of a single matchup.
+#[pyclass]
+#[derive(Clone, Debug, Serialize)]
+pub struct MatchupProjection {
+    #[pyo3(get)]
+    pub team1: String,
+
+    #[pyo3(get)]
+    pub team2: String,
+
+    #[pyo3(get)]
+    pub team1_score: f64,
+
+    #[pyo3(get)]
+    pub team2_score: f64,
+
+    /// Expected team1 score minus team2 score.
+    #[pyo3(get)]
+    pub margin: f64,
+
+    /// Expected combined score.
+    #[pyo3(get)]
+    pub total: f64,
+
+    /// Probability of team1 winning, with overrides and forfeits applied.
+    #[pyo3(get)]
+    pub win_prob: f64,
+}
+
+#[pymethods]
+impl MatchupProjection {
+    fn __repr__(&self) -> String {
+        format!(
+            "MatchupProjection({} {:.1} - {:.1} {}, margin={:+.1}, total={:.1}, win_prob={:.3})",
+            self.team1, self.team1_score, self.team2_score, self.team2, self.margin, self.total, self.win_prob
+        )
+    }
+}
+
+/// Project a matchup: expected scores, margin, total, and win probability.
+///
+/// Scores come from ratings at any overridden tempo; probability overrides and
+/// forfeits only affect `win_prob`. `params` defaults to
+/// `ModelParams::default()`; `TournamentState::project_matchup` passes the
+/// state's own.
+pub fn project_matchup(
+    team1: &Team,
+    team2: &Team,
+    overrides: Option<&OverridesMap>,
+    forfeit_prob: f64,
+    params: Option<&ModelParams>,
+) -> MatchupProjection {
+    let default_params = ModelParams::default();
+    let params = params.unwrap_or(&default_params);
+    let (team1_score, team2_score) = matchup_expected_scores(team1, team2, overrides, params);
+    MatchupProjection {
+        team1: team1.name.clone(),
+        team2: team2.name.clone(),
+        team1_score,
+        team2_score,
+        margin: team1_score - team2_score,
+        total: team1_score + team2_score,
+        win_prob: calculate_win_prob_with(team1, team2, overrides, forfeit_prob, params),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,7 +604,7 @@ mod tests {
         let team2 = Team::new("B".to_string(), -0.2, 0.2, 60.0, false);
 
         let prob = calculate_win_prob(&team1, &team2, None, 0.0);
-        assert!(prob >= 0.0 && prob <= 1.0, "Probability must be in [0, 1]");
+        assert!((0.0..=1.0).contains(&prob), "Probability must be in [0, 1]");
     }
 
     #[test]
@@ -129,9 +624,179 @@ mod tests {
         let team2 = Team::new("B".to_string(), 0.0, 0.0, 67.7, false);
 
         let mut overrides = OverridesMap::new();
-        overrides.add_override("A", "B", 0.75);
+        overrides.add_override("A", "B", 0.75).unwrap();
 
         let prob = calculate_win_prob(&team1, &team2, Some(&overrides), 0.0);
         assert!((prob - 0.75).abs() < 1e-10, "Override should be used");
     }
+
+    #[test]
+    fn test_tempo_override_applies_to_pairing_only() {
+        let team1 = Team::new("A".to_string(), 0.06, -0.03, 70.0, false);
+        let team2 = Team::new("B".to_string(), 0.0, 0.0, 70.0, false);
+        let team3 = Team::new("C".to_string(), 0.0, 0.0, 70.0, false);
+        let mut overrides = OverridesMap::new();
+        overrides.set_tempo_override("B", "A", 55.0).unwrap();
+
+        // Fewer possessions shrink the total and the favorite's margin...
+        let (slow1, slow2) = matchup_expected_scores(&team1, &team2, Some(&overrides), &ModelParams::default());
+        let (fast1, fast2) = calculate_expected_scores(&team1, &team2);
+        assert!(slow1 + slow2 < fast1 + fast2);
+        assert!(slow1 - slow2 < fast1 - fast2);
+        // ...and the margin's spread in proportion, so the edge is unchanged.
+        let slow_prob = calculate_win_prob(&team1, &team2, Some(&overrides), 0.0);
+        assert!((slow_prob - calculate_win_prob(&team1, &team2, None, 0.0)).abs() < 1e-12);
+        assert_eq!(project_matchup(&team1, &team2, Some(&overrides), 0.0, None).team1_score, slow1);
+
+        let other = calculate_win_prob(&team1, &team3, Some(&overrides), 0.0);
+        assert_eq!(other, calculate_win_prob(&team1, &team3, None, 0.0));
+    }
+
+    #[test]
+    fn test_project_matchup() {
+        let strong = Team::new("Strong".to_string(), 0.1, -0.05, 70.0, false);
+        let weak = Team::new("Weak".to_string(), -0.05, 0.1, 65.0, false);
+        let projection = project_matchup(&strong, &weak, None, 0.0, None);
+
+        let (s1, s2) = calculate_expected_scores(&strong, &weak);
+        assert!((projection.margin - (s1 - s2)).abs() < 1e-12);
+        assert!((projection.total - (s1 + s2)).abs() < 1e-12);
+        assert!(projection.margin > 0.0 && projection.win_prob > 0.5);
+
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("Strong", "Weak", 0.4).unwrap();
+        let overridden = project_matchup(&strong, &weak, Some(&overrides), 0.0, None);
+        assert_eq!(overridden.win_prob, 0.4);
+        assert_eq!(overridden.margin, projection.margin);
+
+        let params = ModelParams { confidence: 0.5, avg_scoring: 110.0, ..Default::default() };
+        let tuned = project_matchup(&strong, &weak, None, 0.0, Some(&params));
+        assert_eq!(tuned.win_prob, calculate_win_prob_with(&strong, &weak, None, 0.0, &params));
+        assert!(tuned.win_prob < projection.win_prob);
+        assert!(tuned.total > projection.total);
+    }
+
+    #[test]
+    fn test_matchup_percentiles_match_win_prob() {
+        let strong = Team::new("Strong".to_string(), 0.1, -0.05, 70.0, false);
+        let weak = Team::new("Weak".to_string(), -0.05, 0.1, 65.0, false);
+        let win_prob = calculate_win_prob(&strong, &weak, None, 0.0);
+
+        // The margin quantile at the loss probability is zero.
+        let pct = matchup_percentiles(&strong, &weak, vec![1.0 - win_prob, 0.1, 0.5, 0.9], None, None).unwrap();
+        assert!(pct.margin[0].abs() < 1e-6);
+        let (s1, s2) = calculate_expected_scores(&strong, &weak);
+        assert!((pct.margin[2] - (s1 - s2)).abs() < 1e-9);
+        assert!((pct.total[2] - (s1 + s2)).abs() < 1e-9);
+        assert!((pct.margin[3] - pct.margin[2] - (pct.margin[2] - pct.margin[1])).abs() < 1e-9);
+        assert!(matchup_percentiles(&strong, &weak, vec![1.0], None, None).is_err());
+
+        // Overridden pace and custom params move the interval with the projection.
+        let mut overrides = OverridesMap::new();
+        overrides.set_tempo_override("Strong", "Weak", 60.0).unwrap();
+        let params = ModelParams { avg_scoring: 110.0, scoring_stddev: 9.0, ..Default::default() };
+        let win_prob = calculate_win_prob_with(&strong, &weak, Some(&overrides), 0.0, &params);
+        let quantiles = vec![1.0 - win_prob, 0.5];
+        let pct = matchup_percentiles(&strong, &weak, quantiles, Some(&overrides), Some(&params)).unwrap();
+        let projection = project_matchup(&strong, &weak, Some(&overrides), 0.0, Some(&params));
+        assert!(pct.margin[0].abs() < 1e-6);
+        assert!((pct.margin[1] - projection.margin).abs() < 1e-9);
+        assert!((pct.total[1] - projection.total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_double_forfeit_policies_match_simulation() {
+        let mut team1 = Team::new("A".to_string(), 0.02, 0.0, 67.7, false);
+        let mut team2 = Team::new("B".to_string(), 0.0, 0.0, 67.7, false);
+        (team1.seed, team2.seed) = (Some(9), Some(8));
+        let forfeit_prob = 0.4;
+        for policy in ["coin_flip", "higher_seed", "redraw"] {
+            let params = ModelParams { double_forfeit: policy.parse().unwrap(), ..ModelParams::default() };
+            let exact = calculate_win_prob_with(&team1, &team2, None, forfeit_prob, &params);
+            let game = calculate_win_prob_with(&team1, &team2, None, 0.0, &params);
+
+            let mut rng = crate::tournament::seeded_rng(Some(5));
+            let n = 200_000;
+            let wins = (0..n)
+                .filter(|_| {
+                    draw_forfeits(&mut rng, forfeit_prob, params.double_forfeit, team1.seed, team2.seed)
+                        .unwrap_or_else(|| rng.gen::<f64>() < game)
+                })
+                .count();
+            let simulated = wins as f64 / n as f64;
+            assert!((simulated - exact).abs() < 0.005, "{policy}: {simulated} vs {exact}");
+        }
+        assert!("first".parse::<DoubleForfeit>().is_err());
+    }
+
+    #[test]
+    fn test_certain_double_forfeit() {
+        let mut team1 = Team::new("A".to_string(), 0.02, 0.0, 67.7, false);
+        let team2 = Team::new("B".to_string(), 0.0, 0.0, 67.7, false);
+        team1.seed = Some(1);
+        let prob = |policy: DoubleForfeit| {
+            let params = ModelParams { double_forfeit: policy, ..ModelParams::default() };
+            calculate_win_prob_with(&team1, &team2, None, 1.0, &params)
+        };
+        assert_eq!(prob(DoubleForfeit::CoinFlip), 0.5);
+        assert_eq!(prob(DoubleForfeit::Redraw), 0.5);
+        // An unseeded opponent leaves higher_seed to a coin flip too.
+        assert_eq!(prob(DoubleForfeit::HigherSeed), 0.5);
+    }
+
+    #[test]
+    fn test_confidence_shrinks_model_not_overrides() {
+        let team1 = Team::new("A".to_string(), 0.05, -0.02, 67.7, false);
+        let team2 = Team::new("B".to_string(), 0.0, 0.0, 67.7, false);
+        let prob = |confidence: f64, overrides: Option<&OverridesMap>| {
+            let params = ModelParams { confidence, ..ModelParams::default() };
+            calculate_win_prob_with(&team1, &team2, overrides, 0.0, &params)
+        };
+        let full = prob(1.0, None);
+        let shrunk = prob(0.5, None);
+        assert!(0.5 < shrunk && shrunk < full);
+        assert!((shrunk - apply_confidence(full, 0.5)).abs() < 1e-12);
+        assert!(prob(2.0, None) > full);
+        assert_eq!(prob(0.0, None), 0.5);
+        assert_eq!(apply_confidence(1.0, 0.5), 1.0);
+
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("A", "B", 0.9).unwrap();
+        assert_eq!(prob(0.5, Some(&overrides)), 0.9);
+        assert!(ModelParams::default().set_confidence(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_prob_bounds_clamp_model_not_overrides() {
+        let team1 = Team::new("A".to_string(), 0.6, -0.6, 67.7, false);
+        let team2 = Team::new("B".to_string(), -0.6, 0.6, 67.7, false);
+        let mut params = ModelParams::default();
+        assert!(calculate_win_prob_with(&team1, &team2, None, 0.0, &params) > 0.9999);
+
+        params.set_prob_bounds(Some((0.01, 0.99))).unwrap();
+        assert_eq!(calculate_win_prob_with(&team1, &team2, None, 0.0, &params), 0.99);
+        assert_eq!(calculate_win_prob_with(&team2, &team1, None, 0.0, &params), 0.01);
+        let mut overrides = OverridesMap::new();
+        overrides.add_override("A", "B", 1.0).unwrap();
+        assert_eq!(calculate_win_prob_with(&team1, &team2, Some(&overrides), 0.0, &params), 1.0);
+
+        assert!(params.set_prob_bounds(Some((0.6, 0.4))).is_err());
+        assert!(params.set_prob_bounds(Some((-0.1, 0.9))).is_err());
+        params.set_prob_bounds(None).unwrap();
+        assert!(calculate_win_prob_with(&team1, &team2, None, 0.0, &params) > 0.9999);
+    }
+
+    #[test]
+    fn test_prob_bounds_checked_on_deserialize() {
+        let json = |bounds: &str| {
+            format!(r#"{{"avg_scoring": 105.0, "avg_tempo": 67.7, "scoring_stddev": 11.0, "prob_bounds": {bounds}}}"#)
+        };
+        let params: ModelParams = serde_json::from_str(&json("[0.05, 0.95]")).unwrap();
+        assert_eq!(params.prob_bounds(), Some((0.05, 0.95)));
+        let err = serde_json::from_str::<ModelParams>(&json("[0.6, 0.4]")).unwrap_err();
+        assert!(err.to_string().contains("prob_bounds"), "{err}");
+        assert!(serde_json::from_str::<ModelParams>(&json("[0.1, 1.5]")).is_err());
+        let params: ModelParams = serde_json::from_str(&json("null")).unwrap();
+        assert_eq!(params.prob_bounds(), None);
+    }
 }
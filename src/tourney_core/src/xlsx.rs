@@ -0,0 +1,225 @@
+//! Excel (xlsx) readers for ratings and positions sheets.
+//!
+//! Columns are found by header name (case-insensitive) in the first row of the
+//! sheet, so commissioners can keep extra columns and any column order. Blank
+//! rows are skipped; any other malformed row is an error naming its
+//! spreadsheet row number.
+//!
+//! * Ratings: `team` (or `name`), `offense`, `defense`, `tempo` as raw
+//!   efficiencies, plus optional `conference`, `seed`, `wins`, `losses`, and
+//!   `short_name`, matching the extras of the plain-text ratings file.
+//! * Positions: a team column and a position column, named by the caller.
+//!   A team listed on several rows holds the sum of its positions.
+
+use calamine::{Data, DataType, Range, Reader, Xlsx};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+use crate::error::{Result, TourneyError};
+use crate::team::Team;
+
+/// Read a ratings sheet into teams by name, applying `adjustments` (points per
+/// team) like the plain-text ratings reader. `sheet` defaults to the first.
+#[pyfunction]
+#[pyo3(signature = (path, sheet = None, adjustments = None))]
+pub fn read_ratings_xlsx(
+    path: &str,
+    sheet: Option<&str>,
+    adjustments: Option<HashMap<String, f64>>,
+) -> Result<HashMap<String, Team>> {
+    parse_ratings_range(&read_range(path, sheet)?, adjustments.as_ref())
+}
+
+/// Read a positions (ownership) sheet into shares per team. `sheet` defaults
+/// to the first.
+#[pyfunction]
+#[pyo3(signature = (path, sheet = None, team_column = "team", position_column = "position"))]
+pub fn read_positions_xlsx(
+    path: &str,
+    sheet: Option<&str>,
+    team_column: &str,
+    position_column: &str,
+) -> Result<HashMap<String, f64>> {
+    parse_positions_range(&read_range(path, sheet)?, team_column, position_column)
+}
+
+fn read_range(path: &str, sheet: Option<&str>) -> Result<Range<Data>> {
+    let file = std::fs::File::open(path).map_err(|err| std::io::Error::new(err.kind(), format!("{path}: {err}")))?;
+    let mut workbook = Xlsx::new(std::io::BufReader::new(file))?;
+    let range = match sheet {
+        Some(name) => workbook.worksheet_range(name)?,
+        None => workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("{path}: workbook has no sheets")))??,
+    };
+    Ok(range)
+}
+
+/// Header row of a sheet and the sheet row number of each data row.
+struct Sheet<'a> {
+    headers: Vec<String>,
+    rows: Vec<(usize, &'a [Data])>,
+}
+
+impl<'a> Sheet<'a> {
+    fn new(range: &'a Range<Data>) -> Result<Self> {
+        let first_row = range.start().map_or(0, |(row, _)| row as usize) + 1;
+        let mut rows = range.rows().enumerate().map(|(i, row)| (first_row + i, row));
+        let (_, header) = rows
+            .next()
+            .ok_or_else(|| TourneyError::InvalidInput("sheet is empty".to_string()))?;
+        let headers = header.iter().map(|cell| cell.to_string().trim().to_lowercase()).collect();
+        let rows = rows.filter(|(_, row)| !row.iter().all(DataType::is_empty)).collect();
+        Ok(Sheet { headers, rows })
+    }
+
+    fn column(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.headers.iter().position(|header| header == &name.to_lowercase()))
+    }
+
+    fn required_column(&self, names: &[&str]) -> Result<usize> {
+        self.column(names)
+            .ok_or_else(|| TourneyError::InvalidInput(format!("sheet has no {} column", names[0])))
+    }
+}
+
+/// Text of a cell, with whole numbers written without a decimal point;
+/// `None` for an empty cell.
+fn cell_text(row: &[Data], col: Option<usize>) -> Option<String> {
+    let cell = row.get(col?)?;
+    let text = match cell {
+        Data::Float(value) if value.fract() == 0.0 => format!("{value:.0}"),
+        _ => cell.to_string().trim().to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn cell_number<T: std::str::FromStr>(row_no: usize, what: &str, row: &[Data], col: Option<usize>) -> Result<Option<T>> {
+    cell_text(row, col)
+        .map(|text| {
+            text.parse()
+                .map_err(|_| TourneyError::InvalidInput(format!("row {row_no}: invalid {what} {text:?}")))
+        })
+        .transpose()
+}
+
+fn required_number<T: std::str::FromStr>(row_no: usize, what: &str, row: &[Data], col: usize) -> Result<T> {
+    cell_number(row_no, what, row, Some(col))?
+        .ok_or_else(|| TourneyError::InvalidInput(format!("row {row_no}: missing {what}")))
+}
+
+/// Parse a ratings sheet; see the module docs for its columns.
+pub fn parse_ratings_range(
+    range: &Range<Data>,
+    adjustments: Option<&HashMap<String, f64>>,
+) -> Result<HashMap<String, Team>> {
+    let sheet = Sheet::new(range)?;
+    let name_col = sheet.required_column(&["team", "name"])?;
+    let offense_col = sheet.required_column(&["offense"])?;
+    let defense_col = sheet.required_column(&["defense"])?;
+    let tempo_col = sheet.required_column(&["tempo"])?;
+
+    let mut ratings = HashMap::new();
+    for &(row_no, row) in &sheet.rows {
+        let name = cell_text(row, Some(name_col))
+            .ok_or_else(|| TourneyError::InvalidInput(format!("row {row_no}: missing team")))?;
+        let adjustment = adjustments.and_then(|a| a.get(&name)).copied().unwrap_or(0.0);
+        let offense = required_number::<f64>(row_no, "offense", row, offense_col)? + adjustment;
+        let defense = required_number::<f64>(row_no, "defense", row, defense_col)? - adjustment;
+        let tempo = required_number(row_no, "tempo", row, tempo_col)?;
+        let mut team = Team::new(name.clone(), offense, defense, tempo, true);
+
+        team.conference = cell_text(row, sheet.column(&["conference"]));
+        team.seed = cell_number(row_no, "seed", row, sheet.column(&["seed"]))?;
+        team.wins = cell_number(row_no, "wins", row, sheet.column(&["wins"]))?;
+        team.losses = cell_number(row_no, "losses", row, sheet.column(&["losses"]))?;
+        team.short_name = cell_text(row, sheet.column(&["short_name"]));
+        ratings.insert(name, team);
+    }
+    Ok(ratings)
+}
+
+/// Parse a positions sheet, summing rows that name the same team.
+pub fn parse_positions_range(
+    range: &Range<Data>,
+    team_column: &str,
+    position_column: &str,
+) -> Result<HashMap<String, f64>> {
+    let sheet = Sheet::new(range)?;
+    let team_col = sheet.required_column(&[team_column])?;
+    let position_col = sheet.required_column(&[position_column])?;
+
+    let mut positions = HashMap::new();
+    for &(row_no, row) in &sheet.rows {
+        let team = cell_text(row, Some(team_col))
+            .ok_or_else(|| TourneyError::InvalidInput(format!("row {row_no}: missing team")))?;
+        let shares: f64 = required_number(row_no, position_column, row, position_col)?;
+        *positions.entry(team).or_insert(0.0) += shares;
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(rows: &[&[Data]]) -> Range<Data> {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0) as u32;
+        let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width - 1));
+        for (r, row) in rows.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                range.set_value((r as u32, c as u32), cell.clone());
+            }
+        }
+        range
+    }
+
+    fn text(value: &str) -> Data {
+        Data::String(value.to_string())
+    }
+
+    #[test]
+    fn test_ratings_sheet_by_header() {
+        let sheet = range(&[
+            &[text("Tempo"), text("Team"), text("Offense"), text("Defense"), text("Notes"), text("Seed")],
+            &[Data::Float(70.0), text("Duke"), Data::Float(120.0), Data::Float(90.0), text("hot"), Data::Float(1.0)],
+            &[Data::Empty, Data::Empty, Data::Empty, Data::Empty, Data::Empty, Data::Empty],
+            &[Data::Int(68), text("Texas"), text("115"), Data::Float(95.0), Data::Empty, Data::Empty],
+        ]);
+        let adjustments: HashMap<String, f64> = [("Texas".to_string(), 2.0)].into_iter().collect();
+        let ratings = parse_ratings_range(&sheet, Some(&adjustments)).unwrap();
+        assert_eq!(ratings.len(), 2);
+        assert_eq!(ratings["Duke"].seed, Some(1));
+        assert_eq!(ratings["Texas"].seed, None);
+        assert_eq!(ratings["Texas"].tempo, 68.0);
+
+        let unadjusted = parse_ratings_range(&sheet, None).unwrap();
+        assert!((ratings["Texas"].net_rating(104.6) - unadjusted["Texas"].net_rating(104.6) - 4.0).abs() < 1e-9);
+
+        let bad = range(&[&[text("team"), text("offense"), text("defense")], &[text("A"), text("1"), text("2")]]);
+        assert!(parse_ratings_range(&bad, None).is_err());
+    }
+
+    #[test]
+    fn test_positions_sheet_sums_rows() {
+        let sheet = range(&[
+            &[text("Owner"), text("Team"), text("Shares")],
+            &[text("me"), text("Duke"), Data::Float(2.5)],
+            &[text("me"), text("Duke"), Data::Int(-1)],
+            &[text("me"), text("Texas"), Data::Float(3.0)],
+        ]);
+        let positions = parse_positions_range(&sheet, "team", "shares").unwrap();
+        assert_eq!(positions["Duke"], 1.5);
+        assert_eq!(positions["Texas"], 3.0);
+        assert!(parse_positions_range(&sheet, "team", "position").is_err());
+
+        let blank = range(&[&[text("team"), text("position")], &[text("Duke"), Data::Empty]]);
+        let Err(err) = parse_positions_range(&blank, "team", "position") else {
+            panic!("missing position accepted");
+        };
+        assert!(err.to_string().contains("row 2"), "{err}");
+    }
+}
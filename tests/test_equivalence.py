@@ -6,6 +6,7 @@ equivalent to the original Python implementation (within floating
 point tolerance).
 """
 
+import asyncio
 import random
 from decimal import Decimal
 
@@ -154,6 +155,34 @@ class TestCalculateWinProb:
         assert abs(prob - 0.75) < TOLERANCE
 
 
+class TestMatchupMath:
+    """Test the spread/total helpers against the reference formulas."""
+
+    def test_expected_tempo_and_scores(self):
+        team1 = tourney_core.Team("A", 0.05, -0.02, 68.0)
+        team2 = tourney_core.Team("B", 0.03, 0.01, 70.0)
+
+        tempo = rust_impl.expected_tempo(team1, team2)
+        assert abs(tempo - 68.0 * 70.0 / rust_impl.AVG_TEMPO) < TOLERANCE
+
+        score1, score2 = rust_impl.calculate_expected_scores(team1, team2)
+        assert abs(score1 - (1 + 0.05 + 0.01) * rust_impl.AVG_SCORING / 100 * tempo) < TOLERANCE
+        assert abs(score2 - (1 + 0.03 - 0.02) * rust_impl.AVG_SCORING / 100 * tempo) < TOLERANCE
+
+    def test_tempo_override(self):
+        team1 = tourney_core.Team("A", 0.05, -0.02, 68.0)
+        team2 = tourney_core.Team("B", 0.03, 0.01, 70.0)
+        overrides = tourney_core.OverridesMap()
+        overrides.set_tempo_override("A", "B", 60.0)
+
+        assert rust_impl.expected_tempo(team2, team1, overrides) == 60.0
+        scale = 60.0 / rust_impl.expected_tempo(team1, team2)
+        base1, base2 = rust_impl.calculate_expected_scores(team1, team2)
+        score1, score2 = rust_impl.calculate_expected_scores(team1, team2, overrides)
+        assert abs(score1 - base1 * scale) < TOLERANCE
+        assert abs(score2 - base2 * scale) < TOLERANCE
+
+
 class TestTournamentState:
     """Test tournament scoring equivalence."""
 
@@ -219,6 +248,14 @@ class TestTournamentState:
         teams = simple_bracket_rust.get_bracket_teams()
         assert set(teams) == {"A", "B", "C", "D"}
 
+    def test_run_simulations_async(self, simple_bracket_rust):
+        """Awaiting run_simulations_async gives the same draws as run_simulations."""
+
+        async def run():
+            return await simple_bracket_rust.run_simulations_async(200, seed=7)
+
+        assert asyncio.run(run()) == simple_bracket_rust.run_simulations(200, seed=7)
+
 
 class TestFileReading:
     """Test file reading functions."""
@@ -0,0 +1,42 @@
+"""Tests that tourney_core.pyi matches the Rust sources and the built module."""
+
+import ast
+from pathlib import Path
+
+import pytest
+
+from scripts.generate_stubs import STUB_PATH, generate
+
+
+def stub_names():
+    """Top-level names in the stub file, mapped to their members for classes."""
+    names = {}
+    for node in ast.parse(STUB_PATH.read_text()).body:
+        if isinstance(node, ast.ClassDef):
+            names[node.name] = {n.name for n in node.body if isinstance(n, ast.FunctionDef)}
+        elif isinstance(node, ast.FunctionDef):
+            names[node.name] = None
+        elif isinstance(node, ast.AnnAssign):
+            names[node.target.id] = None
+    return names
+
+
+def test_stubs_are_up_to_date():
+    assert STUB_PATH.read_text() == generate(), "run scripts/generate_stubs.py"
+
+
+def test_stubs_cover_module():
+    tourney_core = pytest.importorskip("tourney_core")
+    names = stub_names()
+    exported = {name for name in dir(tourney_core) if not name.startswith("__")}
+    assert exported == set(names)
+
+    for name, members in names.items():
+        if members is None:
+            continue
+        methods = {
+            member
+            for member, value in vars(getattr(tourney_core, name)).items()
+            if not member.startswith("__") and (callable(value) or hasattr(value, "__get__"))
+        }
+        assert methods <= members, f"{name} is missing {sorted(methods - members)}"
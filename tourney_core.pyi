@@ -0,0 +1,2288 @@
+"""Type stubs for the tourney_core extension module.
+
+Generated by scripts/generate_stubs.py from the Rust sources; do not edit.
+"""
+
+from typing import Any
+
+AVG_SCORING: float
+AVG_TEMPO: float
+SCORING_STDDEV: float
+ROUND_POINTS: list[float]
+CALCUTTA_POINTS: list[float]
+
+
+class Team:
+    """Team with offensive/defensive efficiency ratings and tempo.
+
+    Ratings are stored as relative efficiency (e.g., 0.05 means 5% above average).
+
+    Teams compare and hash by value, so they work as set members and dict keys
+    from Python; don't mutate one while it is being used as a key.
+    """
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def offense(self) -> float:
+        """Offensive efficiency relative to average (e.g., 0.05 = 5% above average)"""
+    @offense.setter
+    def offense(self, value: float) -> None: ...
+    @property
+    def defense(self) -> float:
+        """Defensive efficiency relative to average (e.g., -0.02 = 2% better than average)"""
+    @defense.setter
+    def defense(self, value: float) -> None: ...
+    @property
+    def tempo(self) -> float:
+        """Expected possessions per game"""
+    @tempo.setter
+    def tempo(self, value: float) -> None: ...
+    @property
+    def conference(self) -> str | None: ...
+    @conference.setter
+    def conference(self, value: str | None) -> None: ...
+    @property
+    def seed(self) -> int | None:
+        """Tournament seed (1-16)"""
+    @seed.setter
+    def seed(self, value: int | None) -> None: ...
+    @property
+    def wins(self) -> int | None: ...
+    @wins.setter
+    def wins(self, value: int | None) -> None: ...
+    @property
+    def losses(self) -> int | None: ...
+    @losses.setter
+    def losses(self, value: int | None) -> None: ...
+    @property
+    def short_name(self) -> str | None:
+        """Abbreviated name for compact displays (e.g. "UNC")"""
+    @short_name.setter
+    def short_name(self, value: str | None) -> None: ...
+    @property
+    def style(self) -> dict[str, float]:
+        """Named style features (e.g. three-point attempt rate) for a
+        `MatchupAdjuster` to read.
+        """
+    @style.setter
+    def style(self, value: dict[str, float]) -> None: ...
+    def __init__(self, name: str, offense: float, defense: float, tempo: float, adjust: bool = False) -> None:
+        """Create a new Team.
+
+        If `adjust` is true, converts raw efficiency ratings (e.g., 115.0 for offense)
+        to relative efficiency (e.g., 0.099 for 115.0/104.6 - 1).
+        """
+    def copy(self) -> Team:
+        """Create a copy of this team"""
+    def display_name(self) -> str:
+        """Short name if set, otherwise the full name."""
+    def record(self) -> str | None:
+        """Win-loss record as "W-L", if known."""
+    def net_rating(self, avg_scoring: float = ...) -> float:
+        """Net efficiency: points per 100 possessions better than an average team."""
+    def label(self) -> str:
+        """Display label with seed and record when known, e.g. "(1) UNC 29-4"."""
+    def __hash__(self) -> int: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+
+
+class MatchupProjection:
+    """Projected outcome of a single matchup."""
+    @property
+    def team1(self) -> str: ...
+    @property
+    def team2(self) -> str: ...
+    @property
+    def team1_score(self) -> float: ...
+    @property
+    def team2_score(self) -> float: ...
+    @property
+    def margin(self) -> float:
+        """Expected team1 score minus team2 score."""
+    @property
+    def total(self) -> float:
+        """Expected combined score."""
+    @property
+    def win_prob(self) -> float:
+        """Probability of team1 winning, with overrides and forfeits applied."""
+    def __repr__(self) -> str: ...
+
+
+class MatchupPercentiles:
+    """Quantiles of a matchup's margin and total distributions."""
+    @property
+    def quantiles(self) -> list[float]: ...
+    @property
+    def margin(self) -> list[float]:
+        """Team1-minus-team2 margin at each quantile."""
+    @property
+    def total(self) -> list[float]:
+        """Combined score at each quantile."""
+    def __repr__(self) -> str: ...
+
+
+class ModelParams:
+    """Constants of the win probability model.
+
+    Defaults are the published constants (`AVG_SCORING`, `AVG_TEMPO`,
+    `SCORING_STDDEV`); a `TournamentState` carries its own copy so the model
+    can be re-run under alternative values.
+    """
+    @property
+    def avg_scoring(self) -> float:
+        """National average scoring (points per 100 possessions)."""
+    @avg_scoring.setter
+    def avg_scoring(self, value: float) -> None: ...
+    @property
+    def avg_tempo(self) -> float:
+        """National average tempo (possessions per game)."""
+    @avg_tempo.setter
+    def avg_tempo(self, value: float) -> None: ...
+    @property
+    def scoring_stddev(self) -> float:
+        """Standard deviation of the scoring margin at average tempo and scoring."""
+    @scoring_stddev.setter
+    def scoring_stddev(self, value: float) -> None: ...
+    @property
+    def ensemble(self) -> EnsembleModel | None:
+        """Models whose blended probabilities replace this model's own win
+        probabilities; see `EnsembleModel`.
+        """
+    @ensemble.setter
+    def ensemble(self, value: EnsembleModel | None) -> None: ...
+    @property
+    def matchup_adjuster(self) -> MatchupAdjuster | None:
+        """Style-based tweaks to each matchup's margin and its standard
+        deviation; see `MatchupAdjuster`.
+        """
+    @matchup_adjuster.setter
+    def matchup_adjuster(self, value: MatchupAdjuster | None) -> None: ...
+    @property
+    def double_forfeit(self) -> str:
+        """Double forfeit policy: `"coin_flip"` (default), `"higher_seed"` or
+        `"redraw"`; see `DoubleForfeit`.
+        """
+    @double_forfeit.setter
+    def double_forfeit(self, value: str) -> None: ...
+    @property
+    def confidence(self) -> float: ...
+    @confidence.setter
+    def confidence(self, value: float) -> None: ...
+    @property
+    def prob_bounds(self) -> tuple[float, float] | None: ...
+    @prob_bounds.setter
+    def prob_bounds(self, value: tuple[float, float] | None) -> None: ...
+    def __init__(self, avg_scoring: float = ..., avg_tempo: float = ..., scoring_stddev: float = ...) -> None: ...
+    @staticmethod
+    def grid(scoring_stddevs: list[float], avg_scorings: list[float] | None = None) -> list[ModelParams]:
+        """Every combination of `scoring_stddevs` and `avg_scorings` (default:
+        just `AVG_SCORING`), stddev-major, at the default tempo.
+        """
+    def __repr__(self) -> str: ...
+
+
+class RatedTeamSeries:
+    """Rating observations over time for a set of teams."""
+    def __init__(self) -> None: ...
+    def add(self, timestamp: int, team: Team) -> None:
+        """Record `team`'s rating as observed at `timestamp`, replacing any
+        observation of the same team at the same time.
+        """
+    def add_ratings(self, timestamp: int, ratings: dict[str, Team]) -> None:
+        """Record a full ratings snapshot observed at `timestamp`."""
+    def teams(self) -> list[str]:
+        """Names of all teams with at least one observation, sorted."""
+    def timestamps(self, team: str) -> list[int]:
+        """Observation timestamps for `team`, oldest first."""
+    def rating_at(self, team: str, timestamp: int, interpolate: bool = True) -> Team | None:
+        """`team`'s rating at `timestamp`, or None before its first observation.
+
+        With `interpolate`, ratings between observations are linearly
+        interpolated; otherwise the latest observation at or before
+        `timestamp` is used.
+        """
+    def ratings_at(self, timestamp: int, interpolate: bool = True) -> dict[str, Team]:
+        """Ratings of every team observed at or before `timestamp`."""
+    def decayed_rating(self, team: str, timestamp: int, half_life: float) -> Team | None:
+        """`team`'s rating at `timestamp` as an exponentially time-decayed average
+        of observations at or before `timestamp`, with weights halving every
+        `half_life` seconds, which must be positive. Metadata comes from the
+        latest observation.
+        """
+    def decayed_ratings(self, timestamp: int, half_life: float) -> dict[str, Team]:
+        """Time-decayed ratings of every team observed at or before `timestamp`."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class OverridesMap:
+    """Manual probability overrides for specific matchups.
+
+    Overrides are stored with team names in lexicographic order.
+    When retrieving an override, the probability is automatically
+    flipped if the teams are provided in reverse order.
+
+    Overrides may carry an `OverrideMeta`. Expired overrides stay in the map but
+    are ignored by lookups once the clock (see `set_clock`) passes their expiry.
+    The clock only moves when it is set: it is the round the tournament has
+    reached and the time, not the round of the game being scored, so an
+    override expiring after round 1 still applies to a projected round-2 game
+    until `current_round` reaches 2. The clock is not serialized.
+
+    A map may sit on top of a `base` layer (e.g. a feed of injury overrides
+    under manual session tweaks): lookups fall through to the base for any
+    matchup this layer has no active override for.
+    """
+    @property
+    def current_round(self) -> int | None:
+        """Current tournament round (0-based) used for round expiries."""
+    @property
+    def as_of(self) -> int | None:
+        """Current Unix timestamp used for date expiries."""
+    @property
+    def clamp(self) -> bool:
+        """Clamp out-of-range probabilities into [0, 1] instead of rejecting them."""
+    @clamp.setter
+    def clamp(self, value: bool) -> None: ...
+    @property
+    def base(self) -> OverridesMap | None:
+        """The layer beneath this one, if any."""
+    def __init__(self) -> None: ...
+    def add_override(self, name1: str, name2: str, prob: float) -> None:
+        """Add or update an override for a matchup.
+
+        Probabilities outside [0, 1] are rejected unless `clamp` is set. Any
+        metadata previously attached to the matchup is dropped.
+        """
+    def set_tempo_override(self, name1: str, name2: str, tempo: float) -> None:
+        """Set the expected tempo (possessions) for a matchup, replacing the
+        ratings-based pace in win probabilities and expected scores for that
+        pairing only.
+        """
+    def remove_tempo_override(self, name1: str, name2: str) -> None: ...
+    def get_tempo_override(self, name1: str, name2: str) -> float | None:
+        """Tempo override for a matchup, falling through to the base layer."""
+    def tempo_overrides(self) -> list[tuple[str, str, float]]:
+        """All tempo overrides across layers as sorted (team1, team2, tempo)
+        tuples, with team1 < team2.
+        """
+    def add_override_with_meta(self, name1: str, name2: str, prob: float, meta: OverrideMeta) -> None:
+        """Add or update an override with provenance and expiry metadata."""
+    def remove_override(self, name1: str, name2: str) -> None:
+        """Remove an override for a matchup."""
+    def get_override(self, name1: str, name2: str) -> float | None:
+        """Get the override probability for a matchup, if one is in effect.
+        Returns the probability of name1 beating name2.
+        """
+    def has_override(self, name1: str, name2: str) -> bool:
+        """Check if an override is in effect for a matchup."""
+    def get_meta(self, name1: str, name2: str) -> OverrideMeta | None:
+        """Metadata attached to a matchup's override, if any."""
+    def set_base(self, base: OverridesMap) -> None:
+        """Layer this map on top of `base`."""
+    def clear_base(self) -> None: ...
+    def flatten(self) -> OverridesMap:
+        """Collapse all layers into a single map (this layer wins, unless its
+        entry has expired under this map's clock and one beneath has not).
+        """
+    def merge(self, other: OverridesMap, conflict_policy: str = "theirs") -> OverridesMap:
+        """Combine with `other` (both flattened, expired overrides dropped) into
+        a new map.
+
+        `conflict_policy` decides matchups present in both with different
+        probabilities: `"ours"` keeps this map's value, `"theirs"` takes
+        `other`'s, `"newest"` takes whichever has the later metadata timestamp
+        (ties go to `other`), and `"error"` fails.
+        """
+    def diff(self, other: OverridesMap) -> OverridesDiff:
+        """Changes needed to turn this map into `other` (both flattened, expired
+        overrides dropped).
+        """
+    def set_clock(self, current_round: int | None = None, timestamp: int | None = None, *, round: int | None = None) -> None:
+        """Set the clock used to decide which overrides have expired: the round
+        the tournament has reached and the time. Advance it as rounds finish;
+        scoring never moves it.
+
+        `timestamp` defaults to the current time. `round` is the deprecated
+        name of `current_round`.
+        """
+    def active_overrides(self) -> list[tuple[str, str, float]]:
+        """Overrides currently in effect across all layers as (team1, team2, prob)
+        tuples, sorted.
+        """
+    def expired_overrides(self) -> list[tuple[str, str, float]]:
+        """Overrides across all layers that have expired under the current clock, sorted."""
+    def purge_expired(self) -> int:
+        """Drop expired overrides from this layer, returning how many were removed."""
+    def items(self) -> list[tuple[str, str, float]]:
+        """Overrides in effect across layers as sorted (team1, team2, prob)
+        tuples, with team1 < team2. Expired entries are left out, as
+        `get_override` leaves them out.
+        """
+    def __iter__(self) -> Any: ...
+    def to_csv(self, path: str | None = None) -> str:
+        """Overrides as `team1,team2,probability` CSV rows (the overrides file
+        format), also written atomically to `path` if given.
+        """
+    @staticmethod
+    def from_csv_str(text: str | bytes) -> OverridesMap:
+        """Like `from_csv`, but also accepting the contents as UTF-8 `bytes`
+        (e.g. an uploaded overrides file).
+        """
+    @staticmethod
+    def from_csv(text: str) -> OverridesMap:
+        """Parse overrides written by `to_csv` (or an overrides file)."""
+    @staticmethod
+    def from_spreads(spreads: list[tuple[str, str, float]], model_params: ModelParams | None = None) -> OverridesMap:
+        """Overrides from point spreads, one `(team1, team2, spread)` per game.
+
+        `spread` is team1's line in betting convention (-5.5 means team1 is
+        favored by 5.5). Margins convert to probabilities the way the model
+        does, with the margin standard deviation of two average teams under
+        `model_params` (default: the published constants).
+        """
+    def audit(self, ratings: dict[str, Team], threshold: float = 0.25, forfeit_prob: float = 0.0) -> list[OverrideAudit]:
+        """Active overrides that differ from the rating-based probability by more
+        than `threshold`, largest disagreement first.
+
+        Matchups involving a team missing from `ratings` are skipped.
+        """
+    def __getitem__(self, matchup: tuple[str, str]) -> float:
+        """`overrides[team1, team2]`: the override probability of `team1`
+        beating `team2`, like `get_override` but raising `KeyError` when none
+        is in effect.
+        """
+    def __setitem__(self, matchup: tuple[str, str], prob: float) -> None:
+        """`overrides[team1, team2] = prob`, as `add_override`."""
+    def __delitem__(self, matchup: tuple[str, str]) -> None:
+        """`del overrides[team1, team2]`, as `remove_override`: raises `KeyError`
+        unless an override is in effect. Only this layer's entry is removed,
+        so one in a base layer still applies afterwards.
+        """
+    def __contains__(self, matchup: tuple[str, str]) -> bool:
+        """`(team1, team2) in overrides`, as `has_override`."""
+    def __len__(self) -> int:
+        """Number of overrides in effect across all layers, matching `items`."""
+    def __repr__(self) -> str: ...
+
+
+class OverrideMeta:
+    """Provenance and expiry of a single override."""
+    @property
+    def source(self) -> str | None:
+        """Where the override came from (e.g. "injury feed", "manual")."""
+    @source.setter
+    def source(self, value: str | None) -> None: ...
+    @property
+    def timestamp(self) -> int | None:
+        """Unix timestamp (seconds) when the override was set."""
+    @timestamp.setter
+    def timestamp(self, value: int | None) -> None: ...
+    @property
+    def note(self) -> str | None: ...
+    @note.setter
+    def note(self, value: str | None) -> None: ...
+    @property
+    def expires_round(self) -> int | None:
+        """Last round (0-based) in which the override applies, judged by the
+        map's clock (`OverridesMap.set_clock`), not by the round of the game
+        being scored.
+        """
+    @expires_round.setter
+    def expires_round(self, value: int | None) -> None: ...
+    @property
+    def expires_at(self) -> int | None:
+        """Unix timestamp after which the override no longer applies."""
+    @expires_at.setter
+    def expires_at(self, value: int | None) -> None: ...
+    def __init__(self, source: str | None = None, timestamp: int | None = None, note: str | None = None, expires_round: int | None = None, expires_at: int | None = None) -> None: ...
+    def is_expired(self, round: int | None = None, timestamp: int | None = None) -> bool:
+        """Whether the override has expired as of `round` and/or `timestamp`."""
+    def __repr__(self) -> str: ...
+
+
+class OverridesDiff:
+    """Differences between two override maps."""
+    @property
+    def added(self) -> list[tuple[str, str, float]]:
+        """Matchups only in the other map, as (team1, team2, prob)."""
+    @property
+    def removed(self) -> list[tuple[str, str, float]]:
+        """Matchups only in this map, as (team1, team2, prob)."""
+    @property
+    def changed(self) -> list[tuple[str, str, float, float]]:
+        """Matchups in both with different probabilities, as (team1, team2, old, new)."""
+    def is_empty(self) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class OverrideAudit:
+    """An override that disagrees sharply with the rating-based probability."""
+    @property
+    def team1(self) -> str: ...
+    @property
+    def team2(self) -> str: ...
+    @property
+    def override_prob(self) -> float:
+        """Overridden probability of team1 beating team2."""
+    @property
+    def model_prob(self) -> float:
+        """Probability of team1 beating team2 from ratings alone."""
+    @property
+    def difference(self) -> float:
+        """`override_prob - model_prob`."""
+    def __repr__(self) -> str: ...
+
+
+class TournamentState:
+    """Tournament state containing bracket, ratings, and scoring rules."""
+    @property
+    def scoring(self) -> list[float]:
+        """Points awarded per round"""
+    @property
+    def forfeit_prob(self) -> float:
+        """Probability of a team forfeiting"""
+    @property
+    def play_in_round(self) -> bool:
+        """Whether play-in (First Four) games are scored as their own round.
+
+        When set, `scoring[0]` is awarded to play-in winners and main-bracket
+        round `r` is scored with `scoring[r + 1]`.
+        """
+    @play_in_round.setter
+    def play_in_round(self, value: bool) -> None: ...
+    @property
+    def team_multipliers(self) -> dict[str, float]:
+        """Per-team scaling of round points (e.g. seed for points-per-seed pools).
+
+        Teams without an entry score at 1x.
+        """
+    @team_multipliers.setter
+    def team_multipliers(self, value: dict[str, float]) -> None: ...
+    @property
+    def model_params(self) -> ModelParams:
+        """Win probability model constants; defaults to the published values."""
+    @model_params.setter
+    def model_params(self, value: ModelParams) -> None: ...
+    @property
+    def deterministic(self) -> bool:
+        """Sum in a fixed order (sorted keys, fixed chunks, pairwise sums) so
+        scores and deltas are bit-identical across runs and thread counts.
+        """
+    @deterministic.setter
+    def deterministic(self, value: bool) -> None: ...
+    @property
+    def reseed(self) -> bool:
+        """Re-pair survivors after every main-bracket round, highest remaining
+        seed against lowest, instead of following the fixed tree.
+
+        Ties between equal seeds go to the team from the earlier bracket slot,
+        and unseeded teams rank below every seed. Pairings then depend on which
+        teams survive, so `calculate_scores_prob` averages `RESEED_SIMULATIONS`
+        fixed-seed simulations instead of propagating probabilities exactly.
+        """
+    @reseed.setter
+    def reseed(self, value: bool) -> None: ...
+    @property
+    def persistent_forfeits(self) -> bool:
+        """Decide forfeits once per team per simulation instead of per game: a
+        team withdraws from the whole event with probability `forfeit_prob`
+        and forfeits every game it reaches.
+
+        Probabilistic scoring tracks each team's chance of reaching a round
+        with and without having withdrawn, which is exact for fixed-tree
+        brackets; brackets it cannot propagate average simulations instead.
+        """
+    @persistent_forfeits.setter
+    def persistent_forfeits(self, value: bool) -> None: ...
+    @property
+    def regions(self) -> list[str]:
+        """Region names in bracket order, each covering an equal block of slots.
+
+        Empty when unknown; set from version 2 bracket files.
+        """
+    @regions.setter
+    def regions(self, value: list[str]) -> None: ...
+    @property
+    def bracket(self) -> BracketView:
+        """Read-only view of the bracket slots; see `BracketView`."""
+    @property
+    def ratings(self) -> RatingsView:
+        """Read-only view of the ratings; see `RatingsView`."""
+    @property
+    def overrides(self) -> OverridesMap:
+        """Get the overrides map. The copy shares its tables with the state until
+        either side is modified, so this is cheap however many overrides exist.
+        """
+    @overrides.setter
+    def overrides(self, value: OverridesMap) -> None: ...
+    @property
+    def confidence(self) -> float:
+        """`model_params.confidence`: how far model probabilities are trusted,
+        as a scale on their log-odds (below 1 shrinks them toward 0.5).
+        """
+    @confidence.setter
+    def confidence(self, value: float) -> None: ...
+    def __init__(self, bracket: list[dict[str, float]], ratings: dict[str, Team], scoring: list[float], overrides: OverridesMap | None = None, forfeit_prob: float = 0.0, equivalence_classes: list[list[str]] | None = None) -> None: ...
+    def calculate_scores_prob(self) -> dict[str, float]:
+        """Calculate expected scores using probabilistic method.
+
+        Returns a map of team names to their expected tournament scores.
+        """
+    def calculate_scores_by_round(self) -> dict[str, list[float]]:
+        """Expected points per team earned in each round.
+
+        Vectors are indexed like `scoring` (index 0 is the play-in round when
+        `play_in_round` is set) and sum to `calculate_scores_prob`.
+        """
+    def strength_of_remaining_schedule(self, team: str) -> ScheduleStrength:
+        """Probability-weighted opponent ratings over `team`'s remaining games."""
+    def win_prob_matrix(self, teams: list[str] | None = None) -> WinProbMatrix:
+        """Pairwise win probabilities among `teams` (default: the bracket teams)
+        under the current ratings, overrides, and model.
+        """
+    def model_disagreement(self, team1: str, team2: str) -> float:
+        """How much the members of `model_params.ensemble` disagree about
+        `team1` beating `team2` (weighted standard deviation of their
+        probabilities); 0 without an ensemble or when an override decides the
+        game.
+        """
+    def calculate_scores_prob_raw(self) -> dict[str, float]:
+        """Expected scores from the ratings model alone, ignoring all overrides."""
+    def override_impact(self) -> dict[str, tuple[float, float]]:
+        """Per-team `(with_overrides, raw)` expected scores, so the value manual
+        overrides move for each team is `with_overrides - raw`.
+        """
+    def update_ratings(self, new_ratings: dict[str, Team]) -> dict[str, tuple[float, float]]:
+        """Replace the ratings with `new_ratings`, returning each bracket team's
+        `(before, after)` expected score.
+
+        `new_ratings` is expanded with the equivalence classes given to `new`,
+        then checked like `TournamentStateBuilder::build` checks ratings: every
+        bracket team must be rated, with finite values. Otherwise nothing
+        changes.
+        """
+    def calculate_scores_sim(self, seed: int | None = None) -> dict[str, float]:
+        """Simulate tournament once using Monte Carlo method.
+
+        Returns a map of team names to their scores in this simulation.
+        """
+    def run_simulations(self, n_simulations: int, seed: int | None = None) -> list[dict[str, float]]:
+        """Run multiple Monte Carlo simulations in parallel.
+
+        Returns a vector of score maps, one for each simulation.
+        Uses all available CPU cores for maximum throughput.
+        """
+    def run_simulation_log(self, n_simulations: int, seed: int | None = None) -> SimulationLog:
+        """`run_simulations` keeping every game's result instead of team scores;
+        see `SimulationLog`. The same seed gives the same simulations.
+        """
+    def simulate_with_diagnostics(self, n_simulations: int, target_std_error: float | None = None, metric: str = "champion", batch_size: int = 10000, seed: int | None = None) -> ConvergenceReport:
+        """`run_simulations` summarized as expected scores and championship
+        probabilities with their Monte Carlo standard errors. With
+        `target_std_error`, stops as soon as every team's standard error for
+        `metric` (`"points"` or `"champion"`) is below it, so `n_simulations`
+        becomes a cap: `target_std_error=0.001` simulates until championship
+        probabilities are within 0.1 percentage points.
+        """
+    def score_intervals(self, n_simulations: int = 10000, positions: dict[str, float] | None = None, rating_sd: float = 0.0, n_rating_draws: int = 100, level: float = 0.95, n_resamples: int = 1000, seed: int | None = None) -> ScoreIntervals:
+        """Bootstrapped confidence intervals on every bracket team's expected
+        score and, with `positions`, on the portfolio's expected value. With
+        `rating_sd` above 0, the intervals also reflect rating uncertainty:
+        simulations are spread over `n_rating_draws` copies of the ratings with
+        each team's offense and defense perturbed by that standard deviation.
+        See `ScoreIntervals`.
+        """
+    def simulate_conditional(self, n_simulations: int, condition: list[tuple[str, int]], seed: int | None = None, max_attempts: int | None = None) -> ConditionalSimulations:
+        """Up to `n_simulations` simulations in which every `(team, round)` in
+        `condition` holds, that is, each team wins its game in that
+        main-bracket round. See `ConditionalSimulations`.
+        """
+    def run_simulations_to_file(self, path: str, n_simulations: int, seed: int | None = None) -> None:
+        """`run_simulations`, streaming results to a binary archive at `path`
+        instead of returning them. Open the file with `SimulationArchive`.
+        """
+    def run_simulations_async(self, n_simulations: int, seed: int | None = None) -> Any:
+        """Awaitable variant of `run_simulations` for asyncio callers.
+
+        Simulations run on the background thread pool, so the caller's event loop
+        keeps serving requests while they complete.
+        """
+    def get_bracket_teams(self) -> list[str]:
+        """Get all teams in the bracket."""
+    def play_in_games(self) -> list[int]:
+        """Indices of bracket slots that are filled by a play-in game."""
+    def round_points(self, round: int) -> float:
+        """Points awarded for winning a game in main-bracket round `round` (0-based),
+        accounting for a scored play-in round.
+        """
+    def game_between(self, team1: str, team2: str) -> GameId | None:
+        """The game in which `team1` and `team2` would meet, or `None` if either
+        is not in the bracket or both are in the same slot.
+        """
+    def game_teams(self, game: GameId) -> list[str]:
+        """Teams that can play in `game`, in bracket order."""
+    def play_in_points(self) -> float:
+        """Points awarded for winning a play-in game, or 0 if play-ins are unscored."""
+    def team_multiplier(self, team: str) -> float:
+        """Scoring multiplier applied to `team`'s round points."""
+    def fit_confidence(self, games: list[tuple[str, str]]) -> float:
+        """Maximum likelihood `confidence` for this state's ratings model given
+        completed games as `(winner, loser)`, e.g. `HistoricalSeason.results`.
+        Predictions ignore overrides and the current confidence.
+        """
+    def project_matchup(self, team1: str, team2: str) -> MatchupProjection:
+        """Project a matchup between two rated teams with this state's model,
+        overrides, and forfeit probability, so `win_prob` matches scoring.
+        """
+    def swap_teams(self, slot_a: int, slot_b: int) -> None:
+        """Exchange the contents of bracket slots `slot_a` and `slot_b`."""
+    def replace_team(self, old: str, new: str, rating: Team | None = None) -> None:
+        """Put `new` in `old`'s place in the bracket, with the same slot
+        probability.
+
+        `rating` is stored for `new` (under that name); without one, `new`
+        must already be rated. `old` keeps its rating, but overrides and
+        multipliers naming it no longer apply to the bracket.
+        """
+    def remove_team(self, name: str, policy: str = "walkover") -> None:
+        """Take `name` out of the tournament.
+
+        `policy` is `"renormalize"` to drop it from a play-in slot it shares,
+        rescaling the other teams' probabilities (an error if it is alone in
+        its slot), or `"walkover"` to leave the slot in place and have `name`
+        lose its first game to whoever it meets, via certain overrides.
+        """
+    def to_dot(self) -> str:
+        """Graphviz DOT graph of the bracket annotated with advancement probabilities."""
+    def to_bracket_json(self) -> str:
+        """Bracket as a nested JSON tree annotated with advancement probabilities."""
+    def to_json(self) -> str:
+        """Serialize this state (bracket, ratings, scoring, overrides) to JSON."""
+    @staticmethod
+    def from_json(json: str) -> TournamentState:
+        """Build a state from JSON produced by `to_json`."""
+    def save(self, path: str, checksum: bool = True) -> None:
+        """Save this state to `path` as JSON, atomically replacing any existing
+        file. With `checksum`, a header line lets `load` detect corruption.
+        """
+    @staticmethod
+    def load(path: str) -> TournamentState:
+        """Load a state written by `save` (or `to_json`), verifying its checksum."""
+    @staticmethod
+    def from_files(bracket_path: str, ratings_path: str, overrides_path: str | None = None, scoring: list[float] | None = None, adjustments_path: str | None = None, equivalence_classes: list[list[str]] | None = None) -> TournamentState:
+        """Load a tournament from the data files: bracket, ratings, and
+        optionally overrides and rating adjustments (see `data_files` for the
+        formats). `scoring` defaults to `ROUND_POINTS`.
+
+        Ratings are expanded with `equivalence_classes` before the bracket is
+        read, so bracket names may use any variant. Seeds and regions from a
+        version 2 bracket file are set on the ratings and `regions`. The result must pass
+        `verify_invariants`; every violation is reported in the error.
+        """
+    @staticmethod
+    def create(*, bracket: list[dict[str, float]], ratings: dict[str, Team], scoring: list[float] | None = None, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0, model: ModelParams | None = None) -> TournamentState:
+        """Build a state from keyword arguments. `scoring` defaults to
+        `ROUND_POINTS` and `model` to the default `ModelParams`. Every bracket
+        team must have a rating, `scoring` must cover every round, and the
+        result must pass `verify_invariants`.
+        """
+    def __repr__(self) -> str: ...
+    def with_override(self, team1: str, team2: str, prob: float) -> TournamentState:
+        """Create a modified copy with an override added"""
+    def as_of(self, series: RatedTeamSeries, timestamp: int, half_life: float | None = None) -> TournamentState:
+        """Copy of this state with ratings taken from `series` as of `timestamp`.
+
+        With `half_life` (seconds) ratings are time-decayed averages of past
+        observations; otherwise they are interpolated. Teams without an
+        observation by `timestamp` keep their current ratings. Fails if
+        `half_life` is not positive.
+        """
+    def with_team_adjustment(self, team_name: str, point_delta: float) -> TournamentState:
+        """Create a modified copy with a team's rating adjusted"""
+    def with_group_adjustment(self, team_names: list[str], point_delta: float) -> TournamentState:
+        """Create a modified copy with every team in `team_names` adjusted by the
+        same `point_delta` (names missing from the ratings are ignored).
+        """
+    def calculate_scores_prob_batch(self, override_scenarios: list[list[tuple[str, str, float]]]) -> list[dict[str, float]]:
+        """Calculate scores for multiple override scenarios in parallel.
+
+        Takes a list of override scenarios, where each scenario is a list of
+        (winner, loser, probability) tuples. Returns a list of score maps.
+
+        This is much more efficient than calling calculate_scores_prob()
+        multiple times from Python, as it avoids GIL overhead and uses
+        true parallelism via Rayon.
+        """
+
+
+class BracketView:
+    """The bracket slots of a state, as a read-only sequence of
+    `{team: probability}` dicts.
+    """
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> dict[str, float]: ...
+    def to_list(self) -> list[dict[str, float]]:
+        """A full copy of the bracket as a list of dicts."""
+    def __repr__(self) -> str: ...
+
+
+class RatingsView:
+    """The ratings of a state, as a read-only mapping of team name to `Team`."""
+    def __len__(self) -> int: ...
+    def __getitem__(self, name: str) -> Team: ...
+    def __contains__(self, name: str) -> bool: ...
+    def __iter__(self) -> Any: ...
+    def get(self, name: str) -> Team | None:
+        """The team rated under `name`, or `None`."""
+    def keys(self) -> list[str]:
+        """Rated team names, sorted."""
+    def values(self) -> list[Team]:
+        """Rated teams, sorted by name."""
+    def items(self) -> list[tuple[str, Team]]:
+        """`(name, team)` pairs, sorted by name."""
+    def to_dict(self) -> dict[str, Team]:
+        """A full copy of the ratings as a dict."""
+    def __repr__(self) -> str: ...
+
+
+class GameId:
+    """One game of the main bracket: game `index` (0-based, in bracket order) of
+    `round`.
+    """
+    @property
+    def round(self) -> int: ...
+    @property
+    def index(self) -> int: ...
+    def __init__(self, round: int, index: int) -> None: ...
+    @staticmethod
+    def for_slot(slot: int, round: int) -> GameId:
+        """The game in `round` that the team in bracket slot `slot` plays, if it
+        gets there.
+        """
+    @staticmethod
+    def in_region(round: int, region: int, game: int, n_slots: int, n_regions: int) -> GameId:
+        """The game in `round` within region `region` (0-based) of a bracket of
+        `n_slots` slots split into `n_regions` equal regions.
+        """
+    def next(self) -> GameId:
+        """The game this game's winner plays next."""
+    def region(self, n_slots: int, n_regions: int) -> int | None:
+        """Region (0-based) of a bracket of `n_slots` slots split into
+        `n_regions` regions, or `None` once the game draws from several.
+        """
+    def slots(self) -> tuple[int, int]:
+        """First and one-past-last bracket slot feeding this game."""
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __lt__(self, other: GameId) -> bool: ...
+    def __le__(self, other: GameId) -> bool: ...
+    def __gt__(self, other: GameId) -> bool: ...
+    def __ge__(self, other: GameId) -> bool: ...
+    def __hash__(self) -> int: ...
+
+
+class PortfolioState:
+    """Portfolio state with precomputed deltas.
+
+    Expected scores and the last simulations drawn are cached for valuing and
+    previewing trades. The tournament and positions are read-only from Python;
+    in Rust, `tournament_mut` and `positions_mut` drop the cache.
+    """
+    @property
+    def tournament(self) -> TournamentState: ...
+    @property
+    def positions(self) -> dict[str, float]: ...
+    @property
+    def team_deltas(self) -> dict[str, float]: ...
+    @property
+    def pairwise_deltas(self) -> dict[str, dict[str, float]]: ...
+    @property
+    def point_delta(self) -> float: ...
+    def __init__(self, tournament: TournamentState, positions: dict[str, float], point_delta: float = 1.0) -> None: ...
+    def compute_deltas(self) -> None:
+        """Compute deltas for all teams."""
+    def compute_deltas_async(self) -> Any:
+        """Awaitable variant of `compute_deltas` for asyncio callers.
+
+        Deltas are computed on the background thread pool and stored on this
+        state once the returned future resolves.
+        """
+    def get_value(self) -> float:
+        """Get the current portfolio value."""
+    def risk(self, n_simulations: int, seed: int | None = None) -> RiskSummary:
+        """Distribution of portfolio value over `n_simulations` simulated tournaments."""
+    def value_attribution(self, n_simulations: int, seed: int | None = None) -> list[PositionAttribution]:
+        """Each position's contribution to expected value, variance, standard
+        deviation, and the 5th/95th value percentiles over `n_simulations`
+        simulated tournaments, largest variance contribution first.
+
+        Percentile components are component VaR: a position's average value in
+        the simulations ranked around that percentile. They sum to the average
+        portfolio value over that window, which approaches the percentile as
+        `n_simulations` grows.
+        """
+    def preview_trade(self, team: str, shares: float, price: float, n_simulations: int = 1000, seed: int | None = None) -> TradePreview:
+        """Preview buying `shares` of `team` at `price` without changing this state.
+
+        New deltas come from the cached pairwise deltas (computed first if
+        missing), since a team's portfolio delta is linear in positions. Risk
+        before and after is measured on the same `n_simulations` simulations,
+        which are kept with the expected scores so previewing more trades
+        against an unchanged state reuses both.
+        """
+    def exposure_report(self, region_names: list[str] | None = None) -> ExposureReport:
+        """Expected value and delta concentration by region, seed band, and round.
+
+        Uses the stored team deltas, computing them first if `compute_deltas`
+        has not been called. Regions split the bracket into equal blocks of
+        slots named by `region_names` (default: the tournament's `regions`, else four).
+        """
+    def __getitem__(self, team: str) -> float:
+        """`portfolio[team]`: shares held in `team`."""
+    def __contains__(self, team: str) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class TeamDelta:
+    """Result of a game delta calculation.
+
+    Deltas order by `total_delta` (ties broken by the remaining fields), so
+    `sorted()` puts the biggest losses first. Floats compare with `total_cmp`,
+    so equality agrees with the order: -0.0 sorts below 0.0 and a NaN equals
+    itself.
+    """
+    @property
+    def team(self) -> str: ...
+    @property
+    def position(self) -> float: ...
+    @property
+    def delta_per_share(self) -> float: ...
+    @property
+    def total_delta(self) -> float: ...
+    @property
+    def win_score(self) -> float | None:
+        """The team's expected score if `matchup.0` wins, when known."""
+    @property
+    def loss_score(self) -> float | None:
+        """The team's expected score if `matchup.1` wins, when known."""
+    @property
+    def matchup(self) -> tuple[str, str] | None:
+        """The `(team1, team2)` game this delta was computed for."""
+    def __init__(self, team: str, position: float, delta_per_share: float, win_score: float | None = None, loss_score: float | None = None, matchup: tuple[str, str] | None = None) -> None: ...
+    def to_dict(self) -> dict[str, Any]:
+        """The delta as a plain dict, with `team1`/`team2` in place of `matchup`."""
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __lt__(self, other: TeamDelta) -> bool: ...
+    def __le__(self, other: TeamDelta) -> bool: ...
+    def __gt__(self, other: TeamDelta) -> bool: ...
+    def __ge__(self, other: TeamDelta) -> bool: ...
+    def __hash__(self) -> int: ...
+
+
+class DeltaMatrix:
+    """Portfolio and pairwise deltas for every bracket team, indexed densely."""
+    @property
+    def teams(self) -> list[str]:
+        """Bracket teams; row and column `i` of the matrices is `teams[i]`."""
+    @property
+    def team_deltas(self) -> list[float]:
+        """`team_deltas[i]`: portfolio delta for a rating change to `teams[i]`."""
+    @property
+    def pairwise(self) -> list[list[float]]:
+        """`pairwise[i][j]`: change in `teams[j]`'s expected score for a rating
+        change to `teams[i]`.
+        """
+    def __repr__(self) -> str: ...
+
+
+class RiskSummary:
+    """Summary statistics of simulated portfolio values."""
+    @property
+    def mean(self) -> float: ...
+    @property
+    def stddev(self) -> float: ...
+    @property
+    def percentile_5(self) -> float:
+        """5th percentile of value (downside tail)."""
+    @property
+    def percentile_95(self) -> float:
+        """95th percentile of value (upside tail)."""
+    def __repr__(self) -> str: ...
+
+
+class PositionAttribution:
+    """One position's share of a portfolio's expected value and risk.
+
+    Each field is the position's component of the matching `RiskSummary`
+    statistic, so summing a field over every position recovers the
+    portfolio's figure (`expected_value` sums to `PortfolioState::get_value`).
+    """
+    @property
+    def team(self) -> str: ...
+    @property
+    def position(self) -> float: ...
+    @property
+    def expected_value(self) -> float:
+        """Position times the team's exact expected score."""
+    @property
+    def variance(self) -> float:
+        """Covariance of the position's value with the portfolio's."""
+    @property
+    def stddev(self) -> float:
+        """Euler component of the standard deviation: `variance / stddev`."""
+    @property
+    def percentile_5(self) -> float:
+        """Component 5th percentile: the position's mean value in simulations
+        whose portfolio value ranks near the 5th percentile.
+        """
+    @property
+    def percentile_95(self) -> float:
+        """Component 95th percentile, as `percentile_5`."""
+    def __repr__(self) -> str: ...
+
+
+class RebalancePlan:
+    """Trades proposed by `rebalance_to_target`."""
+    @property
+    def trades(self) -> dict[str, float]:
+        """Net shares to trade per team (negative to sell)."""
+    @property
+    def turnover(self) -> float:
+        """Sum of `|shares| * price` over every lot traded."""
+    @property
+    def costs(self) -> float:
+        """Transaction costs of the net trades, on top of their notional."""
+    @property
+    def team_deltas(self) -> dict[str, float]:
+        """Portfolio deltas after the trades for every targeted team."""
+    @property
+    def within_bands(self) -> bool:
+        """Whether every targeted delta ended up inside its band."""
+    def __repr__(self) -> str: ...
+
+
+class TransactionCosts:
+    """Fixed, proportional, and spread costs of a trade. The default is free."""
+    @property
+    def fixed(self) -> float:
+        """Flat fee per trade, regardless of size."""
+    @property
+    def proportional(self) -> float:
+        """Fee as a fraction of notional (`|shares| * price`)."""
+    @property
+    def spread(self) -> float:
+        """Full bid-ask spread in price units; each share pays half of it."""
+    def __init__(self, fixed: float = 0.0, proportional: float = 0.0, spread: float = 0.0) -> None: ...
+    def cost(self, shares: float, price: float) -> float:
+        """Cost of trading `shares` (either sign) at `price`, on top of
+        `shares * price`. Zero-share trades are free.
+        """
+    def effective_price(self, shares: float, price: float) -> float:
+        """All-in price per share for trading `shares` at `price`: higher than
+        `price` when buying, lower when selling.
+        """
+    def __repr__(self) -> str: ...
+
+
+class TradePreview:
+    """Effect of a hypothetical trade on a portfolio."""
+    @property
+    def team(self) -> str: ...
+    @property
+    def shares(self) -> float:
+        """Shares bought (positive) or sold (negative)."""
+    @property
+    def price(self) -> float: ...
+    @property
+    def cost(self) -> float:
+        """Cash paid for the trade (`shares * price`; negative for sales)."""
+    @property
+    def value_before(self) -> float: ...
+    @property
+    def value_after(self) -> float: ...
+    @property
+    def edge(self) -> float:
+        """Expected profit of the trade: change in value less its cost."""
+    @property
+    def team_deltas(self) -> dict[str, float]:
+        """Portfolio deltas after the trade for every team whose delta changes."""
+    @property
+    def risk_before(self) -> RiskSummary: ...
+    @property
+    def risk_after(self) -> RiskSummary: ...
+    @property
+    def stddev_change(self) -> float:
+        """Change in value standard deviation caused by the trade."""
+    def __repr__(self) -> str: ...
+
+
+class Edge:
+    """A team whose market price differs from its model value."""
+    @property
+    def team(self) -> str: ...
+    @property
+    def price(self) -> float: ...
+    @property
+    def model_value(self) -> float:
+        """Simulated mean of expected points or championship probability."""
+    @property
+    def edge(self) -> float:
+        """`model_value - price`; positive means the team is cheap."""
+    @property
+    def ci_low(self) -> float:
+        """Lower bound of the 95% confidence interval on `model_value`."""
+    @property
+    def ci_high(self) -> float:
+        """Upper bound of the 95% confidence interval on `model_value`."""
+    @property
+    def significant(self) -> bool:
+        """Whether the price lies outside the confidence interval."""
+    def __repr__(self) -> str: ...
+
+
+class ExposureBucket:
+    """Value and delta held in one slice of the bracket."""
+    @property
+    def value(self) -> float:
+        """Net expected value of positions in this bucket."""
+    @property
+    def value_share(self) -> float:
+        """Fraction of the portfolio's gross expected value in this bucket."""
+    @property
+    def delta(self) -> float:
+        """Net portfolio delta of teams in this bucket."""
+    @property
+    def delta_share(self) -> float:
+        """Fraction of the portfolio's gross delta in this bucket."""
+    def __repr__(self) -> str: ...
+
+
+class ExposureReport:
+    """Portfolio exposure broken down by region, seed band, and round."""
+    @property
+    def by_region(self) -> dict[str, ExposureBucket]: ...
+    @property
+    def by_seed_band(self) -> dict[str, ExposureBucket]:
+        """Keyed by bands of four seeds ("1-4", "5-8", ...)."""
+    @property
+    def by_round(self) -> list[float]:
+        """Expected portfolio value earned in each scoring round."""
+    def __repr__(self) -> str: ...
+
+
+class PortfolioBook:
+    """Collection of per-tournament portfolios valued together."""
+    @property
+    def names(self) -> list[str]:
+        """Event names, in insertion order."""
+    def __init__(self) -> None: ...
+    def add(self, name: str, portfolio: PortfolioState, scale: float = 1.0) -> None:
+        """Add (or replace) the portfolio for event `name`."""
+    def get(self, name: str) -> PortfolioState:
+        """Portfolio for event `name`."""
+    def value_by_tournament(self) -> dict[str, float]:
+        """Scaled expected value of each event's portfolio."""
+    def get_value(self) -> float:
+        """Combined scaled expected value of the book."""
+    def risk(self, n_simulations: int, seed: int | None = None) -> RiskSummary:
+        """Risk of the combined book from `n_simulations` joint simulations."""
+    def compute_deltas(self, point_delta: float = 1.0) -> dict[str, dict[str, float]]:
+        """Scaled portfolio deltas for every team, keyed by event then team."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class Payouts:
+    """Fraction of the pot paid to each finishing place (1st, 2nd, ...)."""
+    @property
+    def fractions(self) -> list[float]: ...
+    def __init__(self, fractions: list[float]) -> None:
+        """Create a payout table. Fractions must be non-negative and sum to at most 1."""
+    @staticmethod
+    def winner_take_all() -> Payouts:
+        """Winner-take-all payout."""
+    def __repr__(self) -> str: ...
+
+
+class ScoringRule:
+    """Points for a correct pick in each round, plus a compounding per-round
+    discount.
+
+    A correct round-`r` pick of a winner seeded `w` over a loser seeded `l`
+    earns `points[r] + seed_bonus[r] * w + upset_bonus[r] * max(w - l, 0)`,
+    scaled by `discount^r`. Missing rounds earn nothing from that component.
+    """
+    @property
+    def points(self) -> list[float]:
+        """Nominal points per round, indexed like `TournamentState::scoring`."""
+    @property
+    def discount(self) -> float:
+        """Value of a point one round later relative to now; round `r` points
+        are worth `discount^r`. 1.0 means no discounting.
+        """
+    @property
+    def seed_bonus(self) -> list[float]:
+        """Points per seed of the winner, per round."""
+    @property
+    def upset_bonus(self) -> list[float]:
+        """Points per seed line of upset margin, per round."""
+    def __init__(self, points: list[float], discount: float = 1.0, seed_bonus: list[float] | None = None, upset_bonus: list[float] | None = None) -> None: ...
+    @staticmethod
+    def seed_bonus_rule(per_seed: list[float]) -> ScoringRule:
+        """A rule awarding only `per_seed[r]` points per seed of a round-`r` winner."""
+    @staticmethod
+    def upset_bonus_rule(per_seed: list[float]) -> ScoringRule:
+        """A rule awarding only `per_seed[r]` points per seed line a round-`r`
+        winner was seeded below its opponent.
+        """
+    @staticmethod
+    def combine(rules: list[ScoringRule]) -> ScoringRule:
+        """The sum of `rules`: every pick earns what each rule would award it.
+        Discounts are folded into the components, so the result is undiscounted.
+        """
+    def __add__(self, other: ScoringRule) -> ScoringRule: ...
+    def effective_points(self) -> list[float]:
+        """Base points per round after discounting."""
+    def pick_points(self, round: int, winner_seed: int, loser_seed: int) -> float:
+        """Points for a correct round-`round` pick of a `winner_seed` team over a
+        `loser_seed` team.
+        """
+    def apply(self, tournament: TournamentState) -> TournamentState:
+        """A copy of `tournament` scored with this rule's effective points.
+
+        Seed and upset bonuses depend on who a team beats, which per-round
+        team scores cannot express, so rules with bonuses are rejected.
+        """
+    def __repr__(self) -> str: ...
+
+
+class BracketEntry:
+    """A complete set of main-bracket picks."""
+    @property
+    def picks(self) -> list[list[str]]:
+        """Picked winners by round: `picks[r][g]` wins game `g` of round `r`, with
+        games in bracket order.
+        """
+    def __init__(self, picks: list[list[str]]) -> None: ...
+    def champion(self) -> str | None:
+        """The picked champion."""
+    def furthest_round(self, team: str) -> int | None:
+        """Last round (0-based) `team` is picked to win, if any."""
+    def expected_score(self, tournament: TournamentState, scoring: list[float] | None = None) -> float:
+        """Expected points of this entry in `tournament`, with `scoring[r]` points
+        per correct round-`r` pick (default: the tournament's round points).
+        """
+    def score_results(self, tournament: TournamentState, results: list[list[str]], rule: ScoringRule | None = None) -> float:
+        """Points earned so far given `results[r]`, the teams that have won
+        main-bracket round `r`, under `rule` (default: the tournament's round
+        points). Rule rounds are main-bracket rounds.
+        """
+    def satisfies(self, constraints: PickConstraints) -> bool:
+        """Whether every lock and forbid in `constraints` holds for this entry."""
+    def __repr__(self) -> str: ...
+
+
+class PickConstraints:
+    """Picks an optimized entry must (locks) or must not (forbids) contain, as
+    `(team, round)` pairs with 0-based main-bracket rounds.
+    """
+    @property
+    def locks(self) -> list[tuple[str, int]]:
+        """`team` must be picked to win every round through `round`."""
+    @property
+    def forbids(self) -> list[tuple[str, int]]:
+        """`team` must not be picked to win `round` or any later round."""
+    def __init__(self, locks: list[tuple[str, int]] | None = None, forbids: list[tuple[str, int]] | None = None) -> None: ...
+    def lock(self, team: str, round: int) -> None:
+        """Require `team` to be picked through `round`."""
+    def forbid(self, team: str, round: int) -> None:
+        """Rule out picking `team` to win `round` (or later)."""
+    def __repr__(self) -> str: ...
+
+
+class MultiEntryResult:
+    """Entries chosen jointly for a pool, in the order they were added."""
+    @property
+    def entries(self) -> list[BracketEntry]: ...
+    @property
+    def win_prob(self) -> float:
+        """Probability that at least one entry wins the pool."""
+    @property
+    def marginal_win_prob(self) -> list[float]:
+        """Win probability each entry adds over the entries before it."""
+    @property
+    def entry_win_prob(self) -> list[float]:
+        """Each entry's own probability of winning the pool."""
+    def __repr__(self) -> str: ...
+
+
+class EntryStanding:
+    """One entry's position in a pool in progress."""
+    @property
+    def current_score(self) -> float:
+        """Points from games already played."""
+    @property
+    def max_score(self) -> float:
+        """Points if every remaining pick still alive comes in."""
+    @property
+    def expected_score(self) -> float:
+        """Mean final score over the simulations."""
+    @property
+    def win_prob(self) -> float:
+        """Share of simulations this entry finishes first, ties split."""
+    def __repr__(self) -> str: ...
+
+
+class EntryStatus:
+    """Best and worst possible finish for an entry over every remaining outcome."""
+    @property
+    def best_place(self) -> int:
+        """Best possible place (1-based); ties count in the entry's favor."""
+    @property
+    def worst_place(self) -> int:
+        """Worst possible place (1-based); ties count against the entry."""
+    @property
+    def clinched(self) -> bool:
+        """Finishes in a paid place however the remaining games go."""
+    @property
+    def eliminated(self) -> bool:
+        """Cannot finish in a paid place however the remaining games go."""
+    def __repr__(self) -> str: ...
+
+
+class OutcomeMatrix:
+    """Every way the remaining games can go, with each entry's final score."""
+    @property
+    def games(self) -> list[GameId]:
+        """Undecided games, in the order `outcomes` lists their winners."""
+    @property
+    def outcomes(self) -> list[list[str]]:
+        """Winners of `games` in each outcome."""
+    @property
+    def probs(self) -> list[float]:
+        """Model probability of each outcome."""
+    @property
+    def scores(self) -> list[list[float]]:
+        """`scores[o][e]`: entry `e`'s final score in outcome `o`."""
+    @property
+    def expected_scores(self) -> list[float]:
+        """Probability-weighted final score per entry."""
+    @property
+    def win_probs(self) -> list[float]:
+        """Probability each entry finishes first, ties split."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class BracketScore:
+    """An entry's points under a scoring rule, split by round."""
+    @property
+    def earned(self) -> float:
+        """Points from correct picks of completed games."""
+    @property
+    def remaining(self) -> float:
+        """Points still available from undecided games whose picked winner is
+        alive.
+        """
+    @property
+    def round_earned(self) -> list[float]:
+        """`earned` per main-bracket round."""
+    @property
+    def round_remaining(self) -> list[float]:
+        """`remaining` per main-bracket round."""
+    @property
+    def round_correct(self) -> list[int]:
+        """Correct picks per main-bracket round."""
+    @property
+    def max_score(self) -> float:
+        """The most the entry can finish with."""
+    def __repr__(self) -> str: ...
+
+
+class EntryScoreTable:
+    """Earned points of many entries, by round."""
+    @property
+    def totals(self) -> list[float]:
+        """Total points per entry."""
+    @property
+    def n_rounds(self) -> int:
+        """Number of main-bracket rounds scored."""
+    @property
+    def round_scores(self) -> bytes:
+        """Per-round points as one buffer of native-endian float64, round by
+        round, without building a list per round:
+        `numpy.frombuffer(table.round_scores, dtype=numpy.float64).reshape(table.n_rounds, -1)`.
+        """
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class RootingInterest:
+    """The stakes of one upcoming game."""
+    @property
+    def team1(self) -> str: ...
+    @property
+    def team2(self) -> str: ...
+    @property
+    def round(self) -> int:
+        """Main-bracket round (0-based) of the game."""
+    @property
+    def game(self) -> GameId:
+        """The game itself."""
+    @property
+    def value_if_team1(self) -> float: ...
+    @property
+    def value_if_team2(self) -> float: ...
+    @property
+    def root_for(self) -> str | None:
+        """The side worth more, or `None` if the outcome does not matter."""
+    @property
+    def stake(self) -> float:
+        """Absolute value difference between the two outcomes."""
+    def __repr__(self) -> str: ...
+
+
+class ScenarioNode:
+    """One node of a scenario tree."""
+    @property
+    def winner(self) -> str | None:
+        """Team whose win led here (`None` at the root)."""
+    @property
+    def prob(self) -> float:
+        """Probability of reaching this node from the root."""
+    @property
+    def value(self) -> float:
+        """Expected portfolio value at this node."""
+    @property
+    def game(self) -> tuple[str, str] | None:
+        """The game this node branches on (`None` at leaves)."""
+    @property
+    def win_prob(self) -> float | None:
+        """Probability the game's first team wins."""
+    @property
+    def hedge_shares(self) -> float | None:
+        """Shares of the game's first team to trade (negative to sell) so the
+        portfolio is indifferent to the game.
+        """
+    @property
+    def children(self) -> list[ScenarioNode]:
+        """Child for the first team winning, then for the second."""
+    def leaves(self) -> list[ScenarioNode]:
+        """Leaves of the subtree rooted here, in outcome order."""
+    def __repr__(self) -> str: ...
+
+
+class GroupStage:
+    """Group stage configuration."""
+    @property
+    def groups(self) -> list[list[str]]: ...
+    @property
+    def advance(self) -> int:
+        """Number of teams advancing from each group."""
+    @property
+    def seeding(self) -> list[tuple[int, int]]:
+        """Knockout slot order as (group index, finishing place) pairs."""
+    @property
+    def group_game_points(self) -> float:
+        """Points awarded per group-stage win."""
+    @group_game_points.setter
+    def group_game_points(self, value: float) -> None: ...
+    def __init__(self, groups: list[list[str]], advance: int, seeding: list[tuple[int, int]] | None = None, group_game_points: float = 0.0) -> None:
+        """Create a group stage.
+
+        Without an explicit `seeding`, two advancing teams per group are crossed
+        over between neighbouring groups (A1 v B2, B1 v A2); otherwise slots are
+        filled group by group.
+        """
+    def finish_probs(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> dict[str, list[float]]:
+        """Probability of each team finishing in each group place.
+
+        Uses `tournament` for ratings, overrides, and forfeit probability.
+        """
+    def run_simulations(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> list[dict[str, float]]:
+        """Simulate the group stage followed by `tournament`'s knockout scoring.
+
+        The knockout bracket is built from each simulated group result; the
+        bracket stored on `tournament` is ignored. Returns per-simulation scores.
+        """
+    def expected_scores(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> dict[str, float]:
+        """Expected scores across group and knockout stages, estimated by simulation."""
+    def __repr__(self) -> str: ...
+
+
+class SwissStage:
+    """Swiss stage configuration.
+
+    Pairings are adjacent rather than folded: within a group of tied records
+    the teams are taken in `teams` order and paired 1 v 2, 3 v 4, ..., not
+    1 v 3, 2 v 4 as in Dutch-system Swiss. A team skips ahead to the next
+    unpaired team it has not yet played.
+    """
+    @property
+    def teams(self) -> list[str]:
+        """Participants in initial seed order (used to break first-round pairings)."""
+    @property
+    def rounds(self) -> int: ...
+    @property
+    def advance(self) -> int:
+        """Number of teams advancing to the knockout bracket."""
+    @property
+    def win_points(self) -> float:
+        """Points awarded per Swiss-round win."""
+    @win_points.setter
+    def win_points(self, value: float) -> None: ...
+    def __init__(self, teams: list[str], rounds: int, advance: int, win_points: float = 0.0) -> None: ...
+    def record_probs(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> dict[str, list[float]]:
+        """Probability of each team finishing with each win total (index = wins)."""
+    def run_simulations(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> list[dict[str, float]]:
+        """Simulate the Swiss stage followed by `tournament`'s knockout scoring.
+
+        The bracket stored on `tournament` is replaced by the advancing teams.
+        """
+    def expected_scores(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> dict[str, float]:
+        """Expected scores across Swiss and knockout stages, estimated by simulation."""
+    def __repr__(self) -> str: ...
+
+
+class RoundRobin:
+    """A league schedule: every team plays its listed games once each."""
+    @property
+    def teams(self) -> list[str]: ...
+    @property
+    def schedule(self) -> list[tuple[str, str]]:
+        """Games as (team1, team2) pairs; a pair listed twice is played twice."""
+    def __init__(self, teams: list[str], schedule: list[tuple[str, str]] | None = None, games_per_pair: int = 1) -> None:
+        """Create a league. Without a `schedule`, every team plays every other
+        team `games_per_pair` times.
+        """
+    def standings(self, tournament: TournamentState, n_simulations: int, seed: int | None = None) -> RoundRobinStandings:
+        """Expected wins, win-total distributions, and finishing-place
+        probabilities, using `tournament` for ratings, overrides, and model.
+        Finishing places are estimated from `n_simulations` simulated seasons.
+        """
+    def __repr__(self) -> str: ...
+
+
+class RoundRobinStandings:
+    """Expected standings for a `RoundRobin` under a tournament's ratings."""
+    @property
+    def expected_wins(self) -> dict[str, float]:
+        """Expected wins per team."""
+    @property
+    def win_probs(self) -> dict[str, list[float]]:
+        """Probability of each win total per team (index = wins)."""
+    @property
+    def finish_probs(self) -> dict[str, list[float]]:
+        """Probability of each finishing place per team (index 0 = first),
+        after tiebreakers.
+        """
+    def __repr__(self) -> str: ...
+
+
+class InvariantViolation:
+    """A failed invariant check."""
+    @property
+    def check(self) -> str:
+        """Short name of the check, e.g. "game_probabilities"."""
+    @property
+    def detail(self) -> str: ...
+    def __repr__(self) -> str: ...
+
+
+class SimulationArchive:
+    """Read-only, memory-mapped view of a simulation archive."""
+    @property
+    def teams(self) -> list[str]:
+        """Team names, in column order."""
+    @property
+    def n_simulations(self) -> int: ...
+    def __init__(self, path: str) -> None:
+        """Open the archive at `path`, validating its header and length."""
+    def __len__(self) -> int: ...
+    def simulation(self, index: int) -> dict[str, float]:
+        """Scores from simulation `index`, in the shape `run_simulations` returns:
+        only teams that scored are included.
+        """
+    def simulations(self, start: int, end: int) -> list[dict[str, float]]:
+        """Simulations `start..end` (clamped to the archive)."""
+    def team_scores(self, team: str) -> list[float]:
+        """Every simulation's score for `team`."""
+    def mean_scores(self) -> dict[str, float]:
+        """Mean score per team across the archive."""
+    def __repr__(self) -> str: ...
+
+
+class SimulationLog:
+    """Every game's winner in each of a set of simulations."""
+    @property
+    def teams(self) -> list[str]:
+        """Bracket teams, in index order."""
+    @property
+    def n_slots(self) -> int: ...
+    @property
+    def n_simulations(self) -> int: ...
+    def __len__(self) -> int: ...
+    def winner(self, sim: int, game: GameId) -> str:
+        """Winner of `game` in simulation `sim`."""
+    def slot_team(self, sim: int, slot: int) -> str:
+        """Team drawn into bracket slot `slot` in simulation `sim` (the play-in
+        winner for play-in slots).
+        """
+    def path(self, sim: int) -> dict[GameId, str]:
+        """Winner of every game in simulation `sim`."""
+    def filter_wins(self, team: str, round: int) -> list[int]:
+        """Indices of the simulations in which `team` won its game in `round`
+        (for example, round 2 of a 64-team bracket for reaching the Elite Eight).
+        """
+    def __repr__(self) -> str: ...
+
+
+class ConditionalSimulations:
+    """Simulations that met a condition."""
+    @property
+    def simulations(self) -> list[dict[str, float]]:
+        """Per-simulation scores in the shape `run_simulations` returns."""
+    @property
+    def attempts(self) -> int:
+        """Simulations drawn to find them."""
+    @property
+    def acceptance_rate(self) -> float:
+        """Fraction of drawn simulations that met the condition: an estimate of
+        its probability.
+        """
+    def expected_scores(self) -> dict[str, float]:
+        """Mean score per team over the accepted simulations."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class ConvergenceReport:
+    """Estimates and their Monte Carlo standard errors."""
+    @property
+    def n_simulations(self) -> int: ...
+    @property
+    def converged(self) -> bool:
+        """Whether the target standard error was reached (always true without one)."""
+    @property
+    def expected_scores(self) -> dict[str, float]: ...
+    @property
+    def score_std_errors(self) -> dict[str, float]: ...
+    @property
+    def champion_probs(self) -> dict[str, float]: ...
+    @property
+    def champion_std_errors(self) -> dict[str, float]: ...
+    @property
+    def history(self) -> list[tuple[int, float]]:
+        """`(simulations so far, largest standard error of the target metric)`
+        after each batch.
+        """
+    def confidence_interval(self, team: str, metric: str = "points", level: float = 0.95) -> tuple[float, float]:
+        """Normal-approximation confidence interval `(low, high)` on `team`'s
+        `metric` (`"points"` or `"champion"`).
+        """
+    def __repr__(self) -> str: ...
+
+
+class EnsembleModel:
+    """Win probability models blended by weight."""
+    @property
+    def members(self) -> list[ModelParams]: ...
+    @property
+    def weights(self) -> list[float]:
+        """Member weights, normalized to sum to 1."""
+    def __init__(self, members: list[ModelParams], weights: list[float] | None = None) -> None:
+        """Blend `members`, equally unless `weights` (any non-negative scale) are
+        given.
+        """
+    def win_prob(self, team1: Team, team2: Team, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0) -> float:
+        """Blended probability that `team1` beats `team2`."""
+    def member_probs(self, team1: Team, team2: Team, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0) -> list[float]:
+        """Each member's probability that `team1` beats `team2`."""
+    def disagreement(self, team1: Team, team2: Team, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0) -> float:
+        """Weighted standard deviation of the members' probabilities that
+        `team1` beats `team2`.
+        """
+    def __repr__(self) -> str: ...
+
+
+class MatchupAdjuster:
+    """Margin and standard deviation adjustments from the teams' styles."""
+    def __init__(self) -> None: ...
+    def add_margin_term(self, feature: str, coefficient: float, against: str | None = None) -> None:
+        """Shift team1's expected margin by `coefficient` points per unit of
+        `feature1 - feature2`, or with `against`, per unit of
+        `feature1 * against2 - feature2 * against1` (one team's strength
+        meeting the other's weakness).
+        """
+    def add_stddev_term(self, feature: str, coefficient: float, combine: str = "sum") -> None:
+        """Scale the margin's standard deviation by `1 + coefficient * x`, where
+        `x` combines the teams' `feature` values by `combine` (`"sum"`,
+        `"abs_difference"`, or `"product"`). Scales from all terms multiply
+        and are floored at 0.
+        """
+    def adjust(self, team1: Team, team2: Team, margin: float, stddev: float) -> tuple[float, float]:
+        """`(margin, stddev)` after this adjuster's terms, for team1's expected
+        `margin` over team2 with standard deviation `stddev`.
+        """
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class Interval:
+    """A point estimate and the confidence interval around it."""
+    @property
+    def low(self) -> float: ...
+    @property
+    def mid(self) -> float:
+        """Estimate from all simulations."""
+    @property
+    def high(self) -> float: ...
+    @property
+    def width(self) -> float: ...
+    def contains(self, value: float) -> bool: ...
+    def __repr__(self) -> str: ...
+
+
+class ScoreIntervals:
+    """Confidence intervals from `score_intervals`."""
+    @property
+    def expected_scores(self) -> dict[str, Interval]:
+        """Per bracket team."""
+    @property
+    def portfolio_value(self) -> Interval | None:
+        """Value of the given positions, when positions were given."""
+    @property
+    def level(self) -> float: ...
+    @property
+    def n_simulations(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class PairedDifference:
+    """One team's paired score difference between two scenarios."""
+    @property
+    def team(self) -> str: ...
+    @property
+    def mean_a(self) -> float:
+        """Mean simulated score in the first state."""
+    @property
+    def mean_b(self) -> float:
+        """Mean simulated score in the second state."""
+    @property
+    def diff(self) -> float:
+        """Mean of the per-simulation differences, `mean_a - mean_b`."""
+    @property
+    def std_error(self) -> float:
+        """Standard error of `diff`."""
+    @property
+    def z_score(self) -> float:
+        """`diff` in units of its standard error (0 when the difference is exact)."""
+    def __repr__(self) -> str: ...
+
+
+class ScheduleStrength:
+    """Expected opponent quality over a team's remaining main-bracket games.
+
+    Ratings are `Team::net_rating` under the state's `avg_scoring`.
+    """
+    @property
+    def team(self) -> str: ...
+    @property
+    def play_prob(self) -> list[float]:
+        """Probability of playing each remaining round."""
+    @property
+    def opponent_rating(self) -> list[float]:
+        """Expected rating of the opponent in each round, given the team plays it."""
+    @property
+    def expected_opponent_rating(self) -> float:
+        """`opponent_rating` averaged over rounds, weighted by `play_prob`."""
+    def __repr__(self) -> str: ...
+
+
+class WinProbMatrix:
+    """Pairwise win probabilities for a list of teams."""
+    @property
+    def teams(self) -> list[str]:
+        """Row and column `i` of `probs` is `teams[i]`."""
+    @property
+    def probs(self) -> list[list[float]]:
+        """`probs[i][j]`: probability `teams[i]` beats `teams[j]` (0.5 on the
+        diagonal).
+        """
+    def get(self, team1: str, team2: str) -> float | None:
+        """Probability `team1` beats `team2`, or `None` if either is not in the matrix."""
+    def to_dict(self) -> dict[str, dict[str, float]]:
+        """Nested `{team1: {team2: probability}}` form."""
+    def __repr__(self) -> str: ...
+
+
+class SeedingModel:
+    """How the selection committee turns ratings into a bracket."""
+    @property
+    def field_size(self) -> int:
+        """Teams selected (a power of two, divisible into `n_regions`)."""
+    @field_size.setter
+    def field_size(self, value: int) -> None: ...
+    @property
+    def n_regions(self) -> int: ...
+    @n_regions.setter
+    def n_regions(self, value: int) -> None: ...
+    @property
+    def noise(self) -> float:
+        """Standard deviation of the committee's view of each team's net rating
+        (points per 100 possessions) around the model's.
+        """
+    @noise.setter
+    def noise(self, value: float) -> None: ...
+    def __init__(self, field_size: int = 64, n_regions: int = 4, noise: float = 2.0) -> None: ...
+    def __repr__(self) -> str: ...
+
+
+class BracketologyResult:
+    """Pre-bracket projections averaged over sampled brackets."""
+    @property
+    def expected_scores(self) -> dict[str, float]:
+        """Expected tournament points, counting 0 when not selected."""
+    @property
+    def selection_prob(self) -> dict[str, float]:
+        """Probability of making the field."""
+    @property
+    def seed_probs(self) -> dict[str, list[float]]:
+        """Probability of each seed line (index 0 = 1 seed), for selected teams."""
+    @property
+    def n_brackets(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class AutoBidImpact:
+    """How conference tournament outcomes change the selected field."""
+    @property
+    def auto_bid_prob(self) -> dict[str, float]:
+        """Probability of winning the conference tournament and its automatic bid."""
+    @property
+    def selection_prob(self) -> dict[str, float]:
+        """Probability of making the field by either route."""
+    @property
+    def at_large_prob(self) -> dict[str, float]:
+        """Probability of making the field without winning the conference."""
+    @property
+    def selection_given_champion(self) -> dict[str, dict[str, dict[str, float]]]:
+        """Conference -> possible champion -> each bubble team's probability of
+        making the field given that champion. Bubble teams are those with a
+        `selection_prob` strictly between 0 and 1.
+        """
+    @property
+    def bid_steal_prob(self) -> dict[str, dict[str, float]]:
+        """Conference -> possible champion -> probability that its automatic bid
+        goes to a team the committee would not have taken at-large, costing
+        the last at-large team its place ("bid stealing").
+        """
+    @property
+    def n_brackets(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+class SensitivityPoint:
+    """Model outputs under one set of parameters."""
+    @property
+    def params(self) -> ModelParams: ...
+    @property
+    def championship(self) -> dict[str, float]:
+        """Probability each bracket team wins the championship."""
+    @property
+    def expected_points(self) -> dict[str, float]: ...
+    @property
+    def portfolio_value(self) -> float | None:
+        """Portfolio value at `expected_points`, when a portfolio was given."""
+    def __repr__(self) -> str: ...
+
+
+class SensitivityReport:
+    """Model outputs across a grid of parameters, alongside the tournament's own."""
+    @property
+    def baseline(self) -> SensitivityPoint:
+        """Outputs under the tournament's current `model_params`."""
+    @property
+    def points(self) -> list[SensitivityPoint]:
+        """Outputs for each grid entry, in grid order."""
+    def max_championship_shift(self) -> dict[str, float]:
+        """Largest absolute change in each team's championship odds from the
+        baseline across the grid.
+        """
+    def championship_range(self, team: str) -> tuple[float, float]:
+        """(min, max) of `team`'s championship odds over the baseline and grid."""
+    def portfolio_value_range(self) -> tuple[float, float] | None:
+        """(min, max) portfolio value over the baseline and grid, if a portfolio
+        was given.
+        """
+    def __repr__(self) -> str: ...
+
+
+class GameResult:
+    """One completed game."""
+    @property
+    def team1(self) -> str:
+        """Home team, unless `neutral`."""
+    @property
+    def team2(self) -> str: ...
+    @property
+    def score1(self) -> float: ...
+    @property
+    def score2(self) -> float: ...
+    @property
+    def neutral(self) -> bool: ...
+    @property
+    def possessions(self) -> float | None:
+        """Possessions each team had, for `adjusted_ratings`."""
+    def __init__(self, team1: str, team2: str, score1: float, score2: float, neutral: bool = False, possessions: float | None = None) -> None: ...
+    @staticmethod
+    def parse_log(text: str) -> list[GameResult]:
+        """Parse a CSV game log with a header naming `team1`, `team2`, `score1`,
+        and `score2` columns, plus optional `neutral` (`true`/`1` for a
+        neutral site) and `possessions` columns, one game per row in date
+        order.
+        """
+    def __repr__(self) -> str: ...
+
+
+class EloRatings:
+    """Elo ratings from `elo_from_games`."""
+    @property
+    def ratings(self) -> dict[str, float]: ...
+    @property
+    def n_games(self) -> int: ...
+    @property
+    def k(self) -> float: ...
+    @property
+    def home_adv(self) -> float: ...
+    def win_prob(self, team1: str, team2: str, neutral: bool = True) -> float:
+        """Elo probability that `team1` beats `team2`, with `team1` at home
+        unless `neutral`.
+        """
+    def to_teams(self, params: ModelParams | None = None, tempo: float | None = None) -> dict[str, Team]:
+        """Ratings as `Team`s (see the module docs), at `tempo` possessions per
+        game (default: the model's average tempo).
+        """
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+
+def py_calculate_win_prob(team1: Team, team2: Team, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0) -> float:
+    """Calculate win probability for a matchup.
+
+    Python-friendly wrapper around the core win probability function.
+    """
+
+
+def py_game_transform_prob(child1: dict[str, float], child2: dict[str, float], teams: dict[str, Team], overrides: OverridesMap | None = None, forfeit_prob: float = 0.0) -> dict[str, float]:
+    """Probabilistic game transformation."""
+
+
+def py_calculate_expected_scores(team1: Team, team2: Team, overrides: OverridesMap | None = None, params: ModelParams | None = None) -> tuple[float, float]:
+    """Expected (team1, team2) scores for a matchup, at the pairing's tempo
+    override if `overrides` has one, under `params` (default: `ModelParams()`).
+    """
+
+
+def py_expected_tempo(team1: Team, team2: Team, overrides: OverridesMap | None = None, params: ModelParams | None = None) -> float:
+    """Expected possessions for a matchup: the pairing's tempo override if
+    `overrides` has one, otherwise the product of the teams' tempos over the
+    national average (`params.avg_tempo`, default: `ModelParams()`).
+    """
+
+
+def py_project_matchup(team1: Team, team2: Team, overrides: OverridesMap | None = None, forfeit_prob: float = 0.0, params: ModelParams | None = None) -> MatchupProjection:
+    """Expected scores, margin, total, and win probability for a matchup, under
+    `params` (default: `ModelParams()`).
+    """
+
+
+def py_matchup_percentiles(team1: Team, team2: Team, quantiles: list[float] = ..., overrides: OverridesMap | None = None, params: ModelParams | None = None) -> MatchupPercentiles:
+    """Quantiles of the margin and total for a matchup, from the same overrides
+    and `params` as `project_matchup`.
+    """
+
+
+def fit_confidence(probs: list[float], outcomes: list[bool]) -> float:
+    """Maximum likelihood confidence for predictions `probs` (each the
+    probability that an event happens, at confidence 1) and `outcomes`
+    (whether it did). Certain predictions carry no information about the
+    confidence and are skipped.
+    """
+
+
+def read_ratings_str(text: str | bytes, adjustments: dict[str, float] | None = None) -> dict[str, Team]:
+    """Parse ratings file contents into teams by name, applying `adjustments`
+    (points per team) if given.
+    """
+
+
+def read_games_str(text: str | bytes, ratings: dict[str, Team], overrides: OverridesMap | None = None) -> list[dict[str, float]]:
+    """Parse bracket file contents into slots. Play-in teams must be rated in
+    `ratings` under the names the bracket uses.
+    """
+
+
+def elo_from_games(game_log: list[GameResult], k: float = 20.0, home_adv: float = 100.0) -> EloRatings:
+    """Play through `game_log` in order from `INITIAL_ELO`, updating both teams
+    by `k` times the result's surprise. `team1` gets `home_adv` Elo points
+    in games not at a neutral site. Ties count as half a win.
+    """
+
+
+def adjusted_ratings(game_log: list[GameResult], max_iterations: int = 100, tolerance: float = 1e-6) -> dict[str, Team]:
+    """Opponent-adjusted offense, defense, and tempo for every team in
+    `game_log`, which must give `possessions` for every game. Ratings are
+    relative to the log's own average efficiency, so they plug into the model
+    as they are. Iterates until no rating moves by more than `tolerance`
+    (points per 100 possessions), or `max_iterations`.
+    """
+
+
+def get_portfolio_value(positions: dict[str, float], values: dict[str, float]) -> float:
+    """Calculate portfolio value given positions and team values.
+
+    # Arguments
+    * `positions` - Map of team names to number of shares held
+    * `values` - Map of team names to expected values (scores)
+
+    # Returns
+    Total portfolio value
+    """
+
+
+def game_delta(positions: dict[str, float], tournament: TournamentState, team1: str, team2: str) -> tuple[float, float, list[TeamDelta]]:
+    """Calculate the impact of a game outcome on portfolio value.
+
+    Returns (win_value, loss_value, team_deltas) where:
+    - win_value: portfolio value if team1 wins
+    - loss_value: portfolio value if team2 wins
+    - team_deltas: per-team impact breakdown
+
+    # Arguments
+    * `positions` - Map of team names to shares held
+    * `tournament` - Tournament state
+    * `team1` - First team in the matchup
+    * `team2` - Second team in the matchup
+    """
+
+
+def game_delta_curve(positions: dict[str, float], tournament: TournamentState, team1: str, team2: str, probs: list[float] | None = None) -> list[tuple[float, float]]:
+    """Portfolio value as a function of the probability that `team1` beats
+    `team2`, evaluated at each of `probs` (default: `DEFAULT_PROB_GRID`).
+
+    Returns `(prob, value)` pairs in the order given. `game_delta` is the two
+    endpoints of this curve.
+    """
+
+
+def get_team_delta(tournament: TournamentState, team: str, point_delta: float = 1.0) -> tuple[dict[str, float], dict[str, float]]:
+    """Calculate the impact of a team's rating change on tournament scores.
+
+    Returns (positive_scores, negative_scores) where:
+    - positive_scores: scores if team's rating improves by point_delta
+    - negative_scores: scores if team's rating worsens by point_delta
+
+    # Arguments
+    * `tournament` - Tournament state
+    * `team` - Team to adjust
+    * `point_delta` - Amount to adjust rating (default 1.0)
+    """
+
+
+def get_team_portfolio_delta(positions: dict[str, float], tournament: TournamentState, team: str, point_delta: float = 1.0) -> float:
+    """Calculate portfolio delta for a team's rating change."""
+
+
+def get_team_pairwise_deltas(tournament: TournamentState, team: str, point_delta: float = 1.0) -> dict[str, float]:
+    """Calculate pairwise deltas for a team's rating change.
+
+    Returns a map of team names to their value change when the specified team's rating changes.
+    """
+
+
+def get_group_delta(tournament: TournamentState, teams: list[str], point_delta: float = 1.0) -> tuple[dict[str, float], dict[str, float]]:
+    """Calculate the impact of moving a group of teams' ratings together.
+
+    Model error is often shared (e.g. a whole conference is overrated), and
+    adjusting the teams at once captures their effect on each other's paths,
+    which summing single-team deltas misses. Returns (positive_scores,
+    negative_scores) like `get_team_delta`.
+    """
+
+
+def get_group_portfolio_delta(positions: dict[str, float], tournament: TournamentState, teams: list[str], point_delta: float = 1.0) -> float:
+    """Calculate portfolio delta for a group of teams' joint rating change."""
+
+
+def get_all_team_deltas(positions: dict[str, float], tournament: TournamentState, point_delta: float = 1.0) -> tuple[dict[str, float], dict[str, dict[str, float]]]:
+    """Calculate deltas for all teams in the bracket.
+
+    Uses parallel processing for better performance.
+
+    Returns (team_deltas, pairwise_deltas) where:
+    - team_deltas: map of team names to portfolio delta
+    - pairwise_deltas: map of team names to their pairwise delta maps
+
+    # Arguments
+    * `positions` - Map of team names to shares held
+    * `tournament` - Tournament state
+    * `point_delta` - Amount to adjust ratings (default 1.0)
+    """
+
+
+def get_delta_matrix(positions: dict[str, float], tournament: TournamentState, point_delta: float = 1.0) -> DeltaMatrix:
+    """Dense portfolio and pairwise deltas for every bracket team.
+
+    The team list is built once and every per-team row is indexed by it, so
+    the parallel work is two scorings per team plus a linear pass. When the
+    bracket propagates exactly, the unadjusted bracket is played once and each
+    scoring replays only the adjusted team's path (`AdvancementCache`).
+    """
+
+
+def find_edges(tournament: TournamentState, market_prices: dict[str, float], min_edge: float = 0.0, basis: str = "points", n_simulations: int = 10000, seed: int | None = None) -> list[Edge]:
+    """Rank teams by the gap between model value and market price.
+
+    `basis` selects what prices are quoted in: `"points"` for expected points or
+    `"championship"` for probability of winning the title. Model values and
+    their 95% confidence intervals come from `n_simulations` simulations.
+    Teams with an absolute edge below `min_edge` are dropped; the rest are
+    sorted by absolute edge, largest first.
+    """
+
+
+def rebalance_to_target(positions: dict[str, float], tournament: TournamentState, target_deltas: dict[str, tuple[float, float]], prices: dict[str, float], lot_size: float = 1.0, max_turnover: float | None = None, point_delta: float = 1.0, costs: TransactionCosts | None = None) -> RebalancePlan:
+    """Trades bringing each team's portfolio delta into its `target_deltas` band
+    `(low, high)`, trading only teams in `prices` in multiples of `lot_size`
+    and spending at most `max_turnover` (default: unlimited).
+
+    Deltas are linear in positions, so each lot's effect comes from the
+    pairwise deltas. Lots are added greedily, each time taking the one that
+    removes the most band violation per unit of turnover plus `costs`
+    (default: free), until every band is met, the budget is spent, or no lot
+    helps. A team's fixed cost is paid by its first lot.
+    """
+
+
+def upcoming_games(tournament: TournamentState) -> list[tuple[str, str, GameId]]:
+    """Upcoming games as `(team1, team2, game)`, in bracket order."""
+
+
+def rooting_interests(positions: dict[str, float], tournament: TournamentState) -> list[RootingInterest]:
+    """Who a portfolio of `positions` should root for in each upcoming game,
+    valued by expected portfolio points.
+    """
+
+
+def entry_rooting_interests(entry: BracketEntry, tournament: TournamentState, scoring: list[float] | None = None) -> list[RootingInterest]:
+    """Who `entry` should root for in each upcoming game, valued by its expected
+    score with `scoring[r]` points per correct round-`r` pick (default: the
+    tournament's round points).
+    """
+
+
+def pool_equity(entries: list[dict[str, float]], tournament: TournamentState, payouts: Payouts, pot: float = 1.0, n_simulations: int = 10000, seed: int | None = None) -> list[float]:
+    """Expected pool winnings for each portfolio entry.
+
+    Simulates the tournament `n_simulations` times, ranks entries by portfolio
+    value in each simulation, and averages the resulting payouts.
+    """
+
+
+def bracket_pool_equity(entries: list[BracketEntry], opponents: list[BracketEntry], tournament: TournamentState, payouts: Payouts, pot: float = 1.0, scoring: list[float] | None = None, n_simulations: int = 10000, seed: int | None = None) -> list[float]:
+    """Expected pool winnings for each of `entries` in a bracket pool that also
+    contains `opponents` (e.g. a field from `sample_field`).
+
+    Entries score `scoring[r]` per correct round-`r` pick (default: the
+    tournament's round points) in each of `n_simulations` simulations.
+    """
+
+
+def sample_field(tournament: TournamentState, ownership: dict[str, list[float]], n_entries: int, seed: int | None = None) -> list[BracketEntry]:
+    """Sample `n_entries` opponent entries from `ownership`, where
+    `ownership[team][r]` is the share of the public picking `team` to win
+    main-bracket round `r` (0-based).
+
+    Teams or rounds missing from `ownership` count as unowned; a game between
+    two unowned teams is a coin flip.
+    """
+
+
+def entries_from_csv(text: str, aliases: dict[str, str] | None = None) -> list[tuple[str, BracketEntry]]:
+    """Parse a wide CSV export into `(name, entry)` pairs."""
+
+
+def entries_to_csv(entries: list[tuple[str, BracketEntry]]) -> str:
+    """Write `(name, entry)` pairs as a wide CSV export, with a header row."""
+
+
+def entries_from_json(text: str, aliases: dict[str, str] | None = None) -> list[tuple[str, BracketEntry]]:
+    """Parse a JSON bracket dump into `(name, entry)` pairs."""
+
+
+def entries_to_json(entries: list[tuple[str, BracketEntry]]) -> str:
+    """Write `(name, entry)` pairs as a JSON bracket dump."""
+
+
+def project_standings(entries: list[BracketEntry], tournament: TournamentState, n_sims: int = 10000, results: list[list[str]] | None = None, scoring: list[float] | None = None, seed: int | None = None) -> list[EntryStanding]:
+    """Current, maximum, and expected score plus win probability for each of
+    `entries`, given `results` so far and the remaining games simulated
+    `n_sims` times from `tournament`. With at most `MAX_OUTCOME_GAMES` games
+    left, every outcome is weighed exactly instead and `n_sims` and `seed` are
+    unused.
+
+    Entries score `scoring[r]` per correct round-`r` pick (default: the
+    tournament's round points).
+    """
+
+
+def outcome_matrix(entries: list[BracketEntry], tournament: TournamentState, results: list[list[str]] | None = None, scoring: list[float] | None = None) -> OutcomeMatrix:
+    """Every outcome of the games left after `results`, with its model
+    probability and each of `entries`' final score (`scoring[r]` per correct
+    round-`r` pick, default: the tournament's round points). Fails if more
+    than `MAX_OUTCOME_GAMES` games remain.
+    """
+
+
+def score_completed_bracket(entry: BracketEntry, tournament: TournamentState, results: list[list[str]] | None = None, rule: ScoringRule | None = None) -> BracketScore:
+    """Points `entry` has earned and can still earn given `results` so far, under
+    `rule` (default: the tournament's round points). Rule rounds are
+    main-bracket rounds.
+
+    With seed or upset bonuses, an undecided pick is counted against its best
+    possible opponent, so `remaining` is an upper bound that different picks
+    may not reach together.
+    """
+
+
+def score_entries_batch(entries: list[BracketEntry], tournament: TournamentState, results: list[list[str]] | None = None, rule: ScoringRule | None = None) -> EntryScoreTable:
+    """Points each of `entries` has earned given `results` so far, under `rule`
+    (default: the tournament's round points), scored in parallel.
+
+    Each completed game's points are worked out once and shared by every
+    entry that picked its winner, as in `score_completed_bracket`.
+    """
+
+
+def entry_status(entries: list[BracketEntry], tournament: TournamentState, results: list[list[str]] | None = None, payouts: Payouts | None = None, scoring: list[float] | None = None) -> list[EntryStatus]:
+    """Whether each of `entries` has clinched or been eliminated from a paid place
+    (default: first only) given `results` so far, checked exactly over every
+    outcome of the remaining games.
+
+    A place is paid if `payouts` pays it anything; an entry tied across the
+    paid cutoff shares in the payout, so it counts as paid. Fails if more than
+    `MAX_EXACT_GAMES` games remain.
+    """
+
+
+def optimize_entry(tournament: TournamentState, scoring: list[float] | None = None, constraints: PickConstraints | None = None) -> BracketEntry:
+    """The entry with the highest expected score in `tournament` that respects
+    `constraints`, with `scoring[r]` points per correct round-`r` pick (default:
+    the tournament's round points). Ties go to the alphabetically first team.
+    """
+
+
+def optimize_entries(tournament: TournamentState, n_entries: int, opponents: list[BracketEntry], scoring: list[float] | None = None, constraints: PickConstraints | None = None, n_candidates: int = 32, n_simulations: int = 2000, seed: int | None = None) -> MultiEntryResult:
+    """Choose `n_entries` entries jointly to maximize the probability that at
+    least one of them beats every entry in `opponents`.
+
+    Up to `n_candidates` candidate entries (each respecting `constraints`) are
+    scored against the field in `n_simulations` simulations; a candidate wins a
+    simulation when it scores at least as much as the best opponent. Entries
+    are then added greedily by the simulations they newly win, which accounts
+    for the overlap between one's own entries.
+    """
+
+
+def compare_states_sim(state_a: TournamentState, state_b: TournamentState, n_simulations: int = 10000, seed: int | None = None) -> list[PairedDifference]:
+    """Simulate `state_a` and `state_b` with common random numbers and return
+    each team's paired score difference, largest absolute difference first.
+
+    Both states must have brackets of the same size; teams missing from one
+    state's bracket score 0 there.
+    """
+
+
+def scenario_tree(positions: dict[str, float], tournament: TournamentState, n_games: int) -> ScenarioNode:
+    """Scenario tree of `positions` over the next `n_games` upcoming games."""
+
+
+def verify_invariants(tournament: TournamentState, tolerance: float = 1e-9) -> list[InvariantViolation]:
+    """Check `tournament` for internal consistency, returning all violations.
+
+    Checks that the bracket has a power-of-2 number of slots whose teams all
+    have ratings; that every slot and every game's outcome probabilities lie in
+    [0, 1] and sum to 1; that P(A beats B) + P(B beats A) = 1 for every pair of
+    bracket teams; and, when no team multipliers are set, that total expected
+    points equal the points available.
+    """
+
+
+def sensitivity_report(tournament: TournamentState, params_grid: list[ModelParams], portfolio: PortfolioState | None = None) -> SensitivityReport:
+    """Championship odds, expected points, and (optionally) portfolio value under
+    each `ModelParams` in `params_grid`.
+
+    `ModelParams.grid` builds a grid over scoring stddev and average scoring.
+    """
+
+
+def project_field(ratings: dict[str, Team], scoring: list[float], model: SeedingModel | None = None, n_brackets: int = 1000, seed: int | None = None, conference_tournaments: dict[str, TournamentState] | None = None) -> BracketologyResult:
+    """Expected scores, selection odds, and seed distributions from `n_brackets`
+    brackets sampled with `model`, scored with `scoring`.
+
+    `conference_tournaments` (conference name to its tournament) adds
+    automatic bids for each sampled conference champion.
+    """
+
+
+def sample_bracket(ratings: dict[str, Team], model: SeedingModel | None = None, seed: int | None = None) -> list[dict[str, float]]:
+    """One bracket drawn from `model`, as bracket slots."""
+
+
+def auto_bid_impact(ratings: dict[str, Team], conference_tournaments: dict[str, TournamentState], model: SeedingModel | None = None, n_brackets: int = 1000, seed: int | None = None) -> AutoBidImpact:
+    """Selection odds from `n_brackets` fields sampled with `model` and the
+    conference champions drawn from `conference_tournaments`, and how each
+    possible champion changes them.
+
+    Conditional odds hold each sample's committee view and other champions
+    fixed while swapping in each candidate champion, so even unlikely
+    champions' effects are measured on every sample.
+    """
+
+
+def bracket_report(tournament: TournamentState, portfolio: PortfolioState | None = None, format: str = "text") -> str:
+    """Formatted bracket report as `"text"` or `"html"`.
+
+    Portfolio columns use the portfolio's stored deltas; call `compute_deltas`
+    first to populate them.
+    """
+
+
+def american_to_decimal(odds: float) -> float:
+    """Convert American odds (+150, -200) to decimal odds (2.5, 1.5)."""
+
+
+def decimal_to_probability(odds: float) -> float:
+    """Implied probability of decimal odds, including the book's margin."""
+
+
+def remove_overround(probs: dict[str, float], method: str = "proportional") -> dict[str, float]:
+    """Rescale raw implied probabilities so they sum to 1.
+
+    `method` is `"proportional"` (divide by the total) or `"power"` (raise each
+    probability to the exponent k that makes them sum to 1).
+    """
+
+
+def implied_probabilities(odds: dict[str, float], format: str = "american", method: str = "proportional") -> dict[str, float]:
+    """Championship probabilities from a book of futures odds.
+
+    `format` is `"american"` or `"decimal"`; see `remove_overround` for `method`.
+    """
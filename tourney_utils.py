@@ -8,6 +8,7 @@ For the original Python reference implementation, see tourney_utils_reference.py
 """
 
 import csv
+import warnings
 from decimal import Decimal
 
 from tourney_core import (
@@ -16,6 +17,11 @@ from tourney_core import (
     TournamentState as _RustTournamentState,
     py_calculate_win_prob as _rust_calculate_win_prob,
     py_game_transform_prob as _rust_game_transform_prob,
+    py_calculate_expected_scores as _rust_calculate_expected_scores,
+    py_expected_tempo as _rust_expected_tempo,
+    py_project_matchup as _rust_project_matchup,
+    py_matchup_percentiles as _rust_matchup_percentiles,
+    MatchupProjection,
     AVG_SCORING,
     AVG_TEMPO,
     SCORING_STDDEV,
@@ -45,11 +51,44 @@ def TournamentState(bracket, ratings, scoring, overrides=None, forfeit_prob=0.0)
     )
 
 
+def load_tournament(bracket_path, ratings_path, overrides_path=None, scoring=None, adjustments_path=None):
+    """Load a TournamentState from the data files in one call.
+
+    Ratings, adjustments, overrides, and the bracket are read and validated
+    in Rust, with equivalence classes auto-injected. `scoring` defaults to
+    ROUND_POINTS.
+    """
+    return _RustTournamentState.from_files(
+        bracket_path, ratings_path, overrides_path, scoring, adjustments_path,
+        equivalence_classes=_EQUIV_LISTS,
+    )
+
+
 def calculate_win_prob(team1, team2, overrides=None, forfeit_prob=0.0):
     """Calculate win probability for team1 vs team2."""
     return _rust_calculate_win_prob(team1, team2, overrides, forfeit_prob)
 
 
+def calculate_expected_scores(team1, team2, overrides=None, params=None):
+    """Expected (team1, team2) scores for a matchup, at the pairing's tempo override if any."""
+    return _rust_calculate_expected_scores(team1, team2, overrides, params)
+
+
+def expected_tempo(team1, team2, overrides=None, params=None):
+    """Expected possessions for a matchup, or the pairing's tempo override if any."""
+    return _rust_expected_tempo(team1, team2, overrides, params)
+
+
+def project_matchup(team1, team2, overrides=None, forfeit_prob=0.0, params=None):
+    """Expected scores, margin, total, and win probability for a matchup."""
+    return _rust_project_matchup(team1, team2, overrides, forfeit_prob, params)
+
+
+def matchup_percentiles(team1, team2, quantiles=(0.1, 0.5, 0.9), overrides=None, params=None):
+    """Quantiles of the margin and total for a matchup."""
+    return _rust_matchup_percentiles(team1, team2, list(quantiles), overrides, params)
+
+
 def game_transform_prob(child1, child2, teams, overrides=None, forfeit_prob=0.0):
     """Probabilistic game transformation."""
     return _rust_game_transform_prob(child1, child2, teams, overrides, forfeit_prob)
@@ -88,6 +127,9 @@ def read_ratings_file(in_file, adjustments=None):
 
     Supports both file objects (for compatibility) and file paths.
     Returns dict mapping team names to Team objects.
+
+    Lines are ``name|offense|defense|tempo`` optionally followed by
+    ``|conference|seed|wins-losses|short_name``; empty fields are skipped.
     """
     if isinstance(in_file, str):
         with open(in_file) as f:
@@ -112,7 +154,19 @@ def read_ratings_file(in_file, adjustments=None):
             offense += adjustments[name]
             defense -= adjustments[name]
 
-        ratings[name] = Team(name, offense, defense, tempo, adjust=True)
+        team = Team(name, offense, defense, tempo, adjust=True)
+        extra = parts[4:] + [""] * (4 - len(parts[4:]))
+        conference, seed, record, short_name = (field.strip() for field in extra[:4])
+        if conference:
+            team.conference = conference
+        if seed:
+            team.seed = int(seed)
+        if record:
+            wins, losses = record.split("-")
+            team.wins, team.losses = int(wins), int(losses)
+        if short_name:
+            team.short_name = short_name
+        ratings[name] = team
     return ratings
 
 
@@ -136,7 +190,14 @@ def read_games_from_file(filepath, ratings, overrides=None):
 
     Uses team name equivalence classes to resolve bracket names
     against the ratings dictionary.
+
+    Deprecated: use load_tournament, which builds the whole TournamentState.
     """
+    warnings.warn(
+        "read_games_from_file is deprecated; use load_tournament",
+        DeprecationWarning,
+        stacklevel=2,
+    )
     games = []
     with open(filepath, "rt") as f:
         reader = csv.reader(f)
@@ -248,11 +309,17 @@ __all__ = [
     'OverridesMap',
     'TournamentState',
     'calculate_win_prob',
+    'calculate_expected_scores',
+    'expected_tempo',
+    'project_matchup',
+    'matchup_percentiles',
+    'MatchupProjection',
     'game_transform_prob',
     'read_ratings_file',
     'read_adjustments_file',
     'read_overrides_file',
     'read_games_from_file',
+    'load_tournament',
     'get_bracket_teams',
     'AVG_SCORING',
     'AVG_TEMPO',